@@ -68,6 +68,9 @@ fn bench_add_fields(c: &mut Criterion) {
                 unreachable!("no sync transform used in these benches");
             }
             Transform::Task(t) => t.transform_events(Box::pin(rx)),
+            Transform::MultiOutputTask(_t) => {
+                unreachable!("no multi-output task transform used in these benches");
+            }
         };
 
         group.bench_function(name.to_owned(), |b| {
@@ -156,6 +159,9 @@ fn bench_field_filter(c: &mut Criterion) {
                 unreachable!("no sync transform used in these benches");
             }
             Transform::Task(t) => t.transform_events(Box::pin(rx)),
+            Transform::MultiOutputTask(_t) => {
+                unreachable!("no multi-output task transform used in these benches");
+            }
         };
 
         group.bench_function(name.to_owned(), |b| {
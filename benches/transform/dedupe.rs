@@ -6,7 +6,7 @@ use criterion::{
     SamplingMode, Throughput,
 };
 use vector::transforms::dedupe::{CacheConfig, Dedupe, DedupeConfig, FieldMatchConfig};
-use vector_core::transform::Transform;
+use vector_core::transform::MultiOutputTaskTransform;
 
 use crate::common::{consume, FixedLogStream};
 
@@ -42,7 +42,13 @@ fn dedupe(c: &mut Criterion) {
                 fields: Some(FieldMatchConfig::IgnoreFields(vec![String::from(
                     "message",
                 )])),
-                cache: CacheConfig { num_events: 4 },
+                cache: CacheConfig {
+                    num_events: 4,
+                    ttl_secs: None,
+                },
+                persist: false,
+                persist_interval_secs: 60,
+                data_dir: None,
             },
         },
         // Modification of previous where field "message" is matched.
@@ -51,7 +57,13 @@ fn dedupe(c: &mut Criterion) {
             input: fixed_stream.clone(),
             dedupe_config: DedupeConfig {
                 fields: Some(FieldMatchConfig::MatchFields(vec![String::from("message")])),
-                cache: CacheConfig { num_events: 4 },
+                cache: CacheConfig {
+                    num_events: 4,
+                    ttl_secs: None,
+                },
+                persist: false,
+                persist_interval_secs: 60,
+                data_dir: None,
             },
         },
         // Measurement where ignore fields do not exist in the event.
@@ -59,7 +71,10 @@ fn dedupe(c: &mut Criterion) {
             slug: "field_ignore_done",
             input: fixed_stream.clone(),
             dedupe_config: DedupeConfig {
-                cache: CacheConfig { num_events: 4 },
+                cache: CacheConfig {
+                    num_events: 4,
+                    ttl_secs: None,
+                },
                 fields: Some(FieldMatchConfig::IgnoreFields(vec![
                     String::from("abcde"),
                     String::from("eabcd"),
@@ -67,6 +82,9 @@ fn dedupe(c: &mut Criterion) {
                     String::from("cdeab"),
                     String::from("bcdea"),
                 ])),
+                persist: false,
+                persist_interval_secs: 60,
+                data_dir: None,
             },
         },
         // Modification of previous where match fields do not exist in the
@@ -75,7 +93,10 @@ fn dedupe(c: &mut Criterion) {
             slug: "field_match_done",
             input: fixed_stream.clone(),
             dedupe_config: DedupeConfig {
-                cache: CacheConfig { num_events: 4 },
+                cache: CacheConfig {
+                    num_events: 4,
+                    ttl_secs: None,
+                },
                 fields: Some(FieldMatchConfig::MatchFields(vec![
                     String::from("abcde"),
                     String::from("eabcd"),
@@ -83,6 +104,9 @@ fn dedupe(c: &mut Criterion) {
                     String::from("cdeab"),
                     String::from("bcdea"),
                 ])),
+                persist: false,
+                persist_interval_secs: 60,
+                data_dir: None,
             },
         },
     ] {
@@ -90,12 +114,11 @@ fn dedupe(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::new("transform", param), &param, |b, param| {
             b.iter_batched(
                 || {
-                    let dedupe =
-                        Transform::event_task(Dedupe::new(param.dedupe_config.clone())).into_task();
+                    let dedupe = Dedupe::new(param.dedupe_config.clone(), None);
                     (Box::new(dedupe), Box::pin(param.input.clone()))
                 },
                 |(dedupe, input)| {
-                    let output = dedupe.transform_events(input);
+                    let output = dedupe.transform(input);
                     consume(output)
                 },
                 BatchSize::SmallInput,
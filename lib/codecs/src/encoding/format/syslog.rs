@@ -0,0 +1,461 @@
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use bytes::BytesMut;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::{log_schema, DataType},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// The value the RFC 5424 format uses for absent header fields.
+const NIL_VALUE: &str = "-";
+
+/// Config used to build a `SyslogSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SyslogSerializerConfig {
+    /// Options for the syslog serializer.
+    #[serde(default)]
+    pub syslog: SyslogSerializerOptions,
+}
+
+impl SyslogSerializerConfig {
+    /// Creates a new `SyslogSerializerConfig`.
+    pub const fn new(syslog: SyslogSerializerOptions) -> Self {
+        Self { syslog }
+    }
+
+    /// Build the `SyslogSerializer` from this configuration.
+    pub fn build(&self) -> SyslogSerializer {
+        SyslogSerializer::new(self.syslog.clone())
+    }
+
+    /// The data type of events that are accepted by `SyslogSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// The syslog message format.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogRfc {
+    /// The legacy BSD syslog format, as described in RFC 3164.
+    Rfc3164,
+    /// The IETF syslog format, as described in RFC 5424.
+    Rfc5424,
+}
+
+impl Default for SyslogRfc {
+    fn default() -> Self {
+        Self::Rfc5424
+    }
+}
+
+/// Options for building a `SyslogSerializer`.
+///
+/// Every string option is a template: `{{ field }}` placeholders are replaced
+/// with the value of the referenced field of the event being encoded.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SyslogSerializerOptions {
+    /// The syslog message format to produce.
+    pub rfc: SyslogRfc,
+    /// The facility, either as a keyword (e.g. `local0`) or as a number.
+    pub facility: String,
+    /// The severity, either as a keyword (e.g. `warning`) or as a number.
+    pub severity: String,
+    /// The hostname. Defaults to the event's host field.
+    pub hostname: Option<String>,
+    /// The name of the application that emitted the message.
+    pub app_name: Option<String>,
+    /// The process ID of the application that emitted the message.
+    pub proc_id: Option<String>,
+    /// The message type identifier. Only used by RFC 5424.
+    pub msg_id: Option<String>,
+    /// Structured data elements, keyed by SD-ID and then by parameter name.
+    /// Only used by RFC 5424.
+    pub structured_data: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Default for SyslogSerializerOptions {
+    fn default() -> Self {
+        Self {
+            rfc: SyslogRfc::default(),
+            facility: "user".to_owned(),
+            severity: "informational".to_owned(),
+            hostname: None,
+            app_name: None,
+            proc_id: None,
+            msg_id: None,
+            structured_data: BTreeMap::new(),
+        }
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the syslog format.
+#[derive(Debug, Clone)]
+pub struct SyslogSerializer {
+    options: SyslogSerializerOptions,
+}
+
+impl SyslogSerializer {
+    /// Creates a new `SyslogSerializer`.
+    pub const fn new(options: SyslogSerializerOptions) -> Self {
+        Self { options }
+    }
+
+    fn priority(&self, log: &LogEvent) -> vector_core::Result<u8> {
+        let facility = render(&self.options.facility, log);
+        let facility = parse_code(&facility, FACILITIES)
+            .filter(|code| *code < 24)
+            .ok_or_else(|| format!("Invalid syslog facility: {:?}.", facility))?;
+        let severity = render(&self.options.severity, log);
+        let severity = parse_code(&severity, SEVERITIES)
+            .filter(|code| *code < 8)
+            .ok_or_else(|| format!("Invalid syslog severity: {:?}.", severity))?;
+
+        Ok(facility * 8 + severity)
+    }
+
+    fn hostname(&self, log: &LogEvent) -> Option<String> {
+        match &self.options.hostname {
+            Some(template) => Some(render(template, log)),
+            None => log
+                .get(log_schema().host_key())
+                .map(|value| value.to_string_lossy()),
+        }
+        .filter(|hostname| !hostname.is_empty())
+    }
+
+    fn encode_rfc5424(
+        &self,
+        log: &LogEvent,
+        priority: u8,
+        timestamp: DateTime<Utc>,
+        output: &mut String,
+    ) {
+        let render_field = |template: &Option<String>, max_length: usize| {
+            template
+                .as_ref()
+                .map(|template| header_field(&render(template, log), max_length))
+                .filter(|field| !field.is_empty())
+                .unwrap_or_else(|| NIL_VALUE.to_owned())
+        };
+
+        let hostname = self
+            .hostname(log)
+            .map(|hostname| header_field(&hostname, 255))
+            .unwrap_or_else(|| NIL_VALUE.to_owned());
+
+        let _ = write!(
+            output,
+            "<{}>1 {} {} {} {} {} ",
+            priority,
+            timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            hostname,
+            render_field(&self.options.app_name, 48),
+            render_field(&self.options.proc_id, 128),
+            render_field(&self.options.msg_id, 32),
+        );
+
+        if self.options.structured_data.is_empty() {
+            output.push_str(NIL_VALUE);
+        } else {
+            for (id, params) in &self.options.structured_data {
+                output.push('[');
+                output.push_str(&sd_name(id));
+                for (name, template) in params {
+                    let _ = write!(
+                        output,
+                        " {}=\"{}\"",
+                        sd_name(name),
+                        escape_param_value(&render(template, log))
+                    );
+                }
+                output.push(']');
+            }
+        }
+    }
+
+    fn encode_rfc3164(
+        &self,
+        log: &LogEvent,
+        priority: u8,
+        timestamp: DateTime<Utc>,
+        output: &mut String,
+    ) {
+        let _ = write!(
+            output,
+            "<{}>{}",
+            priority,
+            timestamp.format("%b %e %H:%M:%S")
+        );
+
+        if let Some(hostname) = self.hostname(log) {
+            output.push(' ');
+            output.push_str(&header_field(&hostname, 255));
+        }
+
+        let app_name = self
+            .options
+            .app_name
+            .as_ref()
+            .map(|template| header_field(&render(template, log), 32))
+            .filter(|app_name| !app_name.is_empty());
+        if let Some(app_name) = app_name {
+            output.push(' ');
+            output.push_str(&app_name);
+            if let Some(proc_id) = self
+                .options
+                .proc_id
+                .as_ref()
+                .map(|template| header_field(&render(template, log), 128))
+                .filter(|proc_id| !proc_id.is_empty())
+            {
+                let _ = write!(output, "[{}]", proc_id);
+            }
+            output.push(':');
+        }
+    }
+}
+
+impl Encoder<Event> for SyslogSerializer {
+    type Error = vector_core::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.as_log();
+        let priority = self.priority(log)?;
+        let timestamp = match log.get(log_schema().timestamp_key()) {
+            Some(Value::Timestamp(timestamp)) => *timestamp,
+            _ => Utc::now(),
+        };
+
+        let mut output = String::new();
+        match self.options.rfc {
+            SyslogRfc::Rfc5424 => self.encode_rfc5424(log, priority, timestamp, &mut output),
+            SyslogRfc::Rfc3164 => self.encode_rfc3164(log, priority, timestamp, &mut output),
+        }
+
+        buffer.extend_from_slice(output.as_bytes());
+        if let Some(message) = log.get(log_schema().message_key()) {
+            buffer.extend_from_slice(b" ");
+            buffer.extend_from_slice(&message.coerce_to_bytes());
+        }
+
+        Ok(())
+    }
+}
+
+const FACILITIES: &[&str] = &[
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+
+const SEVERITIES: &[&str] = &[
+    "emergency",
+    "alert",
+    "critical",
+    "error",
+    "warning",
+    "notice",
+    "informational",
+    "debug",
+];
+
+/// Resolve a facility or severity given either by keyword or by number.
+fn parse_code(value: &str, keywords: &[&str]) -> Option<u8> {
+    let value = value.trim();
+    value.parse::<u8>().ok().or_else(|| {
+        keywords
+            .iter()
+            .position(|keyword| keyword.eq_ignore_ascii_case(value))
+            .or(match value.to_ascii_lowercase().as_str() {
+                "emerg" | "panic" => Some(0),
+                "crit" => Some(2),
+                "err" => Some(3),
+                "warn" => Some(4),
+                "info" => Some(6),
+                _ => None,
+            })
+            .and_then(|code| u8::try_from(code).ok())
+    })
+}
+
+/// Replace `{{ field }}` placeholders in `template` with values from `log`.
+///
+/// Placeholders referring to missing fields are replaced with an empty string.
+fn render(template: &str, log: &LogEvent) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let end = match rest[start + 2..].find("}}") {
+            Some(end) => start + 2 + end,
+            None => break,
+        };
+        output.push_str(&rest[..start]);
+        if let Some(value) = log.get(rest[start + 2..end].trim()) {
+            output.push_str(&value.to_string_lossy());
+        }
+        rest = &rest[end + 2..];
+    }
+    output.push_str(rest);
+
+    output
+}
+
+/// Header fields may only contain printable US-ASCII characters and are
+/// limited in length.
+fn header_field(value: &str, max_length: usize) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_length)
+        .collect()
+}
+
+/// SD-IDs and parameter names may not contain `=`, `]`, `"` or spaces.
+fn sd_name(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"'))
+        .take(32)
+        .collect()
+}
+
+/// Escape `"`, `\` and `]` in parameter values as required by RFC 5424.
+fn escape_param_value(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn event() -> Event {
+        let mut event = Event::from("hello world");
+        let log = event.as_mut_log();
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 6, 1).and_hms_milli(12, 30, 15, 123),
+        );
+        log.insert(log_schema().host_key(), "web-1");
+        log.insert("app", "nginx");
+        log.insert("pid", 4242);
+        log.insert("level", "warning");
+        log.insert("request_id", r#"a"b]c"#);
+        event
+    }
+
+    fn serialize(options: SyslogSerializerOptions, event: Event) -> String {
+        let mut serializer = SyslogSerializer::new(options);
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event, &mut bytes).unwrap();
+
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn serialize_rfc5424() {
+        let options = SyslogSerializerOptions {
+            facility: "local0".to_owned(),
+            severity: "{{ level }}".to_owned(),
+            app_name: Some("{{ app }}".to_owned()),
+            proc_id: Some("{{ pid }}".to_owned()),
+            msg_id: Some("access".to_owned()),
+            structured_data: BTreeMap::from([(
+                "request@32473".to_owned(),
+                BTreeMap::from([("id".to_owned(), "{{ request_id }}".to_owned())]),
+            )]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serialize(options, event()),
+            r#"<132>1 2022-06-01T12:30:15.123Z web-1 nginx 4242 access [request@32473 id="a\"b\]c"] hello world"#
+        );
+    }
+
+    #[test]
+    fn serialize_rfc5424_nil_values() {
+        let mut event = Event::from("hello world");
+        event.as_mut_log().insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 6, 1).and_hms(12, 30, 15),
+        );
+
+        assert_eq!(
+            serialize(SyslogSerializerOptions::default(), event),
+            "<14>1 2022-06-01T12:30:15Z - - - - - hello world"
+        );
+    }
+
+    #[test]
+    fn serialize_rfc3164() {
+        let options = SyslogSerializerOptions {
+            rfc: SyslogRfc::Rfc3164,
+            facility: "4".to_owned(),
+            severity: "err".to_owned(),
+            app_name: Some("{{ app }}".to_owned()),
+            proc_id: Some("{{ pid }}".to_owned()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serialize(options, event()),
+            "<35>Jun  1 12:30:15 web-1 nginx[4242]: hello world"
+        );
+    }
+
+    #[test]
+    fn serialize_invalid_severity() {
+        let options = SyslogSerializerOptions {
+            severity: "{{ missing }}".to_owned(),
+            ..Default::default()
+        };
+        let mut serializer = SyslogSerializer::new(options);
+
+        assert!(serializer.encode(event(), &mut BytesMut::new()).is_err());
+    }
+}
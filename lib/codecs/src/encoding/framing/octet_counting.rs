@@ -0,0 +1,72 @@
+use bytes::BytesMut;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+
+use super::BoxedFramingError;
+
+/// Config used to build an `OctetCountingEncoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct OctetCountingEncoderConfig;
+
+impl OctetCountingEncoderConfig {
+    /// Creates a new `OctetCountingEncoderConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `OctetCountingEncoder` from this configuration.
+    pub const fn build(&self) -> OctetCountingEncoder {
+        OctetCountingEncoder::new()
+    }
+}
+
+/// An encoder that prefixes each frame with its length in bytes as decimal
+/// ASCII digits followed by a space, using the `Octet Counting` format as
+/// specified in https://tools.ietf.org/html/rfc6587#section-3.4.1.
+#[derive(Debug, Clone, Default)]
+pub struct OctetCountingEncoder;
+
+impl OctetCountingEncoder {
+    /// Creates a new `OctetCountingEncoder`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<()> for OctetCountingEncoder {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, _: (), buffer: &mut BytesMut) -> Result<(), BoxedFramingError> {
+        let prefix = format!("{} ", buffer.len());
+        buffer.reserve(prefix.len());
+        let payload = buffer.split();
+        buffer.extend_from_slice(prefix.as_bytes());
+        buffer.unsplit(payload);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let mut codec = OctetCountingEncoder::new();
+
+        let mut buffer = BytesMut::from("<13>1 - - - - - - hello");
+        codec.encode((), &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..], b"23 <13>1 - - - - - - hello");
+    }
+
+    #[test]
+    fn encode_empty() {
+        let mut codec = OctetCountingEncoder::new();
+
+        let mut buffer = BytesMut::new();
+        codec.encode((), &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..], b"0 ");
+    }
+}
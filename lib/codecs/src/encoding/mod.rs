@@ -10,12 +10,14 @@ use bytes::BytesMut;
 pub use format::{
     JsonSerializer, JsonSerializerConfig, LogfmtSerializer, LogfmtSerializerConfig,
     NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig,
-    RawMessageSerializer, RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    RawMessageSerializer, RawMessageSerializerConfig, SyslogRfc, SyslogSerializer,
+    SyslogSerializerConfig, SyslogSerializerOptions, TextSerializer, TextSerializerConfig,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
     CharacterDelimitedEncoderConfig, CharacterDelimitedEncoderOptions, LengthDelimitedEncoder,
     LengthDelimitedEncoderConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
+    OctetCountingEncoder, OctetCountingEncoderConfig,
 };
 use serde::{Deserialize, Serialize};
 use vector_core::{config::DataType, event::Event, schema};
@@ -64,6 +66,8 @@ pub enum FramingConfig {
     LengthDelimited,
     /// Configures the `NewlineDelimitedEncoder`.
     NewlineDelimited,
+    /// Configures the `OctetCountingEncoder`.
+    OctetCounting,
 }
 
 impl From<BytesEncoderConfig> for FramingConfig {
@@ -92,6 +96,12 @@ impl From<NewlineDelimitedEncoderConfig> for FramingConfig {
     }
 }
 
+impl From<OctetCountingEncoderConfig> for FramingConfig {
+    fn from(_: OctetCountingEncoderConfig) -> Self {
+        Self::OctetCounting
+    }
+}
+
 impl FramingConfig {
     /// Build the `Framer` from this configuration.
     pub fn build(&self) -> Framer {
@@ -111,6 +121,9 @@ impl FramingConfig {
             FramingConfig::NewlineDelimited => {
                 Framer::NewlineDelimited(NewlineDelimitedEncoderConfig.build())
             }
+            FramingConfig::OctetCounting => {
+                Framer::OctetCounting(OctetCountingEncoderConfig.build())
+            }
         }
     }
 }
@@ -126,6 +139,8 @@ pub enum Framer {
     LengthDelimited(LengthDelimitedEncoder),
     /// Uses a `NewlineDelimitedEncoder` for framing.
     NewlineDelimited(NewlineDelimitedEncoder),
+    /// Uses an `OctetCountingEncoder` for framing.
+    OctetCounting(OctetCountingEncoder),
     /// Uses an opaque `Encoder` implementation for framing.
     Boxed(BoxedFramer),
 }
@@ -154,6 +169,12 @@ impl From<NewlineDelimitedEncoder> for Framer {
     }
 }
 
+impl From<OctetCountingEncoder> for Framer {
+    fn from(encoder: OctetCountingEncoder) -> Self {
+        Self::OctetCounting(encoder)
+    }
+}
+
 impl From<BoxedFramer> for Framer {
     fn from(encoder: BoxedFramer) -> Self {
         Self::Boxed(encoder)
@@ -169,6 +190,7 @@ impl tokio_util::codec::Encoder<()> for Framer {
             Framer::CharacterDelimited(framer) => framer.encode((), buffer),
             Framer::LengthDelimited(framer) => framer.encode((), buffer),
             Framer::NewlineDelimited(framer) => framer.encode((), buffer),
+            Framer::OctetCounting(framer) => framer.encode((), buffer),
             Framer::Boxed(framer) => framer.encode((), buffer),
         }
     }
@@ -191,6 +213,12 @@ pub enum SerializerConfig {
     NativeJson,
     /// Configures the `RawMessageSerializer`.
     RawMessage,
+    /// Configures the `SyslogSerializer`.
+    Syslog {
+        /// Options for the syslog serializer.
+        #[serde(default)]
+        syslog: SyslogSerializerOptions,
+    },
     /// Configures the `TextSerializer`.
     Text,
 }
@@ -225,6 +253,14 @@ impl From<RawMessageSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<SyslogSerializerConfig> for SerializerConfig {
+    fn from(config: SyslogSerializerConfig) -> Self {
+        Self::Syslog {
+            syslog: config.syslog,
+        }
+    }
+}
+
 impl From<TextSerializerConfig> for SerializerConfig {
     fn from(_: TextSerializerConfig) -> Self {
         Self::Text
@@ -233,7 +269,7 @@ impl From<TextSerializerConfig> for SerializerConfig {
 
 impl SerializerConfig {
     /// Build the `Serializer` from this configuration.
-    pub const fn build(&self) -> Serializer {
+    pub fn build(&self) -> Serializer {
         match self {
             SerializerConfig::Json => Serializer::Json(JsonSerializerConfig.build()),
             SerializerConfig::Logfmt => Serializer::Logfmt(LogfmtSerializerConfig.build()),
@@ -244,6 +280,12 @@ impl SerializerConfig {
             SerializerConfig::RawMessage => {
                 Serializer::RawMessage(RawMessageSerializerConfig.build())
             }
+            SerializerConfig::Syslog { syslog } => Serializer::Syslog(
+                SyslogSerializerConfig {
+                    syslog: syslog.clone(),
+                }
+                .build(),
+            ),
             SerializerConfig::Text => Serializer::Text(TextSerializerConfig.build()),
         }
     }
@@ -256,6 +298,9 @@ impl SerializerConfig {
             SerializerConfig::Native => NativeSerializerConfig.input_type(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.input_type(),
+            SerializerConfig::Syslog { syslog } => {
+                SyslogSerializerConfig::new(syslog.clone()).input_type()
+            }
             SerializerConfig::Text => TextSerializerConfig.input_type(),
         }
     }
@@ -268,6 +313,9 @@ impl SerializerConfig {
             SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.schema_requirement(),
+            SerializerConfig::Syslog { syslog } => {
+                SyslogSerializerConfig::new(syslog.clone()).schema_requirement()
+            }
             SerializerConfig::Text => TextSerializerConfig.schema_requirement(),
         }
     }
@@ -286,6 +334,8 @@ pub enum Serializer {
     NativeJson(NativeJsonSerializer),
     /// Uses a `RawMessageSerializer` for serialization.
     RawMessage(RawMessageSerializer),
+    /// Uses a `SyslogSerializer` for serialization.
+    Syslog(SyslogSerializer),
     /// Uses a `TextSerializer` for serialization.
     Text(TextSerializer),
 }
@@ -298,7 +348,8 @@ impl Serializer {
             Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
-            | Serializer::RawMessage(_) => false,
+            | Serializer::RawMessage(_)
+            | Serializer::Syslog(_) => false,
         }
     }
 
@@ -315,7 +366,8 @@ impl Serializer {
             Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
-            | Serializer::RawMessage(_) => {
+            | Serializer::RawMessage(_)
+            | Serializer::Syslog(_) => {
                 panic!("Serializer does not support JSON")
             }
         }
@@ -352,6 +404,12 @@ impl From<RawMessageSerializer> for Serializer {
     }
 }
 
+impl From<SyslogSerializer> for Serializer {
+    fn from(serializer: SyslogSerializer) -> Self {
+        Self::Syslog(serializer)
+    }
+}
+
 impl From<TextSerializer> for Serializer {
     fn from(serializer: TextSerializer) -> Self {
         Self::Text(serializer)
@@ -368,6 +426,7 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
             Serializer::Native(serializer) => serializer.encode(event, buffer),
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
             Serializer::RawMessage(serializer) => serializer.encode(event, buffer),
+            Serializer::Syslog(serializer) => serializer.encode(event, buffer),
             Serializer::Text(serializer) => serializer.encode(event, buffer),
         }
     }
@@ -22,6 +22,7 @@ pub use encoding::{
     JsonSerializer, JsonSerializerConfig, LengthDelimitedEncoder, LengthDelimitedEncoderConfig,
     LogfmtSerializer, LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig,
     NativeSerializer, NativeSerializerConfig, NewlineDelimitedEncoder,
-    NewlineDelimitedEncoderConfig, RawMessageSerializer, RawMessageSerializerConfig,
+    NewlineDelimitedEncoderConfig, OctetCountingEncoder, OctetCountingEncoderConfig,
+    RawMessageSerializer, RawMessageSerializerConfig, SyslogSerializer, SyslogSerializerConfig,
     TextSerializer, TextSerializerConfig,
 };
@@ -4,8 +4,8 @@ pub mod find_enrichment_table_records;
 pub mod get_enrichment_table_record;
 pub mod tables;
 
-#[cfg(test)]
-mod test_util;
+#[cfg(any(test, feature = "test"))]
+pub mod test_util;
 mod vrl_util;
 use std::collections::BTreeMap;
 
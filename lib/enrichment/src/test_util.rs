@@ -8,24 +8,24 @@ use value::Value;
 use crate::{Case, Condition, IndexHandle, Table, TableRegistry};
 
 #[derive(Debug, Clone)]
-pub(crate) struct DummyEnrichmentTable {
+pub struct DummyEnrichmentTable {
     data: BTreeMap<String, Value>,
     indexes: Arc<Mutex<Vec<Vec<String>>>>,
 }
 
 impl DummyEnrichmentTable {
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Self::new_with_index(Arc::new(Mutex::new(Vec::new())))
     }
 
-    pub(crate) fn new_with_index(indexes: Arc<Mutex<Vec<Vec<String>>>>) -> Self {
+    pub fn new_with_index(indexes: Arc<Mutex<Vec<Vec<String>>>>) -> Self {
         Self {
             data: BTreeMap::from([("field".to_string(), Value::from("result"))]),
             indexes,
         }
     }
 
-    pub(crate) fn new_with_data(data: BTreeMap<String, Value>) -> Self {
+    pub fn new_with_data(data: BTreeMap<String, Value>) -> Self {
         Self {
             data,
             indexes: Default::default(),
@@ -33,6 +33,12 @@ impl DummyEnrichmentTable {
     }
 }
 
+impl Default for DummyEnrichmentTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Table for DummyEnrichmentTable {
     fn find_table_row(
         &self,
@@ -70,7 +76,7 @@ impl Table for DummyEnrichmentTable {
 }
 
 /// Create a table registry with dummy data
-pub(crate) fn get_table_registry() -> TableRegistry {
+pub fn get_table_registry() -> TableRegistry {
     let registry = TableRegistry::default();
 
     let mut tables: HashMap<String, Box<dyn Table + Send + Sync>> = HashMap::new();
@@ -82,8 +88,8 @@ pub(crate) fn get_table_registry() -> TableRegistry {
     registry
 }
 
-/// Create a table registry with dummy data
-pub(crate) fn get_table_registry_with_tables(
+/// Create a table registry with the given dummy tables
+pub fn get_table_registry_with_tables(
     tables: Vec<(String, DummyEnrichmentTable)>,
 ) -> TableRegistry {
     let registry = TableRegistry::default();
@@ -98,3 +104,171 @@ pub(crate) fn get_table_registry_with_tables(
 
     registry
 }
+
+/// An enrichment table holding a fixed set of rows, searched the same way as
+/// the file enrichment table. Used as a fixture when testing VRL programs that
+/// look up enrichment data.
+#[derive(Debug, Clone, Default)]
+pub struct FixtureTable {
+    rows: Vec<BTreeMap<String, Value>>,
+}
+
+impl FixtureTable {
+    pub fn new(rows: Vec<BTreeMap<String, Value>>) -> Self {
+        Self { rows }
+    }
+
+    fn find_rows<'a>(
+        &'a self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&'a [String]>,
+    ) -> impl Iterator<Item = BTreeMap<String, Value>> + 'a {
+        self.rows
+            .iter()
+            .filter(move |row| row_matches(case, condition, row))
+            .map(move |row| {
+                row.iter()
+                    .filter(|(field, _)| select.map_or(true, |select| select.contains(field)))
+                    .map(|(field, value)| (field.clone(), value.clone()))
+                    .collect()
+            })
+    }
+}
+
+fn row_matches(case: Case, condition: &[Condition], row: &BTreeMap<String, Value>) -> bool {
+    condition.iter().all(|condition| match condition {
+        Condition::Equals { field, value } => match (case, row.get(*field), value) {
+            (_, None, _) => false,
+            (Case::Insensitive, Some(Value::Bytes(bytes1)), Value::Bytes(bytes2)) => {
+                match (std::str::from_utf8(bytes1), std::str::from_utf8(bytes2)) {
+                    (Ok(s1), Ok(s2)) => s1.to_lowercase() == s2.to_lowercase(),
+                    (Err(_), Err(_)) => bytes1 == bytes2,
+                    _ => false,
+                }
+            }
+            (_, Some(value1), value2) => value1 == value2,
+        },
+        Condition::BetweenDates { field, from, to } => match row.get(*field) {
+            Some(Value::Timestamp(date)) => from <= date && date <= to,
+            _ => false,
+        },
+    })
+}
+
+impl Table for FixtureTable {
+    fn find_table_row(
+        &self,
+        case: Case,
+        condition: &[Condition],
+        select: Option<&[String]>,
+        _index: Option<IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        let mut rows = self.find_rows(case, condition, select);
+
+        match (rows.next(), rows.next()) {
+            (Some(row), None) => Ok(row),
+            (Some(_), Some(_)) => Err("more than one row found".to_string()),
+            (None, _) => Err("no rows found".to_string()),
+        }
+    }
+
+    fn find_table_rows(
+        &self,
+        case: Case,
+        condition: &[Condition],
+        select: Option<&[String]>,
+        _index: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        Ok(self.find_rows(case, condition, select).collect())
+    }
+
+    fn add_index(&mut self, _case: Case, _fields: &[&str]) -> Result<IndexHandle, String> {
+        Ok(IndexHandle(0))
+    }
+
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        Vec::new()
+    }
+
+    fn needs_reload(&self) -> bool {
+        false
+    }
+}
+
+/// Create a table registry with the given fixture tables
+pub fn get_table_registry_with_fixtures(
+    tables: impl IntoIterator<Item = (String, FixtureTable)>,
+) -> TableRegistry {
+    let registry = TableRegistry::default();
+
+    registry.load(
+        tables
+            .into_iter()
+            .map(|(name, table)| (name, Box::new(table) as Box<dyn Table + Send + Sync>))
+            .collect(),
+    );
+
+    registry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> FixtureTable {
+        FixtureTable::new(vec![
+            BTreeMap::from([
+                ("id".to_string(), Value::from(1)),
+                ("name".to_string(), Value::from("Bob")),
+                ("team".to_string(), Value::from("ops")),
+            ]),
+            BTreeMap::from([
+                ("id".to_string(), Value::from(2)),
+                ("name".to_string(), Value::from("Fred")),
+                ("team".to_string(), Value::from("ops")),
+            ]),
+        ])
+    }
+
+    #[test]
+    fn fixture_finds_row() {
+        let condition = [Condition::Equals {
+            field: "name",
+            value: Value::from("bob"),
+        }];
+
+        assert_eq!(
+            Ok(BTreeMap::from([("id".to_string(), Value::from(1))])),
+            fixture().find_table_row(
+                Case::Insensitive,
+                &condition,
+                Some(&["id".to_string()][..]),
+                None
+            )
+        );
+        assert_eq!(
+            Err("no rows found".to_string()),
+            fixture().find_table_row(Case::Sensitive, &condition, None, None)
+        );
+    }
+
+    #[test]
+    fn fixture_finds_rows() {
+        let condition = [Condition::Equals {
+            field: "team",
+            value: Value::from("ops"),
+        }];
+
+        assert_eq!(
+            Err("more than one row found".to_string()),
+            fixture().find_table_row(Case::Sensitive, &condition, None, None)
+        );
+        assert_eq!(
+            Ok(2),
+            fixture()
+                .find_table_rows(Case::Sensitive, &condition, None, None)
+                .map(|rows| rows.len())
+        );
+    }
+}
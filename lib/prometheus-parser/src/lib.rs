@@ -1,6 +1,9 @@
 #![deny(warnings)]
 
-use std::{collections::BTreeMap, convert::TryFrom};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+};
 
 use indexmap::IndexMap;
 use snafu::ResultExt;
@@ -59,6 +62,8 @@ pub enum ParserError {
     MultipleMetricKinds { name: String },
     #[snafu(display("request is missing metric name label"))]
     RequestNoNameLabel,
+    #[snafu(display("invalid native histogram schema: {}", schema))]
+    InvalidHistogramSchema { schema: i32 },
 }
 
 vector_common::impl_event_data_eq!(ParserError);
@@ -88,11 +93,22 @@ pub struct HistogramBucket {
     pub count: u32,
 }
 
+/// An exemplar attached to a histogram bucket, linking it to e.g. a trace.
+#[derive(Debug, Default, PartialEq)]
+pub struct Exemplar {
+    /// The upper limit of the bucket the exemplar belongs to.
+    pub bucket: f64,
+    pub value: f64,
+    pub timestamp: Option<i64>,
+    pub labels: BTreeMap<String, String>,
+}
+
 #[derive(Debug, Default, PartialEq)]
 pub struct HistogramMetric {
     pub buckets: Vec<HistogramBucket>,
     pub sum: f64,
     pub count: u32,
+    pub exemplars: Vec<Exemplar>,
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -235,6 +251,10 @@ impl GroupKind {
 pub struct MetricGroup {
     pub name: String,
     pub metrics: GroupKind,
+    /// The help text of the metric family, if known.
+    pub help: Option<String>,
+    /// The unit of the metric family, if known.
+    pub unit: Option<String>,
 }
 
 fn try_f64_to_u32(f: f64) -> Result<u32, ParserError> {
@@ -248,7 +268,12 @@ fn try_f64_to_u32(f: f64) -> Result<u32, ParserError> {
 impl MetricGroup {
     fn new(name: String, kind: MetricKind) -> Self {
         let metrics = GroupKind::new(kind);
-        MetricGroup { name, metrics }
+        MetricGroup {
+            name,
+            metrics,
+            help: None,
+            unit: None,
+        }
     }
 
     // For cases where a metric group was not defined with `# TYPE ...`.
@@ -263,6 +288,8 @@ impl MetricGroup {
         MetricGroup {
             name,
             metrics: GroupKind::new_untyped(key, value),
+            help: None,
+            unit: None,
         }
     }
 
@@ -312,35 +339,49 @@ pub fn parse_text(input: &str) -> Result<Vec<MetricGroup>, ParserError> {
 }
 
 #[derive(Default)]
-struct MetricGroupSet(IndexMap<String, GroupKind>);
+struct MetricGroupSet {
+    groups: IndexMap<String, GroupKind>,
+    metadata: HashMap<String, (Option<String>, Option<String>)>,
+}
 
 impl MetricGroupSet {
     fn get_group<'a>(&'a mut self, name: &str) -> (usize, &'a String, &'a mut GroupKind) {
         let len = name.len();
-        let name = if self.0.contains_key(name) {
+        let name = if self.groups.contains_key(name) {
             name
-        } else if name.ends_with("_bucket") && self.0.contains_key(&name[..len - 7]) {
+        } else if name.ends_with("_bucket") && self.groups.contains_key(&name[..len - 7]) {
             &name[..len - 7]
-        } else if name.ends_with("_sum") && self.0.contains_key(&name[..len - 4]) {
+        } else if name.ends_with("_sum") && self.groups.contains_key(&name[..len - 4]) {
             &name[..len - 4]
-        } else if name.ends_with("_count") && self.0.contains_key(&name[..len - 6]) {
+        } else if name.ends_with("_count") && self.groups.contains_key(&name[..len - 6]) {
             &name[..len - 6]
         } else {
-            self.0
+            self.groups
                 .insert(name.into(), GroupKind::new(MetricKind::Untyped));
             name
         };
-        self.0.get_full_mut(name).unwrap()
+        self.groups.get_full_mut(name).unwrap()
     }
 
-    fn insert_metadata(&mut self, name: String, kind: MetricKind) -> Result<(), ParserError> {
-        match self.0.get(&name) {
+    fn insert_metadata(
+        &mut self,
+        name: String,
+        kind: MetricKind,
+        help: String,
+        unit: String,
+    ) -> Result<(), ParserError> {
+        let non_empty = |text: String| (!text.is_empty()).then(|| text);
+        if !help.is_empty() || !unit.is_empty() {
+            self.metadata
+                .insert(name.clone(), (non_empty(help), non_empty(unit)));
+        }
+        match self.groups.get(&name) {
             Some(group) if !group.matches_kind(kind) => {
                 Err(ParserError::MultipleMetricKinds { name })
             }
             Some(_) => Ok(()), // metadata already exists and is the right type
             None => {
-                self.0.insert(name, GroupKind::new(kind));
+                self.groups.insert(name, GroupKind::new(kind));
                 Ok(())
             }
         }
@@ -367,15 +408,215 @@ impl MetricGroupSet {
                 labels: metric.labels,
             };
             let group = GroupKind::new_untyped(key, metric.value);
-            self.0.insert(metric.name, group);
+            self.groups.insert(metric.name, group);
+        }
+        Ok(())
+    }
+
+    /// Attach the exemplars of a classic histogram `_bucket` series to the
+    /// histogram sample it was recorded with.
+    fn insert_exemplars(
+        &mut self,
+        name: &str,
+        labels: &BTreeMap<String, String>,
+        timestamp: Option<i64>,
+        exemplars: Vec<proto::Exemplar>,
+    ) -> Result<(), ParserError> {
+        if !name.ends_with("_bucket") {
+            return Ok(());
+        }
+        let mut labels = labels.clone();
+        let bucket = labels.remove("le").ok_or(ParserError::ExpectedLeTag)?;
+        let (_, bucket) = line::Metric::parse_value(&bucket)
+            .map_err(Into::into)
+            .context(ParseLabelValueSnafu)?;
+        if let (_, _, GroupKind::Histogram(metrics)) = self.get_group(name) {
+            let key = GroupKey { timestamp, labels };
+            matching_group(metrics, key)
+                .exemplars
+                .extend(exemplars.into_iter().map(|exemplar| Exemplar {
+                    bucket,
+                    ..Exemplar::from(exemplar)
+                }));
         }
         Ok(())
     }
 
-    fn finish(self) -> Vec<MetricGroup> {
-        self.0
+    fn insert_native_histograms(
+        &mut self,
+        name: &str,
+        labels: &BTreeMap<String, String>,
+        histograms: Vec<proto::Histogram>,
+        exemplars: Vec<proto::Exemplar>,
+    ) -> Result<(), ParserError> {
+        let group = self
+            .groups
+            .entry(name.into())
+            .or_insert_with(|| GroupKind::new(MetricKind::Histogram));
+        // Without metadata, the group may have been created as untyped.
+        if matches!(group, GroupKind::Untyped(metrics) if metrics.is_empty()) {
+            *group = GroupKind::new(MetricKind::Histogram);
+        }
+        let metrics = match group {
+            GroupKind::Histogram(metrics) => metrics,
+            _ => return Err(ParserError::MultipleMetricKinds { name: name.into() }),
+        };
+
+        let mut exemplars = exemplars
             .into_iter()
-            .map(|(name, metrics)| MetricGroup { name, metrics })
+            .map(Exemplar::from)
+            .collect::<Vec<_>>();
+        for histogram in histograms {
+            let key = GroupKey {
+                timestamp: Some(histogram.timestamp),
+                labels: labels.clone(),
+            };
+            let mut metric = native_histogram_to_classic(&histogram)?;
+            // Exemplars are not tied to a specific bucket in native histograms,
+            // so assign them to the bucket their value falls into.
+            for mut exemplar in exemplars.drain(..) {
+                exemplar.bucket = metric
+                    .buckets
+                    .iter()
+                    .map(|bucket| bucket.bucket)
+                    .find(|bucket| exemplar.value <= *bucket)
+                    .unwrap_or(f64::INFINITY);
+                metric.exemplars.push(exemplar);
+            }
+            metrics.insert(key, metric);
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Vec<MetricGroup> {
+        self.groups
+            .into_iter()
+            .map(|(name, metrics)| {
+                let (help, unit) = self.metadata.remove(&name).unwrap_or_default();
+                MetricGroup {
+                    name,
+                    metrics,
+                    help,
+                    unit,
+                }
+            })
+            .collect()
+    }
+}
+
+impl From<proto::Exemplar> for Exemplar {
+    fn from(exemplar: proto::Exemplar) -> Self {
+        Self {
+            bucket: f64::INFINITY,
+            value: exemplar.value,
+            timestamp: (exemplar.timestamp != 0).then(|| exemplar.timestamp),
+            labels: exemplar
+                .labels
+                .into_iter()
+                .map(|label| (label.name, label.value))
+                .collect(),
+        }
+    }
+}
+
+/// Convert a native histogram into cumulative buckets with explicit upper
+/// limits, as they would have been reported by a classic histogram.
+fn native_histogram_to_classic(
+    histogram: &proto::Histogram,
+) -> Result<HistogramMetric, ParserError> {
+    use proto::histogram::{Count, ZeroCount};
+
+    if !(-4..=8).contains(&histogram.schema) {
+        return Err(ParserError::InvalidHistogramSchema {
+            schema: histogram.schema,
+        });
+    }
+    // Bucket boundaries are powers of 2^(2^-schema).
+    let factor = 2_f64.powi(-histogram.schema);
+    let boundary = |index: i32| 2_f64.powf(index as f64 * factor);
+
+    let mut buckets = Vec::new();
+    let zero_count = match histogram.zero_count {
+        Some(ZeroCount::ZeroCountInt(count)) => count as f64,
+        Some(ZeroCount::ZeroCountFloat(count)) => count,
+        None => 0.0,
+    };
+    if zero_count > 0.0 {
+        buckets.push((histogram.zero_threshold, zero_count));
+    }
+    for (index, count) in native_buckets(
+        &histogram.positive_spans,
+        &histogram.positive_deltas,
+        &histogram.positive_counts,
+    ) {
+        // Positive bucket `index` covers (boundary(index - 1), boundary(index)].
+        buckets.push((boundary(index), count));
+    }
+    for (index, count) in native_buckets(
+        &histogram.negative_spans,
+        &histogram.negative_deltas,
+        &histogram.negative_counts,
+    ) {
+        // Negative bucket `index` covers [-boundary(index), -boundary(index - 1)).
+        buckets.push((-boundary(index - 1), count));
+    }
+    buckets.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut cumulative = 0.0;
+    let mut buckets = buckets
+        .into_iter()
+        .map(|(bucket, count)| {
+            cumulative += count;
+            try_f64_to_u32(cumulative).map(|count| HistogramBucket { bucket, count })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let count = match histogram.count {
+        Some(Count::CountInt(count)) => count as f64,
+        Some(Count::CountFloat(count)) => count,
+        None => cumulative,
+    };
+    let count = try_f64_to_u32(count)?;
+    buckets.push(HistogramBucket {
+        bucket: f64::INFINITY,
+        count,
+    });
+
+    Ok(HistogramMetric {
+        buckets,
+        sum: histogram.sum,
+        count,
+        exemplars: Vec::new(),
+    })
+}
+
+/// Expand the spans of a native histogram into `(index, count)` pairs, taking
+/// the counts either from the integer deltas or from the float counts.
+fn native_buckets(spans: &[proto::BucketSpan], deltas: &[i64], counts: &[f64]) -> Vec<(i32, f64)> {
+    let mut indexes = Vec::new();
+    let mut index = 0;
+    for span in spans {
+        // The offset of the first span is the index of its first bucket, the
+        // offset of all following spans is relative to the end of the
+        // previous span.
+        index += span.offset;
+        for _ in 0..span.length {
+            indexes.push(index);
+            index += 1;
+        }
+    }
+
+    if deltas.is_empty() {
+        indexes.into_iter().zip(counts.iter().copied()).collect()
+    } else {
+        let mut count = 0;
+        indexes
+            .into_iter()
+            .zip(deltas)
+            .map(|(index, delta)| {
+                count += delta;
+                (index, count as f64)
+            })
             .collect()
     }
 }
@@ -390,7 +631,7 @@ pub fn parse_request(request: proto::WriteRequest) -> Result<Vec<MetricGroup>, P
         let kind = proto::MetricType::try_from(metadata.r#type)
             .unwrap_or(proto::MetricType::Unknown)
             .into();
-        groups.insert_metadata(name, kind)?;
+        groups.insert_metadata(name, kind, metadata.help, metadata.unit)?;
     }
 
     for timeseries in request.timeseries {
@@ -404,9 +645,23 @@ pub fn parse_request(request: proto::WriteRequest) -> Result<Vec<MetricGroup>, P
             None => return Err(ParserError::RequestNoNameLabel),
         };
 
+        if !timeseries.histograms.is_empty() {
+            groups.insert_native_histograms(
+                &name,
+                &labels,
+                timeseries.histograms,
+                timeseries.exemplars,
+            )?;
+            continue;
+        }
+
+        let timestamp = timeseries.samples.last().map(|sample| sample.timestamp);
         for sample in timeseries.samples {
             groups.insert_sample(&name, &labels, sample)?;
         }
+        if !timeseries.exemplars.is_empty() {
+            groups.insert_exemplars(&name, &labels, timestamp, timeseries.exemplars)?;
+        }
     }
 
     Ok(groups.finish())
@@ -690,6 +945,8 @@ mod test {
                     samples: vec![
                         $( proto::Sample { value: $sample as f64, timestamp: $timestamp as i64 }, )*
                     ],
+                    exemplars: vec![],
+                    histograms: vec![],
                 }, )* ],
             }
         };
@@ -793,6 +1050,7 @@ mod test {
                         ],
                         count: 19,
                         sum: 12.0,
+                        exemplars: vec![],
                     })
             );
         });
@@ -840,4 +1098,112 @@ mod test {
             assert_eq!(metrics.get_index(0).unwrap(), simple_metric!(Some(1395066367700), labels!(), 24.0));
         });
     }
+
+    #[test]
+    fn parse_request_metadata_help_and_unit() {
+        let mut request = write_request!(["one" = Gauge], []);
+        request.metadata[0].help = "Some help.".into();
+        request.metadata[0].unit = "seconds".into();
+
+        let parsed = parse_request(request).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].help.as_deref(), Some("Some help."));
+        assert_eq!(parsed[0].unit.as_deref(), Some("seconds"));
+    }
+
+    #[test]
+    fn parse_request_histogram_exemplars() {
+        let mut request = write_request!(
+            ["one" = Histogram],
+            [
+                [__name__ => "one_bucket", le => "1"] => [ 15 @ 1395066367700 ],
+                [__name__ => "one_count"] => [ 15 @ 1395066367700 ],
+                [__name__ => "one_sum"] => [ 12 @ 1395066367700 ]
+            ]
+        );
+        request.timeseries[0].exemplars.push(proto::Exemplar {
+            labels: vec![proto::Label {
+                name: "trace_id".into(),
+                value: "abc123".into(),
+            }],
+            value: 0.7,
+            timestamp: 1395066367650,
+        });
+
+        let parsed = parse_request(request).unwrap();
+        match_group!(parsed[0], "one", Histogram => |metrics: &MetricMap<HistogramMetric>| {
+            let (_, metric) = metrics.get_index(0).unwrap();
+            assert_eq!(
+                metric.exemplars,
+                vec![Exemplar {
+                    bucket: 1.0,
+                    value: 0.7,
+                    timestamp: Some(1395066367650),
+                    labels: labels!(trace_id => "abc123"),
+                }]
+            );
+        });
+    }
+
+    #[test]
+    fn parse_request_native_histogram() {
+        let request = proto::WriteRequest {
+            metadata: vec![],
+            timeseries: vec![proto::TimeSeries {
+                labels: vec![proto::Label {
+                    name: "__name__".into(),
+                    value: "one".into(),
+                }],
+                samples: vec![],
+                exemplars: vec![],
+                histograms: vec![proto::Histogram {
+                    count: Some(proto::histogram::Count::CountInt(7)),
+                    sum: 9.5,
+                    // Each power of two is divided into two buckets.
+                    schema: 1,
+                    zero_threshold: 0.001,
+                    zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(1)),
+                    negative_spans: vec![],
+                    negative_deltas: vec![],
+                    negative_counts: vec![],
+                    // Buckets 1 and 2, then a gap of one bucket, then bucket 4.
+                    positive_spans: vec![
+                        proto::BucketSpan {
+                            offset: 1,
+                            length: 2,
+                        },
+                        proto::BucketSpan {
+                            offset: 1,
+                            length: 1,
+                        },
+                    ],
+                    positive_deltas: vec![2, 1, -2],
+                    positive_counts: vec![],
+                    reset_hint: 0,
+                    timestamp: 1395066367700,
+                }],
+            }],
+        };
+
+        let parsed = parse_request(request).unwrap();
+        assert_eq!(parsed.len(), 1);
+        match_group!(parsed[0], "one", Histogram => |metrics: &MetricMap<HistogramMetric>| {
+            let (key, metric) = metrics.get_index(0).unwrap();
+            assert_eq!(key.timestamp, Some(1395066367700));
+            assert_eq!(metric.count, 7);
+            assert_eq!(metric.sum, 9.5);
+            let buckets = metric
+                .buckets
+                .iter()
+                .map(|bucket| (bucket.bucket, bucket.count))
+                .collect::<Vec<_>>();
+            assert_eq!(buckets.len(), 5);
+            assert_eq!(buckets[0], (0.001, 1));
+            assert!((buckets[1].0 - 2_f64.sqrt()).abs() < 1e-9);
+            assert_eq!(buckets[1].1, 3);
+            assert_eq!(buckets[2], (2.0, 6));
+            assert_eq!(buckets[3], (4.0, 7));
+            assert_eq!(buckets[4], (f64::INFINITY, 7));
+        });
+    }
 }
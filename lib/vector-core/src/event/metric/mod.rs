@@ -138,6 +138,11 @@ impl Metric {
         &self.series
     }
 
+    /// Gets a mutable reference to the series of this metric.
+    pub fn series_mut(&mut self) -> &mut MetricSeries {
+        &mut self.series
+    }
+
     /// Gets a reference to the data of this metric.
     pub fn data(&self) -> &MetricData {
         &self.data
@@ -25,6 +25,7 @@ pub enum Transform {
     Function(Box<dyn FunctionTransform>),
     Synchronous(Box<dyn SyncTransform>),
     Task(Box<dyn TaskTransform<EventArray>>),
+    MultiOutputTask(Box<dyn MultiOutputTaskTransform>),
 }
 
 impl Transform {
@@ -103,6 +104,14 @@ impl Transform {
         Transform::Task(Box::new(WrapEventTask(v)))
     }
 
+    /// Create a new task transform that writes to multiple outputs.
+    ///
+    /// Like [`Transform::event_task`], but the task emits [`TransformOutputsBuf`]s, allowing it to
+    /// write to the outputs returned via `TransformConfig::outputs`.
+    pub fn multi_output_task(v: impl MultiOutputTaskTransform + 'static) -> Self {
+        Transform::MultiOutputTask(Box::new(v))
+    }
+
     /// Mutably borrow the inner transform as a task transform.
     ///
     /// # Panics
@@ -175,6 +184,19 @@ pub trait TaskTransform<T: EventContainer + 'static>: Send + 'static {
     }
 }
 
+/// Task transforms that write to multiple outputs.
+///
+/// These are coordinated like [`TaskTransform`]s, but map a stream of `Event`s to a stream of
+/// [`TransformOutputsBuf`]s. Those outputs must be known in advanced and returned via
+/// `TransformConfig::outputs`. Attempting to send to any output not registered in advance is
+/// considered a bug and will cause a panic.
+pub trait MultiOutputTaskTransform: Send + 'static {
+    fn transform(
+        self: Box<Self>,
+        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = TransformOutputsBuf> + Send>>;
+}
+
 /// Broader than the simple [`FunctionTransform`], this trait allows transforms to write to
 /// multiple outputs. Those outputs must be known in advanced and returned via
 /// `TransformConfig::outputs`. Attempting to send to any output not registered in advance is
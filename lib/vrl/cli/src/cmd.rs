@@ -8,18 +8,25 @@ use std::{
 };
 
 use ::value::Value;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use value::Secrets;
 use vector_common::TimeZone;
 use vrl::{diagnostic::Formatter, state, Program, Runtime, Target, VrlRuntime};
 
 #[cfg(feature = "repl")]
 use super::repl;
-use super::Error;
+use super::{fmt, lsp, test, Error};
 
 #[derive(Parser, Debug)]
-#[clap(name = "VRL", about = "Vector Remap Language CLI")]
+#[clap(
+    name = "VRL",
+    about = "Vector Remap Language CLI",
+    args_conflicts_with_subcommands = true
+)]
 pub struct Opts {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     /// The VRL program to execute. The program ".foo = true", for example, sets the event object's
     /// `foo` field to `true`.
     #[clap(name = "PROGRAM")]
@@ -51,6 +58,18 @@ pub struct Opts {
     print_warnings: bool,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the VRL test files found in the given paths.
+    Test(test::Opts),
+
+    /// Format VRL programs.
+    Fmt(fmt::Opts),
+
+    /// Run a language server for VRL programs, communicating over stdin and stdout.
+    Lsp(lsp::Opts),
+}
+
 impl Opts {
     fn timezone(&self) -> Result<TimeZone, Error> {
         if let Some(ref tz) = self.timezone {
@@ -92,6 +111,13 @@ impl Opts {
 }
 
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match &opts.command {
+        Some(Command::Test(opts)) => return test::cmd(opts),
+        Some(Command::Fmt(opts)) => return fmt::cmd(opts),
+        Some(Command::Lsp(opts)) => return lsp::cmd(opts),
+        None => {}
+    }
+
     match run(opts) {
         Ok(_) => exitcode::OK,
         Err(err) => {
@@ -120,7 +146,14 @@ fn run(opts: &Opts) -> Result<(), Error> {
     } else {
         let objects = opts.read_into_objects()?;
         let source = opts.read_program()?;
-        let (program, warnings) = vrl::compile(&source, &stdlib::all()).map_err(|diagnostics| {
+        let mut external = state::ExternalEnv::default();
+        // Imports of a program file are relative to that file.
+        if let Some(dir) = opts.program_file.as_ref().and_then(|path| path.parent()) {
+            external.set_import_dir(dir);
+        }
+
+        let (program, warnings) = vrl::compile_with_state(&source, &stdlib::all(), &mut external)
+            .map_err(|diagnostics| {
             Error::Parse(Formatter::new(&source, diagnostics).colored().to_string())
         })?;
 
@@ -183,7 +216,7 @@ fn repl(_objects: Vec<Value>, _timezone: &TimeZone, _vrl_runtime: VrlRuntime) ->
     Err(Error::ReplFeature)
 }
 
-fn execute(
+pub(crate) fn execute(
     object: &mut impl Target,
     program: &Program,
     timezone: &TimeZone,
@@ -194,6 +227,9 @@ fn execute(
         VrlRuntime::Ast => runtime
             .resolve(object, program, timezone)
             .map_err(Error::Runtime),
+        VrlRuntime::Vm => runtime
+            .run_vm(object, &program.compile_to_vm(), timezone)
+            .map_err(Error::Runtime),
     }
 }
 
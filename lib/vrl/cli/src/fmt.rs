@@ -0,0 +1,86 @@
+//! Formats VRL programs.
+
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::PathBuf,
+};
+
+use clap::Parser;
+use vrl::diagnostic::{DiagnosticList, Formatter};
+
+use super::Error;
+
+#[derive(Parser, Debug)]
+pub struct Opts {
+    /// The VRL files to format in place. The program is read from stdin, and written to stdout,
+    /// if no file is given.
+    #[clap(name = "PATH", parse(from_os_str))]
+    paths: Vec<PathBuf>,
+
+    /// Don't write the formatted programs, but exit with an error if any of them isn't formatted.
+    #[clap(short, long)]
+    check: bool,
+}
+
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match run(opts) {
+        Ok(true) => exitcode::OK,
+        Ok(false) => exitcode::DATAERR,
+        Err(err) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{}", err);
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+/// Formats the programs, returning whether all of them were already formatted
+/// when checking them.
+#[allow(clippy::print_stdout)]
+fn run(opts: &Opts) -> Result<bool, Error> {
+    if opts.paths.is_empty() {
+        let mut source = String::new();
+        io::stdin().read_to_string(&mut source)?;
+
+        let formatted = format(&source)?;
+        if opts.check {
+            return Ok(formatted == source);
+        }
+
+        io::stdout().write_all(formatted.as_bytes())?;
+
+        return Ok(true);
+    }
+
+    let mut formatted_all = true;
+
+    for path in &opts.paths {
+        let source = fs::read_to_string(path)?;
+        let formatted =
+            format(&source).map_err(|err| Error::Parse(format!("{}:\n{}", path.display(), err)))?;
+
+        if formatted == source {
+            continue;
+        }
+
+        if opts.check {
+            println!("{} isn't formatted", path.display());
+            formatted_all = false;
+        } else {
+            fs::write(path, formatted)?;
+        }
+    }
+
+    Ok(formatted_all)
+}
+
+fn format(source: &str) -> Result<String, Error> {
+    parser::format(source).map_err(|err| {
+        let diagnostics = DiagnosticList::from(vec![Box::new(err) as Box<_>]);
+
+        Error::Parse(Formatter::new(source, diagnostics).to_string())
+    })
+}
@@ -7,8 +7,11 @@
 #![deny(unused_comparisons)]
 
 pub mod cmd;
+pub mod fmt;
+pub mod lsp;
 #[cfg(feature = "repl")]
 mod repl;
+pub mod test;
 
 pub use cmd::{cmd, Opts};
 
@@ -27,6 +30,9 @@ pub enum Error {
     #[error("input error: {}", .0)]
     Json(#[from] serde_json::Error),
 
+    #[error("invalid test file {}: {}", .0.display(), .1)]
+    TestFile(std::path::PathBuf, String),
+
     #[error("repl feature disabled, program input required")]
     ReplFeature,
 }
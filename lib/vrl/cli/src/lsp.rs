@@ -0,0 +1,385 @@
+//! A language server for VRL programs, speaking the language server protocol
+//! over stdin and stdout.
+//!
+//! Documents are synced in full, and compiled on every change to publish
+//! their diagnostics. Hovering shows the signature and documentation of
+//! functions, and the type of event paths. Completion offers the functions of
+//! the standard library, and the event paths the program uses.
+
+mod document;
+
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Read, Write},
+};
+
+use clap::Parser;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value as JsonValue};
+use vrl::Function;
+
+use self::document::{Document, Position};
+use super::Error;
+
+/// The error code of requests for methods the server doesn't support.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// The error code of requests with invalid parameters.
+const INVALID_PARAMS: i64 = -32602;
+
+/// The error code of requests the server failed to handle.
+const INTERNAL_ERROR: i64 = -32603;
+
+#[derive(Parser, Debug)]
+pub struct Opts {}
+
+pub fn cmd(_opts: &Opts) -> exitcode::ExitCode {
+    let stdin = io::stdin();
+    let mut server = Server {
+        functions: functions(),
+        documents: HashMap::new(),
+        output: io::stdout(),
+        shutdown: false,
+    };
+
+    match server.run(&mut stdin.lock()) {
+        Ok(true) => exitcode::OK,
+        // The client exited without shutting down the server first.
+        Ok(false) => exitcode::SOFTWARE,
+        Err(err) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{}", err);
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct Message {
+    id: Option<JsonValue>,
+    method: Option<String>,
+    #[serde(default)]
+    params: JsonValue,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct TextDocumentItem {
+    uri: String,
+    text: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct TextDocumentIdentifier {
+    uri: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct DidOpenParams {
+    text_document: TextDocumentItem,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct DidChangeParams {
+    text_document: TextDocumentIdentifier,
+    content_changes: Vec<ContentChange>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ContentChange {
+    text: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct DidCloseParams {
+    text_document: TextDocumentIdentifier,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PositionParams {
+    text_document: TextDocumentIdentifier,
+    position: Position,
+}
+
+struct Server<W> {
+    functions: Vec<Box<dyn Function>>,
+    documents: HashMap<String, Document>,
+    output: W,
+    shutdown: bool,
+}
+
+impl<W: Write> Server<W> {
+    /// Handles messages until the client exits, returning whether the server
+    /// was shut down first.
+    fn run(&mut self, input: &mut impl BufRead) -> Result<bool, Error> {
+        while let Some(message) = read_message(input)? {
+            let message: Message = serde_json::from_value(message)?;
+
+            match message.method.as_deref() {
+                Some("exit") => return Ok(self.shutdown),
+                Some(method) => self.handle(method, message.id, message.params)?,
+                // Responses to requests of the server, which it doesn't send.
+                None => {}
+            }
+        }
+
+        Ok(self.shutdown)
+    }
+
+    fn handle(
+        &mut self,
+        method: &str,
+        id: Option<JsonValue>,
+        params: JsonValue,
+    ) -> Result<(), Error> {
+        let result = match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    // Documents are synced in full.
+                    "textDocumentSync": 1,
+                    "hoverProvider": true,
+                    "completionProvider": { "triggerCharacters": ["."] },
+                },
+                "serverInfo": { "name": "vrl" },
+            })),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(JsonValue::Null)
+            }
+            "textDocument/didOpen" => parse(params).and_then(|params: DidOpenParams| {
+                let doc = params.text_document;
+                self.update(doc.uri, doc.text, false)
+            }),
+            "textDocument/didChange" => parse(params).and_then(|params: DidChangeParams| {
+                match params.content_changes.into_iter().last() {
+                    Some(change) => self.update(params.text_document.uri, change.text, true),
+                    None => Ok(JsonValue::Null),
+                }
+            }),
+            "textDocument/didClose" => parse(params).and_then(|params: DidCloseParams| {
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                self.publish_diagnostics(&uri, json!([]))?;
+
+                Ok(JsonValue::Null)
+            }),
+            "textDocument/hover" => parse(params).map(|params: PositionParams| {
+                self.documents
+                    .get(&params.text_document.uri)
+                    .and_then(|doc| doc.hover(params.position, &self.functions))
+                    .map_or(
+                        JsonValue::Null,
+                        |value| json!({ "contents": { "kind": "markdown", "value": value } }),
+                    )
+            }),
+            "textDocument/completion" => parse(params).map(|params: PositionParams| {
+                self.documents.get(&params.text_document.uri).map_or_else(
+                    || json!([]),
+                    |doc| doc.completions(params.position, &self.functions),
+                )
+            }),
+            _ => Err((METHOD_NOT_FOUND, format!("unsupported method: {}", method))),
+        };
+
+        // Notifications don't get a response, even if they fail.
+        let id = match id {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+
+        match result {
+            Ok(result) => self.send(json!({ "jsonrpc": "2.0", "id": id, "result": result })),
+            Err((code, message)) => self.send(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message },
+            })),
+        }
+    }
+
+    /// Compiles a new or changed document, and publishes its diagnostics.
+    fn update(
+        &mut self,
+        uri: String,
+        source: String,
+        changed: bool,
+    ) -> Result<JsonValue, (i64, String)> {
+        let previous = if changed {
+            self.documents.get(&uri)
+        } else {
+            None
+        };
+        let doc = Document::new(source, &self.functions, previous);
+
+        self.publish_diagnostics(&uri, doc.diagnostics())?;
+        self.documents.insert(uri, doc);
+
+        Ok(JsonValue::Null)
+    }
+
+    fn publish_diagnostics(
+        &mut self,
+        uri: &str,
+        diagnostics: JsonValue,
+    ) -> Result<(), (i64, String)> {
+        self.send(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        }))
+        .map_err(|err| (INTERNAL_ERROR, err.to_string()))
+    }
+
+    fn send(&mut self, message: JsonValue) -> Result<(), Error> {
+        let body = message.to_string();
+
+        write!(
+            self.output,
+            "Content-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )?;
+        self.output.flush()?;
+
+        Ok(())
+    }
+}
+
+fn parse<T: DeserializeOwned>(params: JsonValue) -> Result<T, (i64, String)> {
+    serde_json::from_value(params).map_err(|err| (INVALID_PARAMS, err.to_string()))
+}
+
+/// Reads a message, which is a JSON body preceded by headers, of which only
+/// `Content-Length` is required. Returns `None` at the end of the input.
+fn read_message(input: &mut impl BufRead) -> Result<Option<JsonValue>, Error> {
+    let mut length = None;
+
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let length = length.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length header")
+    })?;
+
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+fn functions() -> Vec<Box<dyn Function>> {
+    let mut functions = stdlib::all();
+    functions.extend(vector_vrl_functions::vrl_functions());
+    functions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(body: JsonValue) -> String {
+        let body = body.to_string();
+
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    }
+
+    /// Runs the server with the given messages, returning the messages it
+    /// sent.
+    fn run(messages: Vec<JsonValue>) -> Vec<JsonValue> {
+        let input = messages.into_iter().map(message).collect::<String>();
+        let mut server = Server {
+            functions: functions(),
+            documents: HashMap::new(),
+            output: vec![],
+            shutdown: false,
+        };
+
+        assert!(server.run(&mut input.as_bytes()).unwrap());
+
+        let mut output = server.output.as_slice();
+        let mut messages = vec![];
+        while let Some(message) = read_message(&mut output).unwrap() {
+            messages.push(message);
+        }
+
+        messages
+    }
+
+    #[test]
+    fn session() {
+        let uri = "file:///test.vrl";
+        let messages = run(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+            json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": {
+                    "textDocument": {
+                        "uri": uri,
+                        "languageId": "vrl",
+                        "version": 1,
+                        "text": ".message = upcase(.message)",
+                    },
+                },
+            }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "textDocument/hover",
+                "params": {
+                    "textDocument": { "uri": uri },
+                    "position": { "line": 0, "character": 12 },
+                },
+            }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "textDocument/formatting", "params": {} }),
+            json!({ "jsonrpc": "2.0", "id": 4, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ]);
+
+        assert_eq!(messages.len(), 5);
+        assert_eq!(messages[0]["id"], 1);
+        assert_eq!(messages[0]["result"]["capabilities"]["hoverProvider"], true);
+
+        assert_eq!(messages[1]["method"], "textDocument/publishDiagnostics");
+        assert_eq!(messages[1]["params"]["uri"], uri);
+        assert_eq!(
+            messages[1]["params"]["diagnostics"][0]["severity"], 1,
+            "upcase of a value of any type is an error"
+        );
+
+        assert_eq!(messages[2]["id"], 2);
+        assert!(messages[2]["result"]["contents"]["value"]
+            .as_str()
+            .unwrap()
+            .contains("upcase(value: string)"));
+
+        assert_eq!(messages[3]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(
+            messages[4],
+            json!({ "jsonrpc": "2.0", "id": 4, "result": null })
+        );
+    }
+}
@@ -0,0 +1,397 @@
+use std::collections::BTreeSet;
+
+use ::value::Kind;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use vrl::{
+    diagnostic::{Diagnostic, DiagnosticList, Severity, Span},
+    state, Function,
+};
+
+/// A position in a document, as a zero-based line and UTF-16 code unit
+/// offset, as defined by the language server protocol.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Position {
+    pub(crate) line: u32,
+    pub(crate) character: u32,
+}
+
+/// An open VRL document, along with what the compiler knows about it.
+#[derive(Debug)]
+pub(crate) struct Document {
+    source: String,
+
+    /// The byte offset of the start of every line.
+    line_starts: Vec<usize>,
+
+    diagnostics: DiagnosticList,
+
+    /// The type of the event after the program ran.
+    target_kind: Kind,
+
+    /// The event paths read or written by the program.
+    paths: BTreeSet<String>,
+}
+
+impl Document {
+    /// Compiles the source of a document. The event paths of the previous
+    /// version of the document are kept if the source doesn't compile, which
+    /// it usually doesn't while it's being typed.
+    pub(crate) fn new(
+        source: String,
+        functions: &[Box<dyn Function>],
+        previous: Option<&Document>,
+    ) -> Self {
+        let mut external = state::ExternalEnv::default();
+        let (diagnostics, paths) = match vrl::compile_with_state(&source, functions, &mut external)
+        {
+            Ok((program, warnings)) => {
+                let info = program.info();
+                let paths = info
+                    .target_queries
+                    .iter()
+                    .chain(&info.target_assignments)
+                    .filter(|path| !path.is_root())
+                    .map(|path| format!(".{}", path))
+                    .collect();
+
+                (warnings, paths)
+            }
+            Err(diagnostics) => (
+                diagnostics,
+                previous.map(|doc| doc.paths.clone()).unwrap_or_default(),
+            ),
+        };
+
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+
+        Self {
+            source,
+            line_starts,
+            diagnostics,
+            target_kind: external.target_kind().clone(),
+            paths,
+        }
+    }
+
+    /// The diagnostics of the document, as language server protocol
+    /// diagnostics.
+    pub(crate) fn diagnostics(&self) -> JsonValue {
+        self.diagnostics
+            .iter()
+            .map(|diagnostic| {
+                json!({
+                    "range": self.range(primary_span(diagnostic)),
+                    "severity": severity(diagnostic.severity()),
+                    "source": "vrl",
+                    "message": message(diagnostic),
+                })
+            })
+            .collect()
+    }
+
+    /// The markdown shown when hovering over the given position, which is the
+    /// signature of a function, or the type of an event path.
+    pub(crate) fn hover(
+        &self,
+        position: Position,
+        functions: &[Box<dyn Function>],
+    ) -> Option<String> {
+        let offset = self.offset(position);
+        let (start, end) = self.word_at(offset, is_path_char);
+        let word = &self.source[start..end];
+
+        if word.starts_with('.') {
+            let path = parser::parse_path(word).ok()?;
+            let kind = self.target_kind.find_at_path(&path.to_lookup()).ok()??;
+
+            return Some(format!("```vrl\n{}: {}\n```", word, kind));
+        }
+
+        let (start, end) = self.word_at(offset, is_ident_char);
+        let ident = &self.source[start..end];
+        let is_call = self.source[end..].starts_with('(') || self.source[end..].starts_with("!(");
+        if !is_call {
+            return None;
+        }
+
+        let function = functions.iter().find(|f| f.identifier() == ident)?;
+
+        Some(documentation(function.as_ref()))
+    }
+
+    /// The completions at the given position. Event paths are completed after
+    /// a dot, functions otherwise.
+    pub(crate) fn completions(
+        &self,
+        position: Position,
+        functions: &[Box<dyn Function>],
+    ) -> JsonValue {
+        let offset = self.offset(position);
+        let (start, _) = self.word_at(offset, is_path_char);
+        let prefix = &self.source[start..offset];
+
+        let replace = |label: &str| {
+            json!({
+                "range": self.range(Span::new(start, offset)),
+                "newText": label,
+            })
+        };
+
+        if prefix.starts_with('.') {
+            return self
+                .paths
+                .iter()
+                .filter(|path| path.starts_with(prefix))
+                .map(|path| {
+                    json!({
+                        "label": path,
+                        // Field
+                        "kind": 5,
+                        "textEdit": replace(path),
+                    })
+                })
+                .collect();
+        }
+
+        functions
+            .iter()
+            .filter(|function| function.identifier().starts_with(prefix))
+            .map(|function| {
+                json!({
+                    "label": function.identifier(),
+                    // Function
+                    "kind": 3,
+                    "detail": signature(function.as_ref()),
+                    "textEdit": replace(function.identifier()),
+                })
+            })
+            .collect()
+    }
+
+    /// The start and end byte offsets of the word around the given offset.
+    fn word_at(&self, offset: usize, is_word_char: fn(char) -> bool) -> (usize, usize) {
+        let start = self.source[..offset]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_word_char(*c))
+            .last()
+            .map_or(offset, |(index, _)| index);
+        let end = self.source[offset..]
+            .char_indices()
+            .find(|(_, c)| !is_word_char(*c))
+            .map_or(self.source.len(), |(index, _)| offset + index);
+
+        (start, end)
+    }
+
+    fn offset(&self, position: Position) -> usize {
+        let line = position.line as usize;
+        let start = match self.line_starts.get(line) {
+            Some(start) => *start,
+            None => return self.source.len(),
+        };
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.source.len(), |end| end - 1);
+
+        let mut units = 0;
+        for (index, c) in self.source[start..end].char_indices() {
+            if units >= position.character as usize {
+                return start + index;
+            }
+
+            units += c.len_utf16();
+        }
+
+        end
+    }
+
+    fn position(&self, offset: usize) -> Position {
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        let start = self.line_starts[line];
+
+        Position {
+            line: line as u32,
+            character: self.source[start..offset].encode_utf16().count() as u32,
+        }
+    }
+
+    fn range(&self, span: Span) -> JsonValue {
+        let end = span.end().min(self.source.len());
+        let start = span.start().min(end);
+
+        json!({
+            "start": self.position(start),
+            "end": self.position(end),
+        })
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_path_char(c: char) -> bool {
+    is_ident_char(c) || matches!(c, '.' | '[' | ']' | '@')
+}
+
+/// The span of the first primary label of a diagnostic, which is where the
+/// problem is.
+fn primary_span(diagnostic: &Diagnostic) -> Span {
+    diagnostic
+        .labels()
+        .iter()
+        .find(|label| label.primary)
+        .or_else(|| diagnostic.labels().first())
+        .map_or_else(|| Span::new(0, 0), |label| label.span)
+}
+
+fn severity(severity: Severity) -> u8 {
+    match severity {
+        Severity::Bug | Severity::Error => 1,
+        Severity::Warning => 2,
+        Severity::Note => 3,
+    }
+}
+
+fn message(diagnostic: &Diagnostic) -> String {
+    let mut message = diagnostic.message().to_owned();
+
+    for label in diagnostic.labels() {
+        if !label.message.is_empty() {
+            message.push('\n');
+            message.push_str(&label.message);
+        }
+    }
+
+    for note in diagnostic.notes() {
+        message.push('\n');
+        message.push_str(&note.to_string());
+    }
+
+    message
+}
+
+/// The signature of a function, such as
+/// `parse_json(value: string, max_depth: integer?)`.
+fn signature(function: &dyn Function) -> String {
+    let parameters = function
+        .parameters()
+        .iter()
+        .map(|parameter| {
+            let optional = if parameter.required { "" } else { "?" };
+
+            format!("{}: {}{}", parameter.keyword, parameter.kind(), optional)
+        })
+        .collect::<Vec<_>>();
+
+    let closure = if function.closure().is_some() {
+        " -> |...| { ... }"
+    } else {
+        ""
+    };
+
+    format!(
+        "{}({}){}",
+        function.identifier(),
+        parameters.join(", "),
+        closure
+    )
+}
+
+fn documentation(function: &dyn Function) -> String {
+    let mut documentation = format!("```vrl\n{}\n```", signature(function));
+
+    for text in [function.summary(), function.usage()] {
+        if text != "TODO" {
+            documentation.push_str("\n\n");
+            documentation.push_str(text);
+        }
+    }
+
+    if let Some(example) = function.examples().first() {
+        documentation.push_str(&format!(
+            "\n\n**Example**: {}\n\n```vrl\n{}\n```",
+            example.title, example.source
+        ));
+    }
+
+    documentation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(source: &str) -> Document {
+        Document::new(source.to_owned(), &stdlib::all(), None)
+    }
+
+    fn position(line: u32, character: u32) -> Position {
+        Position { line, character }
+    }
+
+    #[test]
+    fn positions() {
+        let doc = document(".a = \"é\"\n.b = 1\n");
+
+        assert_eq!(doc.offset(position(0, 7)), 8);
+        assert_eq!(doc.position(8), position(0, 7));
+        assert_eq!(doc.offset(position(1, 1)), 11);
+        assert_eq!(doc.position(11), position(1, 1));
+        assert_eq!(doc.offset(position(0, 100)), 8 + 1);
+        assert_eq!(doc.offset(position(5, 0)), doc.source.len());
+    }
+
+    #[test]
+    fn diagnostics() {
+        let doc = document(".a = 1\n.b = parse_json(\"{}\")\n");
+        let diagnostics = doc.diagnostics();
+
+        assert_eq!(diagnostics[0]["severity"], 1);
+        assert_eq!(diagnostics[0]["range"]["start"]["line"], 1);
+    }
+
+    #[test]
+    fn hover_function() {
+        let doc = document(".a = downcase(\"B\")");
+        let hover = doc.hover(position(0, 7), &stdlib::all()).unwrap();
+
+        assert!(hover.starts_with("```vrl\ndowncase(value: string)\n```"));
+    }
+
+    #[test]
+    fn hover_path() {
+        let doc = document(".a.b = 1");
+
+        assert_eq!(
+            doc.hover(position(0, 2), &stdlib::all()),
+            Some("```vrl\n.a.b: integer\n```".to_owned())
+        );
+    }
+
+    #[test]
+    fn complete_paths() {
+        let doc = document(".message = \"foo\"\n.status = 200\n.");
+        let completions = doc.completions(position(2, 1), &stdlib::all());
+
+        assert_eq!(completions[0]["label"], ".message");
+        assert_eq!(completions[1]["label"], ".status");
+    }
+
+    #[test]
+    fn complete_functions() {
+        let doc = document("parse_js");
+        let completions = doc.completions(position(0, 8), &stdlib::all());
+
+        assert_eq!(completions[0]["label"], "parse_json");
+        assert_eq!(
+            completions[0]["textEdit"]["range"]["start"],
+            json!({ "line": 0, "character": 0 })
+        );
+    }
+}
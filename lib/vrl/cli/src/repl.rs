@@ -186,6 +186,9 @@ fn execute(
         VrlRuntime::Ast => runtime
             .resolve(object, &program, timezone)
             .map_err(|err| err.to_string()),
+        VrlRuntime::Vm => runtime
+            .run_vm(object, &program.compile_to_vm(), timezone)
+            .map_err(|err| err.to_string()),
     }
 }
 
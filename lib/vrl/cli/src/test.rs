@@ -0,0 +1,330 @@
+//! Runs user-written tests of VRL programs.
+//!
+//! Tests live in TOML files ending in `.vrl.toml`, next to the program they
+//! test:
+//!
+//! ```toml
+//! # Optional, defaults to the test file without its `.toml` extension. The
+//! # program can also be given inline, using `source`.
+//! program = "parse_nginx.vrl"
+//!
+//! # Enrichment tables the program can search, as a list of rows.
+//! [[enrichment_tables.users]]
+//! id = 1
+//! name = "Bob"
+//!
+//! [[tests]]
+//! name = "parses access log"
+//! input = { message = "..." }
+//! # The event after the program ran.
+//! output = { message = "...", status = 200 }
+//!
+//! [[tests]]
+//! name = "rejects invalid log"
+//! input = { message = "" }
+//! # The program fails with an error containing this message.
+//! error = "unable to parse"
+//! ```
+//!
+//! Instead of (or in addition to) `output`, `result` compares the value of
+//! the final expression. Tests can set their own `metadata` and
+//! `enrichment_tables`.
+
+mod file;
+mod junit;
+
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use ::value::Value;
+use clap::Parser;
+use core::TargetValueRef;
+use value::Secrets;
+use vector_common::TimeZone;
+use vrl::{diagnostic::Formatter, state, Function, Runtime, VrlRuntime};
+
+use self::file::{Expectation, TestCase, TestFile};
+use super::{cmd::execute, Error};
+
+#[derive(Parser, Debug)]
+pub struct Opts {
+    /// The test files to run. Directories are searched recursively for files ending in
+    /// `.vrl.toml`.
+    #[clap(name = "PATH", required = true, parse(from_os_str))]
+    paths: Vec<PathBuf>,
+
+    /// Only run the tests whose name contains the pattern.
+    #[clap(short, long)]
+    pattern: Option<String>,
+
+    /// Stop at the first failing test.
+    #[clap(short, long)]
+    fail_early: bool,
+
+    /// Don't print the difference between the expected and actual outcome of failing tests.
+    #[clap(short, long)]
+    no_diff: bool,
+
+    /// Write a JUnit XML report of the test run to the given file.
+    #[clap(long = "junit", parse(from_os_str))]
+    junit_report: Option<PathBuf>,
+
+    /// The timezone used to parse dates. Defaults to UTC, so tests don't depend on the machine
+    /// running them.
+    #[clap(short = 'z', long)]
+    timezone: Option<String>,
+
+    /// Should we use the VM to evaluate the VRL
+    #[clap(short, long = "runtime", default_value_t)]
+    runtime: VrlRuntime,
+}
+
+impl Opts {
+    fn timezone(&self) -> Result<TimeZone, Error> {
+        let tz = self.timezone.as_deref().unwrap_or("UTC");
+
+        TimeZone::parse(tz).ok_or_else(|| Error::Parse(format!("unable to parse timezone: {}", tz)))
+    }
+}
+
+/// The outcome of the test cases of a single test file.
+#[derive(Debug)]
+pub(crate) struct FileReport {
+    pub(crate) name: String,
+    pub(crate) cases: Vec<CaseReport>,
+}
+
+impl FileReport {
+    pub(crate) fn failures(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.failure.is_some())
+            .count()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct CaseReport {
+    pub(crate) name: String,
+    pub(crate) duration: Duration,
+    pub(crate) failure: Option<Failure>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Failure {
+    /// What went wrong, such as `output` when the event didn't match.
+    pub(crate) kind: &'static str,
+    pub(crate) want: String,
+    pub(crate) got: String,
+}
+
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match run(opts) {
+        Ok(0) => exitcode::OK,
+        Ok(_) => exitcode::DATAERR,
+        Err(err) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{}", err);
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+/// Runs the tests, returning the number of failed tests.
+#[allow(clippy::print_stdout)]
+fn run(opts: &Opts) -> Result<usize, Error> {
+    let timezone = opts.timezone()?;
+    let files = file::find(&opts.paths)?
+        .iter()
+        .map(|path| TestFile::load(path))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut reports = vec![];
+    let mut failed_count = 0;
+
+    'files: for file in files {
+        let mut report = FileReport {
+            name: file.path.to_string_lossy().into_owned(),
+            cases: vec![],
+        };
+
+        let cases = file
+            .cases
+            .iter()
+            .filter(|case| {
+                opts.pattern
+                    .as_ref()
+                    .map_or(true, |pattern| case.name.contains(pattern))
+            })
+            .collect::<Vec<_>>();
+
+        if cases.is_empty() {
+            continue;
+        }
+
+        println!("{}", report.name);
+
+        for case in cases {
+            let start = Instant::now();
+            let failure = run_case(&file, case, &timezone, opts.runtime).err();
+            let duration = start.elapsed();
+
+            let name = case.name.chars().take(58).collect::<String>();
+            let dots = ".".repeat(60 - name.chars().count());
+
+            match &failure {
+                None => println!("  {}{}OK", name, dots),
+                Some(failure) => {
+                    println!("  {}{}FAILED ({})", name, dots, failure.kind);
+                    failed_count += 1;
+
+                    if !opts.no_diff {
+                        println!("{}", prettydiff::diff_lines(&failure.want, &failure.got));
+                    }
+                }
+            }
+
+            let failed = failure.is_some();
+            report.cases.push(CaseReport {
+                name: case.name.clone(),
+                duration,
+                failure,
+            });
+
+            if failed && opts.fail_early {
+                reports.push(report);
+                break 'files;
+            }
+        }
+
+        reports.push(report);
+    }
+
+    if let Some(path) = &opts.junit_report {
+        junit::write(path, &reports)?;
+    }
+
+    let total = reports
+        .iter()
+        .map(|report| report.cases.len())
+        .sum::<usize>();
+    if failed_count > 0 {
+        println!(
+            "\n  Overall result: FAILED\n\n    Number failed: {}/{}\n",
+            failed_count, total
+        );
+    } else {
+        println!(
+            "\n  Overall result: SUCCESS\n\n    Number passed: {}\n",
+            total
+        );
+    }
+
+    Ok(failed_count)
+}
+
+fn run_case(
+    file: &TestFile,
+    case: &TestCase,
+    timezone: &TimeZone,
+    vrl_runtime: VrlRuntime,
+) -> Result<(), Failure> {
+    let registry =
+        enrichment::test_util::get_table_registry_with_fixtures(case.enrichment_tables.clone());
+
+    let mut external = state::ExternalEnv::default();
+    external.set_external_context(registry.clone());
+    external.set_import_dir(file.dir());
+
+    let program = match vrl::compile_with_state(&file.source, &functions(), &mut external) {
+        Ok((program, _warnings)) => program,
+        Err(diagnostics) => {
+            let got = Formatter::new(&file.source, diagnostics).to_string();
+
+            return expect_error(case, "compilation", got);
+        }
+    };
+
+    registry.finish_load();
+
+    let mut object = case.input.clone();
+    let mut metadata = case.metadata.clone();
+    let mut secrets = Secrets::new();
+    let mut target = TargetValueRef {
+        value: &mut object,
+        metadata: &mut metadata,
+        secrets: &mut secrets,
+    };
+    let runtime = Runtime::new(state::Runtime::default());
+
+    let result = match execute(&mut target, &program, timezone, runtime, vrl_runtime) {
+        Ok(result) => result,
+        Err(err) => return expect_error(case, "runtime", err.to_string()),
+    };
+
+    match &case.expectation {
+        Expectation::Error(want) => Err(Failure {
+            kind: "expected error",
+            want: want.clone(),
+            got: pretty(&object),
+        }),
+        Expectation::Success {
+            output,
+            result: want_result,
+        } => {
+            if let Some(want) = want_result {
+                if want != &result {
+                    return Err(Failure {
+                        kind: "result",
+                        want: pretty(want),
+                        got: pretty(&result),
+                    });
+                }
+            }
+
+            if let Some(want) = output {
+                if want != &object {
+                    return Err(Failure {
+                        kind: "output",
+                        want: pretty(want),
+                        got: pretty(&object),
+                    });
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Checks a compilation or runtime error against the expected error.
+fn expect_error(case: &TestCase, kind: &'static str, got: String) -> Result<(), Failure> {
+    match &case.expectation {
+        Expectation::Error(want) if got.contains(want.as_str()) => Ok(()),
+        Expectation::Error(want) => Err(Failure {
+            kind,
+            want: want.clone(),
+            got,
+        }),
+        Expectation::Success { .. } => Err(Failure {
+            kind,
+            want: "no error".to_owned(),
+            got,
+        }),
+    }
+}
+
+fn functions() -> Vec<Box<dyn Function>> {
+    let mut functions = stdlib::all();
+    functions.extend(enrichment::vrl_functions());
+    functions.extend(vector_vrl_functions::vrl_functions());
+    functions
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
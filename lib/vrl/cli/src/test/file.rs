@@ -0,0 +1,296 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use ::value::Value;
+use enrichment::test_util::FixtureTable;
+use serde::Deserialize;
+use toml::value::{Table, Value as TomlValue};
+
+use crate::Error;
+
+/// The extension of test files. A test file named `parse_nginx.vrl.toml`
+/// tests the program in `parse_nginx.vrl`, unless it sets `program` or
+/// `source` itself.
+pub(crate) const EXTENSION: &str = ".vrl.toml";
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct FileConfig {
+    program: Option<PathBuf>,
+    source: Option<String>,
+    #[serde(default)]
+    enrichment_tables: BTreeMap<String, Vec<Table>>,
+    #[serde(default)]
+    tests: Vec<CaseConfig>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct CaseConfig {
+    name: String,
+    input: Option<TomlValue>,
+    metadata: Option<TomlValue>,
+    output: Option<TomlValue>,
+    result: Option<TomlValue>,
+    error: Option<String>,
+    #[serde(default)]
+    enrichment_tables: BTreeMap<String, Vec<Table>>,
+}
+
+/// A file containing test cases for a single VRL program.
+#[derive(Debug)]
+pub(crate) struct TestFile {
+    pub(crate) path: PathBuf,
+    pub(crate) source: String,
+    pub(crate) cases: Vec<TestCase>,
+}
+
+#[derive(Debug)]
+pub(crate) struct TestCase {
+    pub(crate) name: String,
+    pub(crate) input: Value,
+    pub(crate) metadata: Value,
+    pub(crate) expectation: Expectation,
+    pub(crate) enrichment_tables: BTreeMap<String, FixtureTable>,
+}
+
+#[derive(Debug)]
+pub(crate) enum Expectation {
+    /// The program succeeds. The modified event and the value of the final
+    /// expression are compared, if given.
+    Success {
+        output: Option<Value>,
+        result: Option<Value>,
+    },
+
+    /// The program fails to compile, or fails at runtime, with an error
+    /// containing the given message.
+    Error(String),
+}
+
+impl TestFile {
+    pub(crate) fn load(path: &Path) -> Result<Self, Error> {
+        let invalid = |error: String| Error::TestFile(path.to_owned(), error);
+
+        let config: FileConfig =
+            toml::from_str(&fs::read_to_string(path)?).map_err(|err| invalid(err.to_string()))?;
+
+        let source = match (config.program, config.source) {
+            (Some(_), Some(_)) => {
+                return Err(invalid(
+                    "only one of `program` and `source` can be set".to_owned(),
+                ))
+            }
+            (None, Some(source)) => source,
+            (program, None) => {
+                let program = match program {
+                    Some(program) => dir(path).join(program),
+                    None => default_program(path),
+                };
+
+                fs::read_to_string(&program).map_err(|err| {
+                    invalid(format!(
+                        "unable to read program {}: {}",
+                        program.display(),
+                        err
+                    ))
+                })?
+            }
+        };
+
+        let cases = config
+            .tests
+            .into_iter()
+            .map(|case| {
+                let name = case.name.clone();
+                TestCase::from_config(case, &config.enrichment_tables)
+                    .map_err(|err| invalid(format!("test {:?}: {}", name, err)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            path: path.to_owned(),
+            source,
+            cases,
+        })
+    }
+
+    /// The directory imports of the program are relative to.
+    pub(crate) fn dir(&self) -> &Path {
+        dir(&self.path)
+    }
+}
+
+impl TestCase {
+    /// Builds a test case. Enrichment tables of the case replace the tables
+    /// of the file with the same name.
+    fn from_config(
+        config: CaseConfig,
+        file_tables: &BTreeMap<String, Vec<Table>>,
+    ) -> Result<Self, String> {
+        let expectation = match (config.output, config.result, config.error) {
+            (None, None, None) => {
+                return Err("one of `output`, `result` or `error` must be set".to_owned())
+            }
+            (None, None, Some(error)) => Expectation::Error(error),
+            (_, _, Some(_)) => {
+                return Err("`error` can't be combined with `output` or `result`".to_owned())
+            }
+            (output, result, None) => Expectation::Success {
+                output: output.map(to_value).transpose()?,
+                result: result.map(to_value).transpose()?,
+            },
+        };
+
+        let mut tables = file_tables.clone();
+        tables.extend(config.enrichment_tables);
+        let enrichment_tables = tables
+            .into_iter()
+            .map(|(name, rows)| fixture(rows).map(|table| (name, table)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            name: config.name,
+            input: to_value(config.input.unwrap_or_else(empty_table))?,
+            metadata: to_value(config.metadata.unwrap_or_else(empty_table))?,
+            expectation,
+            enrichment_tables,
+        })
+    }
+}
+
+fn fixture(rows: Vec<Table>) -> Result<FixtureTable, String> {
+    let rows = rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|(field, value)| to_value(value).map(|value| (field, value)))
+                .collect::<Result<BTreeMap<_, _>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(FixtureTable::new(rows))
+}
+
+fn empty_table() -> TomlValue {
+    TomlValue::Table(Table::new())
+}
+
+fn to_value(value: TomlValue) -> Result<Value, String> {
+    Value::try_from(value).map_err(|err| err.to_string())
+}
+
+fn dir(path: &Path) -> &Path {
+    path.parent().unwrap_or_else(|| Path::new(""))
+}
+
+/// The program tested by a test file that doesn't name one, which is the
+/// test file without its `.toml` extension.
+fn default_program(path: &Path) -> PathBuf {
+    path.with_extension("")
+}
+
+/// Finds the test files in the given paths. Directories are searched
+/// recursively, files are always included.
+pub(crate) fn find(paths: &[PathBuf]) -> Result<Vec<PathBuf>, Error> {
+    let mut files = vec![];
+
+    for path in paths {
+        if path.is_dir() {
+            find_in_dir(path, &mut files)?;
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    Ok(files)
+}
+
+fn find_in_dir(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Error> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            find_in_dir(&path, files)?;
+        } else if path.to_string_lossy().ends_with(EXTENSION) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(config: &str) -> Result<TestCase, String> {
+        TestCase::from_config(toml::from_str(config).unwrap(), &BTreeMap::new())
+    }
+
+    #[test]
+    fn default_program() {
+        assert_eq!(
+            PathBuf::from("tests/parse_nginx.vrl"),
+            super::default_program(Path::new("tests/parse_nginx.vrl.toml"))
+        );
+    }
+
+    #[test]
+    fn case_with_output() {
+        let case = case(indoc::indoc! {r#"
+            name = "parses message"
+            input = { message = "foo=bar" }
+            output = { message = "foo=bar", foo = "bar" }
+
+            [[enrichment_tables.users]]
+            id = 1
+            name = "Bob"
+        "#})
+        .unwrap();
+
+        assert_eq!(
+            Value::from(BTreeMap::from([(
+                "message".to_owned(),
+                Value::from("foo=bar")
+            )])),
+            case.input
+        );
+        assert_eq!(Value::from(BTreeMap::new()), case.metadata);
+        assert!(matches!(
+            case.expectation,
+            Expectation::Success {
+                output: Some(_),
+                result: None
+            }
+        ));
+        assert!(case.enrichment_tables.contains_key("users"));
+    }
+
+    #[test]
+    fn case_without_expectation() {
+        assert_eq!(
+            Err("one of `output`, `result` or `error` must be set".to_owned()),
+            case(r#"name = "nothing""#).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn case_with_conflicting_expectations() {
+        assert_eq!(
+            Err("`error` can't be combined with `output` or `result`".to_owned()),
+            case(indoc::indoc! {r#"
+                name = "conflict"
+                output = {}
+                error = "function call error"
+            "#})
+            .map(|_| ())
+        );
+    }
+}
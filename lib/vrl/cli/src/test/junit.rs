@@ -0,0 +1,139 @@
+use std::{fmt::Write as _, fs, path::Path};
+
+use super::{CaseReport, FileReport};
+use crate::Error;
+
+/// Writes a JUnit XML report, with a test suite for every test file.
+pub(crate) fn write(path: &Path, files: &[FileReport]) -> Result<(), Error> {
+    fs::write(path, render(files))?;
+
+    Ok(())
+}
+
+fn render(files: &[FileReport]) -> String {
+    let tests = files.iter().map(|file| file.cases.len()).sum::<usize>();
+    let failures = files.iter().map(FileReport::failures).sum::<usize>();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        r#"<testsuites name="vrl" tests="{}" failures="{}">"#,
+        tests, failures
+    );
+
+    for file in files {
+        let _ = writeln!(
+            xml,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" time="{:.3}">"#,
+            escape(&file.name),
+            file.cases.len(),
+            file.failures(),
+            file.cases
+                .iter()
+                .map(|case| case.duration.as_secs_f64())
+                .sum::<f64>(),
+        );
+
+        for case in &file.cases {
+            render_case(&mut xml, &file.name, case);
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn render_case(xml: &mut String, classname: &str, case: &CaseReport) {
+    let _ = write!(
+        xml,
+        r#"    <testcase name="{}" classname="{}" time="{:.3}""#,
+        escape(&case.name),
+        escape(classname),
+        case.duration.as_secs_f64()
+    );
+
+    match &case.failure {
+        None => xml.push_str(" />\n"),
+        Some(failure) => {
+            let _ = writeln!(
+                xml,
+                ">\n      <failure message=\"{}\">expected:\n{}\n\ngot:\n{}</failure>\n    </testcase>",
+                escape(failure.kind),
+                escape(&failure.want),
+                escape(&failure.got)
+            );
+        }
+    }
+}
+
+fn escape(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+
+    for c in string.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters aren't allowed in XML 1.0, even escaped.
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::test::Failure;
+
+    #[test]
+    fn render_report() {
+        let files = vec![FileReport {
+            name: "tests/parse.vrl.toml".to_owned(),
+            cases: vec![
+                CaseReport {
+                    name: "parses <message>".to_owned(),
+                    duration: Duration::from_millis(2),
+                    failure: None,
+                },
+                CaseReport {
+                    name: "fails".to_owned(),
+                    duration: Duration::from_millis(1),
+                    failure: Some(Failure {
+                        kind: "output",
+                        want: r#"{ "foo": "bar" }"#.to_owned(),
+                        got: "{}".to_owned(),
+                    }),
+                },
+            ],
+        }];
+
+        assert_eq!(
+            render(&files),
+            indoc::indoc! {r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <testsuites name="vrl" tests="2" failures="1">
+                  <testsuite name="tests/parse.vrl.toml" tests="2" failures="1" time="0.003">
+                    <testcase name="parses &lt;message&gt;" classname="tests/parse.vrl.toml" time="0.002" />
+                    <testcase name="fails" classname="tests/parse.vrl.toml" time="0.001">
+                      <failure message="output">expected:
+                { &quot;foo&quot;: &quot;bar&quot; }
+
+                got:
+                {}</failure>
+                    </testcase>
+                  </testsuite>
+                </testsuites>
+            "#}
+        );
+    }
+}
@@ -1,3 +1,10 @@
+#[cfg(feature = "expr-function_call")]
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
 use diagnostic::{DiagnosticList, DiagnosticMessage, Severity, Span};
 use lookup::LookupBuf;
 use parser::ast::{self, Node, QueryTarget};
@@ -9,9 +16,20 @@ use crate::{
     state::{ExternalEnv, LocalEnv},
     Function, Program,
 };
+#[cfg(feature = "expr-function_call")]
+use crate::{
+    parser::ast::Ident,
+    type_def::Details,
+    value::{Collection, Kind},
+};
 
 pub(crate) type Diagnostics = Vec<Box<dyn DiagnosticMessage>>;
 
+/// The number of times the body of a loop is compiled before the types that
+/// still change between iterations are widened to any type.
+#[cfg(feature = "expr-loop")]
+const MAX_LOOP_PASSES: usize = 8;
+
 pub(crate) struct Compiler<'a> {
     fns: &'a [Box<dyn Function>],
     diagnostics: Diagnostics,
@@ -34,6 +52,30 @@ pub(crate) struct Compiler<'a> {
     /// back to `None`, if the parent expression of a fallible expression
     /// nullifies the fallibility of that expression.
     fallible_expression_error: Option<Box<dyn DiagnosticMessage>>,
+
+    /// The user-defined functions callable by the expressions being compiled,
+    /// either defined or imported before them.
+    #[cfg(feature = "expr-function_call")]
+    user_fns: HashMap<Ident, Arc<UserFunction>>,
+
+    /// The functions defined by the program or module being compiled, which
+    /// are exported when it is imported.
+    #[cfg(feature = "expr-function_call")]
+    defined_fns: Vec<Arc<UserFunction>>,
+
+    /// The files of the modules being imported, to detect import cycles.
+    #[cfg(feature = "expr-function_call")]
+    import_stack: Vec<PathBuf>,
+
+    /// The functions exported by the modules compiled so far, so that a
+    /// module imported in several places is only compiled once.
+    #[cfg(feature = "expr-function_call")]
+    modules: HashMap<PathBuf, Vec<Arc<UserFunction>>>,
+
+    /// The number of loops enclosing the expression being compiled, which
+    /// `break` and `continue` require. Closures start outside of any loop.
+    #[cfg(feature = "expr-loop")]
+    loop_depth: usize,
 }
 
 impl<'a> Compiler<'a> {
@@ -48,6 +90,16 @@ impl<'a> Compiler<'a> {
             external_assignments: vec![],
             skip_missing_query_target: vec![],
             fallible_expression_error: None,
+            #[cfg(feature = "expr-function_call")]
+            user_fns: HashMap::new(),
+            #[cfg(feature = "expr-function_call")]
+            defined_fns: vec![],
+            #[cfg(feature = "expr-function_call")]
+            import_stack: vec![],
+            #[cfg(feature = "expr-function_call")]
+            modules: HashMap::new(),
+            #[cfg(feature = "expr-loop")]
+            loop_depth: 0,
         }
     }
 
@@ -121,10 +173,17 @@ impl<'a> Compiler<'a> {
             Op(node) => self.compile_op(node, external).map(Into::into),
             Assignment(node) => self.compile_assignment(node, external).map(Into::into),
             Query(node) => self.compile_query(node, external).map(Into::into),
+            FunctionCall(node) if self.is_user_function_call(&node) => self
+                .compile_user_function_call(node, external)
+                .map(Into::into),
             FunctionCall(node) => self.compile_function_call(node, external).map(Into::into),
             Variable(node) => self.compile_variable(node, external).map(Into::into),
             Unary(node) => self.compile_unary(node, external).map(Into::into),
             Abort(node) => self.compile_abort(node, external).map(Into::into),
+            Loop(node) => self.compile_loop(node, external).map(Into::into),
+            Walk(node) => self.compile_walk(node, external).map(Into::into),
+            Traverse(node) => self.compile_traverse(node, external).map(Into::into),
+            LoopControl(node) => self.compile_loop_control(node, external).map(Into::into),
         }?;
 
         // If the previously compiled expression is fallible, _and_ we are
@@ -248,6 +307,10 @@ impl<'a> Compiler<'a> {
                         }
                     }
                 }
+                RootExpr::FunctionDefinition(node) => {
+                    self.compile_function_definition(node, external)
+                }
+                RootExpr::Import(node) => self.compile_import(node, external),
                 RootExpr::Error(err) => self.handle_parser_error(err),
             }
         }
@@ -632,6 +695,13 @@ impl<'a> Compiler<'a> {
                 let container = self.compile_container(Node::new(span, container), external)?;
                 Target::Container(container)
             }
+            // The result of a user-defined function is queried like that of
+            // a group, as the query target only holds built-in function calls.
+            FunctionCall(call) if self.is_user_function_call(&call) => {
+                let call = self.compile_user_function_call(Node::new(span, call), external)?;
+                let group = Group::new(call.into());
+                Target::Container(Container::new(group.into()))
+            }
             FunctionCall(call) => {
                 let call = self.compile_function_call(Node::new(span, call), external)?;
                 Target::FunctionCall(call)
@@ -712,7 +782,16 @@ impl<'a> Compiler<'a> {
                 None => None,
                 Some(block) => {
                     let span = block.span();
-                    match self.compile_block(block, external) {
+
+                    #[cfg(feature = "expr-loop")]
+                    let loop_depth = std::mem::take(&mut self.loop_depth);
+                    let block = self.compile_block(block, external);
+                    #[cfg(feature = "expr-loop")]
+                    {
+                        self.loop_depth = loop_depth;
+                    }
+
+                    match block {
                         Some(block) => Some(Node::new(span, block)),
                         None => return None,
                     }
@@ -758,6 +837,372 @@ impl<'a> Compiler<'a> {
         None
     }
 
+    #[cfg(feature = "expr-function_call")]
+    fn is_user_function_call(&self, node: &ast::FunctionCall) -> bool {
+        self.user_fns.contains_key(node.ident.inner())
+    }
+
+    #[cfg(not(feature = "expr-function_call"))]
+    fn is_user_function_call(&self, _: &ast::FunctionCall) -> bool {
+        false
+    }
+
+    #[cfg(feature = "expr-function_call")]
+    fn compile_user_function_call(
+        &mut self,
+        node: Node<ast::FunctionCall>,
+        external: &mut ExternalEnv,
+    ) -> Option<UserFunctionCall> {
+        let call_span = node.span();
+        let ast::FunctionCall {
+            ident,
+            abort_on_error,
+            arguments,
+            closure,
+        } = node.into_inner();
+
+        let (ident_span, ident) = ident.take();
+        let function = self.user_fns.get(&ident)?.clone();
+
+        if let Some(closure) = closure {
+            let error = user_function::Error::UnexpectedClosure {
+                call_span,
+                closure_span: closure.span(),
+            };
+            self.diagnostics.push(Box::new(error));
+            return None;
+        }
+
+        let arguments = arguments
+            .into_iter()
+            .map(|node| {
+                Some(Node::new(
+                    node.span(),
+                    self.compile_function_argument(node, external)?,
+                ))
+            })
+            .collect::<Option<_>>()?;
+
+        let call = UserFunctionCall::new(
+            call_span,
+            ident_span,
+            abort_on_error,
+            function,
+            arguments,
+            (&self.local, external),
+        )
+        .map_err(|err| self.diagnostics.push(Box::new(err)))
+        .ok()?;
+
+        if abort_on_error {
+            self.fallible = true;
+        }
+
+        // The function can assign anything to the target, so nothing is known
+        // about it after the call.
+        if call.function().mutates_target() {
+            *external.target_mut() = Details {
+                type_def: Kind::object(Collection::any()).into(),
+                value: None,
+            };
+        }
+
+        Some(call)
+    }
+
+    #[cfg(not(feature = "expr-function_call"))]
+    fn compile_user_function_call(
+        &mut self,
+        node: Node<ast::FunctionCall>,
+        _: &mut ExternalEnv,
+    ) -> Option<Expr> {
+        self.handle_missing_feature_error(node.span(), "expr-function_call")
+    }
+
+    #[cfg(feature = "expr-function_call")]
+    fn compile_function_definition(
+        &mut self,
+        node: Node<ast::FunctionDefinition>,
+        external: &mut ExternalEnv,
+    ) {
+        let ast::FunctionDefinition {
+            ident,
+            parameters,
+            block,
+        } = node.into_inner();
+        let (ident_span, ident) = ident.take();
+
+        let builtin = self.fns.iter().any(|f| f.identifier() == ident.as_ref());
+        if builtin || self.user_fns.contains_key(&ident) {
+            let error = user_function::Error::AlreadyDefined {
+                ident_span,
+                builtin,
+            };
+            self.diagnostics.push(Box::new(error));
+            return;
+        }
+
+        // The block of the function only has access to its parameters.
+        let mut local = LocalEnv::default();
+        let mut user_parameters = Vec::with_capacity(parameters.len());
+        for parameter in parameters {
+            let ast::FunctionParameter { ident, kind } = parameter.into_inner();
+            let (span, ident) = ident.take();
+
+            let kind = match kind {
+                Some(kind) => match user_function::annotation_kind(&kind.into_inner().0) {
+                    Ok(kind) => kind,
+                    Err(err) => {
+                        self.diagnostics.push(Box::new(err));
+                        return;
+                    }
+                },
+                None => Kind::any(),
+            };
+
+            if local.variable(&ident).is_some() {
+                let error = user_function::Error::DuplicateParameter { span };
+                self.diagnostics.push(Box::new(error));
+                return;
+            }
+
+            let details = Details {
+                type_def: kind.clone().into(),
+                value: None,
+            };
+            local.insert_variable(ident.clone(), details);
+            user_parameters.push(UserParameter { ident, kind });
+        }
+
+        // The function can be called on any event, so its block is checked
+        // against a target of unknown type.
+        let caller_local = std::mem::replace(&mut self.local, local);
+        let caller_target = std::mem::replace(
+            external.target_mut(),
+            Details {
+                type_def: Kind::object(Collection::any()).into(),
+                value: None,
+            },
+        );
+        let assignments = self.external_assignments.len();
+
+        let exprs = self.compile_function_block(block, external);
+        let local = std::mem::replace(&mut self.local, caller_local);
+        let mutates_target = self.external_assignments.len() > assignments;
+
+        let function = match exprs {
+            Some(exprs) => {
+                let block = Block::new(exprs, local);
+                let type_def = block.type_def((&self.local, external));
+
+                Some(UserFunction::new(
+                    ident.clone(),
+                    user_parameters,
+                    block,
+                    type_def,
+                    mutates_target,
+                ))
+            }
+            None => None,
+        };
+        *external.target_mut() = caller_target;
+
+        if let Some(function) = function {
+            let function = Arc::new(function);
+            self.user_fns.insert(ident, function.clone());
+            self.defined_fns.push(function);
+        }
+    }
+
+    #[cfg(not(feature = "expr-function_call"))]
+    fn compile_function_definition(
+        &mut self,
+        node: Node<ast::FunctionDefinition>,
+        _: &mut ExternalEnv,
+    ) {
+        self.handle_missing_feature_error(node.span(), "expr-function_call");
+    }
+
+    /// Compiles the block of a user-defined function, whose expressions must
+    /// handle their errors, like those at the root of a program.
+    #[cfg(feature = "expr-function_call")]
+    fn compile_function_block(
+        &mut self,
+        node: Node<ast::Block>,
+        external: &mut ExternalEnv,
+    ) -> Option<Vec<Expr>> {
+        let mut exprs = vec![];
+        let mut compiled = true;
+
+        for node in node.into_inner() {
+            self.fallible_expression_error = None;
+
+            match self.compile_expr(node, external) {
+                Some(expr) => {
+                    if let Some(error) = self.fallible_expression_error.take() {
+                        self.diagnostics.push(error);
+                    }
+
+                    let type_def = expr.type_def((&self.local, external));
+                    exprs.push(expr);
+
+                    // Expressions after a terminating expression never run.
+                    if type_def.is_never() {
+                        break;
+                    }
+                }
+                None => compiled = false,
+            }
+        }
+
+        if compiled {
+            Some(exprs)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "expr-function_call")]
+    fn compile_import(&mut self, node: Node<ast::Import>, external: &mut ExternalEnv) {
+        let (path_span, path) = node.into_inner().path.take();
+
+        // Nested imports are relative to the file of the importing module.
+        let dir = match self.import_stack.last() {
+            Some(module) => module.parent().map(Path::to_path_buf),
+            None => external.import_dir().map(Path::to_path_buf),
+        }
+        .unwrap_or_default();
+
+        let result = dir
+            .join(&path)
+            .canonicalize()
+            .map_err(|err| (format!(r#"can't read "{}": {}"#, path, err), vec![]))
+            .and_then(|file| {
+                if self.import_stack.contains(&file) {
+                    return Err((format!(r#"import cycle through "{}""#, path), vec![]));
+                }
+
+                match self.modules.get(&file) {
+                    Some(functions) => Ok(functions.clone()),
+                    None => {
+                        let functions = self.compile_module(&file, external)?;
+                        self.modules.insert(file, functions.clone());
+                        Ok(functions)
+                    }
+                }
+            });
+
+        let functions = match result {
+            Ok(functions) => functions,
+            Err((message, diagnostics)) => {
+                let error = user_function::Error::Import {
+                    path_span,
+                    message,
+                    diagnostics,
+                };
+                self.diagnostics.push(Box::new(error));
+                return;
+            }
+        };
+
+        for function in functions {
+            match self.user_fns.get(function.ident()) {
+                // The same module can be imported by several others.
+                Some(defined) if Arc::ptr_eq(defined, &function) => {}
+                Some(_) => {
+                    let error = user_function::Error::Import {
+                        path_span,
+                        message: format!(
+                            r#"imported function "{}" is already defined"#,
+                            function.ident()
+                        ),
+                        diagnostics: vec![],
+                    };
+                    self.diagnostics.push(Box::new(error));
+                }
+                None => {
+                    self.user_fns
+                        .insert(function.ident().clone(), function.clone());
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "expr-function_call"))]
+    fn compile_import(&mut self, node: Node<ast::Import>, _: &mut ExternalEnv) {
+        self.handle_missing_feature_error(node.span(), "expr-function_call");
+    }
+
+    /// Compiles the module in the given file, returning the functions it
+    /// defines, or the formatted diagnostics of the module if it is invalid.
+    #[cfg(feature = "expr-function_call")]
+    fn compile_module(
+        &mut self,
+        file: &Path,
+        external: &mut ExternalEnv,
+    ) -> Result<Vec<Arc<UserFunction>>, (String, Vec<String>)> {
+        let source = std::fs::read_to_string(file).map_err(|err| {
+            (
+                format!(r#"can't read "{}": {}"#, file.display(), err),
+                vec![],
+            )
+        })?;
+
+        let mut compiler = Compiler::new(self.fns);
+        compiler.import_stack = self.import_stack.clone();
+        compiler.import_stack.push(file.to_owned());
+        compiler.modules = std::mem::take(&mut self.modules);
+
+        match parser::parse(&source) {
+            Ok(ast) => {
+                for root_expr in ast {
+                    let span = root_expr.span();
+
+                    match root_expr.into_inner() {
+                        RootExpr::FunctionDefinition(node) => {
+                            compiler.compile_function_definition(node, external)
+                        }
+                        RootExpr::Import(node) => compiler.compile_import(node, external),
+                        RootExpr::Expr(_) => {
+                            let error = user_function::Error::ModuleExpression { span };
+                            compiler.diagnostics.push(Box::new(error));
+                        }
+                        RootExpr::Error(err) => compiler.handle_parser_error(err),
+                    }
+                }
+            }
+            Err(err) => compiler.handle_parser_error(err),
+        }
+
+        self.modules = std::mem::take(&mut compiler.modules);
+        self.fallible |= compiler.fallible;
+        self.abortable |= compiler.abortable;
+        self.external_queries.append(&mut compiler.external_queries);
+        self.external_assignments
+            .append(&mut compiler.external_assignments);
+
+        let errors = compiler
+            .diagnostics
+            .into_iter()
+            .filter(|diagnostic| matches!(diagnostic.severity(), Severity::Bug | Severity::Error))
+            .collect::<Vec<_>>();
+
+        if !errors.is_empty() {
+            let diagnostics = diagnostic::Formatter::new(&source, errors)
+                .to_string()
+                .lines()
+                .map(ToOwned::to_owned)
+                .collect();
+
+            return Err((
+                format!(r#"module "{}" can't be compiled"#, file.display()),
+                diagnostics,
+            ));
+        }
+
+        Ok(compiler.defined_fns)
+    }
+
     fn compile_variable(
         &mut self,
         node: Node<ast::Ident>,
@@ -839,6 +1284,201 @@ impl<'a> Compiler<'a> {
         self.handle_missing_feature_error(node.span(), "expr-abort")
     }
 
+    #[cfg(feature = "expr-loop")]
+    fn compile_loop(&mut self, node: Node<ast::Loop>, external: &mut ExternalEnv) -> Option<Loop> {
+        let (span, ast::Loop { predicate, block }) = node.take();
+
+        // Loops that don't stop within the maximum number of iterations fail
+        // at runtime.
+        self.fallible = true;
+
+        let (predicate, block) = self.compile_repeated(external, |compiler, external| {
+            let predicate = match predicate.clone() {
+                Some(predicate) => Some(
+                    compiler
+                        .compile_predicate(predicate, external)?
+                        .map_err(|err| compiler.diagnostics.push(Box::new(err)))
+                        .ok()?,
+                ),
+                None => None,
+            };
+            let block = compiler.compile_loop_block(block.clone(), external)?;
+
+            Some((predicate, block))
+        })?;
+
+        Some(Loop::new(span, predicate, block))
+    }
+
+    #[cfg(not(feature = "expr-loop"))]
+    fn compile_loop(&mut self, node: Node<ast::Loop>, _: &mut ExternalEnv) -> Option<Expr> {
+        self.handle_missing_feature_error(node.span(), "expr-loop")
+    }
+
+    #[cfg(feature = "expr-loop")]
+    fn compile_walk(&mut self, node: Node<ast::Walk>, external: &mut ExternalEnv) -> Option<Walk> {
+        let (span, walk) = node.take();
+        let ast::Walk {
+            target,
+            variables,
+            block,
+        } = walk;
+
+        let target = self.compile_expr(target, external)?;
+        let variables = variables
+            .into_iter()
+            .map(Node::into_inner)
+            .collect::<Vec<_>>();
+
+        let local_snapshot = self.local.clone();
+        Walk::bind_variables("walk", span, &variables, &mut self.local)
+            .map_err(|err| self.diagnostics.push(Box::new(err)))
+            .ok()?;
+
+        let block = self.compile_repeated(external, |compiler, external| {
+            compiler.compile_loop_block(block.clone(), external)
+        });
+
+        Walk::unbind_variables(&variables, &mut self.local, &local_snapshot);
+
+        Some(Walk::new(target, variables, block?))
+    }
+
+    #[cfg(not(feature = "expr-loop"))]
+    fn compile_walk(&mut self, node: Node<ast::Walk>, _: &mut ExternalEnv) -> Option<Expr> {
+        self.handle_missing_feature_error(node.span(), "expr-loop")
+    }
+
+    #[cfg(feature = "expr-loop")]
+    fn compile_traverse(
+        &mut self,
+        node: Node<ast::Traverse>,
+        external: &mut ExternalEnv,
+    ) -> Option<Traverse> {
+        let (span, traverse) = node.take();
+        let ast::Traverse {
+            target,
+            variables,
+            block,
+        } = traverse;
+
+        let target = self.compile_expr(target, external)?;
+        let variables = variables
+            .into_iter()
+            .map(Node::into_inner)
+            .collect::<Vec<_>>();
+
+        // The variables are bound like those of a walk.
+        let local_snapshot = self.local.clone();
+        Walk::bind_variables("traverse", span, &variables, &mut self.local)
+            .map_err(|err| self.diagnostics.push(Box::new(err)))
+            .ok()?;
+
+        let block = self.compile_repeated(external, |compiler, external| {
+            compiler.compile_loop_block(block.clone(), external)
+        });
+
+        Walk::unbind_variables(&variables, &mut self.local, &local_snapshot);
+
+        Some(Traverse::new(target, variables, block?))
+    }
+
+    #[cfg(not(feature = "expr-loop"))]
+    fn compile_traverse(&mut self, node: Node<ast::Traverse>, _: &mut ExternalEnv) -> Option<Expr> {
+        self.handle_missing_feature_error(node.span(), "expr-loop")
+    }
+
+    #[cfg(feature = "expr-loop")]
+    fn compile_loop_control(
+        &mut self,
+        node: Node<ast::LoopControl>,
+        _: &mut ExternalEnv,
+    ) -> Option<LoopControl> {
+        let (span, variant) = node.take();
+
+        LoopControl::new(span, variant, self.loop_depth)
+            .map_err(|err| self.diagnostics.push(Box::new(err)))
+            .ok()
+    }
+
+    #[cfg(not(feature = "expr-loop"))]
+    fn compile_loop_control(
+        &mut self,
+        node: Node<ast::LoopControl>,
+        _: &mut ExternalEnv,
+    ) -> Option<Expr> {
+        self.handle_missing_feature_error(node.span(), "expr-loop")
+    }
+
+    /// Compiles the block of a loop, in which `break` and `continue` are
+    /// allowed.
+    #[cfg(feature = "expr-loop")]
+    fn compile_loop_block(
+        &mut self,
+        node: Node<ast::Block>,
+        external: &mut ExternalEnv,
+    ) -> Option<Block> {
+        self.loop_depth += 1;
+        let block = self.compile_block(node, external);
+        self.loop_depth -= 1;
+
+        block
+    }
+
+    /// Compiles the part of a loop that runs any number of times.
+    ///
+    /// Each iteration starts with the state the previous one left, so the
+    /// expressions are compiled again with the union of the states before and
+    /// after them, until the types of the variables and of the target no
+    /// longer change. Types that still change after `MAX_LOOP_PASSES` passes
+    /// are widened to any type.
+    #[cfg(feature = "expr-loop")]
+    fn compile_repeated<T>(
+        &mut self,
+        external: &mut ExternalEnv,
+        mut compile: impl FnMut(&mut Self, &mut ExternalEnv) -> Option<T>,
+    ) -> Option<T> {
+        let diagnostics = self.diagnostics.len();
+        let external_queries = self.external_queries.len();
+        let external_assignments = self.external_assignments.len();
+        let fallible_expression_error = self.fallible_expression_error.take();
+
+        let mut passes = 0;
+        let compiled = loop {
+            let local = self.local.clone();
+            let target = external.target().clone();
+
+            let compiled = compile(self, external);
+
+            // The loop either stops, leaving the state as it was, or runs again
+            // with the state of this iteration.
+            self.local = local.clone().merge(self.local.clone());
+            external.update_target(target.clone().merge(external.target().clone()));
+
+            if compiled.is_none() || (self.local == local && *external.target() == target) {
+                break compiled;
+            }
+
+            passes += 1;
+            if passes >= MAX_LOOP_PASSES {
+                self.local = self.local.clone().widen(&local);
+                external.update_target(external.target().clone().widen(&target));
+            }
+
+            // Only the diagnostics of the last pass are relevant.
+            self.diagnostics.truncate(diagnostics);
+            self.external_queries.truncate(external_queries);
+            self.external_assignments.truncate(external_assignments);
+            self.fallible_expression_error = None;
+        };
+
+        if fallible_expression_error.is_some() {
+            self.fallible_expression_error = fallible_expression_error;
+        }
+
+        compiled
+    }
+
     fn handle_parser_error(&mut self, error: parser::Error) {
         self.diagnostics.push(Box::new(error))
     }
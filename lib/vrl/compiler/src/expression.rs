@@ -6,6 +6,7 @@ use value::Value;
 
 use crate::{
     state::{ExternalEnv, LocalEnv},
+    vm::{OpCode, Vm},
     Context, Span, TypeDef,
 };
 
@@ -18,15 +19,23 @@ mod group;
 #[cfg(feature = "expr-if_statement")]
 mod if_statement;
 mod levenstein;
+#[cfg(feature = "expr-loop")]
+mod loop_control;
+#[cfg(feature = "expr-loop")]
+mod loop_statement;
 mod noop;
 #[cfg(feature = "expr-unary")]
 mod not;
 mod object;
 #[cfg(feature = "expr-op")]
 mod op;
+#[cfg(feature = "expr-loop")]
+mod traverse;
 #[cfg(feature = "expr-unary")]
 mod unary;
 mod variable;
+#[cfg(feature = "expr-loop")]
+mod walk;
 
 #[cfg(feature = "expr-assignment")]
 pub(crate) mod assignment;
@@ -39,6 +48,8 @@ pub(crate) mod literal;
 pub(crate) mod predicate;
 #[cfg(feature = "expr-query")]
 pub(crate) mod query;
+#[cfg(feature = "expr-function_call")]
+pub(crate) mod user_function;
 
 pub use core::{ExpressionError, Resolved};
 
@@ -57,6 +68,10 @@ pub use group::Group;
 pub use if_statement::IfStatement;
 #[cfg(feature = "expr-literal")]
 pub use literal::Literal;
+#[cfg(feature = "expr-loop")]
+pub use loop_control::LoopControl;
+#[cfg(feature = "expr-loop")]
+pub use loop_statement::{Loop, MAX_ITERATIONS};
 pub use noop::Noop;
 #[cfg(feature = "expr-unary")]
 pub use not::Not;
@@ -67,9 +82,15 @@ pub use op::Op;
 pub use predicate::Predicate;
 #[cfg(feature = "expr-query")]
 pub use query::{Query, Target};
+#[cfg(feature = "expr-loop")]
+pub use traverse::Traverse;
 #[cfg(feature = "expr-unary")]
 pub use unary::Unary;
+#[cfg(feature = "expr-function_call")]
+pub use user_function::{UserFunction, UserFunctionCall, UserParameter};
 pub use variable::Variable;
+#[cfg(feature = "expr-loop")]
+pub use walk::Walk;
 
 pub trait Expression: Send + Sync + fmt::Debug + DynClone {
     /// Resolve an expression to a concrete [`Value`].
@@ -128,12 +149,22 @@ pub enum Expr {
     Query(Query),
     #[cfg(feature = "expr-function_call")]
     FunctionCall(FunctionCall),
+    #[cfg(feature = "expr-function_call")]
+    UserFunctionCall(UserFunctionCall),
     Variable(Variable),
     Noop(Noop),
     #[cfg(feature = "expr-unary")]
     Unary(Unary),
     #[cfg(feature = "expr-abort")]
     Abort(Abort),
+    #[cfg(feature = "expr-loop")]
+    Loop(Loop),
+    #[cfg(feature = "expr-loop")]
+    Walk(Walk),
+    #[cfg(feature = "expr-loop")]
+    Traverse(Traverse),
+    #[cfg(feature = "expr-loop")]
+    LoopControl(LoopControl),
 }
 
 impl Expr {
@@ -160,12 +191,22 @@ impl Expr {
             Query(..) => "query",
             #[cfg(feature = "expr-function_call")]
             FunctionCall(..) => "function call",
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(..) => "user-defined function call",
             Variable(..) => "variable call",
             Noop(..) => "noop",
             #[cfg(feature = "expr-unary")]
             Unary(..) => "unary operation",
             #[cfg(feature = "expr-abort")]
             Abort(..) => "abort operation",
+            #[cfg(feature = "expr-loop")]
+            Loop(..) => "loop",
+            #[cfg(feature = "expr-loop")]
+            Walk(..) => "walk",
+            #[cfg(feature = "expr-loop")]
+            Traverse(..) => "traverse",
+            #[cfg(feature = "expr-loop")]
+            LoopControl(..) => "loop control",
         }
     }
 
@@ -210,6 +251,41 @@ impl Expr {
         })
     }
 
+    /// Lower the expression to instructions of the given [`Vm`].
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        use Expr::*;
+
+        match self {
+            #[cfg(feature = "expr-literal")]
+            Literal(v) => v.compile_to_vm(vm),
+            Container(v) => v.compile_to_vm(vm),
+            #[cfg(feature = "expr-if_statement")]
+            IfStatement(v) => v.compile_to_vm(vm),
+            #[cfg(feature = "expr-op")]
+            Op(v) => v.compile_to_vm(vm),
+            #[cfg(feature = "expr-assignment")]
+            Assignment(v) => v.compile_to_vm(vm),
+            #[cfg(feature = "expr-query")]
+            Query(v) => v.compile_to_vm(vm),
+            #[cfg(feature = "expr-function_call")]
+            FunctionCall(..) | UserFunctionCall(..) => {
+                let index = vm.add_expression(self.clone());
+                vm.emit(OpCode::Eval(index));
+            }
+            #[cfg(feature = "expr-loop")]
+            Loop(..) | Walk(..) | Traverse(..) | LoopControl(..) => {
+                let index = vm.add_expression(self.clone());
+                vm.emit(OpCode::Eval(index));
+            }
+            Variable(v) => v.compile_to_vm(vm),
+            Noop(v) => v.compile_to_vm(vm),
+            #[cfg(feature = "expr-unary")]
+            Unary(v) => v.compile_to_vm(vm),
+            #[cfg(feature = "expr-abort")]
+            Abort(v) => v.compile_to_vm(vm),
+        }
+    }
+
     pub fn as_enum(
         &self,
         keyword: &'static str,
@@ -244,12 +320,22 @@ impl Expression for Expr {
             Query(v) => v.resolve(ctx),
             #[cfg(feature = "expr-function_call")]
             FunctionCall(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(v) => v.resolve(ctx),
             Variable(v) => v.resolve(ctx),
             Noop(v) => v.resolve(ctx),
             #[cfg(feature = "expr-unary")]
             Unary(v) => v.resolve(ctx),
             #[cfg(feature = "expr-abort")]
             Abort(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-loop")]
+            Loop(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-loop")]
+            Walk(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-loop")]
+            Traverse(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-loop")]
+            LoopControl(v) => v.resolve(ctx),
         }
     }
 
@@ -270,12 +356,22 @@ impl Expression for Expr {
             Query(v) => Expression::as_value(v),
             #[cfg(feature = "expr-function_call")]
             FunctionCall(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(v) => Expression::as_value(v),
             Variable(v) => Expression::as_value(v),
             Noop(v) => Expression::as_value(v),
             #[cfg(feature = "expr-unary")]
             Unary(v) => Expression::as_value(v),
             #[cfg(feature = "expr-abort")]
             Abort(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-loop")]
+            Loop(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-loop")]
+            Walk(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-loop")]
+            Traverse(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-loop")]
+            LoopControl(v) => Expression::as_value(v),
         }
     }
 
@@ -296,12 +392,22 @@ impl Expression for Expr {
             Query(v) => v.type_def(state),
             #[cfg(feature = "expr-function_call")]
             FunctionCall(v) => v.type_def(state),
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(v) => v.type_def(state),
             Variable(v) => v.type_def(state),
             Noop(v) => v.type_def(state),
             #[cfg(feature = "expr-unary")]
             Unary(v) => v.type_def(state),
             #[cfg(feature = "expr-abort")]
             Abort(v) => v.type_def(state),
+            #[cfg(feature = "expr-loop")]
+            Loop(v) => v.type_def(state),
+            #[cfg(feature = "expr-loop")]
+            Walk(v) => v.type_def(state),
+            #[cfg(feature = "expr-loop")]
+            Traverse(v) => v.type_def(state),
+            #[cfg(feature = "expr-loop")]
+            LoopControl(v) => v.type_def(state),
         }
     }
 }
@@ -324,12 +430,22 @@ impl fmt::Display for Expr {
             Query(v) => v.fmt(f),
             #[cfg(feature = "expr-function_call")]
             FunctionCall(v) => v.fmt(f),
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(v) => v.fmt(f),
             Variable(v) => v.fmt(f),
             Noop(v) => v.fmt(f),
             #[cfg(feature = "expr-unary")]
             Unary(v) => v.fmt(f),
             #[cfg(feature = "expr-abort")]
             Abort(v) => v.fmt(f),
+            #[cfg(feature = "expr-loop")]
+            Loop(v) => v.fmt(f),
+            #[cfg(feature = "expr-loop")]
+            Walk(v) => v.fmt(f),
+            #[cfg(feature = "expr-loop")]
+            Traverse(v) => v.fmt(f),
+            #[cfg(feature = "expr-loop")]
+            LoopControl(v) => v.fmt(f),
         }
     }
 }
@@ -384,6 +500,13 @@ impl From<FunctionCall> for Expr {
     }
 }
 
+#[cfg(feature = "expr-function_call")]
+impl From<UserFunctionCall> for Expr {
+    fn from(user_function_call: UserFunctionCall) -> Self {
+        Expr::UserFunctionCall(user_function_call)
+    }
+}
+
 impl From<Variable> for Expr {
     fn from(variable: Variable) -> Self {
         Expr::Variable(variable)
@@ -410,6 +533,34 @@ impl From<Abort> for Expr {
     }
 }
 
+#[cfg(feature = "expr-loop")]
+impl From<Loop> for Expr {
+    fn from(statement: Loop) -> Self {
+        Expr::Loop(statement)
+    }
+}
+
+#[cfg(feature = "expr-loop")]
+impl From<Walk> for Expr {
+    fn from(walk: Walk) -> Self {
+        Expr::Walk(walk)
+    }
+}
+
+#[cfg(feature = "expr-loop")]
+impl From<Traverse> for Expr {
+    fn from(traverse: Traverse) -> Self {
+        Expr::Traverse(traverse)
+    }
+}
+
+#[cfg(feature = "expr-loop")]
+impl From<LoopControl> for Expr {
+    fn from(control: LoopControl) -> Self {
+        Expr::LoopControl(control)
+    }
+}
+
 #[cfg(feature = "expr-literal")]
 impl From<Value> for Expr {
     fn from(value: Value) -> Self {
@@ -9,6 +9,7 @@ use crate::{
     state::{ExternalEnv, LocalEnv},
     value::Kind,
     value::VrlValueConvert,
+    vm::{OpCode, Vm},
     Context, Expression, Span, TypeDef,
 };
 
@@ -47,6 +48,17 @@ impl Abort {
 
         Ok(Self { span, message })
     }
+
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        if let Some(expr) = &self.message {
+            expr.compile_to_vm(vm);
+        }
+
+        vm.emit(OpCode::Abort {
+            span: self.span,
+            message: self.message.is_some(),
+        });
+    }
 }
 
 impl Expression for Abort {
@@ -5,6 +5,7 @@ use value::Value;
 use crate::{
     expression::{Expr, Resolved},
     state::{ExternalEnv, LocalEnv},
+    vm::{OpCode, Vm},
     Context, Expression, TypeDef,
 };
 
@@ -17,6 +18,14 @@ impl Array {
     pub(crate) fn new(inner: Vec<Expr>) -> Self {
         Self { inner }
     }
+
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        for expr in &self.inner {
+            expr.compile_to_vm(vm);
+        }
+
+        vm.emit(OpCode::MakeArray(self.inner.len()));
+    }
 }
 
 impl Deref for Array {
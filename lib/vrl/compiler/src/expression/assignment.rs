@@ -13,6 +13,7 @@ use crate::{
     state::{ExternalEnv, LocalEnv},
     type_def::Details,
     value::kind::DefaultValue,
+    vm::{OpCode, Vm},
     Context, Expression, Span, TypeDef,
 };
 
@@ -154,6 +155,37 @@ impl Assignment {
 
         targets
     }
+
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        match &self.variant {
+            Variant::Single { target, expr } => {
+                expr.compile_to_vm(vm);
+
+                let target = vm.add_target(target.clone());
+                vm.emit(OpCode::Assign(target));
+            }
+            Variant::Infallible {
+                ok,
+                err,
+                expr,
+                default,
+            } => {
+                let ok = vm.add_target(ok.clone());
+                let err = vm.add_target(err.clone());
+                let default = vm.add_constant(default.clone());
+
+                let handler = vm.emit(OpCode::Try(0));
+                expr.compile_to_vm(vm);
+                vm.emit(OpCode::EndTry);
+                vm.emit(OpCode::AssignOk { ok, err });
+                let end = vm.emit(OpCode::Jump(0));
+
+                vm.patch(handler);
+                vm.emit(OpCode::AssignErr { ok, err, default });
+                vm.patch(end);
+            }
+        }
+    }
 }
 
 fn verify_mutable(
@@ -262,7 +294,7 @@ impl Target {
         }
     }
 
-    fn insert(&self, value: Value, ctx: &mut Context) {
+    pub(crate) fn insert(&self, value: Value, ctx: &mut Context) {
         use Target::*;
 
         match self {
@@ -400,6 +432,7 @@ where
                     err.insert(Value::Null, ctx);
                     value
                 }
+                Err(error) if error.is_loop_control() => return Err(error),
                 Err(error) => {
                     ok.insert(default.clone(), ctx);
                     let value = Value::from(error.to_string());
@@ -1,6 +1,7 @@
 use crate::{
     expression::{Expr, Resolved},
     state::{ExternalEnv, LocalEnv},
+    vm::{OpCode, Vm},
     Context, Expression, TypeDef,
 };
 use std::fmt;
@@ -25,6 +26,17 @@ impl Block {
     pub fn into_inner(self) -> Vec<Expr> {
         self.inner
     }
+
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        let (last, other) = self.inner.split_last().expect("at least one expression");
+
+        for expr in other {
+            expr.compile_to_vm(vm);
+            vm.emit(OpCode::Pop);
+        }
+
+        last.compile_to_vm(vm);
+    }
 }
 
 impl Expression for Block {
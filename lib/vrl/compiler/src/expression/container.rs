@@ -3,6 +3,7 @@ use std::fmt;
 use crate::{
     expression::{Array, Block, Group, Object, Resolved, Value},
     state::{ExternalEnv, LocalEnv},
+    vm::Vm,
     Context, Expression, TypeDef,
 };
 
@@ -15,6 +16,17 @@ impl Container {
     pub fn new(variant: Variant) -> Self {
         Self { variant }
     }
+
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        use Variant::*;
+
+        match &self.variant {
+            Group(v) => v.compile_to_vm(vm),
+            Block(v) => v.compile_to_vm(vm),
+            Array(v) => v.compile_to_vm(vm),
+            Object(v) => v.compile_to_vm(vm),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
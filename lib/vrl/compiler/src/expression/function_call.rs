@@ -302,6 +302,18 @@ impl<'a> Builder<'a> {
                                     (kind.into(), None)
                                 }
 
+                                // The variable kind is expected to be equal to
+                                // the kind of the argument of another
+                                // parameter.
+                                VariableKind::Parameter(keyword) => {
+                                    match list.arguments.get(keyword) {
+                                        Some(expr) => {
+                                            (expr.type_def((local, external)), expr.as_value())
+                                        }
+                                        None => (TypeDef::any(), None),
+                                    }
+                                }
+
                                 // The variable kind is expected to be equal to
                                 // the kind of all keys within the target
                                 // collection type.
@@ -589,6 +601,9 @@ impl Expression for FunctionCall {
             ExpressionError::Abort { .. } => {
                 panic!("abort errors must only be defined by `abort` statement")
             }
+            // Arguments can break out of a loop the function call is part of.
+            #[cfg(feature = "expr-loop")]
+            err @ (ExpressionError::Break { .. } | ExpressionError::Continue { .. }) => err,
             ExpressionError::Error {
                 message,
                 mut labels,
@@ -6,6 +6,7 @@ use crate::{
     expression::{Block, Predicate, Resolved},
     state::{ExternalEnv, LocalEnv},
     value::VrlValueConvert,
+    vm::{OpCode, Vm},
     Context, Expression, TypeDef,
 };
 
@@ -16,6 +17,27 @@ pub struct IfStatement {
     pub alternative: Option<Block>,
 }
 
+impl IfStatement {
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        self.predicate.compile_to_vm(vm);
+        let alternative = vm.emit(OpCode::JumpIfFalse(0));
+
+        self.consequent.compile_to_vm(vm);
+        let end = vm.emit(OpCode::Jump(0));
+
+        vm.patch(alternative);
+        match &self.alternative {
+            Some(block) => block.compile_to_vm(vm),
+            None => {
+                let index = vm.add_constant(Value::Null);
+                vm.emit(OpCode::Constant(index));
+            }
+        }
+
+        vm.patch(end);
+    }
+}
+
 impl Expression for IfStatement {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let predicate = self.predicate.resolve(ctx)?.try_boolean()?;
@@ -10,6 +10,7 @@ use value::{Value, ValueRegex};
 use crate::{
     expression::Resolved,
     state::{ExternalEnv, LocalEnv},
+    vm::{OpCode, Vm},
     Context, Expression, Span, TypeDef,
 };
 
@@ -44,6 +45,11 @@ impl Literal {
             Null => Value::Null,
         }
     }
+
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        let index = vm.add_constant(self.to_value());
+        vm.emit(OpCode::Constant(index));
+    }
 }
 
 impl Expression for Literal {
@@ -0,0 +1,94 @@
+use std::fmt;
+
+use diagnostic::{DiagnosticMessage, Label, Note, Urls};
+use parser::ast;
+
+use crate::{
+    expression::{ExpressionError, Resolved},
+    state::{ExternalEnv, LocalEnv},
+    Context, Expression, Span, TypeDef,
+};
+
+/// A `break` or `continue` statement.
+///
+/// It unwinds to the enclosing loop as an [`ExpressionError`], which stops the
+/// loop or starts its next iteration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopControl {
+    span: Span,
+    variant: ast::LoopControl,
+}
+
+impl LoopControl {
+    pub(crate) fn new(
+        span: Span,
+        variant: ast::LoopControl,
+        loop_depth: usize,
+    ) -> Result<Self, Error> {
+        if loop_depth == 0 {
+            return Err(Error { span, variant });
+        }
+
+        Ok(Self { span, variant })
+    }
+}
+
+impl Expression for LoopControl {
+    fn resolve(&self, _: &mut Context) -> Resolved {
+        let span = self.span;
+
+        Err(match self.variant {
+            ast::LoopControl::Break => ExpressionError::Break { span },
+            ast::LoopControl::Continue => ExpressionError::Continue { span },
+        })
+    }
+
+    fn type_def(&self, _: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        TypeDef::never().infallible()
+    }
+}
+
+impl fmt::Display for LoopControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.variant.fmt(f)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct Error {
+    span: Span,
+    variant: ast::LoopControl,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} outside of a loop", self.variant)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        136
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        vec![
+            Label::primary(format!("{} is not inside a loop", self.variant), self.span),
+            Label::context(
+                "closures and function definitions start outside of any loop",
+                self.span,
+            ),
+        ]
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        vec![Note::SeeDocs(
+            "loops".to_owned(),
+            Urls::expression_docs_url("#loop"),
+        )]
+    }
+}
@@ -0,0 +1,73 @@
+use std::fmt;
+
+use diagnostic::Label;
+use value::Value;
+
+use crate::{
+    expression::{Block, ExpressionError, Predicate, Resolved},
+    state::{ExternalEnv, LocalEnv},
+    value::VrlValueConvert,
+    Context, Expression, Span, TypeDef,
+};
+
+/// The maximum number of iterations of a `while` or `loop` statement, after
+/// which it fails at runtime.
+pub const MAX_ITERATIONS: usize = 10_000;
+
+/// A `while` loop, or a `loop` without a predicate, which runs until it is
+/// stopped by `break`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loop {
+    pub predicate: Option<Predicate>,
+    pub block: Block,
+    span: Span,
+}
+
+impl Loop {
+    pub(crate) fn new(span: Span, predicate: Option<Predicate>, block: Block) -> Self {
+        Self {
+            predicate,
+            block,
+            span,
+        }
+    }
+}
+
+impl Expression for Loop {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        for _ in 0..MAX_ITERATIONS {
+            if let Some(predicate) = &self.predicate {
+                if !predicate.resolve(ctx)?.try_boolean()? {
+                    return Ok(Value::Null);
+                }
+            }
+
+            match self.block.resolve(ctx) {
+                Ok(_) | Err(ExpressionError::Continue { .. }) => {}
+                Err(ExpressionError::Break { .. }) => return Ok(Value::Null),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(ExpressionError::Error {
+            message: format!("loop exceeded {} iterations", MAX_ITERATIONS),
+            labels: vec![Label::primary("this loop did not stop", self.span)],
+            notes: vec![],
+        })
+    }
+
+    fn type_def(&self, _: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        // Loops fail once they exceed the maximum number of iterations, even
+        // if their block can't fail.
+        TypeDef::null().fallible()
+    }
+}
+
+impl fmt::Display for Loop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.predicate {
+            Some(predicate) => write!(f, "while {} {}", predicate, self.block),
+            None => write!(f, "loop {}", self.block),
+        }
+    }
+}
@@ -8,6 +8,7 @@ use crate::{
     parser::Node,
     state::{ExternalEnv, LocalEnv},
     value::Kind,
+    vm::{OpCode, Vm},
     Context, Expression, Span, TypeDef,
 };
 
@@ -35,6 +36,11 @@ impl Not {
             inner: Box::new(expr),
         })
     }
+
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        self.inner.compile_to_vm(vm);
+        vm.emit(OpCode::Not);
+    }
 }
 
 impl Expression for Not {
@@ -5,6 +5,7 @@ use value::Value;
 use crate::{
     expression::{Expr, Resolved},
     state::{ExternalEnv, LocalEnv},
+    vm::{OpCode, Vm},
     Context, Expression, TypeDef,
 };
 
@@ -17,6 +18,15 @@ impl Object {
     pub fn new(inner: BTreeMap<String, Expr>) -> Self {
         Self { inner }
     }
+
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        for expr in self.inner.values() {
+            expr.compile_to_vm(vm);
+        }
+
+        let index = vm.add_keys(self.inner.keys().cloned().collect());
+        vm.emit(OpCode::MakeObject(index));
+    }
 }
 
 impl Deref for Object {
@@ -5,6 +5,7 @@ use value::Value;
 
 use crate::state::{ExternalEnv, LocalEnv};
 use crate::value::VrlValueArithmetic;
+use crate::vm::{OpCode, Vm};
 use crate::{
     expression::{self, Expr, Resolved},
     parser::{ast, Node},
@@ -75,6 +76,49 @@ impl Op {
             opcode,
         })
     }
+
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        use ast::Opcode::*;
+
+        match self.opcode {
+            Err => {
+                let handler = vm.emit(OpCode::Try(0));
+                self.lhs.compile_to_vm(vm);
+                vm.emit(OpCode::EndTry);
+                let end = vm.emit(OpCode::Jump(0));
+
+                vm.patch(handler);
+                self.rhs.compile_to_vm(vm);
+                vm.patch(end);
+            }
+            Or => {
+                self.lhs.compile_to_vm(vm);
+                let lhs = vm.emit(OpCode::JumpIfTruthy(0));
+
+                let handler = vm.emit(OpCode::Try(0));
+                self.rhs.compile_to_vm(vm);
+                vm.emit(OpCode::EndTry);
+                let rhs = vm.emit(OpCode::Jump(0));
+
+                vm.patch(handler);
+                vm.emit(OpCode::RethrowOr);
+                vm.patch(lhs);
+                vm.patch(rhs);
+            }
+            And => {
+                self.lhs.compile_to_vm(vm);
+                let end = vm.emit(OpCode::JumpIfFalsy(0));
+                self.rhs.compile_to_vm(vm);
+                vm.emit(OpCode::Binary(And));
+                vm.patch(end);
+            }
+            opcode => {
+                self.lhs.compile_to_vm(vm);
+                self.rhs.compile_to_vm(vm);
+                vm.emit(OpCode::Binary(opcode));
+            }
+        }
+    }
 }
 
 impl Expression for Op {
@@ -83,7 +127,13 @@ impl Expression for Op {
         use value::Value::*;
 
         if let Err = self.opcode {
-            return self.lhs.resolve(ctx).or_else(|_| self.rhs.resolve(ctx));
+            return self
+                .lhs
+                .resolve(ctx)
+                .or_else(|err| match err.is_loop_control() {
+                    true => Err(err),
+                    false => self.rhs.resolve(ctx),
+                });
         } else if let Or = self.opcode {
             return self
                 .lhs
@@ -8,6 +8,7 @@ use crate::{
     parser::Node,
     state::{ExternalEnv, LocalEnv},
     value::Kind,
+    vm::{OpCode, Vm},
     Context, Expression, Span, TypeDef,
 };
 
@@ -51,6 +52,24 @@ impl Predicate {
     pub fn new_unchecked(inner: Vec<Expr>) -> Self {
         Self { inner }
     }
+
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        let (last, other) = match self.inner.split_last() {
+            Some(exprs) => exprs,
+            None => {
+                let index = vm.add_constant(Value::Boolean(false));
+                vm.emit(OpCode::Constant(index));
+                return;
+            }
+        };
+
+        for expr in other {
+            expr.compile_to_vm(vm);
+            vm.emit(OpCode::Pop);
+        }
+
+        last.compile_to_vm(vm);
+    }
 }
 
 impl Expression for Predicate {
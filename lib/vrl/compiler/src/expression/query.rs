@@ -8,6 +8,7 @@ use crate::{
     parser::ast::Ident,
     state::{ExternalEnv, LocalEnv},
     type_def::Details,
+    vm::{OpCode, Vm},
     Context, Expression, TypeDef,
 };
 
@@ -78,6 +79,38 @@ impl Query {
 
         result
     }
+
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        use Target::*;
+
+        match &self.target {
+            External => {
+                let path = vm.add_path(self.path.clone());
+                vm.emit(OpCode::GetTarget(path));
+                return;
+            }
+            Internal(variable) if !self.path.is_root() => {
+                let index = vm.add_variable(variable.ident().clone());
+                let path = vm.add_path(self.path.clone());
+                vm.emit(OpCode::GetVariablePath(index, path));
+                return;
+            }
+            Internal(variable) => variable.compile_to_vm(vm),
+            #[cfg(feature = "expr-function_call")]
+            FunctionCall(call) => {
+                let index = vm.add_expression(call.clone().into());
+                vm.emit(OpCode::Eval(index));
+            }
+            #[cfg(not(feature = "expr-function_call"))]
+            FunctionCall(noop) => noop.compile_to_vm(vm),
+            Container(container) => container.compile_to_vm(vm),
+        }
+
+        if !self.path.is_root() {
+            let path = vm.add_path(self.path.clone());
+            vm.emit(OpCode::GetPath(path));
+        }
+    }
 }
 
 impl Expression for Query {
@@ -0,0 +1,180 @@
+use std::{collections::BTreeMap, fmt};
+
+use value::Value;
+
+use crate::{
+    expression::{Block, Expr, ExpressionError, Resolved},
+    parser::ast::Ident,
+    state::{ExternalEnv, LocalEnv},
+    value::Collection,
+    Context, Expression, TypeDef,
+};
+
+/// A `traverse` statement, which runs its block for every value nested in the
+/// target, with the path to the value and the value itself, and resolves to
+/// the target with every value replaced by the result of the block.
+///
+/// Values are visited depth-first, children before their parents, so the block
+/// gets values whose children are already replaced. As only the values nested
+/// in the target are visited, a traversal always terminates. Returning from
+/// the block with `continue` keeps the current value as it is, and `break`
+/// keeps every value not visited yet as it is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Traverse {
+    target: Box<Expr>,
+    variables: Vec<Ident>,
+    block: Block,
+}
+
+impl Traverse {
+    pub(crate) fn new(target: Expr, variables: Vec<Ident>, block: Block) -> Self {
+        Self {
+            target: Box::new(target),
+            variables,
+            block,
+        }
+    }
+
+    fn ident(&self, index: usize) -> Option<&Ident> {
+        self.variables
+            .get(index)
+            .and_then(|v| (!v.is_empty()).then(|| v))
+    }
+
+    /// Replaces the children of the value, returning `false` if the traversal
+    /// was stopped by `break`.
+    fn traverse(
+        &self,
+        ctx: &mut Context,
+        path: &mut Vec<Value>,
+        value: Value,
+    ) -> Result<(Value, bool), ExpressionError> {
+        match value {
+            Value::Object(object) => {
+                let mut traversed = BTreeMap::new();
+                let mut children = object.into_iter();
+
+                for (key, child) in children.by_ref() {
+                    path.push(Value::from(key.as_str()));
+                    let (child, proceed) = self.visit(ctx, path, child)?;
+                    path.pop();
+
+                    traversed.insert(key, child);
+                    if !proceed {
+                        traversed.extend(children);
+                        return Ok((Value::Object(traversed), false));
+                    }
+                }
+
+                Ok((Value::Object(traversed), true))
+            }
+            Value::Array(array) => {
+                let mut traversed = Vec::with_capacity(array.len());
+                let mut children = array.into_iter().enumerate();
+
+                for (index, child) in children.by_ref() {
+                    path.push(Value::from(index as i64));
+                    let (child, proceed) = self.visit(ctx, path, child)?;
+                    path.pop();
+
+                    traversed.push(child);
+                    if !proceed {
+                        traversed.extend(children.map(|(_, child)| child));
+                        return Ok((Value::Array(traversed), false));
+                    }
+                }
+
+                Ok((Value::Array(traversed), true))
+            }
+            value => Ok((value, true)),
+        }
+    }
+
+    /// Traverses a nested value, and runs the block for it.
+    fn visit(
+        &self,
+        ctx: &mut Context,
+        path: &mut Vec<Value>,
+        value: Value,
+    ) -> Result<(Value, bool), ExpressionError> {
+        let (value, proceed) = self.traverse(ctx, path, value)?;
+        if !proceed {
+            return Ok((value, false));
+        }
+
+        if let Some(ident) = self.ident(0) {
+            ctx.state_mut()
+                .insert_variable(ident.clone(), Value::Array(path.clone()));
+        }
+        if let Some(ident) = self.ident(1) {
+            ctx.state_mut()
+                .insert_variable(ident.clone(), value.clone());
+        }
+
+        match self.block.resolve(ctx) {
+            Ok(replacement) => Ok((replacement, true)),
+            Err(ExpressionError::Continue { .. }) => Ok((value, true)),
+            Err(ExpressionError::Break { .. }) => Ok((value, false)),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl Expression for Traverse {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.target.resolve(ctx)?;
+
+        let shadowed = self
+            .variables
+            .iter()
+            .filter(|ident| !ident.is_empty())
+            .map(|ident| (ident, ctx.state().variable(ident).cloned()))
+            .collect::<Vec<_>>();
+
+        let result = self.traverse(ctx, &mut vec![], value);
+
+        let state = ctx.state_mut();
+        for (ident, value) in shadowed {
+            match value {
+                Some(value) => state.insert_variable(ident.clone(), value),
+                None => state.remove_variable(ident),
+            }
+        }
+
+        result.map(|(value, _)| value)
+    }
+
+    fn type_def(&self, state: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        // The target keeps its type, but the values nested in it can be
+        // replaced by values of any type.
+        let mut kind = self.target.type_def(state).kind().clone();
+        if kind.contains_object() {
+            kind.add_object(Collection::any());
+        }
+        if kind.contains_array() {
+            kind.add_array(Collection::any());
+        }
+
+        TypeDef::from(kind).with_fallibility(self.block.type_def(state).is_fallible())
+    }
+}
+
+impl fmt::Display for Traverse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "traverse {} -> |", self.target)?;
+
+        let mut iter = self.variables.iter().peekable();
+        while let Some(ident) = iter.next() {
+            match ident.is_empty() {
+                true => f.write_str("_")?,
+                false => ident.fmt(f)?,
+            }
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        write!(f, "| {}", self.block)
+    }
+}
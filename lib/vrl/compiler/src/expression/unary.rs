@@ -3,6 +3,7 @@ use std::fmt;
 use crate::{
     expression::{Not, Resolved},
     state::{ExternalEnv, LocalEnv},
+    vm::Vm,
     Context, Expression, TypeDef,
 };
 
@@ -15,6 +16,14 @@ impl Unary {
     pub fn new(variant: Variant) -> Self {
         Self { variant }
     }
+
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        use Variant::*;
+
+        match &self.variant {
+            Not(v) => v.compile_to_vm(vm),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
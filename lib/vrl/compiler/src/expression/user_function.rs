@@ -0,0 +1,521 @@
+use std::{fmt, sync::Arc};
+
+use diagnostic::{DiagnosticMessage, Label, Note};
+
+use crate::{
+    expression::{Block, Expr, FunctionArgument, Resolved},
+    parser::{Ident, Node},
+    state::{ExternalEnv, LocalEnv},
+    value::{Collection, Kind},
+    Context, Expression, Span, TypeDef,
+};
+
+/// A function defined in a VRL program, using the `fn` keyword.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserFunction {
+    ident: Ident,
+    parameters: Vec<UserParameter>,
+    block: Block,
+
+    /// The type of the value returned by the function, inferred from its
+    /// block.
+    type_def: TypeDef,
+
+    /// Whether the function assigns to the external target, in which case the
+    /// type of the target is unknown after calling it.
+    mutates_target: bool,
+}
+
+/// A parameter of a [`UserFunction`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserParameter {
+    pub(crate) ident: Ident,
+    pub(crate) kind: Kind,
+}
+
+impl UserFunction {
+    pub(crate) fn new(
+        ident: Ident,
+        parameters: Vec<UserParameter>,
+        block: Block,
+        type_def: TypeDef,
+        mutates_target: bool,
+    ) -> Self {
+        Self {
+            ident,
+            parameters,
+            block,
+            type_def,
+            mutates_target,
+        }
+    }
+
+    pub fn ident(&self) -> &Ident {
+        &self.ident
+    }
+
+    pub fn parameters(&self) -> &[UserParameter] {
+        &self.parameters
+    }
+
+    pub(crate) fn mutates_target(&self) -> bool {
+        self.mutates_target
+    }
+}
+
+/// Parses the type annotation of a parameter, such as `string | null`.
+pub(crate) fn annotation_kind(names: &[Node<String>]) -> Result<Kind, Error> {
+    let mut kind = Kind::never();
+
+    for name in names {
+        kind = match name.as_str() {
+            "string" => kind.or_bytes(),
+            "integer" => kind.or_integer(),
+            "float" => kind.or_float(),
+            "boolean" => kind.or_boolean(),
+            "timestamp" => kind.or_timestamp(),
+            "regex" => kind.or_regex(),
+            "null" => kind.or_null(),
+            "array" => kind.or_array(Collection::any()),
+            "object" => kind.or_object(Collection::any()),
+            "any" => Kind::any(),
+            _ => {
+                return Err(Error::UnknownType {
+                    span: name.span(),
+                    name: name.inner().clone(),
+                })
+            }
+        };
+    }
+
+    Ok(kind)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserFunctionCall {
+    function: Arc<UserFunction>,
+
+    /// The arguments, in the order of the parameters of the function.
+    arguments: Vec<Expr>,
+
+    /// Whether the kind of each argument must be checked at runtime, because
+    /// it only partially matches the kind of its parameter.
+    checked: Vec<bool>,
+
+    abort_on_error: bool,
+    span: Span,
+}
+
+impl UserFunctionCall {
+    pub(crate) fn new(
+        call_span: Span,
+        ident_span: Span,
+        abort_on_error: bool,
+        function: Arc<UserFunction>,
+        arguments: Vec<Node<FunctionArgument>>,
+        state: (&LocalEnv, &ExternalEnv),
+    ) -> Result<Self, Error> {
+        let parameters = function.parameters();
+
+        if arguments.len() > parameters.len() {
+            let start = arguments[parameters.len()].span().start();
+            let end = arguments.last().expect("exists").span().end();
+
+            return Err(Error::WrongNumberOfArgs {
+                arguments_span: Span::new(start, end),
+                max: parameters.len(),
+            });
+        }
+
+        let mut ordered: Vec<Option<Node<FunctionArgument>>> = vec![None; parameters.len()];
+        let mut index = 0;
+        for node in arguments {
+            let position = match node.keyword() {
+                None => {
+                    while ordered.get(index).map_or(false, Option::is_some) {
+                        index += 1;
+                    }
+                    index
+                }
+                Some(keyword) => parameters
+                    .iter()
+                    .position(|parameter| parameter.ident.as_ref() == keyword)
+                    .ok_or_else(|| Error::UnknownKeyword {
+                        keyword_span: node.keyword_span().expect("exists"),
+                        ident_span,
+                        keywords: parameters.iter().map(|p| p.ident.clone()).collect(),
+                    })?,
+            };
+
+            match ordered.get_mut(position) {
+                Some(slot) if slot.is_none() => *slot = Some(node),
+                _ => {
+                    return Err(Error::DuplicateArgument {
+                        argument_span: node.span(),
+                    })
+                }
+            }
+        }
+
+        let mut exprs = Vec::with_capacity(parameters.len());
+        let mut checked = Vec::with_capacity(parameters.len());
+        for (position, (parameter, argument)) in parameters.iter().zip(ordered).enumerate() {
+            let argument = argument.ok_or_else(|| Error::MissingArgument {
+                call_span,
+                keyword: parameter.ident.clone(),
+                position,
+            })?;
+
+            let type_def = argument.type_def(state);
+            if type_def.is_fallible() {
+                return Err(Error::FallibleArgument {
+                    expr_span: argument.expr_span(),
+                });
+            }
+
+            let kind = type_def.kind();
+            if !parameter.kind.intersects(kind) {
+                return Err(Error::InvalidArgumentKind {
+                    keyword: parameter.ident.clone(),
+                    expected: parameter.kind.clone(),
+                    got: kind.clone(),
+                    expr_span: argument.expr_span(),
+                    argument_span: argument.span(),
+                });
+            }
+
+            checked.push(!parameter.kind.is_superset(kind));
+            exprs.push(argument.into_inner().into_inner());
+        }
+
+        // Only the runtime type checks of the arguments can fail.
+        if abort_on_error && !checked.contains(&true) {
+            return Err(Error::AbortInfallible {
+                ident_span,
+                abort_span: Span::new(ident_span.end(), ident_span.end() + 1),
+            });
+        }
+
+        Ok(Self {
+            function,
+            arguments: exprs,
+            checked,
+            abort_on_error,
+            span: call_span,
+        })
+    }
+
+    pub(crate) fn function(&self) -> &UserFunction {
+        &self.function
+    }
+}
+
+impl Expression for UserFunctionCall {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let mut variables = std::collections::HashMap::with_capacity(self.arguments.len());
+
+        for ((argument, parameter), checked) in self
+            .arguments
+            .iter()
+            .zip(&self.function.parameters)
+            .zip(&self.checked)
+        {
+            let value = argument.resolve(ctx)?;
+
+            if *checked && !parameter.kind.is_superset(&value.kind()) {
+                return Err(format!(
+                    r#"function call error for "{}" at ({}:{}): expected "{}" to be {}, got {}"#,
+                    self.function.ident,
+                    self.span.start(),
+                    self.span.end(),
+                    parameter.ident,
+                    parameter.kind,
+                    value.kind(),
+                )
+                .into());
+            }
+
+            variables.insert(parameter.ident.clone(), value);
+        }
+
+        // The block of the function only has access to its parameters, so
+        // the variables of the caller are set aside while it is resolved.
+        let caller_variables = ctx.state_mut().swap_variables(variables);
+        let resolved = self.function.block.resolve(ctx);
+        ctx.state_mut().swap_variables(caller_variables);
+
+        resolved
+    }
+
+    fn type_def(&self, _: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        self.function
+            .type_def
+            .clone()
+            .with_fallibility(!self.abort_on_error && self.checked.contains(&true))
+    }
+}
+
+impl fmt::Display for UserFunctionCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.function.ident.fmt(f)?;
+        f.write_str("(")?;
+
+        let mut iter = self.arguments.iter().peekable();
+        while let Some(argument) = iter.next() {
+            argument.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        f.write_str(")")
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("unknown type")]
+    UnknownType { span: Span, name: String },
+
+    #[error("duplicate function parameter")]
+    DuplicateParameter { span: Span },
+
+    #[error("function already defined")]
+    AlreadyDefined { ident_span: Span, builtin: bool },
+
+    #[error("wrong number of function arguments")]
+    WrongNumberOfArgs { arguments_span: Span, max: usize },
+
+    #[error("unknown function argument keyword")]
+    UnknownKeyword {
+        keyword_span: Span,
+        ident_span: Span,
+        keywords: Vec<Ident>,
+    },
+
+    #[error("duplicate function argument")]
+    DuplicateArgument { argument_span: Span },
+
+    #[error("missing function argument")]
+    MissingArgument {
+        call_span: Span,
+        keyword: Ident,
+        position: usize,
+    },
+
+    #[error("invalid argument type")]
+    InvalidArgumentKind {
+        keyword: Ident,
+        expected: Kind,
+        got: Kind,
+        expr_span: Span,
+        argument_span: Span,
+    },
+
+    #[error("fallible argument")]
+    FallibleArgument { expr_span: Span },
+
+    #[error("unexpected closure")]
+    UnexpectedClosure { call_span: Span, closure_span: Span },
+
+    #[error("can't abort infallible function")]
+    AbortInfallible { ident_span: Span, abort_span: Span },
+
+    #[error("unexpected expression in module")]
+    ModuleExpression { span: Span },
+
+    #[error("invalid import")]
+    Import {
+        path_span: Span,
+        message: String,
+        diagnostics: Vec<String>,
+    },
+}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        use Error::*;
+
+        match self {
+            UnknownType { .. } => 130,
+            DuplicateParameter { .. } => 131,
+            AlreadyDefined { .. } => 132,
+            WrongNumberOfArgs { .. } => 106,
+            UnknownKeyword { .. } => 108,
+            DuplicateArgument { .. } => 133,
+            MissingArgument { .. } => 107,
+            InvalidArgumentKind { .. } => 110,
+            FallibleArgument { .. } => 630,
+            UnexpectedClosure { .. } => 109,
+            AbortInfallible { .. } => 620,
+            ModuleExpression { .. } => 134,
+            Import { .. } => 135,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use Error::*;
+
+        match self {
+            UnknownType { span, name } => vec![
+                Label::primary(format!(r#"unknown type "{}""#, name), span),
+                Label::context(
+                    "expected one of: string, integer, float, boolean, timestamp, regex, null, array, object, any",
+                    span,
+                ),
+            ],
+
+            DuplicateParameter { span } => vec![Label::primary(
+                "this parameter is already defined",
+                span,
+            )],
+
+            AlreadyDefined { ident_span, builtin } => {
+                let context = if *builtin {
+                    "a built-in function has the same name"
+                } else {
+                    "a function with the same name is defined or imported before"
+                };
+
+                vec![
+                    Label::primary("this function is already defined", ident_span),
+                    Label::context(context, ident_span),
+                ]
+            }
+
+            WrongNumberOfArgs {
+                arguments_span,
+                max,
+            } => {
+                let arg = if *max == 1 { "argument" } else { "arguments" };
+
+                vec![
+                    Label::primary("too many function arguments", arguments_span),
+                    Label::context(
+                        format!("this function takes {} {}", max, arg),
+                        arguments_span,
+                    ),
+                ]
+            }
+
+            UnknownKeyword {
+                keyword_span,
+                ident_span,
+                keywords,
+            } => vec![
+                Label::primary("unknown keyword", keyword_span),
+                Label::context(
+                    format!(
+                        "this function accepts the following keywords: {}",
+                        keywords
+                            .iter()
+                            .map(|k| format!(r#""{}""#, k))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    ident_span,
+                ),
+            ],
+
+            DuplicateArgument { argument_span } => vec![Label::primary(
+                "this parameter is already passed an argument",
+                argument_span,
+            )],
+
+            MissingArgument {
+                call_span,
+                keyword,
+                position,
+            } => vec![Label::primary(
+                format!(
+                    r#"required argument missing: "{}" (position {})"#,
+                    keyword, position
+                ),
+                call_span,
+            )],
+
+            InvalidArgumentKind {
+                keyword,
+                expected,
+                got,
+                expr_span,
+                argument_span,
+            } => vec![
+                Label::primary(
+                    format!("this expression resolves to {}", kind_str(got)),
+                    expr_span,
+                ),
+                Label::context(
+                    format!(
+                        r#"but the parameter "{}" expects {}"#,
+                        keyword,
+                        kind_str(expected)
+                    ),
+                    argument_span,
+                ),
+            ],
+
+            FallibleArgument { expr_span } => vec![
+                Label::primary("this expression can fail", expr_span),
+                Label::context(
+                    "handle the error before passing it in as an argument",
+                    expr_span,
+                ),
+            ],
+
+            UnexpectedClosure {
+                call_span,
+                closure_span,
+            } => vec![
+                Label::primary("unexpected closure", closure_span),
+                Label::context("user-defined functions don't accept a closure", call_span),
+            ],
+
+            AbortInfallible {
+                ident_span,
+                abort_span,
+            } => vec![
+                Label::primary("this function can't fail", ident_span),
+                Label::context("remove this abort-instruction", abort_span),
+            ],
+
+            ModuleExpression { span } => vec![
+                Label::primary("unexpected expression", span),
+                Label::context(
+                    "imported files can only contain function definitions and imports",
+                    span,
+                ),
+            ],
+
+            Import {
+                path_span, message, ..
+            } => vec![Label::primary(message, path_span)],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        use Error::*;
+
+        match self {
+            FallibleArgument { .. } | AbortInfallible { .. } => vec![Note::SeeErrorDocs],
+            Import { diagnostics, .. } => diagnostics
+                .iter()
+                .map(|line| Note::Basic(line.to_owned()))
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+fn kind_str(kind: &Kind) -> String {
+    if kind.is_any() {
+        kind.to_string()
+    } else if kind.is_exact() {
+        format!("the exact type {}", kind)
+    } else {
+        format!("one of {}", kind)
+    }
+}
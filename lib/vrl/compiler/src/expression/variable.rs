@@ -6,6 +6,7 @@ use crate::{
     expression::{levenstein, Resolved},
     parser::ast::Ident,
     state::{ExternalEnv, LocalEnv},
+    vm::{OpCode, Vm},
     Context, Expression, Span, TypeDef,
 };
 
@@ -39,6 +40,11 @@ impl Variable {
     pub fn value(&self) -> Option<&Value> {
         self.value.as_ref()
     }
+
+    pub(crate) fn compile_to_vm(&self, vm: &mut Vm) {
+        let index = vm.add_variable(self.ident.clone());
+        vm.emit(OpCode::GetVariable(index));
+    }
 }
 
 impl Expression for Variable {
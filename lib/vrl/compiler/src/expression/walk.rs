@@ -0,0 +1,234 @@
+use std::fmt;
+
+use diagnostic::{DiagnosticMessage, Label, Note, Urls};
+use value::Value;
+
+use crate::{
+    expression::{Block, Expr, ExpressionError, Resolved},
+    parser::ast::Ident,
+    state::{ExternalEnv, LocalEnv},
+    type_def::Details,
+    value::{Collection, Kind},
+    Context, Expression, Span, TypeDef,
+};
+
+/// A `walk` statement, which runs its block for every value nested in the
+/// target, with the path to the value and the value itself.
+///
+/// Values are visited depth-first, parents before their children. Returning
+/// from the block with `continue` skips the children of the current value,
+/// and `break` stops the walk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Walk {
+    target: Box<Expr>,
+    variables: Vec<Ident>,
+    block: Block,
+}
+
+impl Walk {
+    pub(crate) fn new(target: Expr, variables: Vec<Ident>, block: Block) -> Self {
+        Self {
+            target: Box::new(target),
+            variables,
+            block,
+        }
+    }
+
+    /// Checks the variables of the block of a `walk` or `traverse`, and adds
+    /// them to the local environment before the block is compiled.
+    pub(crate) fn bind_variables(
+        keyword: &'static str,
+        span: Span,
+        variables: &[Ident],
+        local: &mut LocalEnv,
+    ) -> Result<(), Error> {
+        if variables.len() != 2 {
+            return Err(Error {
+                keyword,
+                span,
+                got: variables.len(),
+            });
+        }
+
+        let path = Kind::array(Collection::from_unknown(Kind::bytes().or_integer()));
+        let kinds = [path, Kind::any()];
+
+        for (ident, kind) in variables.iter().zip(kinds) {
+            if !ident.is_empty() {
+                let details = Details {
+                    type_def: kind.into(),
+                    value: None,
+                };
+
+                local.insert_variable(ident.clone(), details);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restores the variables shadowed by those of the block, once the block
+    /// is compiled.
+    pub(crate) fn unbind_variables(variables: &[Ident], local: &mut LocalEnv, snapshot: &LocalEnv) {
+        for ident in variables.iter().filter(|ident| !ident.is_empty()) {
+            match snapshot.variable(ident) {
+                Some(details) => local.insert_variable(ident.clone(), details.clone()),
+                None => {
+                    local.remove_variable(ident);
+                }
+            }
+        }
+    }
+
+    fn ident(&self, index: usize) -> Option<&Ident> {
+        self.variables
+            .get(index)
+            .and_then(|v| (!v.is_empty()).then(|| v))
+    }
+
+    /// Visits the children of the value, returning `false` if the walk was
+    /// stopped by `break`.
+    fn walk(
+        &self,
+        ctx: &mut Context,
+        path: &mut Vec<Value>,
+        value: &Value,
+    ) -> Result<bool, ExpressionError> {
+        let children: Box<dyn Iterator<Item = (Value, &Value)>> = match value {
+            Value::Object(object) => Box::new(
+                object
+                    .iter()
+                    .map(|(key, value)| (Value::from(key.as_str()), value)),
+            ),
+            Value::Array(array) => Box::new(
+                array
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| (Value::from(index as i64), value)),
+            ),
+            _ => return Ok(true),
+        };
+
+        for (segment, child) in children {
+            path.push(segment);
+
+            if let Some(ident) = self.ident(0) {
+                ctx.state_mut()
+                    .insert_variable(ident.clone(), Value::Array(path.clone()));
+            }
+            if let Some(ident) = self.ident(1) {
+                ctx.state_mut()
+                    .insert_variable(ident.clone(), child.clone());
+            }
+
+            let descend = match self.block.resolve(ctx) {
+                Ok(_) => true,
+                Err(ExpressionError::Continue { .. }) => false,
+                Err(ExpressionError::Break { .. }) => return Ok(false),
+                Err(err) => return Err(err),
+            };
+
+            if descend && !self.walk(ctx, path, child)? {
+                return Ok(false);
+            }
+
+            path.pop();
+        }
+
+        Ok(true)
+    }
+}
+
+impl Expression for Walk {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        // The walk visits the value as it was before the walk started, even
+        // if the block changes it.
+        let value = self.target.resolve(ctx)?;
+
+        let shadowed = self
+            .variables
+            .iter()
+            .filter(|ident| !ident.is_empty())
+            .map(|ident| (ident, ctx.state().variable(ident).cloned()))
+            .collect::<Vec<_>>();
+
+        let result = self.walk(ctx, &mut vec![], &value);
+
+        let state = ctx.state_mut();
+        for (ident, value) in shadowed {
+            match value {
+                Some(value) => state.insert_variable(ident.clone(), value),
+                None => state.remove_variable(ident),
+            }
+        }
+
+        result.map(|_| Value::Null)
+    }
+
+    fn type_def(&self, state: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        TypeDef::null().with_fallibility(self.block.type_def(state).is_fallible())
+    }
+}
+
+impl fmt::Display for Walk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "walk {} -> |", self.target)?;
+
+        let mut iter = self.variables.iter().peekable();
+        while let Some(ident) = iter.next() {
+            match ident.is_empty() {
+                true => f.write_str("_")?,
+                false => ident.fmt(f)?,
+            }
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        write!(f, "| {}", self.block)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct Error {
+    keyword: &'static str,
+    span: Span,
+    got: usize,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} variables mismatch", self.keyword)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        120
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        vec![
+            Label::primary(format!("got {} variables", self.got), self.span),
+            Label::context(
+                format!(
+                    "{} binds two variables: the path and the value",
+                    self.keyword
+                ),
+                self.span,
+            ),
+        ]
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        vec![Note::SeeDocs(
+            "loops".to_owned(),
+            Urls::expression_docs_url("#loop"),
+        )]
+    }
+}
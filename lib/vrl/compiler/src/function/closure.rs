@@ -93,6 +93,13 @@ pub enum VariableKind {
     /// a `Value::bytes()`, if it's known to be exactly an array, it is
     /// a `Value::integer()`, otherwise it is one of the two.
     TargetInnerKey,
+
+    /// The variable [`Kind`] is inferred from the argument of the given
+    /// parameter of the function, or "any" if the argument is missing.
+    ///
+    /// For example, the accumulator of `reduce` starts with the `initial`
+    /// argument.
+    Parameter(&'static str),
 }
 
 /// The output type required by the closure block.
@@ -215,6 +222,34 @@ where
         Ok(())
     }
 
+    /// Run the closure to completion, given the provided values for its
+    /// variables, in order, and the runtime context.
+    ///
+    /// The provided values are *NOT* mutated during the run, and the return
+    /// value of the closure is returned.
+    pub fn run_values(
+        &self,
+        ctx: &mut Context,
+        values: Vec<Value>,
+    ) -> Result<Value, ExpressionError> {
+        let old_values = values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let ident = self.ident(index);
+                (ident, insert(ctx.state_mut(), ident, value))
+            })
+            .collect::<Vec<_>>();
+
+        let result = (self.runner)(ctx);
+
+        for (ident, old_value) in old_values {
+            cleanup(ctx.state_mut(), ident, old_value);
+        }
+
+        result
+    }
+
     /// Run the closure to completion, given the provided key, and the runtime
     /// context.
     ///
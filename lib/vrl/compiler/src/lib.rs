@@ -16,6 +16,7 @@ pub mod function;
 pub mod state;
 pub mod type_def;
 pub mod value;
+pub mod vm;
 
 pub use core::{
     value, ExpressionError, MetadataTarget, Resolved, SecretTarget, Target, TargetValue,
@@ -33,6 +34,7 @@ pub use paste::paste;
 pub use program::{Program, ProgramInfo};
 use state::ExternalEnv;
 pub use type_def::TypeDef;
+pub use vm::Vm;
 
 pub type Result<T = (Program, DiagnosticList)> = std::result::Result<T, DiagnosticList>;
 
@@ -41,6 +43,7 @@ pub type Result<T = (Program, DiagnosticList)> = std::result::Result<T, Diagnost
 #[serde(rename_all = "lowercase")]
 pub enum VrlRuntime {
     Ast,
+    Vm,
 }
 
 impl Default for VrlRuntime {
@@ -55,7 +58,8 @@ impl FromStr for VrlRuntime {
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         match s {
             "ast" => Ok(Self::Ast),
-            _ => Err("runtime must be ast."),
+            "vm" => Ok(Self::Vm),
+            _ => Err("runtime must be ast or vm."),
         }
     }
 }
@@ -67,6 +71,7 @@ impl Display for VrlRuntime {
             "{}",
             match self {
                 VrlRuntime::Ast => "ast",
+                VrlRuntime::Vm => "vm",
             }
         )
     }
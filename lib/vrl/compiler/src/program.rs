@@ -3,7 +3,7 @@ use lookup::LookupBuf;
 use crate::{
     expression::{Block, Resolved},
     state::LocalEnv,
-    Context, Expression,
+    Context, Expression, Vm,
 };
 
 #[derive(Debug, Clone)]
@@ -35,6 +35,14 @@ impl Program {
     pub fn resolve(&self, ctx: &mut Context) -> Resolved {
         self.expressions.resolve(ctx)
     }
+
+    /// Lower the program to the instructions of a [`Vm`], which resolves to the
+    /// same [`Value`] as [`Program::resolve`].
+    pub fn compile_to_vm(&self) -> Vm {
+        let mut vm = Vm::default();
+        self.expressions.compile_to_vm(&mut vm);
+        vm
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -42,7 +50,8 @@ pub struct ProgramInfo {
     /// Returns whether the compiled program can fail at runtime.
     ///
     /// A program can only fail at runtime if the fallible-function-call
-    /// (`foo!()`) is used within the source.
+    /// (`foo!()`) is used within the source, or a `while` or `loop` statement,
+    /// which fails when it exceeds its maximum number of iterations.
     pub fallible: bool,
 
     /// Returns whether the compiled program can be aborted at runtime.
@@ -1,4 +1,7 @@
-use std::collections::{hash_map::Entry, HashMap};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    path::{Path, PathBuf},
+};
 
 use anymap::AnyMap;
 use lookup::LookupBuf;
@@ -22,12 +25,16 @@ impl LocalEnv {
         self.bindings.get(ident)
     }
 
-    #[cfg(any(feature = "expr-assignment", feature = "expr-function_call"))]
+    #[cfg(any(
+        feature = "expr-assignment",
+        feature = "expr-function_call",
+        feature = "expr-loop"
+    ))]
     pub(crate) fn insert_variable(&mut self, ident: Ident, details: Details) {
         self.bindings.insert(ident, details);
     }
 
-    #[cfg(feature = "expr-function_call")]
+    #[cfg(any(feature = "expr-function_call", feature = "expr-loop"))]
     pub(crate) fn remove_variable(&mut self, ident: &Ident) -> Option<Details> {
         self.bindings.remove(ident)
     }
@@ -54,6 +61,18 @@ impl LocalEnv {
         }
         self
     }
+
+    /// Widens the variables whose state differs from the other env to any
+    /// type. This is used when the types of a loop don't settle.
+    #[cfg(feature = "expr-loop")]
+    pub(crate) fn widen(mut self, other: &Self) -> Self {
+        for (ident, details) in &mut self.bindings {
+            if let Some(other_details) = other.bindings.get(ident) {
+                *details = details.clone().widen(other_details);
+            }
+        }
+        self
+    }
 }
 
 /// A lexical scope within the program.
@@ -66,6 +85,10 @@ pub struct ExternalEnv {
 
     /// Custom context injected by the external environment
     custom: AnyMap,
+
+    /// The directory the paths of imports are relative to. Defaults to the
+    /// current working directory.
+    import_dir: Option<PathBuf>,
 }
 
 // temporary until paths can point to metadata
@@ -99,6 +122,7 @@ impl ExternalEnv {
             },
             custom: AnyMap::new(),
             read_only_paths: vec![],
+            import_dir: None,
         }
     }
 
@@ -187,6 +211,16 @@ impl ExternalEnv {
         self
     }
 
+    /// Sets the directory the paths of imports are relative to.
+    pub fn set_import_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.import_dir = Some(dir.into());
+    }
+
+    #[cfg(feature = "expr-function_call")]
+    pub(crate) fn import_dir(&self) -> Option<&Path> {
+        self.import_dir.as_deref()
+    }
+
     /// Get external context data from the external environment.
     pub fn get_external_context<T: 'static>(&self) -> Option<&T> {
         self.custom.get::<T>()
@@ -232,6 +266,15 @@ impl Runtime {
         self.variables.remove(ident);
     }
 
+    /// Replaces all variables, returning the previous ones.
+    #[cfg(feature = "expr-function_call")]
+    pub(crate) fn swap_variables(
+        &mut self,
+        variables: HashMap<Ident, Value>,
+    ) -> HashMap<Ident, Value> {
+        std::mem::replace(&mut self.variables, variables)
+    }
+
     pub(crate) fn swap_variable(&mut self, ident: Ident, value: Value) -> Option<Value> {
         match self.variables.entry(ident) {
             Entry::Occupied(mut v) => Some(std::mem::replace(v.get_mut(), value)),
@@ -400,6 +400,19 @@ impl Details {
             },
         }
     }
+
+    /// Returns any type if the state differs from the other one.
+    #[cfg(feature = "expr-loop")]
+    pub(crate) fn widen(self, other: &Self) -> Self {
+        if &self == other {
+            return self;
+        }
+
+        Self {
+            type_def: TypeDef::any(),
+            value: None,
+        }
+    }
 }
 
 #[cfg(test)]
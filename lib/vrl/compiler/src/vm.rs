@@ -0,0 +1,471 @@
+//! A bytecode runtime for compiled VRL programs.
+//!
+//! A [`Program`](crate::Program) is lowered once into a flat list of
+//! [`OpCode`]s, which are then executed against a value stack for every event.
+//! Expressions that have no dedicated instructions (such as function calls)
+//! are kept as-is, and resolved through the [`Expression`] tree when their
+//! [`OpCode::Eval`] instruction is reached.
+
+use std::{collections::BTreeMap, fmt};
+
+use lookup::LookupBuf;
+use value::Value;
+
+#[cfg(feature = "expr-assignment")]
+use crate::expression::assignment;
+#[cfg(feature = "expr-abort")]
+use crate::Span;
+use crate::{
+    expression::Expr,
+    parser::ast::{self, Ident},
+    value::{VrlValueArithmetic, VrlValueConvert},
+    Context, Expression, ExpressionError, Resolved,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OpCode {
+    /// Push the constant at the given index onto the stack.
+    Constant(usize),
+
+    /// Discard the value at the top of the stack.
+    Pop,
+
+    /// Push the value of the variable at the given index.
+    GetVariable(usize),
+
+    /// Push the value at the given path of the variable at the given index.
+    GetVariablePath(usize, usize),
+
+    /// Push the value at the given path of the target.
+    GetTarget(usize),
+
+    /// Replace the value at the top of the stack with the value at the given
+    /// path within it.
+    GetPath(usize),
+
+    /// Assign a copy of the value at the top of the stack to the given target.
+    #[cfg(feature = "expr-assignment")]
+    Assign(usize),
+
+    /// Assign a copy of the value at the top of the stack to the `ok` target,
+    /// and `null` to the `err` target.
+    #[cfg(feature = "expr-assignment")]
+    AssignOk { ok: usize, err: usize },
+
+    /// Assign the default constant to the `ok` target, and the message of the
+    /// caught error to the `err` target, pushing the message.
+    #[cfg(feature = "expr-assignment")]
+    AssignErr {
+        ok: usize,
+        err: usize,
+        default: usize,
+    },
+
+    /// Negate the boolean at the top of the stack.
+    Not,
+
+    /// Pop the right- and left-hand side values, and push the result of the
+    /// operation.
+    Binary(ast::Opcode),
+
+    /// Jump to the given instruction.
+    Jump(usize),
+
+    /// Pop a boolean, and jump to the given instruction if it is `false`.
+    JumpIfFalse(usize),
+
+    /// Jump to the given instruction if the value at the top of the stack is
+    /// `null` or `false`, replacing it with `false`.
+    JumpIfFalsy(usize),
+
+    /// Jump to the given instruction if the value at the top of the stack is
+    /// neither `null` nor `false`, or pop it otherwise.
+    JumpIfTruthy(usize),
+
+    /// Jump to the given instruction if any of the instructions up to the
+    /// matching [`OpCode::EndTry`] fails.
+    Try(usize),
+
+    /// End the region started by the last [`OpCode::Try`].
+    EndTry,
+
+    /// Fail with the caught error, as the right-hand side of an `||`
+    /// operation.
+    RethrowOr,
+
+    /// Pop the given number of values, and push them as an array.
+    MakeArray(usize),
+
+    /// Pop a value for each of the keys at the given index, and push them as
+    /// an object.
+    MakeObject(usize),
+
+    /// Abort the program, with the string at the top of the stack as the
+    /// message if there is one.
+    #[cfg(feature = "expr-abort")]
+    Abort { span: Span, message: bool },
+
+    /// Resolve the expression at the given index, and push its value.
+    Eval(usize),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Vm {
+    instructions: Vec<OpCode>,
+    constants: Vec<Value>,
+    variables: Vec<Ident>,
+    paths: Vec<LookupBuf>,
+    keys: Vec<Vec<String>>,
+    expressions: Vec<Expr>,
+
+    #[cfg(feature = "expr-assignment")]
+    targets: Vec<assignment::Target>,
+}
+
+/// The state of a single run of a [`Vm`].
+#[derive(Debug, Default)]
+struct Machine {
+    stack: Vec<Value>,
+    handlers: Vec<Handler>,
+    error: Option<ExpressionError>,
+}
+
+#[derive(Debug)]
+struct Handler {
+    target: usize,
+    depth: usize,
+}
+
+impl Machine {
+    fn push(&mut self, value: Value) {
+        self.stack.push(value);
+    }
+
+    fn pop(&mut self) -> Value {
+        self.stack.pop().expect("stack underflow")
+    }
+
+    fn top(&mut self) -> &mut Value {
+        self.stack.last_mut().expect("empty stack")
+    }
+
+    fn split_off(&mut self, len: usize) -> Vec<Value> {
+        self.stack.split_off(self.stack.len() - len)
+    }
+
+    fn take_error(&mut self) -> ExpressionError {
+        self.error.take().expect("caught error")
+    }
+}
+
+impl Vm {
+    /// The instructions of the program.
+    pub fn instructions(&self) -> &[OpCode] {
+        &self.instructions
+    }
+
+    /// Run the program to completion.
+    pub fn run(&self, ctx: &mut Context) -> Resolved {
+        let mut machine = Machine::default();
+        let mut ip = 0;
+
+        while let Some(&op) = self.instructions.get(ip) {
+            ip += 1;
+
+            if let Err(error) = self.step(op, &mut ip, &mut machine, ctx) {
+                match machine.handlers.pop() {
+                    Some(Handler { target, depth }) => {
+                        machine.stack.truncate(depth);
+                        machine.error = Some(error);
+                        ip = target;
+                    }
+                    None => return Err(error),
+                }
+            }
+        }
+
+        Ok(machine.stack.pop().unwrap_or(Value::Null))
+    }
+
+    fn step(
+        &self,
+        op: OpCode,
+        ip: &mut usize,
+        machine: &mut Machine,
+        ctx: &mut Context,
+    ) -> Result<(), ExpressionError> {
+        use OpCode::*;
+
+        match op {
+            Constant(index) => machine.push(self.constants[index].clone()),
+            Pop => {
+                machine.pop();
+            }
+            GetVariable(index) => machine.push(
+                ctx.state()
+                    .variable(&self.variables[index])
+                    .cloned()
+                    .unwrap_or(Value::Null),
+            ),
+            GetVariablePath(index, path) => machine.push(
+                ctx.state()
+                    .variable(&self.variables[index])
+                    .and_then(|value| value.get_by_path(&self.paths[path]))
+                    .cloned()
+                    .unwrap_or(Value::Null),
+            ),
+            GetTarget(path) => machine.push(
+                ctx.target()
+                    .target_get(&self.paths[path])
+                    .ok()
+                    .flatten()
+                    .cloned()
+                    .unwrap_or(Value::Null),
+            ),
+            GetPath(path) => {
+                let value = machine.pop();
+                machine.push(
+                    value
+                        .get_by_path(&self.paths[path])
+                        .cloned()
+                        .unwrap_or(Value::Null),
+                );
+            }
+            #[cfg(feature = "expr-assignment")]
+            Assign(target) => {
+                let value = machine.top().clone();
+                self.targets[target].insert(value, ctx);
+            }
+            #[cfg(feature = "expr-assignment")]
+            AssignOk { ok, err } => {
+                let value = machine.top().clone();
+                self.targets[ok].insert(value, ctx);
+                self.targets[err].insert(Value::Null, ctx);
+            }
+            #[cfg(feature = "expr-assignment")]
+            AssignErr { ok, err, default } => {
+                self.targets[ok].insert(self.constants[default].clone(), ctx);
+                let value = Value::from(machine.take_error().to_string());
+                self.targets[err].insert(value.clone(), ctx);
+                machine.push(value);
+            }
+            Not => {
+                let value = machine.pop().try_boolean()?;
+                machine.push((!value).into());
+            }
+            Binary(opcode) => {
+                let rhs = machine.pop();
+                let lhs = machine.pop();
+                machine.push(binary(opcode, lhs, rhs)?);
+            }
+            Jump(target) => *ip = target,
+            JumpIfFalse(target) => {
+                if !machine.pop().try_boolean()? {
+                    *ip = target;
+                }
+            }
+            JumpIfFalsy(target) => {
+                let value = machine.top();
+                if matches!(value, Value::Null | Value::Boolean(false)) {
+                    *value = false.into();
+                    *ip = target;
+                }
+            }
+            JumpIfTruthy(target) => {
+                if matches!(machine.top(), Value::Null | Value::Boolean(false)) {
+                    machine.pop();
+                } else {
+                    *ip = target;
+                }
+            }
+            Try(target) => machine.handlers.push(Handler {
+                target,
+                depth: machine.stack.len(),
+            }),
+            EndTry => {
+                machine.handlers.pop();
+            }
+            RethrowOr => {
+                return Err(crate::value::Error::Or(machine.take_error()).into());
+            }
+            MakeArray(len) => {
+                let values = machine.split_off(len);
+                machine.push(Value::Array(values));
+            }
+            MakeObject(index) => {
+                let keys = &self.keys[index];
+                let values = machine.split_off(keys.len());
+                machine.push(Value::Object(
+                    keys.iter().cloned().zip(values).collect::<BTreeMap<_, _>>(),
+                ));
+            }
+            #[cfg(feature = "expr-abort")]
+            Abort { span, message } => {
+                let message = if message {
+                    let value = machine.pop();
+                    Some(value.try_bytes_utf8_lossy()?.to_string())
+                } else {
+                    None
+                };
+
+                return Err(ExpressionError::Abort { span, message });
+            }
+            Eval(index) => {
+                let value = self.expressions[index].resolve(ctx)?;
+                machine.push(value);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn emit(&mut self, op: OpCode) -> usize {
+        self.instructions.push(op);
+        self.instructions.len() - 1
+    }
+
+    /// Point the jump at the given position to the next instruction.
+    pub(crate) fn patch(&mut self, position: usize) {
+        let next = self.instructions.len();
+
+        match &mut self.instructions[position] {
+            OpCode::Jump(target)
+            | OpCode::JumpIfFalse(target)
+            | OpCode::JumpIfFalsy(target)
+            | OpCode::JumpIfTruthy(target)
+            | OpCode::Try(target) => *target = next,
+            op => unreachable!("{:?} is not a jump", op),
+        }
+    }
+
+    pub(crate) fn add_constant(&mut self, value: Value) -> usize {
+        self.constants.push(value);
+        self.constants.len() - 1
+    }
+
+    pub(crate) fn add_variable(&mut self, ident: Ident) -> usize {
+        self.variables.push(ident);
+        self.variables.len() - 1
+    }
+
+    pub(crate) fn add_path(&mut self, path: LookupBuf) -> usize {
+        self.paths.push(path);
+        self.paths.len() - 1
+    }
+
+    pub(crate) fn add_keys(&mut self, keys: Vec<String>) -> usize {
+        self.keys.push(keys);
+        self.keys.len() - 1
+    }
+
+    pub(crate) fn add_expression(&mut self, expr: Expr) -> usize {
+        self.expressions.push(expr);
+        self.expressions.len() - 1
+    }
+
+    #[cfg(feature = "expr-assignment")]
+    pub(crate) fn add_target(&mut self, target: assignment::Target) -> usize {
+        self.targets.push(target);
+        self.targets.len() - 1
+    }
+}
+
+fn binary(opcode: ast::Opcode, lhs: Value, rhs: Value) -> Resolved {
+    use ast::Opcode::*;
+
+    match opcode {
+        Mul => lhs.try_mul(rhs),
+        Div => lhs.try_div(rhs),
+        Add => lhs.try_add(rhs),
+        Sub => lhs.try_sub(rhs),
+        Rem => lhs.try_rem(rhs),
+        Eq => Ok(lhs.eq_lossy(&rhs).into()),
+        Ne => Ok((!lhs.eq_lossy(&rhs)).into()),
+        Gt => lhs.try_gt(rhs),
+        Ge => lhs.try_ge(rhs),
+        Lt => lhs.try_lt(rhs),
+        Le => lhs.try_le(rhs),
+        Merge => lhs.try_merge(rhs),
+        And => lhs.try_and(rhs),
+        Or | Err => unreachable!("short-circuiting operations are compiled to jumps"),
+    }
+    .map_err(Into::into)
+}
+
+impl fmt::Display for Vm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (position, op) in self.instructions.iter().enumerate() {
+            write!(f, "{:04} ", position)?;
+
+            match op {
+                OpCode::Constant(index) => writeln!(f, "Constant({})", self.constants[*index])?,
+                OpCode::GetVariable(index) => {
+                    writeln!(f, "GetVariable({})", self.variables[*index])?
+                }
+                OpCode::GetVariablePath(index, path) => writeln!(
+                    f,
+                    "GetVariablePath({}, {})",
+                    self.variables[*index], self.paths[*path]
+                )?,
+                OpCode::GetTarget(path) => writeln!(f, "GetTarget({})", self.paths[*path])?,
+                OpCode::GetPath(path) => writeln!(f, "GetPath({})", self.paths[*path])?,
+                OpCode::Eval(index) => writeln!(f, "Eval({})", self.expressions[*index])?,
+                op => writeln!(f, "{:?}", op)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::TimeZone;
+
+    use super::*;
+    use crate::{state, value};
+
+    fn run(source: &str) -> (Resolved, Value, Resolved, Value) {
+        let ast = ::parser::parse(source).unwrap();
+        let (program, _) = crate::compile(ast, &[]).unwrap();
+        let vm = program.compile_to_vm();
+        let timezone = TimeZone::default();
+
+        let mut ast_target = value!({ "foo": 1, "bar": { "baz": "qux" } });
+        let mut vm_target = ast_target.clone();
+
+        let mut runtime = state::Runtime::default();
+        let mut ctx = Context::new(&mut ast_target, &mut runtime, &timezone);
+        let ast_result = program.resolve(&mut ctx);
+
+        let mut runtime = state::Runtime::default();
+        let mut ctx = Context::new(&mut vm_target, &mut runtime, &timezone);
+        let vm_result = vm.run(&mut ctx);
+
+        (ast_result, ast_target, vm_result, vm_target)
+    }
+
+    #[test]
+    fn matches_ast_runtime() {
+        let sources = [
+            r#".a = .foo + 1 ?? 0; .b = .bar.baz; .c = [.a, { "d": .b }]"#,
+            r#"x = .bar; x.baz = "quux"; .x = x; .y = x.baz"#,
+            r#"if .foo == 1 { .a = "one" } else if .foo == 2 { .a = "two" }"#,
+            r#"if !(.foo == 1) { "small" } else { "large" }"#,
+            r#".a = .missing || "default"; .b, .err = .foo && true"#,
+            r#".a = .foo + "one" ?? 3"#,
+            r#".a, .err = .foo * .bar; .b = .a"#,
+            r#".a = { "x": 1 } | { "y": 2 }"#,
+            r#"abort "stop""#,
+            r#".a, .err = .bar.baz / 2"#,
+            r#"null"#,
+        ];
+
+        for source in sources {
+            let (ast_result, ast_target, vm_result, vm_target) = run(source);
+
+            assert_eq!(ast_result, vm_result, "{}", source);
+            assert_eq!(ast_target, vm_target, "{}", source);
+        }
+    }
+}
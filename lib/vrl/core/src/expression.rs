@@ -10,6 +10,10 @@ pub enum ExpressionError {
         span: diagnostic::Span,
         message: Option<String>,
     },
+    #[cfg(feature = "expr-loop")]
+    Break { span: diagnostic::Span },
+    #[cfg(feature = "expr-loop")]
+    Continue { span: diagnostic::Span },
     Error {
         message: String,
         labels: Vec<Label>,
@@ -17,6 +21,18 @@ pub enum ExpressionError {
     },
 }
 
+impl ExpressionError {
+    /// Whether the error is a `break` or `continue` statement unwinding to its
+    /// enclosing loop, which error handling must not catch.
+    pub fn is_loop_control(&self) -> bool {
+        match self {
+            #[cfg(feature = "expr-loop")]
+            ExpressionError::Break { .. } | ExpressionError::Continue { .. } => true,
+            _ => false,
+        }
+    }
+}
+
 impl std::fmt::Display for ExpressionError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.message().fmt(f)
@@ -40,6 +56,10 @@ impl DiagnosticMessage for ExpressionError {
         match self {
             #[cfg(feature = "expr-abort")]
             Abort { message, .. } => message.clone().unwrap_or_else(|| "aborted".to_owned()),
+            #[cfg(feature = "expr-loop")]
+            Break { .. } => "break outside of a loop".to_owned(),
+            #[cfg(feature = "expr-loop")]
+            Continue { .. } => "continue outside of a loop".to_owned(),
             Error { message, .. } => message.clone(),
         }
     }
@@ -52,6 +72,10 @@ impl DiagnosticMessage for ExpressionError {
             Abort { span, .. } => {
                 vec![Label::primary("aborted", span)]
             }
+            #[cfg(feature = "expr-loop")]
+            Break { span } | Continue { span } => {
+                vec![Label::primary("not inside a loop", span)]
+            }
             Error { labels, .. } => labels.clone(),
         }
     }
@@ -62,6 +86,8 @@ impl DiagnosticMessage for ExpressionError {
         match self {
             #[cfg(feature = "expr-abort")]
             Abort { .. } => vec![],
+            #[cfg(feature = "expr-loop")]
+            Break { .. } | Continue { .. } => vec![],
             Error { notes, .. } => notes.clone(),
         }
     }
@@ -187,6 +187,12 @@ impl IntoIterator for Program {
 pub enum RootExpr {
     Expr(Node<Expr>),
 
+    /// A user-defined function, callable by the expressions following it.
+    FunctionDefinition(Node<FunctionDefinition>),
+
+    /// An import of the function definitions of another VRL file.
+    Import(Node<Import>),
+
     /// A special expression that is returned if a given expression could not be
     /// parsed. This allows the parser to continue on to the next expression.
     Error(Error),
@@ -198,6 +204,8 @@ impl fmt::Debug for RootExpr {
 
         let value = match self {
             Expr(v) => format!("{:?}", v),
+            FunctionDefinition(v) => format!("{:?}", v),
+            Import(v) => format!("{:?}", v),
             Error(v) => format!("{:?}", v),
         };
 
@@ -211,6 +219,8 @@ impl fmt::Display for RootExpr {
 
         match self {
             Expr(v) => v.fmt(f),
+            FunctionDefinition(v) => v.fmt(f),
+            Import(v) => v.fmt(f),
             Error(v) => v.fmt(f),
         }
     }
@@ -233,6 +243,10 @@ pub enum Expr {
     Variable(Node<Ident>),
     Unary(Node<Unary>),
     Abort(Node<Abort>),
+    Loop(Node<Loop>),
+    Walk(Node<Walk>),
+    Traverse(Node<Traverse>),
+    LoopControl(Node<LoopControl>),
 }
 
 impl fmt::Debug for Expr {
@@ -250,6 +264,10 @@ impl fmt::Debug for Expr {
             Variable(v) => format!("{:?}", v),
             Unary(v) => format!("{:?}", v),
             Abort(v) => format!("{:?}", v),
+            Loop(v) => format!("{:?}", v),
+            Walk(v) => format!("{:?}", v),
+            Traverse(v) => format!("{:?}", v),
+            LoopControl(v) => format!("{:?}", v),
         };
 
         write!(f, "Expr({})", value)
@@ -271,6 +289,10 @@ impl fmt::Display for Expr {
             Variable(v) => v.fmt(f),
             Unary(v) => v.fmt(f),
             Abort(v) => v.fmt(f),
+            Loop(v) => v.fmt(f),
+            Walk(v) => v.fmt(f),
+            Traverse(v) => v.fmt(f),
+            LoopControl(v) => v.fmt(f),
         }
     }
 }
@@ -658,6 +680,132 @@ impl fmt::Debug for Predicate {
     }
 }
 
+// -----------------------------------------------------------------------------
+// loops
+// -----------------------------------------------------------------------------
+
+/// A `while` loop, or a `loop` without a predicate.
+#[derive(Clone, PartialEq)]
+pub struct Loop {
+    pub predicate: Option<Node<Predicate>>,
+    pub block: Node<Block>,
+}
+
+impl fmt::Debug for Loop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.predicate {
+            Some(predicate) => write!(f, "While({:?}, {:?})", predicate, self.block),
+            None => write!(f, "Loop({:?})", self.block),
+        }
+    }
+}
+
+impl fmt::Display for Loop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.predicate {
+            Some(predicate) => write!(f, "while {} {}", predicate, self.block),
+            None => write!(f, "loop {}", self.block),
+        }
+    }
+}
+
+/// A `walk` over every value nested in the target, which binds the path and
+/// the value to the variables of the block.
+#[derive(Clone, PartialEq)]
+pub struct Walk {
+    pub target: Node<Expr>,
+    pub variables: Vec<Node<Ident>>,
+    pub block: Node<Block>,
+}
+
+impl fmt::Debug for Walk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Walk({:?}, {:?}, {:?})",
+            self.target, self.variables, self.block
+        )
+    }
+}
+
+impl fmt::Display for Walk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "walk {} -> |", self.target)?;
+
+        let mut iter = self.variables.iter().peekable();
+        while let Some(var) = iter.next() {
+            var.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        write!(f, "| {}", self.block)
+    }
+}
+
+/// A `traverse` of every value nested in the target, which binds the path and
+/// the value to the variables of the block, and replaces the value with the
+/// result of the block.
+#[derive(Clone, PartialEq)]
+pub struct Traverse {
+    pub target: Node<Expr>,
+    pub variables: Vec<Node<Ident>>,
+    pub block: Node<Block>,
+}
+
+impl fmt::Debug for Traverse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Traverse({:?}, {:?}, {:?})",
+            self.target, self.variables, self.block
+        )
+    }
+}
+
+impl fmt::Display for Traverse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "traverse {} -> |", self.target)?;
+
+        let mut iter = self.variables.iter().peekable();
+        while let Some(var) = iter.next() {
+            var.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        write!(f, "| {}", self.block)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LoopControl {
+    Break,
+    Continue,
+}
+
+impl fmt::Display for LoopControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoopControl::Break => f.write_str("break"),
+            LoopControl::Continue => f.write_str("continue"),
+        }
+    }
+}
+
+impl fmt::Debug for LoopControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoopControl::Break => f.write_str("Break"),
+            LoopControl::Continue => f.write_str("Continue"),
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // operation
 // -----------------------------------------------------------------------------
@@ -1105,6 +1253,126 @@ impl fmt::Debug for FunctionClosure {
     }
 }
 
+// -----------------------------------------------------------------------------
+// function definition
+// -----------------------------------------------------------------------------
+
+/// A user-defined function.
+///
+/// It contains the identifier of the function, its parameters, and the block
+/// of expressions evaluated when the function is called.
+#[derive(Clone, PartialEq)]
+pub struct FunctionDefinition {
+    pub ident: Node<Ident>,
+    pub parameters: Vec<Node<FunctionParameter>>,
+    pub block: Node<Block>,
+}
+
+impl fmt::Display for FunctionDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fn {}(", self.ident)?;
+
+        let mut iter = self.parameters.iter().peekable();
+        while let Some(parameter) = iter.next() {
+            parameter.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        write!(f, ") {}", self.block)
+    }
+}
+
+impl fmt::Debug for FunctionDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "FunctionDefinition({:?}, {:?}, {:?})",
+            self.ident, self.parameters, self.block
+        )
+    }
+}
+
+/// A parameter of a user-defined function.
+///
+/// The optional type annotation lists the types the argument is allowed to
+/// have, and defaults to any type.
+#[derive(Clone, PartialEq)]
+pub struct FunctionParameter {
+    pub ident: Node<Ident>,
+    pub kind: Option<Node<TypeAnnotation>>,
+}
+
+impl fmt::Display for FunctionParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.ident.fmt(f)?;
+
+        if let Some(kind) = &self.kind {
+            write!(f, ": {}", kind)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for FunctionParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            Some(kind) => write!(f, "Parameter({:?}: {:?})", self.ident, kind),
+            None => write!(f, "Parameter({:?})", self.ident),
+        }
+    }
+}
+
+/// A union of type names, such as `string | null`.
+#[derive(Clone, PartialEq)]
+pub struct TypeAnnotation(pub Vec<Node<String>>);
+
+impl fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut iter = self.0.iter().peekable();
+        while let Some(kind) = iter.next() {
+            kind.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(" | ")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for TypeAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TypeAnnotation({})", self)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// import
+// -----------------------------------------------------------------------------
+
+/// An import of the function definitions of another VRL file.
+#[derive(Clone, PartialEq)]
+pub struct Import {
+    pub path: Node<String>,
+}
+
+impl fmt::Display for Import {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#"import "{}""#, self.path)
+    }
+}
+
+impl fmt::Debug for Import {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Import({:?})", self.path)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // unary
 // -----------------------------------------------------------------------------
@@ -0,0 +1,678 @@
+//! Pretty-printing of VRL programs.
+//!
+//! The program is printed from its AST, so its layout is the same no matter
+//! how it was written. Literals, paths and object keys are printed as they
+//! were written, to preserve their escapes and quoting.
+//!
+//! Comments aren't part of the AST. They're found in between the tokens of
+//! the program, and printed on their own line before the statement (or list
+//! item) that follows them. A comment on the same line as the end of a
+//! statement stays on that line.
+
+use diagnostic::{span, Span};
+
+use crate::{ast::*, lex::Lexer, parse, Error, Token};
+
+/// The string used to indent blocks and broken up lists.
+const INDENT: &str = "  ";
+
+/// Lists that don't fit on a single line of this width are broken up, with
+/// one item per line.
+const MAX_WIDTH: usize = 80;
+
+/// Formats the given VRL program.
+///
+/// # Errors
+///
+/// Errors if the program can't be parsed.
+pub fn format(source: &str) -> Result<String, Error> {
+    let comments = comments(source)?;
+    let program = parse(source)?;
+
+    // Programs are parsed with error recovery, so errors can also be part of
+    // the program.
+    if let Some(err) = program.iter().find_map(|expr| match expr.inner() {
+        RootExpr::Error(err) => Some(err.clone()),
+        _ => None,
+    }) {
+        return Err(err);
+    }
+
+    let mut formatter = Formatter {
+        source,
+        comments,
+        next_comment: 0,
+    };
+
+    Ok(formatter.items(
+        &program,
+        0,
+        source.len(),
+        "",
+        Node::span,
+        Formatter::root_expr,
+    ))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Comment<'a> {
+    span: Span,
+    text: &'a str,
+}
+
+/// Finds the comments in the source, which are all non-whitespace characters
+/// that aren't part of a token.
+fn comments(source: &str) -> Result<Vec<Comment<'_>>, Error> {
+    let mut comments = vec![];
+    let mut end = 0;
+
+    for token in Lexer::new(source) {
+        let (start, token, token_end) = token?;
+
+        // Query boundaries don't represent any characters of the source.
+        if matches!(token, Token::LQuery | Token::RQuery) {
+            continue;
+        }
+
+        comments_between(source, end, start.max(end), &mut comments);
+        end = end.max(token_end);
+    }
+
+    comments_between(source, end, source.len(), &mut comments);
+
+    Ok(comments)
+}
+
+fn comments_between<'a>(
+    source: &'a str,
+    start: usize,
+    end: usize,
+    comments: &mut Vec<Comment<'a>>,
+) {
+    let mut offset = start;
+
+    for line in source[start..end].split_inclusive('\n') {
+        if let Some(index) = line.find('#') {
+            let text = line[index..].trim_end();
+            let start = offset + index;
+
+            comments.push(Comment {
+                span: span(start, start + text.len()),
+                text,
+            });
+        }
+
+        offset += line.len();
+    }
+}
+
+struct Formatter<'a> {
+    source: &'a str,
+    comments: Vec<Comment<'a>>,
+    next_comment: usize,
+}
+
+impl<'a> Formatter<'a> {
+    fn slice(&self, span: Span) -> &'a str {
+        &self.source[span.start()..span.end()]
+    }
+
+    fn has_comment_before(&self, position: usize) -> bool {
+        self.comments
+            .get(self.next_comment)
+            .map_or(false, |comment| comment.span.start() < position)
+    }
+
+    fn take_comment_before(&mut self, position: usize) -> Option<Comment<'a>> {
+        if !self.has_comment_before(position) {
+            return None;
+        }
+
+        self.next_comment += 1;
+        Some(self.comments[self.next_comment - 1])
+    }
+
+    /// Takes the next comment, if it's on the same line as the given position.
+    fn take_trailing_comment(&mut self, position: usize) -> Option<Comment<'a>> {
+        let comment = *self.comments.get(self.next_comment)?;
+
+        if comment.span.start() < position
+            || self.source[position..comment.span.start()].contains('\n')
+        {
+            return None;
+        }
+
+        self.next_comment += 1;
+        Some(comment)
+    }
+
+    /// Whether the source has an empty line in between the two positions.
+    fn has_empty_line(&self, start: usize, end: usize) -> bool {
+        start < end && self.source[start..end].matches('\n').count() > 1
+    }
+
+    /// Formats the comments before the given position, each on its own line.
+    fn leading_comments(
+        &mut self,
+        output: &mut String,
+        indent: usize,
+        previous_end: &mut Option<usize>,
+        position: usize,
+    ) {
+        while let Some(comment) = self.take_comment_before(position) {
+            if previous_end.map_or(false, |end| self.has_empty_line(end, comment.span.start())) {
+                output.push('\n');
+            }
+
+            output.push_str(&INDENT.repeat(indent));
+            output.push_str(comment.text);
+            output.push('\n');
+            *previous_end = Some(comment.span.end());
+        }
+    }
+
+    /// Formats statements, or the items of a broken up list, each on its own
+    /// line, along with the comments in between them. Single empty lines in
+    /// between items are kept.
+    ///
+    /// The `end` of the enclosing block or list is used to format the
+    /// comments following the last item.
+    fn items<T>(
+        &mut self,
+        items: &[T],
+        indent: usize,
+        end: usize,
+        separator: &str,
+        item_span: fn(&T) -> Span,
+        item: fn(&mut Self, &T, usize) -> String,
+    ) -> String {
+        let mut output = String::new();
+        let mut previous_end = None;
+
+        for value in items {
+            let span = item_span(value);
+
+            self.leading_comments(&mut output, indent, &mut previous_end, span.start());
+            if previous_end.map_or(false, |end| self.has_empty_line(end, span.start())) {
+                output.push('\n');
+            }
+
+            output.push_str(&INDENT.repeat(indent));
+            output.push_str(&item(self, value, indent));
+            output.push_str(separator);
+            previous_end = Some(span.end());
+
+            if let Some(comment) = self.take_trailing_comment(span.end()) {
+                output.push(' ');
+                output.push_str(comment.text);
+                previous_end = Some(comment.span.end());
+            }
+
+            output.push('\n');
+        }
+
+        self.leading_comments(&mut output, indent, &mut previous_end, end);
+
+        output
+    }
+
+    /// Formats a comma-separated list on a single line if it fits, or with
+    /// one item per line otherwise.
+    fn list<T>(
+        &mut self,
+        (open, close): (&str, &str),
+        items: &[T],
+        end: usize,
+        indent: usize,
+        item_span: fn(&T) -> Span,
+        item: fn(&mut Self, &T, usize) -> String,
+    ) -> String {
+        let closing_indent = INDENT.repeat(indent);
+
+        // Comments can only be kept when every item is on its own line.
+        if self.has_comment_before(end) {
+            let items = self.items(items, indent + 1, end, ",", item_span, item);
+
+            return format!("{}\n{}{}{}", open, items, closing_indent, close);
+        }
+
+        let items = items
+            .iter()
+            .map(|value| item(self, value, indent + 1))
+            .collect::<Vec<_>>();
+
+        let single_line = match (open, items.is_empty()) {
+            ("{", false) => format!("{{ {} }}", items.join(", ")),
+            _ => format!("{}{}{}", open, items.join(", "), close),
+        };
+
+        if !single_line.contains('\n') && closing_indent.len() + single_line.len() <= MAX_WIDTH {
+            return single_line;
+        }
+
+        let mut output = format!("{}\n", open);
+        for item in items {
+            output.push_str(&INDENT.repeat(indent + 1));
+            output.push_str(&item);
+            output.push_str(",\n");
+        }
+        output.push_str(&closing_indent);
+        output.push_str(close);
+
+        output
+    }
+
+    fn root_expr(&mut self, expr: &Node<RootExpr>, indent: usize) -> String {
+        match expr.inner() {
+            RootExpr::Expr(expr) => self.expr(expr, indent),
+            RootExpr::FunctionDefinition(definition) => {
+                self.function_definition(definition, indent)
+            }
+            RootExpr::Import(import) => format!("import {}", self.slice(import.path.span())),
+            RootExpr::Error(_) => self.slice(expr.span()).to_owned(),
+        }
+    }
+
+    fn expr(&mut self, expr: &Node<Expr>, indent: usize) -> String {
+        match expr.inner() {
+            Expr::Literal(literal) => self.slice(literal.span()).to_owned(),
+            Expr::Container(container) => self.container(container, indent),
+            Expr::IfStatement(if_statement) => self.if_statement(if_statement, indent),
+            Expr::Op(op) => {
+                let Op(lhs, opcode, rhs) = op.inner();
+
+                format!(
+                    "{} {} {}",
+                    self.expr(lhs, indent),
+                    opcode,
+                    self.expr(rhs, indent)
+                )
+            }
+            Expr::Assignment(assignment) => self.assignment(assignment, indent),
+            Expr::Query(query) => self.query(query, indent),
+            Expr::FunctionCall(call) => self.function_call(call, call.span(), indent),
+            Expr::Variable(ident) => ident.to_string(),
+            Expr::Unary(unary) => match unary.inner() {
+                Unary::Not(not) => format!("!{}", self.expr(&not.1, indent)),
+            },
+            Expr::Abort(abort) => match &abort.message {
+                Some(message) => format!("abort {}", self.expr(message, indent)),
+                None => "abort".to_owned(),
+            },
+            Expr::Loop(lp) => match &lp.predicate {
+                Some(predicate) => format!(
+                    "while {} {}",
+                    self.predicate(predicate, indent),
+                    self.block(&lp.block, indent)
+                ),
+                None => format!("loop {}", self.block(&lp.block, indent)),
+            },
+            Expr::Walk(walk) => format!(
+                "walk {} -> |{}| {}",
+                self.expr(&walk.target, indent),
+                variables(&walk.variables),
+                self.block(&walk.block, indent)
+            ),
+            Expr::Traverse(traverse) => format!(
+                "traverse {} -> |{}| {}",
+                self.expr(&traverse.target, indent),
+                variables(&traverse.variables),
+                self.block(&traverse.block, indent)
+            ),
+            Expr::LoopControl(control) => control.to_string(),
+        }
+    }
+
+    fn container(&mut self, container: &Container, indent: usize) -> String {
+        match container {
+            Container::Group(group) => format!("({})", self.expr(&group.0, indent)),
+            Container::Block(block) => self.block(block, indent),
+            Container::Array(array) => self.list(
+                ("[", "]"),
+                &array.0,
+                array.end(),
+                indent,
+                Node::span,
+                Self::expr,
+            ),
+            Container::Object(object) => {
+                let entries = object.0.iter().collect::<Vec<_>>();
+
+                self.list(
+                    ("{", "}"),
+                    &entries,
+                    object.end(),
+                    indent,
+                    |(key, value)| span(key.start(), value.end()),
+                    |this, (key, value), indent| {
+                        format!("{}: {}", this.slice(key.span()), this.expr(value, indent))
+                    },
+                )
+            }
+        }
+    }
+
+    fn block(&mut self, block: &Node<Block>, indent: usize) -> String {
+        let exprs = self.items(
+            &block.0,
+            indent + 1,
+            block.end(),
+            "",
+            Node::span,
+            Self::expr,
+        );
+
+        format!("{{\n{}{}}}", exprs, INDENT.repeat(indent))
+    }
+
+    fn if_statement(&mut self, if_statement: &IfStatement, indent: usize) -> String {
+        let mut output = format!(
+            "if {} {}",
+            self.predicate(&if_statement.predicate, indent),
+            self.block(&if_statement.consequent, indent)
+        );
+
+        if let Some(alternative) = &if_statement.alternative {
+            output.push_str(" else ");
+
+            match else_if(alternative) {
+                Some(if_statement) => output.push_str(&self.if_statement(if_statement, indent)),
+                None => output.push_str(&self.block(alternative, indent)),
+            }
+        }
+
+        output
+    }
+
+    fn predicate(&mut self, predicate: &Node<Predicate>, indent: usize) -> String {
+        match predicate.inner() {
+            Predicate::One(expr) => self.expr(expr, indent),
+            Predicate::Many(exprs) => {
+                let exprs = exprs
+                    .iter()
+                    .map(|expr| self.expr(expr, indent))
+                    .collect::<Vec<_>>();
+
+                format!("({})", exprs.join("; "))
+            }
+        }
+    }
+
+    fn assignment(&mut self, assignment: &Assignment, indent: usize) -> String {
+        match assignment {
+            Assignment::Single { target, op, expr } => format!(
+                "{} {} {}",
+                self.assignment_target(target, indent),
+                op,
+                self.expr(expr, indent)
+            ),
+            Assignment::Infallible { ok, err, op, expr } => format!(
+                "{}, {} {} {}",
+                self.assignment_target(ok, indent),
+                self.assignment_target(err, indent),
+                op,
+                self.expr(expr, indent)
+            ),
+        }
+    }
+
+    fn assignment_target(&mut self, target: &Node<AssignmentTarget>, indent: usize) -> String {
+        match target.inner() {
+            AssignmentTarget::Noop => "_".to_owned(),
+            AssignmentTarget::Query(query) => self.query(query, indent),
+            AssignmentTarget::Internal(ident, None) => ident.to_string(),
+            AssignmentTarget::Internal(..) | AssignmentTarget::External(_) => {
+                self.slice(target.span()).to_owned()
+            }
+        }
+    }
+
+    fn query(&mut self, query: &Query, indent: usize) -> String {
+        let target = match query.target.inner() {
+            QueryTarget::Internal(ident) => ident.to_string(),
+            QueryTarget::External => ".".to_owned(),
+            QueryTarget::FunctionCall(call) => {
+                self.function_call(call, query.target.span(), indent)
+            }
+            QueryTarget::Container(container) => self.container(container, indent),
+        };
+
+        if query.path.is_root() {
+            return target;
+        }
+
+        // The path of an external query starts after the dot of its target,
+        // other paths start with a dot or bracket of their own.
+        format!("{}{}", target, self.slice(query.path.span()))
+    }
+
+    fn function_call(&mut self, call: &FunctionCall, span: Span, indent: usize) -> String {
+        let mut output = call.ident.to_string();
+        if call.abort_on_error {
+            output.push('!');
+        }
+
+        let arguments_end = call
+            .closure
+            .as_ref()
+            .map_or(span.end(), |closure| closure.start());
+
+        output.push_str(&self.list(
+            ("(", ")"),
+            &call.arguments,
+            arguments_end,
+            indent,
+            Node::span,
+            Self::function_argument,
+        ));
+
+        if let Some(closure) = &call.closure {
+            output.push_str(&format!(
+                " -> |{}| {}",
+                variables(&closure.variables),
+                self.block(&closure.block, indent)
+            ));
+        }
+
+        output
+    }
+
+    fn function_argument(&mut self, argument: &Node<FunctionArgument>, indent: usize) -> String {
+        match &argument.ident {
+            Some(ident) => format!("{}: {}", ident, self.expr(&argument.expr, indent)),
+            None => self.expr(&argument.expr, indent),
+        }
+    }
+
+    fn function_definition(&mut self, definition: &FunctionDefinition, indent: usize) -> String {
+        let parameters = definition
+            .parameters
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        format!(
+            "fn {}({}) {}",
+            definition.ident,
+            parameters.join(", "),
+            self.block(&definition.block, indent)
+        )
+    }
+}
+
+/// Returns the `if` statement of an `else if`, which the parser stores as an
+/// `else` block holding only that statement, spanning the same source.
+fn else_if(block: &Node<Block>) -> Option<&IfStatement> {
+    match block.0.as_slice() {
+        [expr] if expr.span() == block.span() => match expr.inner() {
+            Expr::IfStatement(if_statement) => Some(if_statement.inner()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Formats the variables of a closure, `walk` or `traverse`. Ignored variables are
+/// stored without a name.
+fn variables(variables: &[Node<Ident>]) -> String {
+    variables
+        .iter()
+        .map(|variable| match variable.inner().as_ref() {
+            "" => "_",
+            name => name,
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    fn assert_format(source: &str, want: &str) {
+        let got = format(source).unwrap();
+        assert_eq!(got, want);
+
+        // Formatting is idempotent.
+        assert_eq!(format(&got).unwrap(), want);
+    }
+
+    #[test]
+    fn statements() {
+        assert_format(
+            ".foo   =  1 ;.bar=\"baz\"\n\n\n\ndel(.qux)",
+            indoc! {r#"
+                .foo = 1
+                .bar = "baz"
+
+                del(.qux)
+            "#},
+        );
+    }
+
+    #[test]
+    fn literals_and_paths_as_written() {
+        assert_format(
+            indoc! {r#"
+                .x=."foo bar".(a|b)[0]
+                .raw =s'raw \' string'
+                .re=r'^\d+$'
+                .ts = t'2021-02-11T10:32:50.553955473Z'
+            "#},
+            indoc! {r#"
+                .x = ."foo bar".(a|b)[0]
+                .raw = s'raw \' string'
+                .re = r'^\d+$'
+                .ts = t'2021-02-11T10:32:50.553955473Z'
+            "#},
+        );
+    }
+
+    #[test]
+    fn blocks() {
+        assert_format(
+            indoc! {r#"
+                if .status>=500 { .level="error" } else if .status >= 400 {
+                .level = "warn"
+                } else { .level = "info" }
+            "#},
+            indoc! {r#"
+                if .status >= 500 {
+                  .level = "error"
+                } else if .status >= 400 {
+                  .level = "warn"
+                } else {
+                  .level = "info"
+                }
+            "#},
+        );
+    }
+
+    #[test]
+    fn closures_and_definitions() {
+        assert_format(
+            indoc! {r#"
+                fn double(x:integer|float){x*2}
+                .values = map_values(.values)->|v|{double(v)}
+            "#},
+            indoc! {r#"
+                fn double(x: integer | float) {
+                  x * 2
+                }
+                .values = map_values(.values) -> |v| {
+                  double(v)
+                }
+            "#},
+        );
+    }
+
+    #[test]
+    fn lists() {
+        assert_format(
+            indoc! {r#"
+                .a = [1,2,3]
+                .o = {"b":[],"c":{}}
+                . = parse_regex!(.message, r'^(?P<timestamp>\S+) (?P<level>\w+) (?P<host>\S+) (?P<pid>\d+) (?P<message>.*)$')
+            "#},
+            indoc! {r#"
+                .a = [1, 2, 3]
+                .o = { "b": [], "c": {} }
+                . = parse_regex!(
+                  .message,
+                  r'^(?P<timestamp>\S+) (?P<level>\w+) (?P<host>\S+) (?P<pid>\d+) (?P<message>.*)$',
+                )
+            "#},
+        );
+    }
+
+    #[test]
+    fn comments() {
+        assert_format(
+            indoc! {r#"
+                # Parse the message.
+                . = parse_json!(.message) # may fail
+
+                if .status == 200 {
+                    # Successful requests
+                    .ok = true
+                    # trailing
+                }
+                .tags = [
+                  "a", # first
+                  "b"
+                ]
+                # end
+            "#},
+            indoc! {r#"
+                # Parse the message.
+                . = parse_json!(.message) # may fail
+
+                if .status == 200 {
+                  # Successful requests
+                  .ok = true
+                  # trailing
+                }
+                .tags = [
+                  "a", # first
+                  "b",
+                ]
+                # end
+            "#},
+        );
+    }
+
+    #[test]
+    fn hash_in_string_is_not_a_comment() {
+        assert_format(
+            ".a = \"#not a comment\" # comment\n",
+            ".a = \"#not a comment\" # comment\n",
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        assert!(format(".foo = ").is_err());
+    }
+}
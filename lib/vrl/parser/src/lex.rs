@@ -110,6 +110,14 @@ impl DiagnosticMessage for Error {
                         r#""null""#,
                         r#""true""#,
                         r#""if""#,
+                        r#""fn""#,
+                        r#""import""#,
+                        r#""while""#,
+                        r#""loop""#,
+                        r#""walk""#,
+                        r#""traverse""#,
+                        r#""break""#,
+                        r#""continue""#,
                     ];
                     let is_any_ident = any_ident.iter().all(|i| expected.contains(&i.to_string()));
                     if is_any_ident {
@@ -245,6 +253,14 @@ pub enum Token<S> {
     False,
     True,
     Abort,
+    Fn,
+    Import,
+    While,
+    Loop,
+    Walk,
+    Traverse,
+    Break,
+    Continue,
 
     // tokens
     Colon,
@@ -331,6 +347,14 @@ impl<S> Token<S> {
             Null => Null,
             True => True,
             Abort => Abort,
+            Fn => Fn,
+            Import => Import,
+            While => While,
+            Loop => Loop,
+            Walk => Walk,
+            Traverse => Traverse,
+            Break => Break,
+            Continue => Continue,
 
             // tokens
             Colon => Colon,
@@ -386,6 +410,14 @@ where
             Null => "Null",
             True => "True",
             Abort => "Abort",
+            Fn => "Fn",
+            Import => "Import",
+            While => "While",
+            Loop => "Loop",
+            Walk => "Walk",
+            Traverse => "Traverse",
+            Break => "Break",
+            Continue => "Continue",
 
             // tokens
             Colon => "Colon",
@@ -428,14 +460,20 @@ impl<'input> Token<&'input str> {
             "false" => False,
             "null" => Null,
             "abort" => Abort,
+            "fn" => Fn,
+            "import" => Import,
+            "while" => While,
+            "loop" => Loop,
+            "walk" => Walk,
+            "traverse" => Traverse,
+            "break" => Break,
+            "continue" => Continue,
 
             // reserved identifiers
-            "array" | "bool" | "boolean" | "break" | "continue" | "do" | "emit" | "float"
-            | "for" | "forall" | "foreach" | "all" | "each" | "any" | "try" | "undefined"
-            | "int" | "integer" | "iter" | "object" | "regex" | "return" | "string"
-            | "traverse" | "timestamp" | "duration" | "unless" | "walk" | "while" | "loop" => {
-                ReservedIdentifier(s)
-            }
+            "array" | "bool" | "boolean" | "do" | "emit" | "float" | "for" | "forall"
+            | "foreach" | "all" | "each" | "any" | "try" | "undefined" | "int" | "integer"
+            | "iter" | "object" | "regex" | "return" | "string" | "timestamp" | "duration"
+            | "unless" => ReservedIdentifier(s),
 
             _ if s.contains('@') => PathField(s),
 
@@ -1503,6 +1541,30 @@ mod test {
         );
     }
 
+    #[test]
+    fn function_definitions() {
+        test(
+            data(r#"fn foo(x: string | null) { x } "#),
+            vec![
+                (r#"~~                             "#, Fn),
+                (r#"   ~~~                         "#, FunctionCall("foo")),
+                (r#"      ~                        "#, LParen),
+                (r#"       ~                       "#, Identifier("x")),
+                (r#"        ~                      "#, Colon),
+                (
+                    r#"          ~~~~~~               "#,
+                    ReservedIdentifier("string"),
+                ),
+                (r#"                 ~             "#, Operator("|")),
+                (r#"                   ~~~~        "#, Null),
+                (r#"                       ~       "#, RParen),
+                (r#"                         ~     "#, LBrace),
+                (r#"                           ~   "#, Identifier("x")),
+                (r#"                             ~ "#, RBrace),
+            ],
+        );
+    }
+
     #[test]
     fn single_query() {
         test(
@@ -15,14 +15,20 @@ criterion_group!(
               assert_eq,
               r#bool,
               ceil,
+              chunks,
               compact,
               contains,
+              crc32,
+              decode_base16,
               decode_base64,
+              decode_gzip,
+              decode_mime_q,
               decode_percent,
               decrypt,
               // TODO: Cannot pass a Path to bench_function
               //del,
               downcase,
+              encode_base16,
               encode_base64,
               encode_key_value,
               encode_json,
@@ -42,6 +48,7 @@ criterion_group!(
               get,
               get_env_var,
               get_hostname,
+              hmac,
               includes,
               int,
               ip_aton,
@@ -73,6 +80,7 @@ criterion_group!(
               match_datadog_query,
               md5,
               merge,
+              normalize_url,
               // TODO: value is dynamic so we cannot assert equality
               //now,
               object,
@@ -80,9 +88,12 @@ criterion_group!(
               parse_aws_alb_log,
               parse_aws_cloudwatch_log_subscription_message,
               parse_aws_vpc_flow_log,
+              parse_cef,
+              parse_cisco_asa,
               parse_common_log,
               parse_csv,
               parse_duration,
+              parse_etld,
               parse_glog,
               parse_grok,
               parse_groks,
@@ -112,6 +123,7 @@ criterion_group!(
               sha2,
               sha3,
               slice,
+              sort,
               split,
               starts_with,
               string,
@@ -138,6 +150,8 @@ criterion_group!(
               // TODO: value is dynamic so we cannot assert equality
               //uuidv4,
               upcase,
+              xxhash,
+              zip,
 );
 criterion_main!(benches);
 
@@ -213,6 +227,20 @@ bench_function! {
     }
 }
 
+bench_function! {
+    chunks => vrl_stdlib::Chunks;
+
+    array {
+        args: func_args![value: value!([1, 2, 3, 4, 5]), chunk_size: 2],
+        want: Ok(value!([[1, 2], [3, 4], [5]])),
+    }
+
+    string {
+        args: func_args![value: "abcdefgh", chunk_size: 3],
+        want: Ok(value!(["abc", "def", "gh"])),
+    }
+}
+
 bench_function! {
     compact => vrl_stdlib::Compact;
 
@@ -245,6 +273,24 @@ bench_function! {
     }
 }
 
+bench_function! {
+    crc32 => vrl_stdlib::Crc32;
+
+    literal {
+        args: func_args![value: "foobar"],
+        want: Ok(2666930069_i64),
+    }
+}
+
+bench_function! {
+    decode_base16 => vrl_stdlib::DecodeBase16;
+
+    literal {
+        args: func_args![value: "736f6d6520737472696e672076616c7565"],
+        want: Ok("some string value"),
+    }
+}
+
 bench_function! {
     decode_base64 => vrl_stdlib::DecodeBase64;
 
@@ -254,6 +300,26 @@ bench_function! {
     }
 }
 
+bench_function! {
+    decode_gzip => vrl_stdlib::DecodeGzip;
+
+    literal {
+        args: func_args![
+            value: Value::Bytes(Bytes::from_static(b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\xab\xcc\x2f\x55\xc8\x48\x2c\x4b\x55\x28\x2e\x4d\x4e\x4e\x2d\x2e\x4e\x2b\xcd\xc9\xa9\x54\x48\x49\x4d\xce\x4f\x49\x4d\x51\xc8\x4d\x05\x00\xfb\x04\x60\xda\x20\x00\x00\x00")),
+        ],
+        want: Ok("you have successfully decoded me"),
+    }
+}
+
+bench_function! {
+    decode_mime_q => vrl_stdlib::DecodeMimeQ;
+
+    literal {
+        args: func_args![value: "Subject: =?iso-8859-1?Q?Caf=E9?= =?utf-8?B?IOKYlQ==?="],
+        want: Ok("Subject: Café ☕"),
+    }
+}
+
 bench_function! {
     decode_percent => vrl_stdlib::DecodePercent;
 
@@ -272,6 +338,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    encode_base16 => vrl_stdlib::EncodeBase16;
+
+    literal {
+        args: func_args![value: "some string value"],
+        want: Ok("736f6d6520737472696e672076616c7565"),
+    }
+}
+
 bench_function! {
     encode_base64 => vrl_stdlib::EncodeBase64;
 
@@ -519,6 +594,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    hmac => vrl_stdlib::Hmac;
+
+    literal {
+        args: func_args![value: "Hello there", key: "super-secret-key"],
+        want: Ok("78b184f1832f8aff3934f5e0212454671b2d04d494e3b25075c5e45167029662"),
+    }
+}
+
 bench_function! {
     includes => vrl_stdlib::Includes;
 
@@ -1130,6 +1214,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    normalize_url => vrl_stdlib::NormalizeUrl;
+
+    literal {
+        args: func_args![value: "HTTP://www.Example.com:80/a/./b/../c?b=2&a=1#top"],
+        want: Ok("http://www.example.com/a/c?a=1&b=2"),
+    }
+}
+
 bench_function! {
     object => vrl_stdlib::Object;
 
@@ -1316,6 +1409,56 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_cef => vrl_stdlib::ParseCef;
+
+    literal {
+        args: func_args![
+            value: "CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 spt=1232 msg=Detected a threat. No action needed",
+        ],
+        want: Ok(value!({
+            "cefVersion": "0",
+            "deviceEventClassId": "100",
+            "deviceProduct": "threatmanager",
+            "deviceVendor": "Security",
+            "deviceVersion": "1.0",
+            "dst": "2.1.2.2",
+            "msg": "Detected a threat. No action needed",
+            "name": "worm successfully stopped",
+            "severity": "10",
+            "spt": "1232",
+            "src": "10.0.0.1",
+        })),
+    }
+}
+
+bench_function! {
+    parse_cisco_asa => vrl_stdlib::ParseCiscoAsa;
+
+    literal {
+        args: func_args![
+            value: "%ASA-6-302014: Teardown TCP connection 447236 for outside:192.0.2.10/52210 to inside:10.0.0.5/443 duration 0:00:30 bytes 6082 TCP FINs",
+        ],
+        want: Ok(value!({
+            "action": "teardown",
+            "bytes": 6082,
+            "connection_id": 447236,
+            "destination_interface": "inside",
+            "destination_ip": "10.0.0.5",
+            "destination_port": 443,
+            "duration": "0:00:30",
+            "message": "Teardown TCP connection 447236 for outside:192.0.2.10/52210 to inside:10.0.0.5/443 duration 0:00:30 bytes 6082 TCP FINs",
+            "message_id": 302014,
+            "protocol": "TCP",
+            "reason": "TCP FINs",
+            "severity": 6,
+            "source_interface": "outside",
+            "source_ip": "192.0.2.10",
+            "source_port": 52210,
+        })),
+    }
+}
+
 bench_function! {
     parse_common_log => vrl_stdlib::ParseCommonLog;
 
@@ -1354,6 +1497,21 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_etld => vrl_stdlib::ParseEtld;
+
+    literal {
+        args: func_args![value: "www.example.co.uk"],
+        want: Ok(value!({
+            "etld": "co.uk",
+            "etld_plus": "co.uk",
+            "known_suffix": true,
+            "registered_domain": "example.co.uk",
+            "subdomain": "www",
+        })),
+    }
+}
+
 bench_function! {
     parse_glog  => vrl_stdlib::ParseGlog;
 
@@ -2060,6 +2218,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    sort => vrl_stdlib::Sort;
+
+    literal {
+        args: func_args![value: value!([3, "b", 1.5, "a", true, 2])],
+        want: Ok(value!(["a", "b", 1.5, 2, 3, true])),
+    }
+}
+
 bench_function! {
     split => vrl_stdlib::Split;
 
@@ -2409,6 +2576,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    xxhash => vrl_stdlib::Xxhash;
+
+    literal {
+        args: func_args![value: "foobar"],
+        want: Ok(3986901679_i64),
+    }
+}
+
 bench_function! {
     unique => vrl_stdlib::Unique;
 
@@ -2435,3 +2611,17 @@ bench_function! {
         want: Ok("FOO")
     }
 }
+
+bench_function! {
+    zip => vrl_stdlib::Zip;
+
+    two_arrays {
+        args: func_args![array_0: value!([1, 2, 3]), array_1: value!(["a", "b", "c"])],
+        want: Ok(value!([[1, "a"], [2, "b"], [3, "c"]])),
+    }
+
+    array_of_arrays {
+        args: func_args![array_0: value!([[1, 2, 3], ["a", "b", "c"]])],
+        want: Ok(value!([[1, "a"], [2, "b"], [3, "c"]])),
+    }
+}
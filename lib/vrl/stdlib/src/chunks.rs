@@ -0,0 +1,173 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn chunks(value: Value, chunk_size: Value) -> Resolved {
+    let chunk_size = get_chunk_size(chunk_size)?;
+
+    match value {
+        Value::Bytes(bytes) => Ok(bytes
+            .chunks(chunk_size)
+            .map(|chunk| Value::from(Bytes::copy_from_slice(chunk)))
+            .collect::<Vec<_>>()
+            .into()),
+        Value::Array(array) => Ok(array
+            .chunks(chunk_size)
+            .map(|chunk| Value::from(chunk.to_vec()))
+            .collect::<Vec<_>>()
+            .into()),
+        value => Err(value::Error::Expected {
+            got: value.kind(),
+            expected: Kind::bytes() | Kind::array(Collection::any()),
+        }
+        .into()),
+    }
+}
+
+const INVALID_CHUNK_SIZE: &str = "chunk_size must be at least 1";
+
+fn get_chunk_size(value: Value) -> std::result::Result<usize, ExpressionError> {
+    let chunk_size = value.try_integer()?;
+    if chunk_size < 1 {
+        return Err(INVALID_CHUNK_SIZE.into());
+    }
+    Ok(chunk_size as usize)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Chunks;
+
+impl Function for Chunks {
+    fn identifier(&self) -> &'static str {
+        "chunks"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ARRAY | kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "chunk_size",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "split an array into chunks",
+                source: r#"chunks([1, 2, 3, 4, 5], 2)"#,
+                result: Ok("[[1, 2], [3, 4], [5]]"),
+            },
+            Example {
+                title: "split a string into chunks",
+                source: r#"chunks("abcdefgh", 3)"#,
+                result: Ok(r#"["abc", "def", "gh"]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let chunk_size = arguments.required("chunk_size");
+
+        if let Some(literal) = chunk_size.as_value() {
+            if get_chunk_size(literal.clone()).is_err() {
+                return Err(vrl::function::Error::InvalidArgument {
+                    keyword: "chunk_size",
+                    value: literal,
+                    error: INVALID_CHUNK_SIZE,
+                }
+                .into());
+            }
+        }
+
+        Ok(Box::new(ChunksFn { value, chunk_size }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ChunksFn {
+    value: Box<dyn Expression>,
+    chunk_size: Box<dyn Expression>,
+}
+
+impl Expression for ChunksFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let chunk_size = self.chunk_size.resolve(ctx)?;
+
+        chunks(value, chunk_size)
+    }
+
+    fn type_def(&self, state: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        let value = self.value.type_def(state);
+
+        // A string is split into strings, and an array into arrays holding any
+        // of its items.
+        let mut chunk = Kind::never();
+        if value.contains_bytes() {
+            chunk.add_bytes();
+        }
+        if value.contains_array() {
+            let items = value.restrict_array().collect_subtypes();
+            chunk = chunk.or_array(items.as_array().cloned().unwrap_or_else(Collection::any));
+        }
+
+        let type_def = TypeDef::array(Collection::from_unknown(chunk));
+
+        // The chunk size is only checked at runtime if it isn't a literal.
+        match self.chunk_size.as_value() {
+            Some(_) => type_def,
+            None => type_def.fallible(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        chunks => Chunks;
+
+        array {
+            args: func_args![value: value!([1, 2, 3, 4, 5]), chunk_size: 2],
+            want: Ok(value!([[1, 2], [3, 4], [5]])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::array(Collection::from_unknown(Kind::integer())))),
+        }
+
+        string {
+            args: func_args![value: "abcdefgh", chunk_size: 3],
+            want: Ok(value!(["abc", "def", "gh"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+
+        larger_than_value {
+            args: func_args![value: "abc", chunk_size: 10],
+            want: Ok(value!(["abc"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+
+        empty {
+            args: func_args![value: value!([]), chunk_size: 10],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::array(Collection::empty()))),
+        }
+
+        zero_chunk_size {
+            args: func_args![value: "abc", chunk_size: 0],
+            want: Err("invalid argument"),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+    ];
+}
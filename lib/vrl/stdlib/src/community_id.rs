@@ -0,0 +1,367 @@
+use std::net::IpAddr;
+
+use ::sha1::{Digest, Sha1};
+use ::value::Value;
+use vrl::prelude::*;
+
+const ICMP: u8 = 1;
+const TCP: u8 = 6;
+const UDP: u8 = 17;
+const ICMPV6: u8 = 58;
+const SCTP: u8 = 132;
+
+fn community_id(
+    source_ip: Value,
+    destination_ip: Value,
+    protocol: Value,
+    source_port: Option<Value>,
+    destination_port: Option<Value>,
+    seed: Option<Value>,
+) -> Resolved {
+    let source_ip = parse_ip("source_ip", source_ip)?;
+    let destination_ip = parse_ip("destination_ip", destination_ip)?;
+    if source_ip.is_ipv4() != destination_ip.is_ipv4() {
+        return Err("source_ip and destination_ip must be of the same IP version".into());
+    }
+
+    let protocol = parse_int::<u8>("protocol", protocol)?;
+    let seed = seed
+        .map(|seed| parse_int::<u16>("seed", seed))
+        .transpose()?
+        .unwrap_or(0);
+    let ports = match (source_port, destination_port) {
+        (Some(source_port), Some(destination_port)) => Some((
+            parse_int::<u16>("source_port", source_port)?,
+            parse_int::<u16>("destination_port", destination_port)?,
+        )),
+        _ => None,
+    };
+
+    let flow = Flow {
+        source: octets(source_ip),
+        destination: octets(destination_ip),
+        protocol,
+        ports: ports.filter(|_| matches!(protocol, ICMP | TCP | UDP | ICMPV6 | SCTP)),
+    };
+
+    Ok(flow.community_id(seed).into())
+}
+
+fn parse_ip(keyword: &str, value: Value) -> std::result::Result<IpAddr, ExpressionError> {
+    let value = value.try_bytes_utf8_lossy()?;
+
+    value
+        .parse()
+        .map_err(|_| format!("unable to parse {} {:?} as an IP address", keyword, value).into())
+}
+
+fn parse_int<T: TryFrom<i64>>(
+    keyword: &str,
+    value: Value,
+) -> std::result::Result<T, ExpressionError> {
+    let value = value.try_integer()?;
+
+    T::try_from(value).map_err(|_| format!("{} {} is out of range", keyword, value).into())
+}
+
+fn octets(ip: IpAddr) -> Vec<u8> {
+    match ip {
+        IpAddr::V4(ip) => ip.octets().to_vec(),
+        IpAddr::V6(ip) => ip.octets().to_vec(),
+    }
+}
+
+/// A network flow tuple, hashed as described in version 1 of the
+/// [Community ID specification](https://github.com/corelight/community-id-spec).
+struct Flow {
+    source: Vec<u8>,
+    destination: Vec<u8>,
+    protocol: u8,
+
+    /// The source and destination ports, or the ICMP type and code.
+    ports: Option<(u16, u16)>,
+}
+
+impl Flow {
+    fn community_id(mut self, seed: u16) -> String {
+        // Both directions of a flow must have the same ID, so the endpoints
+        // are put in a fixed order, unless the flow is known to only go one
+        // way.
+        let one_way = self.normalize_icmp();
+        let ordered = match self.ports {
+            Some((source_port, destination_port)) => {
+                (&self.source, source_port) < (&self.destination, destination_port)
+            }
+            None => self.source <= self.destination,
+        };
+        if !one_way && !ordered {
+            std::mem::swap(&mut self.source, &mut self.destination);
+            self.ports = self
+                .ports
+                .map(|(source, destination)| (destination, source));
+        }
+
+        let mut hasher = Sha1::new();
+        hasher.update(seed.to_be_bytes());
+        hasher.update(&self.source);
+        hasher.update(&self.destination);
+        hasher.update([self.protocol, 0]);
+        if let Some((source_port, destination_port)) = self.ports {
+            hasher.update(source_port.to_be_bytes());
+            hasher.update(destination_port.to_be_bytes());
+        }
+
+        format!("1:{}", base64::encode(hasher.finalize()))
+    }
+
+    /// Replaces the ICMP code with the type of the matching reply or request,
+    /// so that both sides of an exchange hash the same, returning `true` if
+    /// the message type has no counterpart.
+    fn normalize_icmp(&mut self) -> bool {
+        let (message_type, _) = match self.ports {
+            Some(ports) if matches!(self.protocol, ICMP | ICMPV6) => ports,
+            _ => return false,
+        };
+
+        let counterpart = match (self.protocol, message_type) {
+            // Echo, timestamp, information, router and address mask messages.
+            (ICMP, 8) => 0,
+            (ICMP, 0) => 8,
+            (ICMP, 13) => 14,
+            (ICMP, 14) => 13,
+            (ICMP, 15) => 16,
+            (ICMP, 16) => 15,
+            (ICMP, 10) => 9,
+            (ICMP, 9) => 10,
+            (ICMP, 17) => 18,
+            (ICMP, 18) => 17,
+            // Echo, multicast listener, router, neighbor, node information
+            // and home agent address discovery messages.
+            (ICMPV6, 128) => 129,
+            (ICMPV6, 129) => 128,
+            (ICMPV6, 130) => 131,
+            (ICMPV6, 131) => 130,
+            (ICMPV6, 133) => 134,
+            (ICMPV6, 134) => 133,
+            (ICMPV6, 135) => 136,
+            (ICMPV6, 136) => 135,
+            (ICMPV6, 139) => 140,
+            (ICMPV6, 140) => 139,
+            (ICMPV6, 144) => 145,
+            (ICMPV6, 145) => 144,
+            _ => return true,
+        };
+
+        self.ports = Some((message_type, counterpart));
+        false
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CommunityId;
+
+impl Function for CommunityId {
+    fn identifier(&self) -> &'static str {
+        "community_id"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "source_ip",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "destination_ip",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "protocol",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "source_port",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "destination_port",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "seed",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "TCP flow",
+                source: r#"community_id!(source_ip: "128.232.110.120", destination_ip: "66.35.250.204", protocol: 6, source_port: 34855, destination_port: 80)"#,
+                result: Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg="),
+            },
+            Example {
+                title: "reverse direction",
+                source: r#"community_id!(source_ip: "66.35.250.204", destination_ip: "128.232.110.120", protocol: 6, source_port: 80, destination_port: 34855)"#,
+                result: Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg="),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let source_ip = arguments.required("source_ip");
+        let destination_ip = arguments.required("destination_ip");
+        let protocol = arguments.required("protocol");
+        let source_port = arguments.optional("source_port");
+        let destination_port = arguments.optional("destination_port");
+        let seed = arguments.optional("seed");
+
+        Ok(Box::new(CommunityIdFn {
+            source_ip,
+            destination_ip,
+            protocol,
+            source_port,
+            destination_port,
+            seed,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CommunityIdFn {
+    source_ip: Box<dyn Expression>,
+    destination_ip: Box<dyn Expression>,
+    protocol: Box<dyn Expression>,
+    source_port: Option<Box<dyn Expression>>,
+    destination_port: Option<Box<dyn Expression>>,
+    seed: Option<Box<dyn Expression>>,
+}
+
+impl Expression for CommunityIdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let source_ip = self.source_ip.resolve(ctx)?;
+        let destination_ip = self.destination_ip.resolve(ctx)?;
+        let protocol = self.protocol.resolve(ctx)?;
+        let source_port = self
+            .source_port
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+        let destination_port = self
+            .destination_port
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+        let seed = self
+            .seed
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        community_id(
+            source_ip,
+            destination_ip,
+            protocol,
+            source_port,
+            destination_port,
+            seed,
+        )
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        community_id => CommunityId;
+
+        tcp {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 6, source_port: 1122, destination_port: 3344],
+            want: Ok(value!("1:wCb3OG7yAFWelaUydu0D+125CLM=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        tcp_reverse {
+            args: func_args![source_ip: "5.6.7.8", destination_ip: "1.2.3.4", protocol: 6, source_port: 3344, destination_port: 1122],
+            want: Ok(value!("1:wCb3OG7yAFWelaUydu0D+125CLM=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        udp {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 17, source_port: 1122, destination_port: 3344],
+            want: Ok(value!("1:0Mu9InQx6z4ZiCZM/7HXi2WMhOg=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        seed {
+            args: func_args![source_ip: "128.232.110.120", destination_ip: "66.35.250.204", protocol: 6, source_port: 34855, destination_port: 80, seed: 1],
+            want: Ok(value!("1:3V71V58M3Ksw/yuFALMcW0LAHvc=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp_request {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 1, source_port: 8, destination_port: 0],
+            want: Ok(value!("1:crodRHL2FEsHjbv3UkRrfbs4bZ0=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp_reply {
+            args: func_args![source_ip: "5.6.7.8", destination_ip: "1.2.3.4", protocol: 1, source_port: 0, destination_port: 0],
+            want: Ok(value!("1:crodRHL2FEsHjbv3UkRrfbs4bZ0=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp_one_way {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 1, source_port: 11, destination_port: 0],
+            want: Ok(value!("1:f/YiSyWqczrTgfUCZlBUnvHRcPk=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmpv6 {
+            args: func_args![source_ip: "fe80::200:86ff:fe05:80da", destination_ip: "fe80::260:97ff:fe07:69ea", protocol: 58, source_port: 135, destination_port: 0],
+            want: Ok(value!("1:dGHyGvjMfljg6Bppwm3bg0LO8TY=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        no_ports {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 46],
+            want: Ok(value!("1:ikv3kmf89luf73WPz1jOs49S768=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_ip {
+            args: func_args![source_ip: "1.2.3", destination_ip: "5.6.7.8", protocol: 6],
+            want: Err(r#"unable to parse source_ip "1.2.3" as an IP address"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        mixed_ip_versions {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "::1", protocol: 6],
+            want: Err("source_ip and destination_ip must be of the same IP version"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        port_out_of_range {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 6, source_port: 65536, destination_port: 80],
+            want: Err("source_port 65536 is out of range"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
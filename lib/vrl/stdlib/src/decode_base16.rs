@@ -0,0 +1,104 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn decode_base16(value: Value) -> Resolved {
+    let value = value.try_bytes()?;
+
+    match hex::decode(value) {
+        Ok(buf) => Ok(Value::Bytes(buf.into())),
+        Err(_) => Err("unable to decode value from base16".into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeBase16;
+
+impl Function for DecodeBase16 {
+    fn identifier(&self) -> &'static str {
+        "decode_base16"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodeBase16Fn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_base16!("736f6d6520737472696e672076616c7565")"#,
+            result: Ok("some string value"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeBase16Fn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeBase16Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_base16(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        // Always fallible due to the possibility of decoding errors that VRL can't detect
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    test_function![
+        decode_base16 => DecodeBase16;
+
+        lowercase {
+            args: func_args![value: value!("736f6d6520737472696e672076616c7565")],
+            want: Ok(value!("some string value")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        uppercase {
+            args: func_args![value: value!("736F6D6520737472696E672076616C7565")],
+            want: Ok(value!("some string value")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        empty {
+            args: func_args![value: value!("")],
+            want: Ok(value!("")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        odd_length {
+            args: func_args![value: value!("736")],
+            want: Err("unable to decode value from base16"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_character {
+            args: func_args![value: value!("zz")],
+            want: Err("unable to decode value from base16"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,101 @@
+use std::io::Read;
+
+use ::value::Value;
+use flate2::read::MultiGzDecoder;
+use vrl::prelude::*;
+
+fn decode_gzip(value: Value) -> Resolved {
+    let value = value.try_bytes()?;
+    let mut buf = Vec::new();
+    let result = MultiGzDecoder::new(std::io::Cursor::new(value)).read_to_end(&mut buf);
+
+    match result {
+        Ok(_) => Ok(Value::Bytes(buf.into())),
+        Err(_) => Err("unable to decode value with gzip decoder".into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeGzip;
+
+impl Function for DecodeGzip {
+    fn identifier(&self) -> &'static str {
+        "decode_gzip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodeGzipFn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_gzip!(decode_base64!("H4sIAAAAAAACA6vML1XISCxLVSguTU5OLS5OK83JqVRISU3OT0lNUchNBQD7BGDaIAAAAA=="))"#,
+            result: Ok("you have successfully decoded me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeGzipFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeGzipFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_gzip(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        // Always fallible due to the possibility of decoding errors that VRL can't detect
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    fn encode(text: &str) -> Value {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        Value::Bytes(encoder.finish().unwrap().into())
+    }
+
+    test_function![
+        decode_gzip => DecodeGzip;
+
+        right_gzip {
+            args: func_args![value: encode("sphinx of black quartz, judge my vow")],
+            want: Ok(value!("sphinx of black quartz, judge my vow")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        wrong_gzip {
+            args: func_args![value: value!("some string value")],
+            want: Err("unable to decode value with gzip decoder"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,232 @@
+use ::value::Value;
+use encoding_rs::Encoding;
+use vrl::prelude::*;
+
+fn decode_mime_q(value: Value) -> Resolved {
+    let value = value.try_bytes_utf8_lossy()?;
+
+    decode_encoded_words(&value)
+        .map(Into::into)
+        .map_err(Into::into)
+}
+
+/// Decodes the [RFC 2047] encoded-words of the input, keeping the rest of the
+/// input as is.
+///
+/// Whitespace between two encoded-words is dropped, so that a long value
+/// split across several encoded-words is joined back together.
+///
+/// [RFC 2047]: https://datatracker.ietf.org/doc/html/rfc2047
+fn decode_encoded_words(input: &str) -> std::result::Result<String, String> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    let mut after_word = false;
+
+    while let Some(start) = rest.find("=?") {
+        let (before, candidate) = rest.split_at(start);
+
+        match parse_encoded_word(candidate)? {
+            Some((decoded, len)) => {
+                if !(after_word && before.chars().all(char::is_whitespace)) {
+                    output.push_str(before);
+                }
+                output.push_str(&decoded);
+                rest = &candidate[len..];
+                after_word = true;
+            }
+            None => {
+                output.push_str(before);
+                output.push_str("=?");
+                rest = &candidate[2..];
+                after_word = false;
+            }
+        }
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Parses the encoded-word at the start of the input, returning the decoded
+/// text and the length of the encoded-word, or `None` if the input doesn't
+/// start with an encoded-word.
+fn parse_encoded_word(input: &str) -> std::result::Result<Option<(String, usize)>, String> {
+    let mut parts = input[2..].splitn(3, '?');
+
+    let (charset, encoding, rest) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(charset), Some(encoding), Some(rest)) => (charset, encoding, rest),
+        _ => return Ok(None),
+    };
+    let text = match rest.find("?=") {
+        Some(end) => &rest[..end],
+        None => return Ok(None),
+    };
+    if charset.is_empty()
+        || [charset, encoding, text]
+            .iter()
+            .any(|part| part.contains(char::is_whitespace))
+    {
+        return Ok(None);
+    }
+    let len = "=?".len() + charset.len() + 1 + encoding.len() + 1 + text.len() + "?=".len();
+
+    let bytes = match encoding {
+        "Q" | "q" => decode_q(text)?,
+        "B" | "b" => base64::decode(text)
+            .map_err(|_| format!("unable to decode base64 encoded-word: {}", text))?,
+        _ => return Ok(None),
+    };
+
+    // The charset can carry an RFC 2231 language suffix, such as `utf-8*en`.
+    let label = charset.split('*').next().unwrap_or(charset);
+    let encoding = Encoding::for_label(label.as_bytes())
+        .ok_or_else(|| format!("unknown charset: {}", label))?;
+    let (decoded, _) = encoding.decode_without_bom_handling(&bytes);
+
+    Ok(Some((decoded.into_owned(), len)))
+}
+
+/// Decodes the "Q" encoding, where `_` is a space and `=XX` is the byte with
+/// the hexadecimal value `XX`.
+fn decode_q(text: &str) -> std::result::Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut iter = text.bytes();
+
+    while let Some(byte) = iter.next() {
+        match byte {
+            b'_' => bytes.push(b' '),
+            b'=' => {
+                let hex = [iter.next(), iter.next()];
+                let byte = match hex {
+                    [Some(high), Some(low)] => std::str::from_utf8(&[high, low])
+                        .ok()
+                        .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+                    _ => None,
+                }
+                .ok_or_else(|| format!("invalid Q encoded-word: {}", text))?;
+
+                bytes.push(byte);
+            }
+            byte => bytes.push(byte),
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeMimeQ;
+
+impl Function for DecodeMimeQ {
+    fn identifier(&self) -> &'static str {
+        "decode_mime_q"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodeMimeQFn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "single encoded-word",
+                source: r#"decode_mime_q!("=?utf-8?Q?hello_world=21?=")"#,
+                result: Ok("hello world!"),
+            },
+            Example {
+                title: "embedded encoded-words",
+                source: r#"decode_mime_q!("Subject: =?iso-8859-1?Q?Caf=E9?= =?utf-8?B?IOKYlQ==?=")"#,
+                result: Ok("Subject: Café ☕"),
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeMimeQFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeMimeQFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_mime_q(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    test_function![
+        decode_mime_q => DecodeMimeQ;
+
+        q_encoding {
+            args: func_args![value: value!("=?utf-8?Q?hello_world=21?=")],
+            want: Ok(value!("hello world!")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        b_encoding {
+            args: func_args![value: value!("=?UTF-8?B?aGVsbG8gd29ybGQ=?=")],
+            want: Ok(value!("hello world")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        other_charset {
+            args: func_args![value: value!("=?iso-8859-1?q?Caf=E9?=")],
+            want: Ok(value!("Café")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        adjacent_words {
+            args: func_args![value: value!("=?utf-8?Q?hello?=  =?utf-8?Q?_world?= and more")],
+            want: Ok(value!("hello world and more")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        plain_text {
+            args: func_args![value: value!("no =? encoded-words ?= here")],
+            want: Ok(value!("no =? encoded-words ?= here")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        language_suffix {
+            args: func_args![value: value!("=?utf-8*en?Q?hello?=")],
+            want: Ok(value!("hello")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        unknown_charset {
+            args: func_args![value: value!("=?klingon?Q?hello?=")],
+            want: Err("unknown charset: klingon"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_q {
+            args: func_args![value: value!("=?utf-8?Q?hello=ZZ?=")],
+            want: Err("invalid Q encoded-word: hello=ZZ"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,97 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn decode_punycode(value: Value) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    let (decoded, result) = idna::domain_to_unicode(&string);
+    result.map_err(|_| format!("unable to decode {:?} from punycode", string))?;
+
+    Ok(decoded.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodePunycode;
+
+impl Function for DecodePunycode {
+    fn identifier(&self) -> &'static str {
+        "decode_punycode"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "punycode domain name",
+                source: r#"decode_punycode!("www.xn--caf-dma.com")"#,
+                result: Ok("www.café.com"),
+            },
+            Example {
+                title: "ascii domain name",
+                source: r#"decode_punycode!("www.cafe.com")"#,
+                result: Ok("www.cafe.com"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodePunycodeFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DecodePunycodeFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodePunycodeFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_punycode(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        decode_punycode => DecodePunycode;
+
+        idn {
+            args: func_args![value: "www.xn--caf-dma.com"],
+            want: Ok(value!("www.café.com")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        multiple_labels {
+            args: func_args![value: "xn--mnchen-3ya.xn--bcher-kva.de"],
+            want: Ok(value!("münchen.bücher.de")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        ascii {
+            args: func_args![value: "vector.dev"],
+            want: Ok(value!("vector.dev")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
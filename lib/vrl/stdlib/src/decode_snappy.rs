@@ -0,0 +1,95 @@
+use ::value::Value;
+use snap::raw::Decoder;
+use vrl::prelude::*;
+
+fn decode_snappy(value: Value) -> Resolved {
+    let value = value.try_bytes()?;
+
+    // Snappy payloads embedded in other formats, such as Prometheus remote
+    // write requests, use the raw block format rather than the framed one.
+    match Decoder::new().decompress_vec(&value) {
+        Ok(buf) => Ok(Value::Bytes(buf.into())),
+        Err(_) => Err("unable to decode value with snappy decoder".into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeSnappy;
+
+impl Function for DecodeSnappy {
+    fn identifier(&self) -> &'static str {
+        "decode_snappy"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodeSnappyFn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_snappy!(decode_base64!("IHx5b3UgaGF2ZSBzdWNjZXNzZnVsbHkgZGVjb2RlZCBtZQ=="))"#,
+            result: Ok("you have successfully decoded me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeSnappyFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeSnappyFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_snappy(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        // Always fallible due to the possibility of decoding errors that VRL can't detect
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use snap::raw::Encoder;
+
+    use super::*;
+
+    fn encode(text: &str) -> Value {
+        Value::Bytes(Encoder::new().compress_vec(text.as_bytes()).unwrap().into())
+    }
+
+    test_function![
+        decode_snappy => DecodeSnappy;
+
+        right_snappy {
+            args: func_args![value: encode("sphinx of black quartz, judge my vow")],
+            want: Ok(value!("sphinx of black quartz, judge my vow")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        wrong_snappy {
+            args: func_args![value: value!("some string value")],
+            want: Err("unable to decode value with snappy decoder"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,101 @@
+use std::io::Read;
+
+use ::value::Value;
+use flate2::read::ZlibDecoder;
+use vrl::prelude::*;
+
+fn decode_zlib(value: Value) -> Resolved {
+    let value = value.try_bytes()?;
+    let mut buf = Vec::new();
+    let result = ZlibDecoder::new(std::io::Cursor::new(value)).read_to_end(&mut buf);
+
+    match result {
+        Ok(_) => Ok(Value::Bytes(buf.into())),
+        Err(_) => Err("unable to decode value with zlib decoder".into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeZlib;
+
+impl Function for DecodeZlib {
+    fn identifier(&self) -> &'static str {
+        "decode_zlib"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodeZlibFn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_zlib!(decode_base64!("eJyrzC9VyEgsS1UoLk1OTi0uTivNyalUSElNzk9JTVHITQUAzLIMQQ=="))"#,
+            result: Ok("you have successfully decoded me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeZlibFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeZlibFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_zlib(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        // Always fallible due to the possibility of decoding errors that VRL can't detect
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use flate2::{write::ZlibEncoder, Compression};
+
+    use super::*;
+
+    fn encode(text: &str) -> Value {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        Value::Bytes(encoder.finish().unwrap().into())
+    }
+
+    test_function![
+        decode_zlib => DecodeZlib;
+
+        right_zlib {
+            args: func_args![value: encode("sphinx of black quartz, judge my vow")],
+            want: Ok(value!("sphinx of black quartz, judge my vow")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        wrong_zlib {
+            args: func_args![value: value!("some string value")],
+            want: Err("unable to decode value with zlib decoder"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,90 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn decode_zstd(value: Value) -> Resolved {
+    let value = value.try_bytes()?;
+
+    match zstd::stream::decode_all(std::io::Cursor::new(value)) {
+        Ok(buf) => Ok(Value::Bytes(buf.into())),
+        Err(_) => Err("unable to decode value with zstd decoder".into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeZstd;
+
+impl Function for DecodeZstd {
+    fn identifier(&self) -> &'static str {
+        "decode_zstd"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodeZstdFn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_zstd!(decode_base64!("KLUv/QBYAQEAeW91IGhhdmUgc3VjY2Vzc2Z1bGx5IGRlY29kZWQgbWU="))"#,
+            result: Ok("you have successfully decoded me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeZstdFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeZstdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_zstd(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        // Always fallible due to the possibility of decoding errors that VRL can't detect
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn encode(text: &str) -> Value {
+        Value::Bytes(zstd::stream::encode_all(text.as_bytes(), 0).unwrap().into())
+    }
+
+    test_function![
+        decode_zstd => DecodeZstd;
+
+        right_zstd {
+            args: func_args![value: encode("sphinx of black quartz, judge my vow")],
+            want: Ok(value!("sphinx of black quartz, judge my vow")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        wrong_zstd {
+            args: func_args![value: value!("some string value")],
+            want: Err("unable to decode value with zstd decoder"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,82 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn encode_base16(value: Value) -> Resolved {
+    let value = value.try_bytes()?;
+
+    Ok(hex::encode(value).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeBase16;
+
+impl Function for EncodeBase16 {
+    fn identifier(&self) -> &'static str {
+        "encode_base16"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(EncodeBase16Fn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"encode_base16("some string value")"#,
+            result: Ok(r#""736f6d6520737472696e672076616c7565""#),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeBase16Fn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for EncodeBase16Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        encode_base16(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    test_function![
+        encode_base16 => EncodeBase16;
+
+        string {
+            args: func_args![value: value!("some string value")],
+            want: Ok(value!("736f6d6520737472696e672076616c7565")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        empty {
+            args: func_args![value: value!("")],
+            want: Ok(value!("")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
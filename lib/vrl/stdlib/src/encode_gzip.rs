@@ -0,0 +1,164 @@
+use std::io::Write;
+
+use ::value::Value;
+use flate2::{write::GzEncoder, Compression};
+use vrl::prelude::*;
+
+const MAX_COMPRESSION_LEVEL: i64 = 9;
+
+fn encode_gzip(value: Value, compression_level: Option<Value>) -> Resolved {
+    let compression = match compression_level {
+        Some(level) => get_compression(level)?,
+        None => Compression::default(),
+    };
+    let value = value.try_bytes()?;
+
+    let mut encoder = GzEncoder::new(Vec::new(), compression);
+    encoder
+        .write_all(&value)
+        .expect("writing to a vector cannot fail");
+    let buf = encoder.finish().expect("writing to a vector cannot fail");
+
+    Ok(Value::Bytes(buf.into()))
+}
+
+const INVALID_COMPRESSION_LEVEL: &str = "compression_level must be between 0 and 9";
+
+fn get_compression(value: Value) -> std::result::Result<Compression, ExpressionError> {
+    let level = value.try_integer()?;
+    if !(0..=MAX_COMPRESSION_LEVEL).contains(&level) {
+        return Err(INVALID_COMPRESSION_LEVEL.into());
+    }
+    Ok(Compression::new(level as u32))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeGzip;
+
+impl Function for EncodeGzip {
+    fn identifier(&self) -> &'static str {
+        "encode_gzip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "compression_level",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let compression_level = arguments.optional("compression_level");
+
+        if let Some(literal) = compression_level.as_ref().and_then(|expr| expr.as_value()) {
+            if get_compression(literal.clone()).is_err() {
+                return Err(vrl::function::Error::InvalidArgument {
+                    keyword: "compression_level",
+                    value: literal,
+                    error: INVALID_COMPRESSION_LEVEL,
+                }
+                .into());
+            }
+        }
+
+        Ok(Box::new(EncodeGzipFn {
+            value,
+            compression_level,
+        }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "round trip",
+            source: r#"decode_gzip!(encode_gzip("please encode me", compression_level: 9))"#,
+            result: Ok("please encode me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeGzipFn {
+    value: Box<dyn Expression>,
+    compression_level: Option<Box<dyn Expression>>,
+}
+
+impl Expression for EncodeGzipFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let compression_level = self
+            .compression_level
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        encode_gzip(value, compression_level)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        // The compression level is only checked at runtime if it isn't a
+        // literal.
+        match &self.compression_level {
+            Some(expr) if expr.as_value().is_none() => TypeDef::bytes().fallible(),
+            _ => TypeDef::bytes().infallible(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn encode(text: &str, compression: Compression) -> Value {
+        let mut encoder = GzEncoder::new(Vec::new(), compression);
+        encoder.write_all(text.as_bytes()).unwrap();
+        Value::Bytes(encoder.finish().unwrap().into())
+    }
+
+    test_function![
+        encode_gzip => EncodeGzip;
+
+        default_level {
+            args: func_args![value: "please encode me"],
+            want: Ok(encode("please encode me", Compression::default())),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        max_level {
+            args: func_args![value: "please encode me", compression_level: 9],
+            want: Ok(encode("please encode me", Compression::new(9))),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        no_compression {
+            args: func_args![value: "please encode me", compression_level: 0],
+            want: Ok(encode("please encode me", Compression::new(0))),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        invalid_level {
+            args: func_args![value: "please encode me", compression_level: 10],
+            want: Err("invalid argument"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        negative_level {
+            args: func_args![value: "please encode me", compression_level: -1],
+            want: Err("invalid argument"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
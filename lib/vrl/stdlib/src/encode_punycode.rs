@@ -0,0 +1,108 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn encode_punycode(value: Value) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    let encoded = idna::domain_to_ascii(&string)
+        .map_err(|_| format!("unable to encode {:?} to punycode", string))?;
+
+    Ok(encoded.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodePunycode;
+
+impl Function for EncodePunycode {
+    fn identifier(&self) -> &'static str {
+        "encode_punycode"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "internationalized domain name",
+                source: r#"encode_punycode!("www.café.com")"#,
+                result: Ok("www.xn--caf-dma.com"),
+            },
+            Example {
+                title: "mixed case domain name",
+                source: r#"encode_punycode!("www.CAFÉ.com")"#,
+                result: Ok("www.xn--caf-dma.com"),
+            },
+            Example {
+                title: "ascii domain name",
+                source: r#"encode_punycode!("www.cafe.com")"#,
+                result: Ok("www.cafe.com"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(EncodePunycodeFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EncodePunycodeFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for EncodePunycodeFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        encode_punycode(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        encode_punycode => EncodePunycode;
+
+        idn {
+            args: func_args![value: "www.café.com"],
+            want: Ok(value!("www.xn--caf-dma.com")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        mixed_case {
+            args: func_args![value: "www.CAFÉ.com"],
+            want: Ok(value!("www.xn--caf-dma.com")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        multiple_labels {
+            args: func_args![value: "münchen.bücher.de"],
+            want: Ok(value!("xn--mnchen-3ya.xn--bcher-kva.de")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        ascii {
+            args: func_args![value: "vector.dev"],
+            want: Ok(value!("vector.dev")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
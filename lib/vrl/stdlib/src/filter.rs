@@ -0,0 +1,168 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn filter<T>(value: Value, ctx: &mut Context, runner: closure::Runner<T>) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    match value {
+        Value::Object(object) => {
+            let mut filtered = BTreeMap::new();
+
+            for (key, value) in object {
+                let keep = runner
+                    .run_values(ctx, vec![key.as_str().into(), value.clone()])?
+                    .try_boolean()?;
+
+                if keep {
+                    filtered.insert(key, value);
+                }
+            }
+
+            Ok(filtered.into())
+        }
+        Value::Array(array) => {
+            let mut filtered = vec![];
+
+            for (index, value) in array.into_iter().enumerate() {
+                let keep = runner
+                    .run_values(ctx, vec![index.into(), value.clone()])?
+                    .try_boolean()?;
+
+                if keep {
+                    filtered.push(value);
+                }
+            }
+
+            Ok(filtered.into())
+        }
+        _ => unreachable!("expected object or array"),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Filter;
+
+impl Function for Filter {
+    fn identifier(&self) -> &'static str {
+        "filter"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "filter array",
+                source: r#"filter([1, 2, 3, 4]) -> |_index, value| { value > 2 }"#,
+                result: Ok("[3, 4]"),
+            },
+            Example {
+                title: "filter object",
+                source: r#"filter({ "a": 1, "b": null }) -> |_key, value| { value != null }"#,
+                result: Ok(r#"{ "a": 1 }"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure()?;
+
+        Ok(Box::new(FilterFn { value, closure }))
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::object(Collection::any()).or_array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::TargetInnerKey,
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::boolean()),
+                example: Example {
+                    title: "filter array",
+                    source: r#"filter(["a", "", "b"]) -> |_index, value| { value != "" }"#,
+                    result: Ok(r#"["a", "b"]"#),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FilterFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for FilterFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let FunctionClosure { variables, block } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        filter(value, ctx, runner)
+    }
+
+    fn type_def(&self, ctx: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        let fallible = self.closure.block.type_def(ctx).is_fallible();
+
+        // Any item can be removed, so the type of each item is only known to
+        // be one of the types of the items of the value.
+        self.value
+            .type_def(ctx)
+            .collect_subtypes()
+            .with_fallibility(fallible)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::run_program;
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            run_program("filter([]) -> |_index, value| { value == 1 }"),
+            Ok(value!([]))
+        );
+    }
+
+    #[test]
+    fn closure_type_error() {
+        assert_eq!(
+            run_program("filter([1, 2]) -> |_index, value| { value }"),
+            Err("type mismatch in closure return type".to_owned())
+        );
+    }
+
+    #[test]
+    fn closure_error() {
+        let err = run_program(r#"filter(["a", 1]) -> |_index, value| { string!(value) != "" }"#)
+            .unwrap_err();
+
+        assert!(err.contains("expected string, got integer"), "{}", err);
+    }
+}
@@ -0,0 +1,144 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn find_index<T>(value: Value, ctx: &mut Context, runner: closure::Runner<T>) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    let array = value.try_array()?;
+
+    for (index, item) in array.into_iter().enumerate() {
+        let found = runner
+            .run_values(ctx, vec![index.into(), item])?
+            .try_boolean()?;
+
+        if found {
+            return Ok(index.into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FindIndex;
+
+impl Function for FindIndex {
+    fn identifier(&self) -> &'static str {
+        "find_index"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "find the first match",
+                source: r#"find_index(["a", "b", "c", "b"]) -> |_index, value| { value == "b" }"#,
+                result: Ok("1"),
+            },
+            Example {
+                title: "no match",
+                source: r#"find_index([1, 2, 3]) -> |_index, value| { value == 4 }"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure()?;
+
+        Ok(Box::new(FindIndexFn { value, closure }))
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::TargetInnerKey,
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::boolean()),
+                example: Example {
+                    title: "find the first match",
+                    source: r#"find_index(["a", "b", "c"]) -> |_index, value| { value == "c" }"#,
+                    result: Ok("2"),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FindIndexFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for FindIndexFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let FunctionClosure { variables, block } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        find_index(value, ctx, runner)
+    }
+
+    fn type_def(&self, ctx: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        let fallible = self.closure.block.type_def(ctx).is_fallible();
+
+        TypeDef::integer().add_null().with_fallibility(fallible)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::run_program;
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            run_program("find_index([]) -> |_index, value| { value == 1 }"),
+            Ok(value!(null))
+        );
+    }
+
+    #[test]
+    fn closure_type_error() {
+        assert_eq!(
+            run_program("find_index([1, 2]) -> |_index, value| { value }"),
+            Err("type mismatch in closure return type".to_owned())
+        );
+    }
+
+    #[test]
+    fn closure_error() {
+        let err =
+            run_program(r#"find_index([1, "a"]) -> |_index, value| { string!(value) == "a" }"#)
+                .unwrap_err();
+
+        assert!(err.contains("expected string, got integer"), "{}", err);
+    }
+}
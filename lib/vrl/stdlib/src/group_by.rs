@@ -0,0 +1,152 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn group_by<T>(value: Value, ctx: &mut Context, runner: closure::Runner<T>) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    let array = value.try_array()?;
+    let mut groups = BTreeMap::<String, Value>::new();
+
+    for (index, item) in array.into_iter().enumerate() {
+        let key = runner
+            .run_values(ctx, vec![index.into(), item.clone()])?
+            .try_bytes_utf8_lossy()?
+            .into_owned();
+
+        match groups.entry(key).or_insert_with(|| Value::Array(vec![])) {
+            Value::Array(group) => group.push(item),
+            _ => unreachable!("groups are arrays"),
+        }
+    }
+
+    Ok(groups.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GroupBy;
+
+impl Function for GroupBy {
+    fn identifier(&self) -> &'static str {
+        "group_by"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "group objects by field",
+                source: r#"group_by([{ "level": "info", "id": 1 }, { "level": "error", "id": 2 }, { "level": "info", "id": 3 }]) -> |_index, value| { string!(value.level) }"#,
+                result: Ok(
+                    r#"{ "error": [{ "level": "error", "id": 2 }], "info": [{ "level": "info", "id": 1 }, { "level": "info", "id": 3 }] }"#,
+                ),
+            },
+            Example {
+                title: "group values by type",
+                source: r#"group_by([1, "a", 2, "b"]) -> |_index, value| { if is_string(value) { "string" } else { "other" } }"#,
+                result: Ok(r#"{ "other": [1, 2], "string": ["a", "b"] }"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure()?;
+
+        Ok(Box::new(GroupByFn { value, closure }))
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::TargetInnerKey,
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::bytes()),
+                example: Example {
+                    title: "group strings by first letter",
+                    source: r#"group_by(["apple", "avocado", "banana"]) -> |_index, value| { slice!(value, 0, 1) }"#,
+                    result: Ok(r#"{ "a": ["apple", "avocado"], "b": ["banana"] }"#),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GroupByFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for GroupByFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let FunctionClosure { variables, block } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        group_by(value, ctx, runner)
+    }
+
+    fn type_def(&self, ctx: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        let fallible = self.closure.block.type_def(ctx).is_fallible();
+
+        // Each group is an array holding any of the items of the value, under
+        // a key only known at runtime.
+        let group = self.value.type_def(ctx).restrict_array().collect_subtypes();
+
+        TypeDef::object(Collection::from_unknown(Kind::from(group))).with_fallibility(fallible)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::run_program;
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            run_program("group_by([]) -> |index, _value| { to_string(index) }"),
+            Ok(value!({}))
+        );
+    }
+
+    #[test]
+    fn closure_type_error() {
+        assert_eq!(
+            run_program("group_by([1, 2]) -> |_index, value| { value }"),
+            Err("type mismatch in closure return type".to_owned())
+        );
+    }
+
+    #[test]
+    fn closure_error() {
+        let err =
+            run_program(r#"group_by(["a", 1]) -> |_index, value| { string!(value) }"#).unwrap_err();
+
+        assert!(err.contains("expected string, got integer"), "{}", err);
+    }
+}
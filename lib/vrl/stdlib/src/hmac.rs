@@ -0,0 +1,174 @@
+use ::hmac::{Hmac as HmacHasher, Mac};
+use ::sha1::Sha1;
+use ::value::Value;
+use sha_2::{Sha256, Sha512};
+use vrl::prelude::*;
+
+fn hmac(value: Value, key: Value, algorithm: &Bytes) -> Resolved {
+    let value = value.try_bytes()?;
+    let key = key.try_bytes()?;
+
+    let code = match algorithm.as_ref() {
+        b"SHA1" => encode::<HmacHasher<Sha1>>(&key, &value),
+        b"SHA-256" => encode::<HmacHasher<Sha256>>(&key, &value),
+        b"SHA-512" => encode::<HmacHasher<Sha512>>(&key, &value),
+        _ => unreachable!("enum invariant"),
+    };
+
+    Ok(code.into())
+}
+
+#[inline]
+fn encode<T: Mac + hmac::digest::KeyInit>(key: &[u8], value: &[u8]) -> String {
+    let mut mac = <T as Mac>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(value);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+fn algorithms() -> Vec<Value> {
+    vec![value!("SHA1"), value!("SHA-256"), value!("SHA-512")]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Hmac;
+
+impl Function for Hmac {
+    fn identifier(&self) -> &'static str {
+        "hmac"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "default algorithm",
+                source: r#"hmac("Hello there", "super-secret-key")"#,
+                result: Ok("78b184f1832f8aff3934f5e0212454671b2d04d494e3b25075c5e45167029662"),
+            },
+            Example {
+                title: "key from a secret",
+                source: r#"hmac("user-1234", string!(get_secret("my_secret")), algorithm: "SHA1")"#,
+                result: Ok("ee3de7aec362ff014025fed998e0bf82a8080954"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let key = arguments.required("key");
+        let algorithm = arguments
+            .optional_enum("algorithm", &algorithms())?
+            .unwrap_or_else(|| value!("SHA-256"))
+            .try_bytes()
+            .expect("algorithm not bytes");
+
+        Ok(Box::new(HmacFn {
+            value,
+            key,
+            algorithm,
+        }))
+    }
+
+    fn compile_argument(
+        &self,
+        _args: &[(&'static str, Option<FunctionArgument>)],
+        _ctx: &mut FunctionCompileContext,
+        name: &str,
+        expr: Option<&expression::Expr>,
+    ) -> CompiledArgument {
+        match (name, expr) {
+            ("algorithm", Some(expr)) => {
+                let algorithm = expr
+                    .as_enum("algorithm", algorithms())?
+                    .try_bytes()
+                    .expect("algorithm not bytes");
+
+                Ok(Some(Box::new(algorithm) as _))
+            }
+            ("algorithm", None) => Ok(Some(Box::new(Bytes::from("SHA-256")) as _)),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HmacFn {
+    value: Box<dyn Expression>,
+    key: Box<dyn Expression>,
+    algorithm: Bytes,
+}
+
+impl Expression for HmacFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let key = self.key.resolve(ctx)?;
+
+        hmac(value, key, &self.algorithm)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        hmac => Hmac;
+
+        sha1 {
+            args: func_args![
+                value: "The quick brown fox jumps over the lazy dog",
+                key: "key",
+                algorithm: "SHA1",
+            ],
+            want: Ok(value!("de7c9b85b8b78aa6bc8a7a36f70a90701c9db4d9")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        sha256 {
+            args: func_args![
+                value: "The quick brown fox jumps over the lazy dog",
+                key: "key",
+            ],
+            want: Ok(value!("f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        sha512 {
+            args: func_args![
+                value: "The quick brown fox jumps over the lazy dog",
+                key: "key",
+                algorithm: "SHA-512",
+            ],
+            want: Ok(value!("b42af09057bac1e2d41708e48a902e09b5ff7f12ab428a4fe86653c73dd248fb82f948a549f7b791a5b41915ee4d1ec3935357e4e2317250d0372afa2ebeeb3a")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
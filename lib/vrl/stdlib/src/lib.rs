@@ -20,22 +20,46 @@ mod assert_eq;
 mod boolean;
 #[cfg(feature = "ceil")]
 mod ceil;
+#[cfg(feature = "chunks")]
+mod chunks;
+#[cfg(feature = "community_id")]
+mod community_id;
 #[cfg(feature = "compact")]
 mod compact;
 #[cfg(feature = "contains")]
 mod contains;
+#[cfg(feature = "crc32")]
+mod crc32;
+#[cfg(feature = "decode_base16")]
+mod decode_base16;
 #[cfg(feature = "decode_base64")]
 mod decode_base64;
+#[cfg(feature = "decode_gzip")]
+mod decode_gzip;
+#[cfg(feature = "decode_mime_q")]
+mod decode_mime_q;
 #[cfg(feature = "decode_percent")]
 mod decode_percent;
+#[cfg(feature = "decode_punycode")]
+mod decode_punycode;
+#[cfg(feature = "decode_snappy")]
+mod decode_snappy;
+#[cfg(feature = "decode_zlib")]
+mod decode_zlib;
+#[cfg(feature = "decode_zstd")]
+mod decode_zstd;
 #[cfg(feature = "decrypt")]
 mod decrypt;
 #[cfg(feature = "del")]
 mod del;
 #[cfg(feature = "downcase")]
 mod downcase;
+#[cfg(feature = "encode_base16")]
+mod encode_base16;
 #[cfg(feature = "encode_base64")]
 mod encode_base64;
+#[cfg(feature = "encode_gzip")]
+mod encode_gzip;
 #[cfg(feature = "encode_json")]
 mod encode_json;
 #[cfg(feature = "encode_key_value")]
@@ -44,14 +68,20 @@ mod encode_key_value;
 mod encode_logfmt;
 #[cfg(feature = "encode_percent")]
 mod encode_percent;
+#[cfg(feature = "encode_punycode")]
+mod encode_punycode;
 #[cfg(feature = "encrypt")]
 mod encrypt;
 #[cfg(feature = "ends_with")]
 mod ends_with;
 #[cfg(feature = "exists")]
 mod exists;
+#[cfg(feature = "filter")]
+mod filter;
 #[cfg(feature = "find")]
 mod find;
+#[cfg(feature = "find_index")]
+mod find_index;
 #[cfg(feature = "flatten")]
 mod flatten;
 #[cfg(feature = "float")]
@@ -72,6 +102,10 @@ mod get;
 mod get_env_var;
 #[cfg(feature = "get_hostname")]
 mod get_hostname;
+#[cfg(feature = "group_by")]
+mod group_by;
+#[cfg(feature = "hmac")]
+mod hmac;
 #[cfg(feature = "includes")]
 mod includes;
 #[cfg(feature = "integer")]
@@ -144,6 +178,8 @@ mod match_datadog_query;
 mod md5;
 #[cfg(feature = "merge")]
 mod merge;
+#[cfg(feature = "normalize_url")]
+mod normalize_url;
 #[cfg(feature = "now")]
 mod now;
 #[cfg(feature = "object")]
@@ -158,12 +194,20 @@ mod parse_aws_alb_log;
 mod parse_aws_cloudwatch_log_subscription_message;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 mod parse_aws_vpc_flow_log;
+#[cfg(feature = "parse_cbor")]
+mod parse_cbor;
+#[cfg(feature = "parse_cef")]
+mod parse_cef;
+#[cfg(feature = "parse_cisco_asa")]
+mod parse_cisco_asa;
 #[cfg(feature = "parse_common_log")]
 mod parse_common_log;
 #[cfg(feature = "parse_csv")]
 mod parse_csv;
 #[cfg(feature = "parse_duration")]
 mod parse_duration;
+#[cfg(feature = "parse_etld")]
+mod parse_etld;
 #[cfg(feature = "parse_glog")]
 mod parse_glog;
 #[cfg(feature = "parse_grok")]
@@ -178,10 +222,14 @@ mod parse_json;
 mod parse_key_value;
 #[cfg(feature = "parse_klog")]
 mod parse_klog;
+#[cfg(feature = "parse_leef")]
+mod parse_leef;
 #[cfg(feature = "parse_linux_authorization")]
 mod parse_linux_authorization;
 #[cfg(feature = "parse_logfmt")]
 mod parse_logfmt;
+#[cfg(feature = "parse_msgpack")]
+mod parse_msgpack;
 #[cfg(feature = "parse_nginx_log")]
 mod parse_nginx_log;
 #[cfg(feature = "parse_query_string")]
@@ -202,6 +250,8 @@ mod parse_tokens;
 mod parse_url;
 #[cfg(feature = "parse_user_agent")]
 mod parse_user_agent;
+#[cfg(feature = "parse_windows_event_xml")]
+mod parse_windows_event_xml;
 #[cfg(feature = "parse_xml")]
 mod parse_xml;
 #[cfg(feature = "push")]
@@ -210,6 +260,8 @@ mod push;
 mod random_bytes;
 #[cfg(feature = "redact")]
 mod redact;
+#[cfg(feature = "reduce")]
+mod reduce;
 #[cfg(feature = "remove")]
 mod remove;
 #[cfg(feature = "replace")]
@@ -218,6 +270,8 @@ mod replace;
 mod reverse_dns;
 #[cfg(feature = "round")]
 mod round;
+#[cfg(feature = "seahash")]
+mod seahash;
 #[cfg(feature = "set")]
 mod set;
 #[cfg(feature = "sha1")]
@@ -228,6 +282,10 @@ mod sha2;
 mod sha3;
 #[cfg(feature = "slice")]
 mod slice;
+#[cfg(feature = "sort")]
+mod sort;
+#[cfg(feature = "sort_by")]
+mod sort_by;
 #[cfg(feature = "split")]
 mod split;
 #[cfg(feature = "starts_with")]
@@ -280,9 +338,17 @@ mod unnest;
 mod upcase;
 #[cfg(feature = "uuid_v4")]
 mod uuid_v4;
+#[cfg(feature = "xxhash")]
+mod xxhash;
+#[cfg(feature = "zip")]
+mod zip;
 
 // -----------------------------------------------------------------------------
 
+#[cfg(feature = "hmac")]
+pub use crate::hmac::Hmac;
+#[cfg(feature = "seahash")]
+pub use crate::seahash::Seahash;
 #[cfg(feature = "append")]
 pub use append::Append;
 #[cfg(feature = "assert")]
@@ -293,22 +359,46 @@ pub use assert_eq::AssertEq;
 pub use boolean::Boolean;
 #[cfg(feature = "ceil")]
 pub use ceil::Ceil;
+#[cfg(feature = "chunks")]
+pub use chunks::Chunks;
+#[cfg(feature = "community_id")]
+pub use community_id::CommunityId;
 #[cfg(feature = "compact")]
 pub use compact::Compact;
 #[cfg(feature = "contains")]
 pub use contains::Contains;
+#[cfg(feature = "crc32")]
+pub use crc32::Crc32;
+#[cfg(feature = "decode_base16")]
+pub use decode_base16::DecodeBase16;
 #[cfg(feature = "decode_base64")]
 pub use decode_base64::DecodeBase64;
+#[cfg(feature = "decode_gzip")]
+pub use decode_gzip::DecodeGzip;
+#[cfg(feature = "decode_mime_q")]
+pub use decode_mime_q::DecodeMimeQ;
 #[cfg(feature = "decode_percent")]
 pub use decode_percent::DecodePercent;
+#[cfg(feature = "decode_punycode")]
+pub use decode_punycode::DecodePunycode;
+#[cfg(feature = "decode_snappy")]
+pub use decode_snappy::DecodeSnappy;
+#[cfg(feature = "decode_zlib")]
+pub use decode_zlib::DecodeZlib;
+#[cfg(feature = "decode_zstd")]
+pub use decode_zstd::DecodeZstd;
 #[cfg(feature = "decrypt")]
 pub use decrypt::Decrypt;
 #[cfg(feature = "del")]
 pub use del::Del;
 #[cfg(feature = "downcase")]
 pub use downcase::Downcase;
+#[cfg(feature = "encode_base16")]
+pub use encode_base16::EncodeBase16;
 #[cfg(feature = "encode_base64")]
 pub use encode_base64::EncodeBase64;
+#[cfg(feature = "encode_gzip")]
+pub use encode_gzip::EncodeGzip;
 #[cfg(feature = "encode_json")]
 pub use encode_json::EncodeJson;
 #[cfg(feature = "encode_key_value")]
@@ -317,14 +407,20 @@ pub use encode_key_value::EncodeKeyValue;
 pub use encode_logfmt::EncodeLogfmt;
 #[cfg(feature = "encode_percent")]
 pub use encode_percent::EncodePercent;
+#[cfg(feature = "encode_punycode")]
+pub use encode_punycode::EncodePunycode;
 #[cfg(feature = "encrypt")]
 pub use encrypt::Encrypt;
 #[cfg(feature = "ends_with")]
 pub use ends_with::EndsWith;
 #[cfg(feature = "exists")]
 pub use exists::Exists;
+#[cfg(feature = "filter")]
+pub use filter::Filter;
 #[cfg(feature = "find")]
 pub use find::Find;
+#[cfg(feature = "find_index")]
+pub use find_index::FindIndex;
 #[cfg(feature = "flatten")]
 pub use flatten::Flatten;
 #[cfg(feature = "float")]
@@ -345,6 +441,8 @@ pub use get::Get;
 pub use get_env_var::GetEnvVar;
 #[cfg(feature = "get_hostname")]
 pub use get_hostname::GetHostname;
+#[cfg(feature = "group_by")]
+pub use group_by::GroupBy;
 #[cfg(feature = "includes")]
 pub use includes::Includes;
 #[cfg(feature = "integer")]
@@ -407,6 +505,8 @@ pub use match_array::MatchArray;
 pub use match_datadog_query::MatchDatadogQuery;
 #[cfg(feature = "merge")]
 pub use merge::Merge;
+#[cfg(feature = "normalize_url")]
+pub use normalize_url::NormalizeUrl;
 #[cfg(feature = "now")]
 pub use now::Now;
 #[cfg(feature = "object")]
@@ -421,12 +521,20 @@ pub use parse_aws_alb_log::ParseAwsAlbLog;
 pub use parse_aws_cloudwatch_log_subscription_message::ParseAwsCloudWatchLogSubscriptionMessage;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 pub use parse_aws_vpc_flow_log::ParseAwsVpcFlowLog;
+#[cfg(feature = "parse_cbor")]
+pub use parse_cbor::ParseCbor;
+#[cfg(feature = "parse_cef")]
+pub use parse_cef::ParseCef;
+#[cfg(feature = "parse_cisco_asa")]
+pub use parse_cisco_asa::ParseCiscoAsa;
 #[cfg(feature = "parse_common_log")]
 pub use parse_common_log::ParseCommonLog;
 #[cfg(feature = "parse_csv")]
 pub use parse_csv::ParseCsv;
 #[cfg(feature = "parse_duration")]
 pub use parse_duration::ParseDuration;
+#[cfg(feature = "parse_etld")]
+pub use parse_etld::ParseEtld;
 #[cfg(feature = "parse_glog")]
 pub use parse_glog::ParseGlog;
 #[cfg(feature = "parse_grok")]
@@ -441,10 +549,14 @@ pub use parse_json::ParseJson;
 pub use parse_key_value::ParseKeyValue;
 #[cfg(feature = "parse_klog")]
 pub use parse_klog::ParseKlog;
+#[cfg(feature = "parse_leef")]
+pub use parse_leef::ParseLeef;
 #[cfg(feature = "parse_linux_authorization")]
 pub use parse_linux_authorization::ParseLinuxAuthorization;
 #[cfg(feature = "parse_logfmt")]
 pub use parse_logfmt::ParseLogFmt;
+#[cfg(feature = "parse_msgpack")]
+pub use parse_msgpack::ParseMsgpack;
 #[cfg(feature = "parse_nginx_log")]
 pub use parse_nginx_log::ParseNginxLog;
 #[cfg(feature = "parse_query_string")]
@@ -465,6 +577,8 @@ pub use parse_tokens::ParseTokens;
 pub use parse_url::ParseUrl;
 #[cfg(feature = "parse_user_agent")]
 pub use parse_user_agent::ParseUserAgent;
+#[cfg(feature = "parse_windows_event_xml")]
+pub use parse_windows_event_xml::ParseWindowsEventXml;
 #[cfg(feature = "parse_xml")]
 pub use parse_xml::ParseXml;
 #[cfg(feature = "push")]
@@ -475,6 +589,8 @@ pub use r#match::Match;
 pub use random_bytes::RandomBytes;
 #[cfg(feature = "redact")]
 pub use redact::Redact;
+#[cfg(feature = "reduce")]
+pub use reduce::Reduce;
 #[cfg(feature = "remove")]
 pub use remove::Remove;
 #[cfg(feature = "replace")]
@@ -491,6 +607,10 @@ pub use sha2::Sha2;
 pub use sha3::Sha3;
 #[cfg(feature = "slice")]
 pub use slice::Slice;
+#[cfg(feature = "sort")]
+pub use sort::Sort;
+#[cfg(feature = "sort_by")]
+pub use sort_by::SortBy;
 #[cfg(feature = "split")]
 pub use split::Split;
 #[cfg(feature = "starts_with")]
@@ -550,6 +670,10 @@ pub use crate::array::Array;
 pub use crate::md5::Md5;
 #[cfg(feature = "sha1")]
 pub use crate::sha1::Sha1;
+#[cfg(feature = "xxhash")]
+pub use xxhash::Xxhash;
+#[cfg(feature = "zip")]
+pub use zip::Zip;
 
 pub fn all() -> Vec<Box<dyn vrl::Function>> {
     vec![
@@ -565,22 +689,46 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Boolean),
         #[cfg(feature = "ceil")]
         Box::new(Ceil),
+        #[cfg(feature = "chunks")]
+        Box::new(Chunks),
+        #[cfg(feature = "community_id")]
+        Box::new(CommunityId),
         #[cfg(feature = "compact")]
         Box::new(Compact),
         #[cfg(feature = "contains")]
         Box::new(Contains),
+        #[cfg(feature = "crc32")]
+        Box::new(Crc32),
+        #[cfg(feature = "decode_base16")]
+        Box::new(DecodeBase16),
         #[cfg(feature = "decode_base64")]
         Box::new(DecodeBase64),
+        #[cfg(feature = "decode_gzip")]
+        Box::new(DecodeGzip),
+        #[cfg(feature = "decode_mime_q")]
+        Box::new(DecodeMimeQ),
         #[cfg(feature = "decode_percent")]
         Box::new(DecodePercent),
+        #[cfg(feature = "decode_punycode")]
+        Box::new(DecodePunycode),
+        #[cfg(feature = "decode_snappy")]
+        Box::new(DecodeSnappy),
+        #[cfg(feature = "decode_zlib")]
+        Box::new(DecodeZlib),
+        #[cfg(feature = "decode_zstd")]
+        Box::new(DecodeZstd),
         #[cfg(feature = "decrypt")]
         Box::new(Decrypt),
         #[cfg(feature = "del")]
         Box::new(Del),
         #[cfg(feature = "downcase")]
         Box::new(Downcase),
+        #[cfg(feature = "encode_base16")]
+        Box::new(EncodeBase16),
         #[cfg(feature = "encode_base64")]
         Box::new(EncodeBase64),
+        #[cfg(feature = "encode_gzip")]
+        Box::new(EncodeGzip),
         #[cfg(feature = "encode_json")]
         Box::new(EncodeJson),
         #[cfg(feature = "encode_key_value")]
@@ -589,14 +737,20 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(EncodeLogfmt),
         #[cfg(feature = "encode_percent")]
         Box::new(EncodePercent),
+        #[cfg(feature = "encode_punycode")]
+        Box::new(EncodePunycode),
         #[cfg(feature = "encrypt")]
         Box::new(Encrypt),
         #[cfg(feature = "ends_with")]
         Box::new(EndsWith),
         #[cfg(feature = "exists")]
         Box::new(Exists),
+        #[cfg(feature = "filter")]
+        Box::new(Filter),
         #[cfg(feature = "find")]
         Box::new(Find),
+        #[cfg(feature = "find_index")]
+        Box::new(FindIndex),
         #[cfg(feature = "flatten")]
         Box::new(Flatten),
         #[cfg(feature = "float")]
@@ -617,6 +771,10 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(GetEnvVar),
         #[cfg(feature = "get_hostname")]
         Box::new(GetHostname),
+        #[cfg(feature = "group_by")]
+        Box::new(GroupBy),
+        #[cfg(feature = "hmac")]
+        Box::new(Hmac),
         #[cfg(feature = "includes")]
         Box::new(Includes),
         #[cfg(feature = "integer")]
@@ -683,6 +841,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Md5),
         #[cfg(feature = "merge")]
         Box::new(Merge),
+        #[cfg(feature = "normalize_url")]
+        Box::new(NormalizeUrl),
         #[cfg(feature = "now")]
         Box::new(Now),
         // We are not sure if this is the way we want to expose this functionality yet
@@ -699,12 +859,20 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseAwsCloudWatchLogSubscriptionMessage),
         #[cfg(feature = "parse_aws_vpc_flow_log")]
         Box::new(ParseAwsVpcFlowLog),
+        #[cfg(feature = "parse_cbor")]
+        Box::new(ParseCbor),
+        #[cfg(feature = "parse_cef")]
+        Box::new(ParseCef),
+        #[cfg(feature = "parse_cisco_asa")]
+        Box::new(ParseCiscoAsa),
         #[cfg(feature = "parse_common_log")]
         Box::new(ParseCommonLog),
         #[cfg(feature = "parse_csv")]
         Box::new(ParseCsv),
         #[cfg(feature = "parse_duration")]
         Box::new(ParseDuration),
+        #[cfg(feature = "parse_etld")]
+        Box::new(ParseEtld),
         #[cfg(feature = "parse_glog")]
         Box::new(ParseGlog),
         #[cfg(feature = "parse_grok")]
@@ -719,10 +887,14 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseKeyValue),
         #[cfg(feature = "parse_klog")]
         Box::new(ParseKlog),
+        #[cfg(feature = "parse_leef")]
+        Box::new(ParseLeef),
         #[cfg(feature = "parse_linux_authorization")]
         Box::new(ParseLinuxAuthorization),
         #[cfg(feature = "parse_logfmt")]
         Box::new(ParseLogFmt),
+        #[cfg(feature = "parse_msgpack")]
+        Box::new(ParseMsgpack),
         #[cfg(feature = "parse_nginx_log")]
         Box::new(ParseNginxLog),
         #[cfg(feature = "parse_query_string")]
@@ -743,6 +915,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseUrl),
         #[cfg(feature = "parse_user_agent")]
         Box::new(ParseUserAgent),
+        #[cfg(feature = "parse_windows_event_xml")]
+        Box::new(ParseWindowsEventXml),
         #[cfg(feature = "parse_xml")]
         Box::new(ParseXml),
         #[cfg(feature = "push")]
@@ -751,6 +925,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(RandomBytes),
         #[cfg(feature = "redact")]
         Box::new(Redact),
+        #[cfg(feature = "reduce")]
+        Box::new(Reduce),
         #[cfg(feature = "remove")]
         Box::new(Remove),
         #[cfg(feature = "replace")]
@@ -759,6 +935,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ReverseDns),
         #[cfg(feature = "round")]
         Box::new(Round),
+        #[cfg(feature = "seahash")]
+        Box::new(Seahash),
         #[cfg(feature = "set")]
         Box::new(Set),
         #[cfg(feature = "sha1")]
@@ -769,6 +947,10 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Sha3),
         #[cfg(feature = "slice")]
         Box::new(Slice),
+        #[cfg(feature = "sort")]
+        Box::new(Sort),
+        #[cfg(feature = "sort_by")]
+        Box::new(SortBy),
         #[cfg(feature = "split")]
         Box::new(Split),
         #[cfg(feature = "starts_with")]
@@ -821,5 +1003,9 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Upcase),
         #[cfg(feature = "uuid_v4")]
         Box::new(UuidV4),
+        #[cfg(feature = "xxhash")]
+        Box::new(Xxhash),
+        #[cfg(feature = "zip")]
+        Box::new(Zip),
     ]
 }
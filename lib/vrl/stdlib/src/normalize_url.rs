@@ -0,0 +1,181 @@
+use ::value::Value;
+use url::Url;
+use vrl::prelude::*;
+
+fn normalize_url(value: Value, sort_query: Value, remove_fragment: Value) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    let sort_query = sort_query.try_boolean()?;
+    let remove_fragment = remove_fragment.try_boolean()?;
+
+    // Parsing already lowercases the scheme and host, encodes international
+    // hosts as punycode, drops default ports and resolves `.` and `..` path
+    // segments.
+    let mut url = Url::parse(&string).map_err(|e| format!("unable to parse url: {}", e))?;
+
+    if remove_fragment {
+        url.set_fragment(None);
+    }
+
+    if sort_query {
+        let mut pairs = url.query_pairs().into_owned().collect::<Vec<_>>();
+        pairs.sort();
+
+        if pairs.is_empty() {
+            url.set_query(None);
+        } else {
+            url.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+    } else if url.query() == Some("") {
+        url.set_query(None);
+    }
+
+    Ok(String::from(url).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct NormalizeUrl;
+
+impl Function for NormalizeUrl {
+    fn identifier(&self) -> &'static str {
+        "normalize_url"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "sort_query",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "remove_fragment",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "normalize url",
+                source: r#"normalize_url!("HTTP://www.Example.com:80/a/./b/../c?b=2&a=1#top")"#,
+                result: Ok("http://www.example.com/a/c?a=1&b=2"),
+            },
+            Example {
+                title: "internationalized host",
+                source: r#"normalize_url!("https://café.example/menu")"#,
+                result: Ok("https://xn--caf-dma.example/menu"),
+            },
+            Example {
+                title: "keep query order and fragment",
+                source: r#"normalize_url!("https://example.com/?b=2&a=1#top", sort_query: false, remove_fragment: false)"#,
+                result: Ok("https://example.com/?b=2&a=1#top"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let sort_query = arguments
+            .optional("sort_query")
+            .unwrap_or_else(|| expr!(true));
+        let remove_fragment = arguments
+            .optional("remove_fragment")
+            .unwrap_or_else(|| expr!(true));
+
+        Ok(Box::new(NormalizeUrlFn {
+            value,
+            sort_query,
+            remove_fragment,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NormalizeUrlFn {
+    value: Box<dyn Expression>,
+    sort_query: Box<dyn Expression>,
+    remove_fragment: Box<dyn Expression>,
+}
+
+impl Expression for NormalizeUrlFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let sort_query = self.sort_query.resolve(ctx)?;
+        let remove_fragment = self.remove_fragment.resolve(ctx)?;
+
+        normalize_url(value, sort_query, remove_fragment)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        normalize_url => NormalizeUrl;
+
+        case_and_default_port {
+            args: func_args![value: "HTTPS://Example.COM:443"],
+            want: Ok(value!("https://example.com/")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        custom_port {
+            args: func_args![value: "http://example.com:8080/"],
+            want: Ok(value!("http://example.com:8080/")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        dot_segments {
+            args: func_args![value: "http://example.com/a/./b/../../c/"],
+            want: Ok(value!("http://example.com/c/")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        sorted_query {
+            args: func_args![value: "http://example.com/search?q=vector&a=2&a=1"],
+            want: Ok(value!("http://example.com/search?a=1&a=2&q=vector")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        empty_query_and_fragment {
+            args: func_args![value: "http://example.com/path?#"],
+            want: Ok(value!("http://example.com/path")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        keep_fragment {
+            args: func_args![value: "http://example.com/path#section", remove_fragment: false],
+            want: Ok(value!("http://example.com/path#section")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        keep_query_order {
+            args: func_args![value: "http://example.com/?b=2&a=1", sort_query: false],
+            want: Ok(value!("http://example.com/?b=2&a=1")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "example.com"],
+            want: Err("unable to parse url: relative URL without a base"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,145 @@
+use ::value::Value;
+use serde_cbor::Value as CborValue;
+use vrl::prelude::*;
+
+use crate::util::binary_format_type_def;
+
+fn parse_cbor(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let value = serde_cbor::from_slice::<CborValue>(&bytes)
+        .map_err(|e| format!("unable to parse cbor: {}", e))?;
+
+    convert(value).map_err(|e| format!("unable to parse cbor: {}", e).into())
+}
+
+fn convert(value: CborValue) -> std::result::Result<Value, String> {
+    Ok(match value {
+        CborValue::Null => Value::Null,
+        CborValue::Bool(b) => b.into(),
+        CborValue::Integer(i) => i64::try_from(i)
+            .map_err(|_| format!("integer {} is out of range", i))?
+            .into(),
+        CborValue::Float(f) => Value::from_f64_or_zero(f),
+        CborValue::Bytes(bytes) => Value::Bytes(bytes.into()),
+        CborValue::Text(text) => text.into(),
+        CborValue::Array(array) => array
+            .into_iter()
+            .map(convert)
+            .collect::<std::result::Result<Vec<_>, _>>()?
+            .into(),
+        CborValue::Map(map) => map
+            .into_iter()
+            .map(|(key, value)| Ok((convert_key(key)?, convert(value)?)))
+            .collect::<std::result::Result<BTreeMap<_, _>, String>>()?
+            .into(),
+        // Tags only add meaning to the value they wrap, such as marking a
+        // string as a date, so the wrapped value is kept as is.
+        CborValue::Tag(_, value) => convert(*value)?,
+        _ => return Err("unsupported value".to_owned()),
+    })
+}
+
+fn convert_key(key: CborValue) -> std::result::Result<String, String> {
+    match key {
+        CborValue::Text(text) => Ok(text),
+        CborValue::Integer(i) => Ok(i.to_string()),
+        CborValue::Bytes(bytes) => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+        _ => Err("map keys must be strings, integers or bytes".to_owned()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseCbor;
+
+impl Function for ParseCbor {
+    fn identifier(&self) -> &'static str {
+        "parse_cbor"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "object",
+            source: r#"parse_cbor!(decode_base16!("a2616101616282f5f6"))"#,
+            result: Ok(r#"{ "a": 1, "b": [true, null] }"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ParseCborFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseCborFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ParseCborFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_cbor(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        binary_format_type_def()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(bytes: &'static [u8]) -> Value {
+        Value::Bytes(Bytes::from_static(bytes))
+    }
+
+    test_function![
+        parse_cbor => ParseCbor;
+
+        object {
+            args: func_args![value: bytes(b"\xa2\x61a\x01\x61b\x82\xf5\xf6")],
+            want: Ok(value!({ "a": 1, "b": [true, null] })),
+            tdef: binary_format_type_def(),
+        }
+
+        scalars {
+            args: func_args![value: bytes(b"\x84\x20\xfb\x3f\xf8\x00\x00\x00\x00\x00\x00\x63abc\x44\x01\x02\x03\x04")],
+            want: Ok(value!([-1, 1.5, "abc", "\u{1}\u{2}\u{3}\u{4}"])),
+            tdef: binary_format_type_def(),
+        }
+
+        integer_keys {
+            args: func_args![value: bytes(b"\xa1\x01\x61a")],
+            want: Ok(value!({ "1": "a" })),
+            tdef: binary_format_type_def(),
+        }
+
+        tagged {
+            args: func_args![value: bytes(b"\xc1\x1a\x51\x4b\x67\xb0")],
+            want: Ok(value!(1363896240)),
+            tdef: binary_format_type_def(),
+        }
+
+        out_of_range {
+            args: func_args![value: bytes(b"\x1b\xff\xff\xff\xff\xff\xff\xff\xff")],
+            want: Err("unable to parse cbor: integer 18446744073709551615 is out of range"),
+            tdef: binary_format_type_def(),
+        }
+    ];
+}
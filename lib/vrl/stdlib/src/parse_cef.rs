@@ -0,0 +1,401 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use ::value::Value;
+use vrl::prelude::*;
+
+const HEADER_FIELDS: [&str; 7] = [
+    "cefVersion",
+    "deviceVendor",
+    "deviceProduct",
+    "deviceVersion",
+    "deviceEventClassId",
+    "name",
+    "severity",
+];
+
+fn parse_cef(value: Value, translate_custom_fields: Value) -> Resolved {
+    let bytes = value.try_bytes_utf8_lossy()?;
+    let translate_custom_fields = translate_custom_fields.try_boolean()?;
+
+    // Anything in front of the CEF message, such as a syslog header, is
+    // skipped.
+    let start = bytes
+        .find("CEF:")
+        .ok_or("unable to parse CEF: missing CEF prefix")?;
+    let (header, extension) = parse_header(&bytes[start + 4..])?;
+
+    let mut map = HEADER_FIELDS
+        .iter()
+        .map(|field| field.to_string())
+        .zip(header.into_iter().map(Value::from))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut fields = parse_extension(extension)?;
+    if translate_custom_fields {
+        fields = translate(fields);
+    }
+
+    for (key, value) in fields {
+        map.insert(key, value.into());
+    }
+
+    Ok(map.into())
+}
+
+/// Splits the seven pipe-delimited header fields from the extension, handling
+/// the `\|` and `\\` escapes.
+fn parse_header(input: &str) -> std::result::Result<(Vec<String>, &str), String> {
+    let mut fields = Vec::with_capacity(HEADER_FIELDS.len());
+    let mut field = String::new();
+    let mut chars = input.char_indices();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, c @ ('|' | '\\'))) => field.push(c),
+                Some((_, c)) => {
+                    field.push('\\');
+                    field.push(c);
+                }
+                None => field.push('\\'),
+            },
+            '|' => {
+                fields.push(std::mem::take(&mut field));
+                if fields.len() == HEADER_FIELDS.len() {
+                    return Ok((fields, &input[index + 1..]));
+                }
+            }
+            c => field.push(c),
+        }
+    }
+
+    Err(format!(
+        "unable to parse CEF: expected {} header fields, found {}",
+        HEADER_FIELDS.len(),
+        fields.len()
+    ))
+}
+
+/// Parses the space separated `key=value` pairs of the extension.
+///
+/// Values may contain unescaped spaces, so a value runs until the whitespace
+/// before the next key.
+fn parse_extension(input: &str) -> std::result::Result<Vec<(String, String)>, String> {
+    let mut fields = Vec::new();
+    let mut rest = input.trim_start();
+
+    while !rest.is_empty() {
+        let key_len = key_len(rest)
+            .ok_or_else(|| format!("unable to parse CEF: invalid extension at {:?}", rest))?;
+        let key = &rest[..key_len];
+        rest = &rest[key_len + 1..];
+
+        let end = next_key(rest).unwrap_or(rest.len());
+        fields.push((key.to_owned(), unescape(rest[..end].trim_end())));
+        rest = rest[end..].trim_start();
+    }
+
+    Ok(fields)
+}
+
+/// Returns the length of the key at the start of `input`, if it is followed
+/// by an `=`.
+fn key_len(input: &str) -> Option<usize> {
+    let len = input
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '[' | ']')))
+        .unwrap_or(input.len());
+
+    (len > 0 && input[len..].starts_with('=')).then(|| len)
+}
+
+/// Returns the position of the next key following whitespace in `input`.
+fn next_key(input: &str) -> Option<usize> {
+    input
+        .char_indices()
+        .filter(|(_, c)| c.is_whitespace())
+        .map(|(index, c)| index + c.len_utf8())
+        .find(|&index| key_len(&input[index..]).is_some())
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(c @ ('=' | '\\')) => unescaped.push(c),
+            Some(c) => {
+                unescaped.push('\\');
+                unescaped.push(c);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+/// Replaces custom extension keys such as `cs1` with the value of their
+/// matching label key, such as `cs1Label`.
+fn translate(fields: Vec<(String, String)>) -> Vec<(String, String)> {
+    let keys = fields
+        .iter()
+        .map(|(key, _)| key.clone())
+        .collect::<BTreeSet<_>>();
+    let labels = fields
+        .iter()
+        .filter_map(|(key, value)| {
+            key.strip_suffix("Label")
+                .filter(|key| keys.contains(*key))
+                .map(|key| (key.to_owned(), value.clone()))
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    fields
+        .into_iter()
+        .filter(|(key, _)| {
+            key.strip_suffix("Label")
+                .map_or(true, |key| !labels.contains_key(key))
+        })
+        .map(|(key, value)| match labels.get(&key) {
+            Some(label) => (label.clone(), value),
+            None => (key, value),
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseCef;
+
+impl Function for ParseCef {
+    fn identifier(&self) -> &'static str {
+        "parse_cef"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "translate_custom_fields",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "parse CEF message",
+                source: r#"parse_cef!(s'CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 spt=1232')"#,
+                result: Ok(indoc! {r#"{
+                    "cefVersion": "0",
+                    "deviceVendor": "Security",
+                    "deviceProduct": "threatmanager",
+                    "deviceVersion": "1.0",
+                    "deviceEventClassId": "100",
+                    "name": "worm successfully stopped",
+                    "severity": "10",
+                    "src": "10.0.0.1",
+                    "dst": "2.1.2.2",
+                    "spt": "1232"
+                }"#}),
+            },
+            Example {
+                title: "translate custom fields",
+                source: r#"parse_cef!(s'CEF:0|Dev|firewall|2.2|1|Connection denied|5|cs1Label=rule cs1=block all c6a1Label=Device IPv6 Address c6a1=2001:db8::1', translate_custom_fields: true)"#,
+                result: Ok(indoc! {r#"{
+                    "cefVersion": "0",
+                    "deviceVendor": "Dev",
+                    "deviceProduct": "firewall",
+                    "deviceVersion": "2.2",
+                    "deviceEventClassId": "1",
+                    "name": "Connection denied",
+                    "severity": "5",
+                    "rule": "block all",
+                    "Device IPv6 Address": "2001:db8::1"
+                }"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let translate_custom_fields = arguments
+            .optional("translate_custom_fields")
+            .unwrap_or_else(|| expr!(false));
+
+        Ok(Box::new(ParseCefFn {
+            value,
+            translate_custom_fields,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseCefFn {
+    value: Box<dyn Expression>,
+    translate_custom_fields: Box<dyn Expression>,
+}
+
+impl Expression for ParseCefFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let translate_custom_fields = self.translate_custom_fields.resolve(ctx)?;
+
+        parse_cef(value, translate_custom_fields)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> Collection<Field> {
+    let mut collection = Collection::from(
+        HEADER_FIELDS
+            .iter()
+            .map(|field| (Field::from(*field), Kind::bytes()))
+            .collect::<BTreeMap<_, _>>(),
+    );
+    collection.set_unknown(Kind::bytes());
+    collection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_cef => ParseCef;
+
+        header_only {
+            args: func_args![value: "CEF:1|Vendor|Product|2.0|4000|Login|Low|"],
+            want: Ok(value!({
+                cefVersion: "1",
+                deviceVendor: "Vendor",
+                deviceProduct: "Product",
+                deviceVersion: "2.0",
+                deviceEventClassId: "4000",
+                name: "Login",
+                severity: "Low",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        syslog_prefix {
+            args: func_args![value: "Sep 29 08:26:10 host CEF:0|Vendor|Product|1.0|100|Name|3|src=10.0.0.1"],
+            want: Ok(value!({
+                cefVersion: "0",
+                deviceVendor: "Vendor",
+                deviceProduct: "Product",
+                deviceVersion: "1.0",
+                deviceEventClassId: "100",
+                name: "Name",
+                severity: "3",
+                src: "10.0.0.1",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        header_escapes {
+            args: func_args![value: r#"CEF:0|security|threat\|manager|1.0|100|detected a \\ in message|10|"#],
+            want: Ok(value!({
+                cefVersion: "0",
+                deviceVendor: "security",
+                deviceProduct: "threat|manager",
+                deviceVersion: "1.0",
+                deviceEventClassId: "100",
+                name: r#"detected a \ in message"#,
+                severity: "10",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        extension_escapes {
+            args: func_args![value: r#"CEF:0|Vendor|Product|1.0|100|Name|3|msg=a\=b and c\\d line\nbreak act=blocked  request=https://example.com/a|b"#],
+            want: Ok(value!({
+                cefVersion: "0",
+                deviceVendor: "Vendor",
+                deviceProduct: "Product",
+                deviceVersion: "1.0",
+                deviceEventClassId: "100",
+                name: "Name",
+                severity: "3",
+                msg: "a=b and c\\d line\nbreak",
+                act: "blocked",
+                request: "https://example.com/a|b",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        empty_value {
+            args: func_args![value: "CEF:0|Vendor|Product|1.0|100|Name|3|suser= duser=bob"],
+            want: Ok(value!({
+                cefVersion: "0",
+                deviceVendor: "Vendor",
+                deviceProduct: "Product",
+                deviceVersion: "1.0",
+                deviceEventClassId: "100",
+                name: "Name",
+                severity: "3",
+                suser: "",
+                duser: "bob",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        translate_custom_fields {
+            args: func_args![
+                value: "CEF:0|Vendor|Product|1.0|100|Name|3|cn1=42 cn1Label=count cs2Label=unused",
+                translate_custom_fields: true,
+            ],
+            want: Ok(value!({
+                cefVersion: "0",
+                deviceVendor: "Vendor",
+                deviceProduct: "Product",
+                deviceVersion: "1.0",
+                deviceEventClassId: "100",
+                name: "Name",
+                severity: "3",
+                count: "42",
+                cs2Label: "unused",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        missing_prefix {
+            args: func_args![value: "0|Vendor|Product|1.0|100|Name|3|"],
+            want: Err("unable to parse CEF: missing CEF prefix"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        missing_header_fields {
+            args: func_args![value: "CEF:0|Vendor|Product|1.0"],
+            want: Err("unable to parse CEF: expected 7 header fields, found 3"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_extension {
+            args: func_args![value: "CEF:0|Vendor|Product|1.0|100|Name|3|=foo"],
+            want: Err(r#"unable to parse CEF: invalid extension at "=foo""#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
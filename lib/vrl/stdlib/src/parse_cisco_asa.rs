@@ -0,0 +1,462 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use vrl::prelude::*;
+
+static HEADER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"%(?:ASA|FTD|PIX|FWSM)-(?P<severity>[0-7])-(?P<message_id>\d{6}):\s*(?P<message>.*?)\s*$",
+    )
+    .expect("failed compiling regex for Cisco ASA header")
+});
+
+/// Fields parsed as integers when present in a message.
+const INTEGER_FIELDS: [&str; 9] = [
+    "bytes",
+    "connection_id",
+    "destination_mapped_port",
+    "destination_port",
+    "hit_count",
+    "icmp_code",
+    "icmp_type",
+    "source_mapped_port",
+    "source_port",
+];
+
+/// The messages of the catalog for which fields are extracted, mapping their
+/// IDs to the action they report and a pattern for their text.
+///
+/// Addresses are named by the order in which they appear in the message, so
+/// the `source` of a connection built `outbound` is the foreign host.
+static CATALOG: Lazy<BTreeMap<i64, (Option<&'static str>, Regex)>> = Lazy::new(|| {
+    // An `interface:ip/port` endpoint.
+    let endpoint = |prefix: &str| {
+        format!(
+            r"(?P<{0}_interface>[^:\s]+):(?P<{0}_ip>[^/\s]+)/(?P<{0}_port>\d+)",
+            prefix
+        )
+    };
+    // An `ip/port` address.
+    let address = |prefix: &str| format!(r"(?P<{0}_ip>[^/\s]+)/(?P<{0}_port>\d+)", prefix);
+    // A `(ip/port)` mapped address.
+    let mapped = |prefix: &str| {
+        format!(
+            r"\((?P<{0}_mapped_ip>[^/\s]+)/(?P<{0}_mapped_port>\d+)\)",
+            prefix
+        )
+    };
+    // An optional `(user)` identity firewall user.
+    let user = |prefix: &str| format!(r"(?: \((?P<{}_user>[^)]*)\))?", prefix);
+    // Optional identity firewall details that aren't extracted.
+    let details = r"(?: ?\([^)]*\))?";
+
+    let entries: Vec<(&[i64], Option<&str>, String)> = vec![
+        (
+            &[106001],
+            Some("denied"),
+            format!(
+                r"^Inbound (?P<protocol>\S+) connection denied from {} to {} flags (?P<tcp_flags>.*?) +on interface (?P<source_interface>\S+?)\.?$",
+                address("source"),
+                address("destination"),
+            ),
+        ),
+        (
+            &[106006, 106007],
+            Some("denied"),
+            format!(
+                r"^Deny inbound (?P<protocol>\S+) from {} to {} (?:on interface (?P<source_interface>\S+?)|due to (?P<reason>.+?))\.?$",
+                address("source"),
+                address("destination"),
+            ),
+        ),
+        (
+            &[106014],
+            Some("denied"),
+            format!(
+                r"^Deny (?P<direction>inbound|outbound) (?P<protocol>icmp) src (?P<source_interface>[^:\s]+):(?P<source_ip>[^\s(]+){0} dst (?P<destination_interface>[^:\s]+):(?P<destination_ip>[^\s(]+){0} \(type (?P<icmp_type>\d+), code (?P<icmp_code>\d+)\)",
+                details,
+            ),
+        ),
+        (
+            &[106015],
+            Some("denied"),
+            format!(
+                r"^Deny (?P<protocol>\S+) \(no connection\) from {} to {} flags (?P<tcp_flags>.*?) +on interface (?P<source_interface>\S+?)\.?$",
+                address("source"),
+                address("destination"),
+            ),
+        ),
+        (
+            &[106023],
+            Some("denied"),
+            format!(
+                r#"^Deny (?P<protocol>\S+) src (?P<source_interface>[^:\s]+):(?P<source_ip>[^/\s(]+)(?:/(?P<source_port>\d+))?{} dst (?P<destination_interface>[^:\s]+):(?P<destination_ip>[^/\s(]+)(?:/(?P<destination_port>\d+))?(?: ?\((?:type (?P<icmp_type>\d+), code (?P<icmp_code>\d+)|[^)]*)\))* by access-group "(?P<access_group>[^"]*)""#,
+                details,
+            ),
+        ),
+        (
+            &[106100],
+            None,
+            format!(
+                r"^access-list (?P<access_group>\S+) (?P<action>permitted|denied|est-allowed) (?P<protocol>\S+) (?P<source_interface>[^/\s]+)/(?P<source_ip>[^(\s]+)\((?P<source_port>\d+)\){0} -> (?P<destination_interface>[^/\s]+)/(?P<destination_ip>[^(\s]+)\((?P<destination_port>\d+)\){0} hit-cnt (?P<hit_count>\d+)",
+                details,
+            ),
+        ),
+        (
+            &[302013, 302015],
+            Some("built"),
+            format!(
+                r"^Built (?P<direction>inbound|outbound) (?P<protocol>TCP|UDP) connection (?P<connection_id>\d+) for {} {}{} to {} {}{}",
+                endpoint("source"),
+                mapped("source"),
+                user("source"),
+                endpoint("destination"),
+                mapped("destination"),
+                user("destination"),
+            ),
+        ),
+        (
+            &[302014, 302016],
+            Some("teardown"),
+            format!(
+                r"^Teardown (?P<protocol>TCP|UDP) connection (?P<connection_id>\d+) for {}{} to {}{} duration (?P<duration>\d+:\d{{2}}:\d{{2}}) bytes (?P<bytes>\d+)(?: (?P<reason>[^(]+?))?(?: \((?P<user>[^)]*)\))?$",
+                endpoint("source"),
+                user("source"),
+                endpoint("destination"),
+                user("destination"),
+            ),
+        ),
+        (
+            &[305011],
+            Some("built"),
+            format!(
+                r"^Built (?P<translation_type>dynamic|static) (?P<protocol>TCP|UDP|ICMP) translation from {}{} to (?P<mapped_interface>[^:\s]+):(?P<source_mapped_ip>[^/\s]+)/(?P<source_mapped_port>\d+)",
+                endpoint("source"),
+                details,
+            ),
+        ),
+        (
+            &[305012],
+            Some("teardown"),
+            format!(
+                r"^Teardown (?P<translation_type>dynamic|static) (?P<protocol>TCP|UDP|ICMP) translation from (?P<source_interface>[^:\s(]+)(?:\([^)]*\))?:(?P<source_ip>[^/\s]+)/(?P<source_port>\d+){} to (?P<mapped_interface>[^:\s]+):(?P<source_mapped_ip>[^/\s]+)/(?P<source_mapped_port>\d+) duration (?P<duration>\d+:\d{{2}}:\d{{2}})",
+                details,
+            ),
+        ),
+        (
+            &[113004],
+            Some("successful"),
+            r"^AAA user (?P<aaa_type>authentication|authorization|accounting) Successful\s*:\s*server\s*=\s*(?P<server_ip>\S+)\s*:\s*user\s*=\s*(?P<user>.+?)$".to_owned(),
+        ),
+        (
+            &[113005],
+            Some("rejected"),
+            r"^AAA user (?P<aaa_type>authentication|authorization) Rejected\s*:\s*reason\s*=\s*(?P<reason>.+?)\s*:\s*server\s*=\s*(?P<server_ip>\S+)\s*:\s*user\s*=\s*(?P<user>.+?)(?:\s*:\s*user IP\s*=\s*(?P<source_ip>\S+))?$".to_owned(),
+        ),
+        (
+            &[605004, 605005],
+            None,
+            format!(
+                r#"^Login (?P<action>denied|permitted) from {} to (?P<destination_interface>[^:\s]+):(?P<destination_ip>[^/\s]+)/(?P<service>\S+) for user ["“”]?(?P<user>[^"“”]*)["“”]?$"#,
+                address("source"),
+            ),
+        ),
+        (
+            &[111008],
+            Some("executed"),
+            r"^User '(?P<user>[^']*)' executed the '(?P<command>.*)' command\.?$".to_owned(),
+        ),
+        (
+            &[710003],
+            Some("denied"),
+            format!(
+                r"^(?P<protocol>TCP|UDP) access denied by ACL from {} to (?P<destination_interface>[^:\s]+):(?P<destination_ip>[^/\s]+)/(?P<service>\S+)",
+                address("source"),
+            ),
+        ),
+    ];
+
+    entries
+        .into_iter()
+        .flat_map(|(ids, action, pattern)| {
+            let regex = Regex::new(&pattern).expect("failed compiling regex for Cisco ASA message");
+            ids.iter().map(move |id| (*id, (action, regex.clone())))
+        })
+        .collect()
+});
+
+fn parse_cisco_asa(value: Value) -> Resolved {
+    let bytes = value.try_bytes_utf8_lossy()?;
+
+    let captures = HEADER
+        .captures(&bytes)
+        .ok_or("unable to parse Cisco ASA message: missing %ASA-<severity>-<id> prefix")?;
+    let severity = parse_integer(&captures["severity"]);
+    let message_id = parse_integer(&captures["message_id"]);
+    let message = &captures["message"];
+
+    let mut map = BTreeMap::from([
+        ("severity".to_owned(), severity),
+        ("message_id".to_owned(), message_id.clone()),
+        ("message".to_owned(), Value::from(message)),
+    ]);
+
+    // Messages outside of the catalog, or whose format differs between
+    // releases, are returned without extracted fields.
+    let entry = message_id.as_integer().and_then(|id| CATALOG.get(&id));
+    if let Some((action, regex)) = entry {
+        if let Some(captures) = regex.captures(message) {
+            if let Some(action) = action {
+                map.insert("action".to_owned(), (*action).into());
+            }
+
+            for name in regex.capture_names().flatten() {
+                if let Some(capture) = captures.name(name) {
+                    let value = if INTEGER_FIELDS.contains(&name) {
+                        parse_integer(capture.as_str())
+                    } else {
+                        capture.as_str().into()
+                    };
+                    map.insert(name.to_owned(), value);
+                }
+            }
+        }
+    }
+
+    Ok(map.into())
+}
+
+fn parse_integer(value: &str) -> Value {
+    value
+        .parse::<i64>()
+        .map(Value::from)
+        .unwrap_or_else(|_| value.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseCiscoAsa;
+
+impl Function for ParseCiscoAsa {
+    fn identifier(&self) -> &'static str {
+        "parse_cisco_asa"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "parse connection message",
+                source: r#"parse_cisco_asa!("%ASA-6-302013: Built inbound TCP connection 447236 for outside:192.0.2.10/52210 (192.0.2.10/52210) to inside:10.0.0.5/443 (203.0.113.5/443)")"#,
+                result: Ok(indoc! {r#"{
+                    "action": "built",
+                    "connection_id": 447236,
+                    "destination_interface": "inside",
+                    "destination_ip": "10.0.0.5",
+                    "destination_mapped_ip": "203.0.113.5",
+                    "destination_mapped_port": 443,
+                    "destination_port": 443,
+                    "direction": "inbound",
+                    "message": "Built inbound TCP connection 447236 for outside:192.0.2.10/52210 (192.0.2.10/52210) to inside:10.0.0.5/443 (203.0.113.5/443)",
+                    "message_id": 302013,
+                    "protocol": "TCP",
+                    "severity": 6,
+                    "source_interface": "outside",
+                    "source_ip": "192.0.2.10",
+                    "source_mapped_ip": "192.0.2.10",
+                    "source_mapped_port": 52210,
+                    "source_port": 52210
+                }"#}),
+            },
+            Example {
+                title: "parse message outside of the catalog",
+                source: r#"parse_cisco_asa!("<166>Jan 05 2022 10:00:00 asa01 : %ASA-5-111010: User 'admin', running 'CLI' from IP 10.0.0.1, executed 'write memory'")"#,
+                result: Ok(indoc! {r#"{
+                    "message": "User 'admin', running 'CLI' from IP 10.0.0.1, executed 'write memory'",
+                    "message_id": 111010,
+                    "severity": 5
+                }"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ParseCiscoAsaFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseCiscoAsaFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ParseCiscoAsaFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_cisco_asa(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> Collection<Field> {
+    let mut collection = Collection::from(BTreeMap::from([
+        (Field::from("severity"), Kind::integer()),
+        (Field::from("message_id"), Kind::integer()),
+        (Field::from("message"), Kind::bytes()),
+    ]));
+    collection.set_unknown(Kind::bytes() | Kind::integer());
+    collection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_cisco_asa => ParseCiscoAsa;
+
+        teardown {
+            args: func_args![value: "Mar 02 2022 10:15:01 asa01 : %ASA-6-302014: Teardown TCP connection 447236 for outside:192.0.2.10/52210 to inside:10.0.0.5/443 duration 0:00:30 bytes 6082 TCP FINs"],
+            want: Ok(value!({
+                action: "teardown",
+                bytes: 6082,
+                connection_id: 447236,
+                destination_interface: "inside",
+                destination_ip: "10.0.0.5",
+                destination_port: 443,
+                duration: "0:00:30",
+                message: "Teardown TCP connection 447236 for outside:192.0.2.10/52210 to inside:10.0.0.5/443 duration 0:00:30 bytes 6082 TCP FINs",
+                message_id: 302014,
+                protocol: "TCP",
+                reason: "TCP FINs",
+                severity: 6,
+                source_interface: "outside",
+                source_ip: "192.0.2.10",
+                source_port: 52210,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        access_group_deny {
+            args: func_args![value: r#"%ASA-4-106023: Deny udp src outside:198.51.100.7/5353 dst inside:10.0.0.9/53 by access-group "OUTSIDE_IN" [0x0, 0x0]"#],
+            want: Ok(value!({
+                access_group: "OUTSIDE_IN",
+                action: "denied",
+                destination_interface: "inside",
+                destination_ip: "10.0.0.9",
+                destination_port: 53,
+                message: r#"Deny udp src outside:198.51.100.7/5353 dst inside:10.0.0.9/53 by access-group "OUTSIDE_IN" [0x0, 0x0]"#,
+                message_id: 106023,
+                protocol: "udp",
+                severity: 4,
+                source_interface: "outside",
+                source_ip: "198.51.100.7",
+                source_port: 5353,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        access_group_deny_icmp {
+            args: func_args![value: r#"%ASA-4-106023: Deny icmp src outside:198.51.100.7 dst inside:10.0.0.9 (type 8, code 0) by access-group "OUTSIDE_IN" [0x0, 0x0]"#],
+            want: Ok(value!({
+                access_group: "OUTSIDE_IN",
+                action: "denied",
+                destination_interface: "inside",
+                destination_ip: "10.0.0.9",
+                icmp_code: 0,
+                icmp_type: 8,
+                message: r#"Deny icmp src outside:198.51.100.7 dst inside:10.0.0.9 (type 8, code 0) by access-group "OUTSIDE_IN" [0x0, 0x0]"#,
+                message_id: 106023,
+                protocol: "icmp",
+                severity: 4,
+                source_interface: "outside",
+                source_ip: "198.51.100.7",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        access_list {
+            args: func_args![value: "%ASA-6-106100: access-list inside_in permitted tcp inside/10.0.0.5(50000) -> outside/192.0.2.10(443) hit-cnt 1 first hit [0x2c1ae9e1, 0x0]"],
+            want: Ok(value!({
+                access_group: "inside_in",
+                action: "permitted",
+                destination_interface: "outside",
+                destination_ip: "192.0.2.10",
+                destination_port: 443,
+                hit_count: 1,
+                message: "access-list inside_in permitted tcp inside/10.0.0.5(50000) -> outside/192.0.2.10(443) hit-cnt 1 first hit [0x2c1ae9e1, 0x0]",
+                message_id: 106100,
+                protocol: "tcp",
+                severity: 6,
+                source_interface: "inside",
+                source_ip: "10.0.0.5",
+                source_port: 50000,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        aaa_rejected {
+            args: func_args![value: "%ASA-2-113005: AAA user authentication Rejected : reason = AAA failure : server = 10.0.0.20 : user = ***** : user IP = 192.0.2.44"],
+            want: Ok(value!({
+                aaa_type: "authentication",
+                action: "rejected",
+                message: "AAA user authentication Rejected : reason = AAA failure : server = 10.0.0.20 : user = ***** : user IP = 192.0.2.44",
+                message_id: 113005,
+                reason: "AAA failure",
+                server_ip: "10.0.0.20",
+                severity: 2,
+                source_ip: "192.0.2.44",
+                user: "*****",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        command {
+            args: func_args![value: "%FTD-5-111008: User 'admin' executed the 'logging host inside 10.0.0.1' command."],
+            want: Ok(value!({
+                action: "executed",
+                command: "logging host inside 10.0.0.1",
+                message: "User 'admin' executed the 'logging host inside 10.0.0.1' command.",
+                message_id: 111008,
+                severity: 5,
+                user: "admin",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        unmatched_catalog_message {
+            args: func_args![value: "%ASA-6-302013: Built inbound TCP connection"],
+            want: Ok(value!({
+                message: "Built inbound TCP connection",
+                message_id: 302013,
+                severity: 6,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        missing_prefix {
+            args: func_args![value: "Built inbound TCP connection"],
+            want: Err("unable to parse Cisco ASA message: missing %ASA-<severity>-<id> prefix"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
@@ -0,0 +1,299 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use ::value::Value;
+use psl::Psl;
+use publicsuffix::List;
+use vrl::prelude::*;
+
+fn parse_etld(value: Value, plus_parts: Value, list: Option<&List>) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    let plus_parts = plus_parts.try_integer()?;
+    let plus_parts = usize::try_from(plus_parts).map_err(|_| "plus_parts must not be negative")?;
+
+    let domain = string.trim_end_matches('.').to_lowercase();
+    match list {
+        Some(list) => parse_domain(list, &domain, plus_parts),
+        None => parse_domain(&psl::List, &domain, plus_parts),
+    }
+}
+
+fn parse_domain(list: &impl Psl, domain: &str, plus_parts: usize) -> Resolved {
+    let suffix = list
+        .suffix(domain.as_bytes())
+        .ok_or_else(|| format!("unable to determine eTLD for {:?}", domain))?;
+    let etld = String::from_utf8_lossy(suffix.as_bytes()).into_owned();
+    let known_suffix = suffix.is_known();
+
+    let registered_domain = list
+        .domain(domain.as_bytes())
+        .map(|domain| String::from_utf8_lossy(domain.as_bytes()).into_owned());
+
+    let subdomain = registered_domain.as_ref().and_then(|registered_domain| {
+        domain
+            .strip_suffix(registered_domain.as_str())
+            .and_then(|subdomain| subdomain.strip_suffix('.'))
+            .map(ToOwned::to_owned)
+    });
+
+    // The eTLD, along with the given number of labels to its left.
+    let labels = domain.split('.').collect::<Vec<_>>();
+    let parts = etld.split('.').count() + plus_parts;
+    let etld_plus = labels[labels.len().saturating_sub(parts)..].join(".");
+
+    Ok(BTreeMap::from([
+        ("etld".to_owned(), Value::from(etld)),
+        ("etld_plus".to_owned(), etld_plus.into()),
+        ("known_suffix".to_owned(), known_suffix.into()),
+        ("registered_domain".to_owned(), registered_domain.into()),
+        ("subdomain".to_owned(), subdomain.into()),
+    ])
+    .into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseEtld;
+
+impl Function for ParseEtld {
+    fn identifier(&self) -> &'static str {
+        "parse_etld"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "plus_parts",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "psl",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "parse eTLD",
+                source: r#"parse_etld!("www.example.co.uk")"#,
+                result: Ok(indoc! {r#"{
+                    "etld": "co.uk",
+                    "etld_plus": "co.uk",
+                    "known_suffix": true,
+                    "registered_domain": "example.co.uk",
+                    "subdomain": "www"
+                }"#}),
+            },
+            Example {
+                title: "parse eTLD with parts",
+                source: r#"parse_etld!("a.b.example.com", plus_parts: 2)"#,
+                result: Ok(indoc! {r#"{
+                    "etld": "com",
+                    "etld_plus": "b.example.com",
+                    "known_suffix": true,
+                    "registered_domain": "example.com",
+                    "subdomain": "a.b"
+                }"#}),
+            },
+            Example {
+                title: "parse unknown eTLD",
+                source: r#"parse_etld!("vector.local")"#,
+                result: Ok(indoc! {r#"{
+                    "etld": "local",
+                    "etld_plus": "local",
+                    "known_suffix": false,
+                    "registered_domain": "vector.local",
+                    "subdomain": null
+                }"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let plus_parts = arguments.optional("plus_parts").unwrap_or_else(|| expr!(0));
+
+        // A custom list is read once, when the program is compiled.
+        let list = match arguments.optional_literal("psl")? {
+            Some(literal) => {
+                let path = literal.to_value();
+                let list =
+                    read_list(&path).map_err(|error| vrl::function::Error::InvalidArgument {
+                        keyword: "psl",
+                        value: path,
+                        error,
+                    })?;
+                Some(Arc::new(list))
+            }
+            None => None,
+        };
+
+        Ok(Box::new(ParseEtldFn {
+            value,
+            plus_parts,
+            list,
+        }))
+    }
+}
+
+fn read_list(path: &Value) -> std::result::Result<List, &'static str> {
+    let path = path
+        .as_str()
+        .ok_or("public suffix list path must be a string")?;
+    let list =
+        std::fs::read_to_string(path.as_ref()).map_err(|_| "unable to read public suffix list")?;
+
+    list.parse()
+        .map_err(|_| "unable to parse public suffix list")
+}
+
+#[derive(Debug, Clone)]
+struct ParseEtldFn {
+    value: Box<dyn Expression>,
+    plus_parts: Box<dyn Expression>,
+    list: Option<Arc<List>>,
+}
+
+impl Expression for ParseEtldFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let plus_parts = self.plus_parts.resolve(ctx)?;
+
+        parse_etld(value, plus_parts, self.list.as_deref())
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        (Field::from("etld"), Kind::bytes()),
+        (Field::from("etld_plus"), Kind::bytes()),
+        (Field::from("known_suffix"), Kind::boolean()),
+        (
+            Field::from("registered_domain"),
+            Kind::bytes() | Kind::null(),
+        ),
+        (Field::from("subdomain"), Kind::bytes() | Kind::null()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_etld => ParseEtld;
+
+        naive {
+            args: func_args![value: "vector.dev"],
+            want: Ok(value!({
+                etld: "dev",
+                etld_plus: "dev",
+                known_suffix: true,
+                registered_domain: "vector.dev",
+                subdomain: null,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        multi_label_suffix {
+            args: func_args![value: "www.Example.CO.UK."],
+            want: Ok(value!({
+                etld: "co.uk",
+                etld_plus: "co.uk",
+                known_suffix: true,
+                registered_domain: "example.co.uk",
+                subdomain: "www",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        plus_parts {
+            args: func_args![value: "a.b.example.com", plus_parts: 1],
+            want: Ok(value!({
+                etld: "com",
+                etld_plus: "example.com",
+                known_suffix: true,
+                registered_domain: "example.com",
+                subdomain: "a.b",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        plus_parts_overflow {
+            args: func_args![value: "example.com", plus_parts: 10],
+            want: Ok(value!({
+                etld: "com",
+                etld_plus: "example.com",
+                known_suffix: true,
+                registered_domain: "example.com",
+                subdomain: null,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        suffix_only {
+            args: func_args![value: "co.uk"],
+            want: Ok(value!({
+                etld: "co.uk",
+                etld_plus: "co.uk",
+                known_suffix: true,
+                registered_domain: null,
+                subdomain: null,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        unknown_suffix {
+            args: func_args![value: "vector.local"],
+            want: Ok(value!({
+                etld: "local",
+                etld_plus: "local",
+                known_suffix: false,
+                registered_domain: "vector.local",
+                subdomain: null,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        negative_plus_parts {
+            args: func_args![value: "vector.dev", plus_parts: -1],
+            want: Err("plus_parts must not be negative"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+
+    #[test]
+    fn custom_list() {
+        let list = "// ===BEGIN ICANN DOMAINS===\nexample\n// ===END ICANN DOMAINS===\n"
+            .parse::<List>()
+            .unwrap();
+
+        let result = parse_etld(value!("www.corp.example"), value!(0), Some(&list)).unwrap();
+        assert_eq!(
+            result,
+            value!({
+                etld: "example",
+                etld_plus: "example",
+                known_suffix: true,
+                registered_domain: "corp.example",
+                subdomain: "www",
+            })
+        );
+    }
+}
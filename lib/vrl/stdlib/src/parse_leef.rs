@@ -0,0 +1,283 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+const HEADER_FIELDS: [&str; 5] = [
+    "leefVersion",
+    "deviceVendor",
+    "deviceProduct",
+    "deviceVersion",
+    "eventId",
+];
+
+fn parse_leef(value: Value) -> Resolved {
+    let bytes = value.try_bytes_utf8_lossy()?;
+
+    // Anything in front of the LEEF message, such as a syslog header, is
+    // skipped.
+    let start = bytes
+        .find("LEEF:")
+        .ok_or("unable to parse LEEF: missing LEEF prefix")?;
+    let mut rest = &bytes[start + 5..];
+
+    let mut header = Vec::with_capacity(HEADER_FIELDS.len());
+    while header.len() < HEADER_FIELDS.len() {
+        let (value, remainder) = rest.split_once('|').ok_or_else(|| {
+            format!(
+                "unable to parse LEEF: expected {} header fields, found {}",
+                HEADER_FIELDS.len(),
+                header.len()
+            )
+        })?;
+        header.push(value);
+        rest = remainder;
+    }
+
+    let delimiter = match header[0].chars().next() {
+        Some('1') => '\t',
+        Some('2') => {
+            let (delimiter, attributes) = split_delimiter(rest)?;
+            rest = attributes;
+            delimiter
+        }
+        _ => return Err("unable to parse LEEF: unsupported LEEF version".into()),
+    };
+
+    let mut map = HEADER_FIELDS
+        .iter()
+        .map(|field| field.to_string())
+        .zip(header.into_iter().map(Value::from))
+        .collect::<BTreeMap<_, _>>();
+
+    for attribute in rest.split(delimiter) {
+        // Trailing line endings and empty attributes are ignored.
+        let attribute = attribute.trim_end_matches(|c: char| c == '\r' || c == '\n');
+        if attribute.is_empty() {
+            continue;
+        }
+
+        let (key, value) = attribute
+            .split_once('=')
+            .ok_or_else(|| format!("unable to parse LEEF: invalid attribute {:?}", attribute))?;
+        map.insert(key.trim().to_owned(), value.into());
+    }
+
+    Ok(map.into())
+}
+
+/// Splits the optional attribute delimiter header field of LEEF 2.0 from the
+/// attributes. The delimiter is either a single character, or its code in
+/// hex, such as `x5E` or `0x5E`. Attributes default to being tab separated.
+fn split_delimiter(input: &str) -> std::result::Result<(char, &str), String> {
+    let (field, attributes) = match input.split_once('|') {
+        Some((field, attributes)) if !field.contains('=') => (field, attributes),
+        _ => return Ok(('\t', input)),
+    };
+
+    let hex = field
+        .strip_prefix("0x")
+        .or_else(|| field.strip_prefix("0X"))
+        .or_else(|| field.strip_prefix('x'))
+        .or_else(|| field.strip_prefix('X'))
+        .filter(|hex| !hex.is_empty());
+
+    let delimiter = match hex {
+        Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+        None => {
+            let mut chars = field.chars();
+            match (chars.next(), chars.next()) {
+                (None, _) => Some('\t'),
+                (Some(c), None) => Some(c),
+                _ => None,
+            }
+        }
+    };
+
+    delimiter
+        .map(|delimiter| (delimiter, attributes))
+        .ok_or_else(|| format!("unable to parse LEEF: invalid delimiter {:?}", field))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseLeef;
+
+impl Function for ParseLeef {
+    fn identifier(&self) -> &'static str {
+        "parse_leef"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "parse LEEF 1.0 message",
+                source: r#"parse_leef!("LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=192.0.2.0\tdst=172.50.123.1\tsev=5")"#,
+                result: Ok(indoc! {r#"{
+                    "leefVersion": "1.0",
+                    "deviceVendor": "Microsoft",
+                    "deviceProduct": "MSExchange",
+                    "deviceVersion": "4.0 SP1",
+                    "eventId": "15345",
+                    "src": "192.0.2.0",
+                    "dst": "172.50.123.1",
+                    "sev": "5"
+                }"#}),
+            },
+            Example {
+                title: "parse LEEF 2.0 message with a custom delimiter",
+                source: r#"parse_leef!("LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^sev=5")"#,
+                result: Ok(indoc! {r#"{
+                    "leefVersion": "2.0",
+                    "deviceVendor": "Lancope",
+                    "deviceProduct": "StealthWatch",
+                    "deviceVersion": "1.0",
+                    "eventId": "41",
+                    "src": "10.0.1.8",
+                    "dst": "10.0.0.5",
+                    "sev": "5"
+                }"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ParseLeefFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseLeefFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ParseLeefFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_leef(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> Collection<Field> {
+    let mut collection = Collection::from(
+        HEADER_FIELDS
+            .iter()
+            .map(|field| (Field::from(*field), Kind::bytes()))
+            .collect::<BTreeMap<_, _>>(),
+    );
+    collection.set_unknown(Kind::bytes());
+    collection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_leef => ParseLeef;
+
+        leef_1 {
+            args: func_args![value: "<13>Jan 18 11:07:53 host LEEF:1.0|IBM|QRadar|7.3|Login|usrName=alice\tsrc=10.0.0.1\tdevTime=Jan 18 2022 11:07:53\n"],
+            want: Ok(value!({
+                leefVersion: "1.0",
+                deviceVendor: "IBM",
+                deviceProduct: "QRadar",
+                deviceVersion: "7.3",
+                eventId: "Login",
+                usrName: "alice",
+                src: "10.0.0.1",
+                devTime: "Jan 18 2022 11:07:53",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        leef_1_header_only {
+            args: func_args![value: "LEEF:1.0|IBM|QRadar|7.3|Login|"],
+            want: Ok(value!({
+                leefVersion: "1.0",
+                deviceVendor: "IBM",
+                deviceProduct: "QRadar",
+                deviceVersion: "7.3",
+                eventId: "Login",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        leef_2_hex_delimiter {
+            args: func_args![value: "LEEF:2.0|Vendor|Product|1.0|100|x7C|src=10.0.0.1|msg=a=b"],
+            want: Ok(value!({
+                leefVersion: "2.0",
+                deviceVendor: "Vendor",
+                deviceProduct: "Product",
+                deviceVersion: "1.0",
+                eventId: "100",
+                src: "10.0.0.1",
+                msg: "a=b",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        leef_2_default_delimiter {
+            args: func_args![value: "LEEF:2.0|Vendor|Product|1.0|100|src=10.0.0.1\tdst=10.0.0.2"],
+            want: Ok(value!({
+                leefVersion: "2.0",
+                deviceVendor: "Vendor",
+                deviceProduct: "Product",
+                deviceVersion: "1.0",
+                eventId: "100",
+                src: "10.0.0.1",
+                dst: "10.0.0.2",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        missing_prefix {
+            args: func_args![value: "CEF:0|Vendor|Product|1.0|100|Name|3|"],
+            want: Err("unable to parse LEEF: missing LEEF prefix"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        missing_header_fields {
+            args: func_args![value: "LEEF:1.0|Vendor|Product"],
+            want: Err("unable to parse LEEF: expected 5 header fields, found 2"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        unsupported_version {
+            args: func_args![value: "LEEF:3.0|Vendor|Product|1.0|100|"],
+            want: Err("unable to parse LEEF: unsupported LEEF version"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_delimiter {
+            args: func_args![value: "LEEF:2.0|Vendor|Product|1.0|100|xZZ|src=10.0.0.1"],
+            want: Err(r#"unable to parse LEEF: invalid delimiter "xZZ""#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_attribute {
+            args: func_args![value: "LEEF:1.0|Vendor|Product|1.0|100|src"],
+            want: Err(r#"unable to parse LEEF: invalid attribute "src""#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
@@ -0,0 +1,140 @@
+use ::value::Value;
+use rmpv::Value as MsgpackValue;
+use vrl::prelude::*;
+
+use crate::util::binary_format_type_def;
+
+fn parse_msgpack(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let value = rmpv::decode::read_value(&mut &bytes[..])
+        .map_err(|e| format!("unable to parse msgpack: {}", e))?;
+
+    convert(value).map_err(|e| format!("unable to parse msgpack: {}", e).into())
+}
+
+fn convert(value: MsgpackValue) -> std::result::Result<Value, String> {
+    Ok(match value {
+        MsgpackValue::Nil => Value::Null,
+        MsgpackValue::Boolean(b) => b.into(),
+        MsgpackValue::Integer(i) => i
+            .as_i64()
+            .ok_or_else(|| format!("integer {} is out of range", i))?
+            .into(),
+        MsgpackValue::F32(f) => Value::from_f64_or_zero(f64::from(f)),
+        MsgpackValue::F64(f) => Value::from_f64_or_zero(f),
+        MsgpackValue::String(s) => Value::Bytes(Bytes::copy_from_slice(s.as_bytes())),
+        MsgpackValue::Binary(bytes) => Value::Bytes(bytes.into()),
+        MsgpackValue::Array(array) => array
+            .into_iter()
+            .map(convert)
+            .collect::<std::result::Result<Vec<_>, _>>()?
+            .into(),
+        MsgpackValue::Map(map) => map
+            .into_iter()
+            .map(|(key, value)| Ok((convert_key(key)?, convert(value)?)))
+            .collect::<std::result::Result<BTreeMap<_, _>, String>>()?
+            .into(),
+        // Extension types are application-defined, so only their data is
+        // kept.
+        MsgpackValue::Ext(_, data) => Value::Bytes(data.into()),
+    })
+}
+
+fn convert_key(key: MsgpackValue) -> std::result::Result<String, String> {
+    match key {
+        MsgpackValue::String(s) => Ok(String::from_utf8_lossy(s.as_bytes()).into_owned()),
+        MsgpackValue::Integer(i) => Ok(i.to_string()),
+        MsgpackValue::Binary(bytes) => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+        _ => Err("map keys must be strings, integers or bytes".to_owned()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseMsgpack;
+
+impl Function for ParseMsgpack {
+    fn identifier(&self) -> &'static str {
+        "parse_msgpack"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "object",
+            source: r#"parse_msgpack!(decode_base16!("82a16101a16292c3c0"))"#,
+            result: Ok(r#"{ "a": 1, "b": [true, null] }"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ParseMsgpackFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseMsgpackFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ParseMsgpackFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_msgpack(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        binary_format_type_def()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(bytes: &'static [u8]) -> Value {
+        Value::Bytes(Bytes::from_static(bytes))
+    }
+
+    test_function![
+        parse_msgpack => ParseMsgpack;
+
+        object {
+            args: func_args![value: bytes(b"\x82\xa1a\x01\xa1b\x92\xc3\xc0")],
+            want: Ok(value!({ "a": 1, "b": [true, null] })),
+            tdef: binary_format_type_def(),
+        }
+
+        scalars {
+            args: func_args![value: bytes(b"\x94\xff\xcb\x3f\xf8\x00\x00\x00\x00\x00\x00\xa3abc\xc4\x02\x01\x02")],
+            want: Ok(value!([-1, 1.5, "abc", "\u{1}\u{2}"])),
+            tdef: binary_format_type_def(),
+        }
+
+        integer_keys {
+            args: func_args![value: bytes(b"\x81\x01\xa1a")],
+            want: Ok(value!({ "1": "a" })),
+            tdef: binary_format_type_def(),
+        }
+
+        out_of_range {
+            args: func_args![value: bytes(b"\xcf\xff\xff\xff\xff\xff\xff\xff\xff")],
+            want: Err("unable to parse msgpack: integer 18446744073709551615 is out of range"),
+            tdef: binary_format_type_def(),
+        }
+    ];
+}
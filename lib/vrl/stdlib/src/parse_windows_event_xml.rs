@@ -0,0 +1,417 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use chrono::{DateTime, Utc};
+use roxmltree::{Document, Node};
+use vrl::prelude::*;
+
+fn parse_windows_event_xml(value: Value) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    let doc =
+        Document::parse(&string).map_err(|e| format!("unable to parse Windows event: {}", e))?;
+
+    let event = doc.root_element();
+    if event.tag_name().name() != "Event" {
+        return Err("unable to parse Windows event: missing Event element".into());
+    }
+
+    let system =
+        child(event, "System").ok_or("unable to parse Windows event: missing System element")?;
+
+    let mut map = BTreeMap::new();
+    for node in system.children().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "Provider" => {
+                insert_attribute(&mut map, "provider_name", node, "Name");
+                insert_attribute(&mut map, "provider_guid", node, "Guid");
+                insert_attribute(&mut map, "event_source_name", node, "EventSourceName");
+            }
+            "EventID" => {
+                insert_integer(&mut map, "event_id", Some(text(node)))?;
+                insert_integer(&mut map, "qualifiers", node.attribute("Qualifiers"))?;
+            }
+            "Version" => insert_integer(&mut map, "version", Some(text(node)))?,
+            "Level" => insert_integer(&mut map, "level", Some(text(node)))?,
+            "Task" => insert_integer(&mut map, "task", Some(text(node)))?,
+            "Opcode" => insert_integer(&mut map, "opcode", Some(text(node)))?,
+            "Keywords" => {
+                map.insert("keywords".to_owned(), text(node).into());
+            }
+            "TimeCreated" => {
+                if let Some(time) = node.attribute("SystemTime") {
+                    let timestamp = DateTime::parse_from_rfc3339(time).map_err(|_| {
+                        format!(
+                            "unable to parse Windows event: invalid SystemTime {:?}",
+                            time
+                        )
+                    })?;
+                    map.insert(
+                        "time_created".to_owned(),
+                        timestamp.with_timezone(&Utc).into(),
+                    );
+                }
+            }
+            "EventRecordID" => insert_integer(&mut map, "record_id", Some(text(node)))?,
+            "Correlation" => {
+                insert_attribute(&mut map, "activity_id", node, "ActivityID");
+                insert_attribute(&mut map, "related_activity_id", node, "RelatedActivityID");
+            }
+            "Execution" => {
+                insert_integer(&mut map, "process_id", node.attribute("ProcessID"))?;
+                insert_integer(&mut map, "thread_id", node.attribute("ThreadID"))?;
+            }
+            "Channel" => {
+                map.insert("channel".to_owned(), text(node).into());
+            }
+            "Computer" => {
+                map.insert("computer".to_owned(), text(node).into());
+            }
+            "Security" => insert_attribute(&mut map, "user_id", node, "UserID"),
+            _ => {}
+        }
+    }
+
+    if let Some(event_data) = child(event, "EventData") {
+        map.insert("event_data".to_owned(), parse_event_data(event_data));
+    }
+
+    if let Some(user_data) = child(event, "UserData") {
+        map.insert("user_data".to_owned(), element_value(user_data));
+    }
+
+    if let Some(message) = child(event, "RenderingInfo").and_then(|node| child(node, "Message")) {
+        map.insert("message".to_owned(), text(message).into());
+    }
+
+    Ok(map.into())
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|child| child.is_element() && child.tag_name().name() == name)
+}
+
+fn text<'a>(node: Node<'a, '_>) -> &'a str {
+    node.text().unwrap_or_default().trim()
+}
+
+fn insert_attribute(map: &mut BTreeMap<String, Value>, key: &str, node: Node, attribute: &str) {
+    if let Some(value) = node.attribute(attribute) {
+        map.insert(key.to_owned(), value.into());
+    }
+}
+
+fn insert_integer(
+    map: &mut BTreeMap<String, Value>,
+    key: &str,
+    value: Option<&str>,
+) -> std::result::Result<(), String> {
+    if let Some(value) = value {
+        let integer = value
+            .parse::<i64>()
+            .map_err(|_| format!("unable to parse Windows event: invalid {} {:?}", key, value))?;
+        map.insert(key.to_owned(), integer.into());
+    }
+
+    Ok(())
+}
+
+/// Collects the `<Data Name="...">` elements of the event data into an object.
+/// Unnamed elements are keyed by their position, as `param1`, `param2`, and so
+/// on.
+fn parse_event_data(node: Node) -> Value {
+    let mut map = BTreeMap::new();
+    let mut unnamed = 0;
+
+    for node in node.children().filter(Node::is_element) {
+        let key = match (node.tag_name().name(), node.attribute("Name")) {
+            ("Data", Some(name)) => name.to_owned(),
+            ("Data", None) => {
+                unnamed += 1;
+                format!("param{}", unnamed)
+            }
+            (name, _) => name.to_owned(),
+        };
+        map.insert(key, Value::from(text(node)));
+    }
+
+    map.into()
+}
+
+/// Converts an element into an object of its child elements, or its text if
+/// it has none.
+fn element_value(node: Node) -> Value {
+    let mut children = node.children().filter(Node::is_element).peekable();
+    if children.peek().is_none() {
+        return text(node).into();
+    }
+
+    children
+        .map(|child| (child.tag_name().name().to_owned(), element_value(child)))
+        .collect::<BTreeMap<_, _>>()
+        .into()
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseWindowsEventXml;
+
+impl Function for ParseWindowsEventXml {
+    fn identifier(&self) -> &'static str {
+        "parse_windows_event_xml"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse forwarded event",
+            source: r#"parse_windows_event_xml!(s'<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event"><System><Provider Name="Microsoft-Windows-Security-Auditing" Guid="{54849625-5478-4994-a5ba-3e3b0328c30d}"/><EventID>4625</EventID><Version>0</Version><Level>0</Level><Task>12544</Task><Opcode>0</Opcode><Keywords>0x8010000000000000</Keywords><TimeCreated SystemTime="2022-03-14T09:21:07.5461529Z"/><EventRecordID>188921</EventRecordID><Execution ProcessID="652" ThreadID="4308"/><Channel>Security</Channel><Computer>dc01.example.com</Computer><Security/></System><EventData><Data Name="TargetUserName">alice</Data><Data Name="IpAddress">192.0.2.15</Data></EventData></Event>')"#,
+            result: Ok(indoc! {r#"{
+                "channel": "Security",
+                "computer": "dc01.example.com",
+                "event_data": {
+                    "IpAddress": "192.0.2.15",
+                    "TargetUserName": "alice"
+                },
+                "event_id": 4625,
+                "keywords": "0x8010000000000000",
+                "level": 0,
+                "opcode": 0,
+                "process_id": 652,
+                "provider_guid": "{54849625-5478-4994-a5ba-3e3b0328c30d}",
+                "provider_name": "Microsoft-Windows-Security-Auditing",
+                "record_id": 188921,
+                "task": 12544,
+                "thread_id": 4308,
+                "time_created": "2022-03-14T09:21:07.546152900Z",
+                "version": 0
+            }"#}),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ParseWindowsEventXmlFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseWindowsEventXmlFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ParseWindowsEventXmlFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_windows_event_xml(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        (Field::from("activity_id"), Kind::bytes() | Kind::null()),
+        (Field::from("channel"), Kind::bytes() | Kind::null()),
+        (Field::from("computer"), Kind::bytes() | Kind::null()),
+        (
+            Field::from("event_data"),
+            Kind::object(Collection::from_unknown(Kind::bytes())) | Kind::null(),
+        ),
+        (Field::from("event_id"), Kind::integer() | Kind::null()),
+        (
+            Field::from("event_source_name"),
+            Kind::bytes() | Kind::null(),
+        ),
+        (Field::from("keywords"), Kind::bytes() | Kind::null()),
+        (Field::from("level"), Kind::integer() | Kind::null()),
+        (Field::from("message"), Kind::bytes() | Kind::null()),
+        (Field::from("opcode"), Kind::integer() | Kind::null()),
+        (Field::from("process_id"), Kind::integer() | Kind::null()),
+        (Field::from("provider_guid"), Kind::bytes() | Kind::null()),
+        (Field::from("provider_name"), Kind::bytes() | Kind::null()),
+        (Field::from("qualifiers"), Kind::integer() | Kind::null()),
+        (Field::from("record_id"), Kind::integer() | Kind::null()),
+        (
+            Field::from("related_activity_id"),
+            Kind::bytes() | Kind::null(),
+        ),
+        (Field::from("task"), Kind::integer() | Kind::null()),
+        (Field::from("thread_id"), Kind::integer() | Kind::null()),
+        (
+            Field::from("time_created"),
+            Kind::timestamp() | Kind::null(),
+        ),
+        (
+            Field::from("user_data"),
+            Kind::object(Collection::any()) | Kind::null(),
+        ),
+        (Field::from("user_id"), Kind::bytes() | Kind::null()),
+        (Field::from("version"), Kind::integer() | Kind::null()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    test_function![
+        parse_windows_event_xml => ParseWindowsEventXml;
+
+        security_event {
+            args: func_args![value: indoc! {r#"
+                <Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+                  <System>
+                    <Provider Name="Microsoft-Windows-Security-Auditing" Guid="{54849625-5478-4994-a5ba-3e3b0328c30d}"/>
+                    <EventID>4624</EventID>
+                    <Version>2</Version>
+                    <Level>0</Level>
+                    <Task>12544</Task>
+                    <Opcode>0</Opcode>
+                    <Keywords>0x8020000000000000</Keywords>
+                    <TimeCreated SystemTime="2022-03-14T09:21:07Z"/>
+                    <EventRecordID>188922</EventRecordID>
+                    <Correlation ActivityID="{8a3b4c2d-0001-0000-0000-000000000000}"/>
+                    <Execution ProcessID="652" ThreadID="716"/>
+                    <Channel>Security</Channel>
+                    <Computer>dc01.example.com</Computer>
+                    <Security UserID="S-1-5-18"/>
+                  </System>
+                  <EventData>
+                    <Data Name="TargetUserName">alice</Data>
+                    <Data Name="LogonType">3</Data>
+                    <Data Name="WorkstationName"/>
+                  </EventData>
+                  <RenderingInfo Culture="en-US">
+                    <Message>An account was successfully logged on.</Message>
+                  </RenderingInfo>
+                </Event>
+            "#}],
+            want: Ok(value!({
+                activity_id: "{8a3b4c2d-0001-0000-0000-000000000000}",
+                channel: "Security",
+                computer: "dc01.example.com",
+                event_data: {
+                    LogonType: "3",
+                    TargetUserName: "alice",
+                    WorkstationName: "",
+                },
+                event_id: 4624,
+                keywords: "0x8020000000000000",
+                level: 0,
+                message: "An account was successfully logged on.",
+                opcode: 0,
+                process_id: 652,
+                provider_guid: "{54849625-5478-4994-a5ba-3e3b0328c30d}",
+                provider_name: "Microsoft-Windows-Security-Auditing",
+                record_id: 188922,
+                task: 12544,
+                thread_id: 716,
+                time_created: (Utc.ymd(2022, 3, 14).and_hms(9, 21, 7)),
+                user_id: "S-1-5-18",
+                version: 2,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        classic_event {
+            args: func_args![value: indoc! {r#"
+                <Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+                  <System>
+                    <Provider Name="Application Error"/>
+                    <EventID Qualifiers="0">1000</EventID>
+                    <Level>2</Level>
+                    <Task>100</Task>
+                    <Keywords>0x80000000000000</Keywords>
+                    <EventRecordID>5110</EventRecordID>
+                    <Channel>Application</Channel>
+                    <Computer>ws01</Computer>
+                  </System>
+                  <EventData>
+                    <Data>app.exe</Data>
+                    <Data>1.0.0.0</Data>
+                    <Binary>00FF</Binary>
+                  </EventData>
+                </Event>
+            "#}],
+            want: Ok(value!({
+                channel: "Application",
+                computer: "ws01",
+                event_data: {
+                    Binary: "00FF",
+                    param1: "app.exe",
+                    param2: "1.0.0.0",
+                },
+                event_id: 1000,
+                keywords: "0x80000000000000",
+                level: 2,
+                provider_name: "Application Error",
+                qualifiers: 0,
+                record_id: 5110,
+                task: 100,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        user_data {
+            args: func_args![value: indoc! {r#"
+                <Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+                  <System>
+                    <EventID>1102</EventID>
+                  </System>
+                  <UserData>
+                    <LogFileCleared xmlns="http://manifests.microsoft.com/win/2004/08/windows/eventlog">
+                      <SubjectUserName>admin</SubjectUserName>
+                      <SubjectDomainName>EXAMPLE</SubjectDomainName>
+                    </LogFileCleared>
+                  </UserData>
+                </Event>
+            "#}],
+            want: Ok(value!({
+                event_id: 1102,
+                user_data: {
+                    LogFileCleared: {
+                        SubjectDomainName: "EXAMPLE",
+                        SubjectUserName: "admin",
+                    },
+                },
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_event_id {
+            args: func_args![value: "<Event><System><EventID>abc</EventID></System></Event>"],
+            want: Err(r#"unable to parse Windows event: invalid event_id "abc""#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        missing_system {
+            args: func_args![value: "<Event><EventData/></Event>"],
+            want: Err("unable to parse Windows event: missing System element"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        not_an_event {
+            args: func_args![value: "<Events/>"],
+            want: Err("unable to parse Windows event: missing Event element"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
@@ -0,0 +1,168 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn reduce<T>(
+    value: Value,
+    initial: Value,
+    ctx: &mut Context,
+    runner: closure::Runner<T>,
+) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    let items = match value {
+        Value::Object(object) => object.into_values().collect(),
+        Value::Array(array) => array,
+        _ => unreachable!("expected object or array"),
+    };
+
+    let mut accumulator = initial;
+    for item in items {
+        accumulator = runner.run_values(ctx, vec![accumulator, item])?;
+    }
+
+    Ok(accumulator)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Reduce;
+
+impl Function for Reduce {
+    fn identifier(&self) -> &'static str {
+        "reduce"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT | kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "initial",
+                kind: kind::ANY,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "sum array",
+                source: r#"reduce([1, 2, 3], 0) -> |sum, value| { sum + value }"#,
+                result: Ok("6"),
+            },
+            Example {
+                title: "reduce object values",
+                source: r#"reduce({ "a": "x", "b": "y" }, "") -> |joined, value| { joined + value }"#,
+                result: Ok(r#""xy""#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let initial = arguments.required("initial");
+        let closure = arguments.required_closure()?;
+
+        Ok(Box::new(ReduceFn {
+            value,
+            initial,
+            closure,
+        }))
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::object(Collection::any()).or_array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::Parameter("initial"),
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::any()),
+                example: Example {
+                    title: "sum array",
+                    source: r#"reduce([1, 2, 3], 0) -> |sum, value| { sum + value }"#,
+                    result: Ok("6"),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ReduceFn {
+    value: Box<dyn Expression>,
+    initial: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for ReduceFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let initial = self.initial.resolve(ctx)?;
+        let FunctionClosure { variables, block } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        reduce(value, initial, ctx, runner)
+    }
+
+    fn type_def(&self, ctx: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        let closure = self.closure.block.type_def(ctx);
+        let fallible = closure.is_fallible();
+
+        // The value is either the initial value, for an empty collection, or
+        // the result of the last run of the closure.
+        self.initial
+            .type_def(ctx)
+            .merge_deep(closure)
+            .with_fallibility(fallible)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::run_program;
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            run_program("reduce([], 0) -> |sum, _value| { sum + 1 }"),
+            Ok(value!(0))
+        );
+    }
+
+    #[test]
+    fn closure_type_error() {
+        assert_eq!(
+            run_program(r#"reduce([1, "a"], 0) -> |sum, value| { sum + value }"#),
+            Err("unhandled error".to_owned())
+        );
+    }
+
+    #[test]
+    fn closure_error() {
+        let err =
+            run_program(r#"reduce(["a", 1], 0) -> |sum, value| { sum + length(string!(value)) }"#)
+                .unwrap_err();
+
+        assert!(err.contains("expected string, got integer"), "{}", err);
+    }
+}
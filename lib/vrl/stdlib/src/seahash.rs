@@ -0,0 +1,83 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn seahash(value: Value) -> Resolved {
+    let value = value.try_bytes()?;
+
+    // The hash is an unsigned 64-bit integer, reinterpreted as a signed one
+    // to fit in a VRL integer.
+    Ok((seahash::hash(&value) as i64).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Seahash;
+
+impl Function for Seahash {
+    fn identifier(&self) -> &'static str {
+        "seahash"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "seahash",
+            source: r#"seahash("foobar")"#,
+            result: Ok("5348458858952426560"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(SeahashFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SeahashFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for SeahashFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        seahash(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        seahash => Seahash;
+
+        seahash {
+            args: func_args![value: "to be or not to be"],
+            want: Ok(value!(1988685042348123509_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        negative {
+            args: func_args![value: "The quick brown fox jumps over the lazy dog"],
+            want: Ok(value!(-5347679245320120724_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+    ];
+}
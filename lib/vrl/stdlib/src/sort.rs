@@ -0,0 +1,125 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::compare_values;
+
+fn sort(value: Value, reverse: Value) -> Resolved {
+    let mut array = value.try_array()?;
+    let reverse = reverse.try_boolean()?;
+
+    array.sort_by(|a, b| match reverse {
+        true => compare_values(b, a),
+        false => compare_values(a, b),
+    });
+
+    Ok(array.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sort;
+
+impl Function for Sort {
+    fn identifier(&self) -> &'static str {
+        "sort"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "reverse",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "sort numbers",
+                source: r#"sort([3, 1.5, 2])"#,
+                result: Ok("[1.5, 2, 3]"),
+            },
+            Example {
+                title: "sort strings in reverse",
+                source: r#"sort(["a", "c", "b"], reverse: true)"#,
+                result: Ok(r#"["c", "b", "a"]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let reverse = arguments.optional("reverse").unwrap_or(expr!(false));
+
+        Ok(Box::new(SortFn { value, reverse }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SortFn {
+    value: Box<dyn Expression>,
+    reverse: Box<dyn Expression>,
+}
+
+impl Expression for SortFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let reverse = self.reverse.resolve(ctx)?;
+
+        sort(value, reverse)
+    }
+
+    fn type_def(&self, state: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        // Items change position, so the type of each item is only known to be
+        // one of the types of the items of the array.
+        self.value
+            .type_def(state)
+            .restrict_array()
+            .collect_subtypes()
+            .infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        sort => Sort;
+
+        empty {
+            args: func_args![value: value!([])],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::empty()),
+        }
+
+        numbers {
+            args: func_args![value: value!([3, 1.5, -2, 2])],
+            want: Ok(value!([-2, 1.5, 2, 3])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer().or_float())),
+        }
+
+        mixed_types {
+            args: func_args![value: value!([true, "b", 1, "a"])],
+            want: Ok(value!(["a", "b", 1, true])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer().or_bytes().or_boolean())),
+        }
+
+        reverse {
+            args: func_args![value: value!(["a", "c", "b"]), reverse: true],
+            want: Ok(value!(["c", "b", "a"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+    ];
+}
@@ -0,0 +1,172 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::compare_values;
+
+fn sort_by<T>(
+    value: Value,
+    reverse: bool,
+    ctx: &mut Context,
+    runner: closure::Runner<T>,
+) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    let array = value.try_array()?;
+
+    // The closure runs once per item, rather than once per comparison.
+    let mut keyed = Vec::with_capacity(array.len());
+    for item in array {
+        let key = runner.run_values(ctx, vec![item.clone()])?;
+        keyed.push((key, item));
+    }
+
+    keyed.sort_by(|(a, _), (b, _)| match reverse {
+        true => compare_values(b, a),
+        false => compare_values(a, b),
+    });
+
+    Ok(keyed
+        .into_iter()
+        .map(|(_, item)| item)
+        .collect::<Vec<_>>()
+        .into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SortBy;
+
+impl Function for SortBy {
+    fn identifier(&self) -> &'static str {
+        "sort_by"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "reverse",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "sort objects by field",
+                source: r#"sort_by([{ "n": 2 }, { "n": 1 }]) -> |value| { value.n }"#,
+                result: Ok(r#"[{ "n": 1 }, { "n": 2 }]"#),
+            },
+            Example {
+                title: "sort strings by length in reverse",
+                source: r#"sort_by(["a", "ccc", "bb"], reverse: true) -> |value| { strlen(string!(value)) }"#,
+                result: Ok(r#"["ccc", "bb", "a"]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let reverse = arguments.optional("reverse");
+        let closure = arguments.required_closure()?;
+
+        Ok(Box::new(SortByFn {
+            value,
+            reverse,
+            closure,
+        }))
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::array(Collection::any()),
+                variables: vec![Variable {
+                    kind: VariableKind::TargetInnerValue,
+                }],
+                output: Output::Kind(Kind::any()),
+                example: Example {
+                    title: "sort objects by field",
+                    source: r#"sort_by([{ "n": 2 }, { "n": 1 }]) -> |value| { value.n }"#,
+                    result: Ok(r#"[{ "n": 1 }, { "n": 2 }]"#),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SortByFn {
+    value: Box<dyn Expression>,
+    reverse: Option<Box<dyn Expression>>,
+    closure: FunctionClosure,
+}
+
+impl Expression for SortByFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let reverse = match &self.reverse {
+            None => false,
+            Some(expr) => expr.resolve(ctx)?.try_boolean()?,
+        };
+
+        let value = self.value.resolve(ctx)?;
+        let FunctionClosure { variables, block } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        sort_by(value, reverse, ctx, runner)
+    }
+
+    fn type_def(&self, ctx: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        let fallible = self.closure.block.type_def(ctx).is_fallible();
+
+        self.value
+            .type_def(ctx)
+            .restrict_array()
+            .collect_subtypes()
+            .with_fallibility(fallible)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::run_program;
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            run_program("sort_by([]) -> |value| { value }"),
+            Ok(value!([]))
+        );
+    }
+
+    #[test]
+    fn closure_type_error() {
+        assert_eq!(
+            run_program("sort_by([1, 2]) -> |value| { upcase(value) }"),
+            Err("invalid argument type".to_owned())
+        );
+    }
+
+    #[test]
+    fn closure_error() {
+        let err = run_program(r#"sort_by(["a", 1]) -> |value| { string!(value) }"#).unwrap_err();
+
+        assert!(err.contains("expected string, got integer"), "{}", err);
+    }
+}
@@ -82,6 +82,27 @@ pub(crate) fn is_nullish(value: &::value::Value) -> bool {
     }
 }
 
+/// Orders two values for sorting.
+///
+/// Integers and floats are compared by their numeric value, other values of
+/// the same type by their natural order, and values of different types by
+/// their type, so that a sort groups them together.
+#[cfg(any(feature = "sort", feature = "sort_by"))]
+pub(crate) fn compare_values(a: &::value::Value, b: &::value::Value) -> std::cmp::Ordering {
+    use ::value::Value;
+
+    match (a, b) {
+        (Value::Integer(a), Value::Float(b)) => (*a as f64)
+            .partial_cmp(&b.into_inner())
+            .unwrap_or(std::cmp::Ordering::Equal),
+        (Value::Float(a), Value::Integer(b)) => a
+            .into_inner()
+            .partial_cmp(&(*b as f64))
+            .unwrap_or(std::cmp::Ordering::Equal),
+        _ => a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal),
+    }
+}
+
 #[cfg(any(feature = "decode_base64", feature = "encode_base64"))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Base64Charset {
@@ -122,3 +143,42 @@ impl std::str::FromStr for Base64Charset {
         }
     }
 }
+
+/// The type of a value decoded from a self-describing binary format, such as
+/// CBOR or MessagePack.
+#[cfg(any(feature = "parse_cbor", feature = "parse_msgpack"))]
+pub(crate) fn binary_format_type_def() -> vrl::prelude::TypeDef {
+    use vrl::prelude::{Collection, Kind, TypeDef};
+
+    let inner_kind = Kind::null()
+        | Kind::bytes()
+        | Kind::integer()
+        | Kind::float()
+        | Kind::boolean()
+        | Kind::array(Collection::any())
+        | Kind::object(Collection::any());
+
+    TypeDef::bytes()
+        .fallible()
+        .add_boolean()
+        .add_integer()
+        .add_float()
+        .add_null()
+        .add_array(Collection::from_unknown(inner_kind.clone()))
+        .add_object(Collection::from_unknown(inner_kind))
+}
+
+/// Compiles and runs a program with the functions of the standard library,
+/// for testing functions whose closures can't be passed to `test_function!`.
+/// Returns the result of the program, or the message of its first compilation
+/// or runtime error.
+#[cfg(test)]
+pub(crate) fn run_program(source: &str) -> Result<::value::Value, String> {
+    let (program, _) = vrl::compile(source, &crate::all())
+        .map_err(|diagnostics| diagnostics[0].message().to_owned())?;
+
+    let mut target = ::value::Value::from(std::collections::BTreeMap::new());
+    vrl::Runtime::new(vrl::state::Runtime::default())
+        .resolve(&mut target, &program, &vector_common::TimeZone::default())
+        .map_err(|err| err.to_string())
+}
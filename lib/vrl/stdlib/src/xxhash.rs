@@ -0,0 +1,179 @@
+use std::hash::Hasher;
+
+use ::value::Value;
+use twox_hash::{xxh3, XxHash32, XxHash64};
+use vrl::prelude::*;
+
+fn xxhash(value: Value, variant: &Bytes) -> Resolved {
+    let value = value.try_bytes()?;
+
+    // 64-bit hashes are reinterpreted as signed integers to fit in a VRL
+    // integer, and 128-bit hashes are returned as strings.
+    let hash = match variant.as_ref() {
+        b"XXH32" => Value::from(hash_with::<XxHash32>(&value) as i64),
+        b"XXH64" => Value::from(hash_with::<XxHash64>(&value) as i64),
+        b"XXH3-64" => Value::from(xxh3::hash64(&value) as i64),
+        b"XXH3-128" => Value::from(xxh3::hash128(&value).to_string()),
+        _ => unreachable!("enum invariant"),
+    };
+
+    Ok(hash)
+}
+
+#[inline]
+fn hash_with<T: Hasher + Default>(value: &[u8]) -> u64 {
+    let mut hasher = T::default();
+    hasher.write(value);
+    hasher.finish()
+}
+
+fn variants() -> Vec<Value> {
+    vec![
+        value!("XXH32"),
+        value!("XXH64"),
+        value!("XXH3-64"),
+        value!("XXH3-128"),
+    ]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Xxhash;
+
+impl Function for Xxhash {
+    fn identifier(&self) -> &'static str {
+        "xxhash"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "variant",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "default variant",
+                source: r#"xxhash("foobar")"#,
+                result: Ok("3986901679"),
+            },
+            Example {
+                title: "64-bit variant",
+                source: r#"xxhash("foobar", "XXH64")"#,
+                result: Ok("-6725556575634347271"),
+            },
+            Example {
+                title: "128-bit variant",
+                source: r#"xxhash("", "XXH3-128")"#,
+                result: Ok(r#""204254712233039002205064565430793619839""#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let variant = arguments
+            .optional_enum("variant", &variants())?
+            .unwrap_or_else(|| value!("XXH32"))
+            .try_bytes()
+            .expect("variant not bytes");
+
+        Ok(Box::new(XxhashFn { value, variant }))
+    }
+
+    fn compile_argument(
+        &self,
+        _args: &[(&'static str, Option<FunctionArgument>)],
+        _ctx: &mut FunctionCompileContext,
+        name: &str,
+        expr: Option<&expression::Expr>,
+    ) -> CompiledArgument {
+        match (name, expr) {
+            ("variant", Some(expr)) => {
+                let variant = expr
+                    .as_enum("variant", variants())?
+                    .try_bytes()
+                    .expect("variant not bytes");
+
+                Ok(Some(Box::new(variant) as _))
+            }
+            ("variant", None) => Ok(Some(Box::new(Bytes::from("XXH32")) as _)),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct XxhashFn {
+    value: Box<dyn Expression>,
+    variant: Bytes,
+}
+
+impl Expression for XxhashFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let variant = &self.variant;
+
+        xxhash(value, variant)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        match self.variant.as_ref() {
+            b"XXH3-128" => TypeDef::bytes().infallible(),
+            _ => TypeDef::integer().infallible(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        xxhash => Xxhash;
+
+        xxh32 {
+            args: func_args![value: "The quick brown fox jumps over the lazy dog"],
+            want: Ok(value!(3898516702_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        xxh64 {
+            args: func_args![value: "The quick brown fox jumps over the lazy dog", variant: "XXH64"],
+            want: Ok(value!(802816344064684476_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        xxh64_negative {
+            args: func_args![value: "foobar", variant: "XXH64"],
+            want: Ok(value!(-6725556575634347271_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        xxh3_64 {
+            args: func_args![value: "", variant: "XXH3-64"],
+            want: Ok(value!(3244421341483603138_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        xxh3_128 {
+            args: func_args![value: "", variant: "XXH3-128"],
+            want: Ok(value!("204254712233039002205064565430793619839")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
@@ -0,0 +1,175 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn zip(array_0: Value, array_1: Option<Value>) -> Resolved {
+    let arrays = match array_1 {
+        Some(array_1) => vec![array_0.try_array()?, array_1.try_array()?],
+        None => array_0
+            .try_array()?
+            .into_iter()
+            .map(VrlValueConvert::try_array)
+            .collect::<std::result::Result<Vec<_>, _>>()?,
+    };
+
+    let len = arrays.iter().map(Vec::len).min().unwrap_or(0);
+    let mut iters = arrays.into_iter().map(Vec::into_iter).collect::<Vec<_>>();
+
+    let zipped = (0..len)
+        .map(|_| {
+            iters
+                .iter_mut()
+                .map(|iter| iter.next().expect("shorter than the shortest array"))
+                .collect::<Vec<_>>()
+                .into()
+        })
+        .collect::<Vec<Value>>();
+
+    Ok(zipped.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Zip;
+
+impl Function for Zip {
+    fn identifier(&self) -> &'static str {
+        "zip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "array_0",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "array_1",
+                kind: kind::ARRAY,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "zip two arrays",
+                source: r#"zip([1, 2, 3], ["a", "b", "c"])"#,
+                result: Ok(r#"[[1, "a"], [2, "b"], [3, "c"]]"#),
+            },
+            Example {
+                title: "zip an array of arrays",
+                source: r#"zip([[1, 2], ["a", "b"], [true, false, null]])"#,
+                result: Ok(r#"[[1, "a", true], [2, "b", false]]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let array_0 = arguments.required("array_0");
+        let array_1 = arguments.optional("array_1");
+
+        Ok(Box::new(ZipFn { array_0, array_1 }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ZipFn {
+    array_0: Box<dyn Expression>,
+    array_1: Option<Box<dyn Expression>>,
+}
+
+impl Expression for ZipFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let array_0 = self.array_0.resolve(ctx)?;
+        let array_1 = self
+            .array_1
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        zip(array_0, array_1)
+    }
+
+    fn type_def(&self, state: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        let items = |expr: &dyn Expression| {
+            expr.type_def(state)
+                .restrict_array()
+                .as_array()
+                .map(Collection::reduced_kind)
+                .unwrap_or_else(Kind::any)
+        };
+
+        match &self.array_1 {
+            // Each pair holds an item of the first array, then one of the
+            // second.
+            Some(array_1) => {
+                let pair = BTreeMap::from([
+                    (0.into(), items(self.array_0.as_ref())),
+                    (1.into(), items(array_1.as_ref())),
+                ]);
+
+                TypeDef::array(Collection::from_unknown(Kind::array(pair))).infallible()
+            }
+
+            // Each group holds an item of any of the nested arrays, and fails
+            // if any of the items of the array isn't an array.
+            None => {
+                let arrays = items(self.array_0.as_ref());
+                let group = arrays
+                    .as_array()
+                    .map(Collection::reduced_kind)
+                    .unwrap_or_else(Kind::never);
+                let type_def = TypeDef::array(Collection::from_unknown(Kind::array(
+                    Collection::from_unknown(group),
+                )));
+
+                match arrays.is_never() || arrays.is_array() {
+                    true => type_def.infallible(),
+                    false => type_def.fallible(),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        zip => Zip;
+
+        two_arrays {
+            args: func_args![array_0: value!([1, 2, 3]), array_1: value!(["a", "b"])],
+            want: Ok(value!([[1, "a"], [2, "b"]])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::array(BTreeMap::from([
+                (0.into(), Kind::integer()),
+                (1.into(), Kind::bytes()),
+            ])))),
+        }
+
+        array_of_arrays {
+            args: func_args![array_0: value!([[1, 2], ["a", "b", "c"]])],
+            want: Ok(value!([[1, "a"], [2, "b"]])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::array(Collection::from_unknown(Kind::integer().or_bytes())))),
+        }
+
+        not_an_array_of_arrays {
+            args: func_args![array_0: value!([[1, 2], "a"])],
+            want: Err("expected array, got string"),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::array(Collection::from_unknown(Kind::integer())))).fallible(),
+        }
+
+        empty {
+            args: func_args![array_0: value!([])],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::array(Collection::from_unknown(Kind::never())))),
+        }
+    ];
+}
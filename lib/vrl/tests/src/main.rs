@@ -411,6 +411,11 @@ fn run_vrl(
             test_enrichment.finish_load();
             runtime.resolve(&mut target, &program, &timezone)
         }
+        VrlRuntime::Vm => {
+            let vm = program.compile_to_vm();
+            test_enrichment.finish_load();
+            runtime.run_vm(&mut target, &vm, &timezone)
+        }
     }
 }
 
@@ -113,6 +113,22 @@ fn benchmark_vrl_runtimes(c: &mut Criterion) {
                 },
             )
         });
+
+        let vm = program.compile_to_vm();
+        group.bench_with_input(BenchmarkId::new(source.name, "vm"), &(), |b, _| {
+            let state = state::Runtime::default();
+            let mut runtime = Runtime::new(state);
+            let target: Value = serde_json::from_str(source.target).expect("valid json");
+
+            b.iter_with_setup(
+                || target.clone(),
+                |mut obj| {
+                    let _ = black_box(runtime.run_vm(&mut obj, &vm, &tz));
+                    runtime.clear();
+                    obj
+                },
+            )
+        });
     }
 }
 
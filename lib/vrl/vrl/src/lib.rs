@@ -12,7 +12,7 @@ mod runtime;
 
 pub use compiler::{
     function, state, value, Context, Expression, Function, MetadataTarget, Program, ProgramInfo,
-    SecretTarget, Target, TargetValue, TargetValueRef, VrlRuntime,
+    SecretTarget, Target, TargetValue, TargetValueRef, Vm, VrlRuntime,
 };
 pub use diagnostic;
 pub use runtime::{Runtime, RuntimeResult, Terminate};
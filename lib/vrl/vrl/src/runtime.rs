@@ -5,7 +5,7 @@ use lookup::LookupBuf;
 use value::Value;
 use vector_common::TimeZone;
 
-use crate::{state, Context, Program, Target};
+use crate::{state, Context, Program, Target, Vm};
 
 pub type RuntimeResult = Result<Value, Terminate>;
 
@@ -73,27 +73,52 @@ impl Runtime {
         program: &Program,
         timezone: &TimeZone,
     ) -> RuntimeResult {
-        // Validate that the path is a value.
-        match target.target_get(&self.root_lookup) {
-            Ok(Some(_)) => {}
-            Ok(None) => {
-                return Err(Terminate::Error(
-                    "expected target object, got nothing".to_owned().into(),
-                ))
-            }
-            Err(err) => {
-                return Err(Terminate::Error(
-                    format!("error querying target object: {}", err).into(),
-                ))
-            }
-        };
+        self.validate_target(target)?;
 
         let mut ctx = Context::new(target, &mut self.state, timezone);
 
-        program.resolve(&mut ctx).map_err(|err| match err {
-            #[cfg(feature = "expr-abort")]
-            ExpressionError::Abort { .. } => Terminate::Abort(err),
-            err @ ExpressionError::Error { .. } => Terminate::Error(err),
-        })
+        program.resolve(&mut ctx).map_err(terminate)
+    }
+
+    /// Given the provided [`Target`], run the provided [`Vm`] to completion.
+    ///
+    /// The [`Vm`] is compiled once from a [`Program`], using
+    /// [`Program::compile_to_vm`].
+    pub fn run_vm(
+        &mut self,
+        target: &mut dyn Target,
+        vm: &Vm,
+        timezone: &TimeZone,
+    ) -> RuntimeResult {
+        self.validate_target(target)?;
+
+        let mut ctx = Context::new(target, &mut self.state, timezone);
+
+        vm.run(&mut ctx).map_err(terminate)
+    }
+
+    /// Validate that the root path of the target is a value.
+    fn validate_target(&self, target: &dyn Target) -> Result<(), Terminate> {
+        match target.target_get(&self.root_lookup) {
+            Ok(Some(_)) => Ok(()),
+            Ok(None) => Err(Terminate::Error(
+                "expected target object, got nothing".to_owned().into(),
+            )),
+            Err(err) => Err(Terminate::Error(
+                format!("error querying target object: {}", err).into(),
+            )),
+        }
+    }
+}
+
+fn terminate(err: ExpressionError) -> Terminate {
+    match err {
+        #[cfg(feature = "expr-abort")]
+        ExpressionError::Abort { .. } => Terminate::Abort(err),
+        #[cfg(feature = "expr-loop")]
+        err @ (ExpressionError::Break { .. } | ExpressionError::Continue { .. }) => {
+            Terminate::Error(err)
+        }
+        err @ ExpressionError::Error { .. } => Terminate::Error(err),
     }
 }
@@ -112,19 +112,45 @@ inventory::collect!(ConditionDescription);
 /// This will result in an error when serializing to json
 /// which we need to do when determining which transforms have changed
 /// when a config is reloaded.
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone)]
 #[serde(untagged)]
 pub enum AnyCondition {
     String(String),
     Map(Box<dyn ConditionConfig>),
 }
 
+impl<'de> Deserialize<'de> for AnyCondition {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            String(String),
+            Map(Box<dyn ConditionConfig>),
+        }
+
+        // Raw strings are deserialized as VRL conditions here, so that they know the directory
+        // of the config defining them to resolve imports against.
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::String(source) => AnyCondition::Map(Box::new(VrlConfig {
+                source,
+                runtime: Default::default(),
+                config_dir: crate::config::current_config_dir(),
+            })),
+            Repr::Map(config) => AnyCondition::Map(config),
+        })
+    }
+}
+
 impl AnyCondition {
     pub fn build(&self, enrichment_tables: &enrichment::TableRegistry) -> crate::Result<Condition> {
         match self {
             AnyCondition::String(s) => VrlConfig {
                 source: s.clone(),
                 runtime: Default::default(),
+                config_dir: None,
             }
             .build(enrichment_tables),
             AnyCondition::Map(m) => m.build(enrichment_tables),
@@ -147,7 +173,7 @@ mod tests {
     fn deserialize_anycondition_default() {
         let conf: Test = toml::from_str(r#"condition = ".nork == false""#).unwrap();
         assert_eq!(
-            r#"String(".nork == false")"#,
+            r#"Map(VrlConfig { source: ".nork == false", runtime: Ast, config_dir: None })"#,
             format!("{:?}", conf.condition)
         )
     }
@@ -175,7 +201,7 @@ mod tests {
         .unwrap();
 
         assert_eq!(
-            r#"Map(VrlConfig { source: ".nork == true", runtime: Ast })"#,
+            r#"Map(VrlConfig { source: ".nork == true", runtime: Ast, config_dir: None })"#,
             format!("{:?}", conf.condition)
         )
     }
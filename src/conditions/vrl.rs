@@ -1,7 +1,9 @@
+use std::{path::PathBuf, sync::Arc};
+
 use serde::{Deserialize, Serialize};
 use value::Value;
 use vector_common::TimeZone;
-use vrl::{diagnostic::Formatter, Program, Runtime, VrlRuntime};
+use vrl::{diagnostic::Formatter, Program, Runtime, Vm, VrlRuntime};
 
 use crate::event::TargetEvents;
 use crate::{
@@ -17,6 +19,10 @@ pub struct VrlConfig {
 
     #[serde(default)]
     pub(crate) runtime: VrlRuntime,
+
+    /// The directory of the config file defining the condition, which imports are relative to.
+    #[serde(skip, default = "crate::config::current_config_dir")]
+    pub(crate) config_dir: Option<PathBuf>,
 }
 
 inventory::submit! {
@@ -46,6 +52,9 @@ impl ConditionConfig for VrlConfig {
 
         let mut state = vrl::state::ExternalEnv::default().read_only();
         state.set_external_context(enrichment_tables.clone());
+        if let Some(dir) = &self.config_dir {
+            state.set_import_dir(dir);
+        }
 
         let (program, warnings) = vrl::compile_with_state(&self.source, &functions, &mut state)
             .map_err(|diagnostics| {
@@ -59,12 +68,16 @@ impl ConditionConfig for VrlConfig {
             warn!(message = "VRL compilation warning.", %warnings);
         }
 
-        match self.runtime {
-            VrlRuntime::Ast => Ok(Condition::Vrl(Vrl {
-                program,
-                source: self.source.clone(),
-            })),
-        }
+        let vm = match self.runtime {
+            VrlRuntime::Ast => None,
+            VrlRuntime::Vm => Some(Arc::new(program.compile_to_vm())),
+        };
+
+        Ok(Condition::Vrl(Vrl {
+            program,
+            vm,
+            source: self.source.clone(),
+        }))
     }
 }
 
@@ -73,6 +86,7 @@ impl ConditionConfig for VrlConfig {
 #[derive(Debug, Clone)]
 pub struct Vrl {
     pub(super) program: Program,
+    pub(super) vm: Option<Arc<Vm>>,
     pub(super) source: String,
 }
 
@@ -82,7 +96,11 @@ impl Vrl {
         // TODO: use timezone from remap config
         let timezone = TimeZone::default();
 
-        let result = Runtime::default().resolve(&mut target, &self.program, &timezone);
+        let mut runtime = Runtime::default();
+        let result = match &self.vm {
+            Some(vm) => runtime.run_vm(&mut target, vm, &timezone),
+            None => runtime.resolve(&mut target, &self.program, &timezone),
+        };
         let original_event = match target.into_events() {
             TargetEvents::One(event) => event,
             _ => panic!("Event was modified in a condition. This is an internal compiler error."),
@@ -234,7 +252,7 @@ mod test {
             let source = source.to_owned();
             let config = VrlConfig {
                 source,
-                runtime: Default::default(),
+                ..Default::default()
             };
 
             assert_eq!(
@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 use serde_toml_merge::merge_into_table;
 use toml::value::{Table, Value};
 
-use super::{component_name, open_file, read_dir, Format};
+use super::{component_name, open_file, read_dir, with_config_dir, Format};
 use crate::config::format;
 
 /// Provides a hint to the loading system of the type of components that should be found
@@ -231,7 +231,7 @@ where
     /// Returns a vector of non-fatal warnings on success, or a vector of error strings on failure.
     fn load_from_file(&mut self, path: &Path, format: Format) -> Result<Vec<String>, Vec<String>> {
         if let Some((_, table, warnings)) = self.load_file(path, format)? {
-            self.merge(table, None)?;
+            with_config_dir(path.parent(), || self.merge(table, None))?;
             Ok(warnings)
         } else {
             Ok(vec![])
@@ -268,7 +268,7 @@ where
         }
 
         // Merge the 'root' config value first.
-        self.merge(root, None)?;
+        with_config_dir(Some(path), || self.merge(root, None))?;
 
         // Loop over each component path. If it exists, load files and merge.
         for (path, hint) in paths {
@@ -280,7 +280,7 @@ where
                 let (table, warns) =
                     self.load_dir(&path, matches!(hint, ComponentHint::Transform))?;
 
-                self.merge(table, Some(hint))?;
+                with_config_dir(Some(&path), || self.merge(table, Some(hint)))?;
 
                 warnings.extend(warns);
             }
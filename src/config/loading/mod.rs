@@ -4,6 +4,7 @@ mod secret;
 mod source;
 
 use std::{
+    cell::RefCell,
     collections::HashMap,
     fmt::Debug,
     fs::{File, ReadDir},
@@ -27,6 +28,25 @@ use crate::signal;
 
 pub static CONFIG_PATHS: Lazy<Mutex<Vec<ConfigPath>>> = Lazy::new(Mutex::default);
 
+thread_local! {
+    /// The directory of the config file whose components are being deserialized.
+    static CONFIG_DIR: RefCell<Option<PathBuf>> = RefCell::new(None);
+}
+
+/// Returns the directory of the config file whose components are being deserialized, if they are
+/// loaded from a file. Components use it to resolve paths relative to the config defining them.
+pub fn current_config_dir() -> Option<PathBuf> {
+    CONFIG_DIR.with(|dir| dir.borrow().clone())
+}
+
+/// Runs `f` with `dir` as the directory of the config file being deserialized.
+pub(super) fn with_config_dir<T>(dir: Option<&Path>, f: impl FnOnce() -> T) -> T {
+    let previous = CONFIG_DIR.with(|current| current.replace(dir.map(Path::to_path_buf)));
+    let result = f();
+    CONFIG_DIR.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
 pub(super) fn read_dir<P: AsRef<Path> + Debug>(path: P) -> Result<ReadDir, Vec<String>> {
     path.as_ref()
         .read_dir()
@@ -90,6 +90,13 @@ where
         self.map.iter_mut().map(|(k, (v, _delayed_key))| (k, v))
     }
 
+    /// Remove all the keys and values of ExpiringHashMap, irrespective of
+    /// expiration. Useful for flushing the remaining values on shutdown.
+    pub fn drain(&mut self) -> impl Iterator<Item = (K, V)> + '_ {
+        self.expiration_queue.clear();
+        self.map.drain().map(|(k, (v, _delayed_key))| (k, v))
+    }
+
     /// Check whether the [`ExpiringHashMap`] is empty.
     /// If it's empty, the `next_expired` function immediately resolves to
     /// [`None`]. Be aware that this may cause a spinlock behaviour if the
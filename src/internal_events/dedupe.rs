@@ -1,14 +1,69 @@
+use std::path::Path;
+
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
+use super::prelude::{error_stage, error_type};
+
 #[derive(Debug)]
-pub struct DedupeEventDiscarded {
-    pub event: crate::event::Event,
+pub struct DedupeEventDiscarded<'a> {
+    pub event: &'a crate::event::Event,
 }
 
-impl InternalEvent for DedupeEventDiscarded {
+impl InternalEvent for DedupeEventDiscarded<'_> {
     fn emit(self) {
         trace!(message = "Encountered duplicate event; discarding.", event = ?self.event);
         counter!("events_discarded_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct DedupeLoadError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for DedupeLoadError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to load persisted cache; starting with an empty cache.",
+            error = %self.error,
+            path = ?self.path,
+            error_code = "loading_cache",
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "loading_cache",
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct DedupePersistError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for DedupePersistError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to persist cache.",
+            error = %self.error,
+            path = ?self.path,
+            error_code = "persisting_cache",
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 30,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "persisting_cache",
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
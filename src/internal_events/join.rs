@@ -0,0 +1,18 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct JoinPartialEventFlushed {
+    pub missing: usize,
+}
+
+impl InternalEvent for JoinPartialEventFlushed {
+    fn emit(self) {
+        debug!(
+            message = "Flushing partially joined event.",
+            missing = self.missing,
+            internal_log_rate_secs = 30,
+        );
+        counter!("join_partial_events_flushed_total", 1);
+    }
+}
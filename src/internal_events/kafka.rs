@@ -147,6 +147,29 @@ impl InternalEvent for KafkaStatisticsReceived<'_> {
             "kafka_consumed_messages_bytes_total",
             self.statistics.rxmsg_bytes as u64
         );
+
+        if let Some(cgrp) = &self.statistics.cgrp {
+            gauge!(
+                "kafka_consumer_assigned_partitions",
+                cgrp.assignment_size as f64
+            );
+            update_counter!("kafka_consumer_rebalances_total", cgrp.rebalance_cnt as u64);
+        }
+        for (topic_id, topic) in &self.statistics.topics {
+            for (partition_id, partition) in &topic.partitions {
+                // Skip the internal "unassigned" partition, and partitions for which the lag is
+                // unknown, such as those of a producer.
+                if *partition_id < 0 || partition.consumer_lag < 0 {
+                    continue;
+                }
+                gauge!(
+                    "kafka_consumer_lag",
+                    partition.consumer_lag as f64,
+                    "topic_id" => topic_id.clone(),
+                    "partition_id" => partition_id.to_string(),
+                );
+            }
+        }
     }
 }
 
@@ -199,3 +222,31 @@ impl InternalEvent for KafkaNegativeAcknowledgmentError<'_> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct KafkaTransactionAborted<'a> {
+    pub error: &'a rdkafka::error::KafkaError,
+    pub count: usize,
+    pub will_retry: bool,
+}
+
+impl InternalEvent for KafkaTransactionAborted<'_> {
+    fn emit(self) {
+        error!(
+            message = "Kafka transaction aborted.",
+            error = %self.error,
+            error_code = "transaction_aborted",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            count = self.count,
+            will_retry = self.will_retry,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "transaction_aborted",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        counter!("kafka_transactions_aborted_total", 1);
+    }
+}
@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct LogPatternsLoadError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for LogPatternsLoadError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to load persisted patterns; starting without them.",
+            error = %self.error,
+            path = ?self.path,
+            error_code = "loading_patterns",
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "loading_patterns",
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct LogPatternsPersistError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for LogPatternsPersistError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to persist patterns.",
+            error = %self.error,
+            path = ?self.path,
+            error_code = "persisting_patterns",
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 30,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "persisting_patterns",
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -49,3 +49,24 @@ impl InternalEvent for LokiOutOfOrderEventRewritten {
         counter!("rewritten_timestamp_events_total", self.count as u64);
     }
 }
+
+#[derive(Debug)]
+pub struct LokiLabelsDemoted {
+    pub count: usize,
+    pub reason: &'static str,
+}
+
+impl InternalEvent for LokiLabelsDemoted {
+    fn emit(self) {
+        debug!(
+            message = "Label cardinality limit reached; moving labels into the event.",
+            count = %self.count,
+            reason = %self.reason,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "demoted_labels_total", self.count as u64,
+            "reason" => self.reason,
+        );
+    }
+}
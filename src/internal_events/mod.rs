@@ -65,6 +65,8 @@ mod http;
 pub mod http_client;
 #[cfg(feature = "sources-internal_logs")]
 mod internal_logs;
+#[cfg(feature = "transforms-join")]
+mod join;
 #[cfg(all(unix, feature = "sources-journald"))]
 mod journald;
 #[cfg(feature = "transforms-json_parser")]
@@ -75,6 +77,8 @@ mod kafka;
 mod key_value_parser;
 #[cfg(feature = "sources-kubernetes_logs")]
 mod kubernetes_logs;
+#[cfg(feature = "transforms-log_patterns")]
+mod log_patterns;
 #[cfg(feature = "transforms-log_to_metric")]
 mod log_to_metric;
 mod logplex;
@@ -114,6 +118,8 @@ mod pulsar;
 mod redis;
 #[cfg(feature = "transforms-reduce")]
 mod reduce;
+#[cfg(feature = "transforms-relabel")]
+mod relabel;
 mod remap;
 #[cfg(feature = "transforms-remove_fields")]
 mod remove_fields;
@@ -137,6 +143,8 @@ mod tcp;
 mod template;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
+#[cfg(feature = "transforms-trace_sampler")]
+mod trace_sampler;
 mod udp;
 mod unix;
 mod vector;
@@ -227,6 +235,8 @@ pub(crate) use self::geoip::*;
 pub(crate) use self::http::*;
 #[cfg(feature = "sources-internal_logs")]
 pub(crate) use self::internal_logs::*;
+#[cfg(feature = "transforms-join")]
+pub(crate) use self::join::*;
 #[cfg(all(unix, feature = "sources-journald"))]
 pub(crate) use self::journald::*;
 #[cfg(feature = "transforms-json_parser")]
@@ -237,6 +247,8 @@ pub(crate) use self::kafka::*;
 pub(crate) use self::key_value_parser::*;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub(crate) use self::kubernetes_logs::*;
+#[cfg(feature = "transforms-log_patterns")]
+pub(crate) use self::log_patterns::*;
 #[cfg(feature = "transforms-log_to_metric")]
 pub(crate) use self::log_to_metric::*;
 #[cfg(feature = "sources-heroku_logs")]
@@ -274,6 +286,8 @@ pub(crate) use self::pulsar::*;
 pub(crate) use self::redis::*;
 #[cfg(feature = "transforms-reduce")]
 pub(crate) use self::reduce::*;
+#[cfg(feature = "transforms-relabel")]
+pub(crate) use self::relabel::*;
 #[cfg(feature = "transforms-remap")]
 pub(crate) use self::remap::*;
 #[cfg(feature = "transforms-remove_fields")]
@@ -296,6 +310,8 @@ pub(crate) use self::syslog::*;
 pub(crate) use self::tag_cardinality_limit::*;
 #[cfg(feature = "transforms-throttle")]
 pub(crate) use self::throttle::*;
+#[cfg(feature = "transforms-trace_sampler")]
+pub(crate) use self::trace_sampler::*;
 #[cfg(all(
     any(
         feature = "sinks-socket",
@@ -0,0 +1,29 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct TraceSamplerTraceSampled {
+    pub kept: bool,
+    pub policy: &'static str,
+    pub span_events: usize,
+}
+
+impl InternalEvent for TraceSamplerTraceSampled {
+    fn emit(self) {
+        let decision = if self.kept { "keep" } else { "drop" };
+        trace!(
+            message = "Sampled trace.",
+            decision,
+            policy = self.policy,
+            span_events = self.span_events,
+        );
+        counter!(
+            "sampled_traces_total", 1,
+            "decision" => decision,
+            "policy" => self.policy,
+        );
+        if !self.kept {
+            counter!("events_discarded_total", self.span_events as u64);
+        }
+    }
+}
@@ -163,6 +163,7 @@ impl S3SinkConfig {
                 Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
                 | Serializer::RawMessage(_)
+                | Serializer::Syslog(_)
                 | Serializer::Text(_),
             ) => NewlineDelimitedEncoder::new().into(),
         };
@@ -152,6 +152,7 @@ impl AzureBlobSinkConfig {
                 Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
                 | Serializer::RawMessage(_)
+                | Serializer::Syslog(_)
                 | Serializer::Text(_),
             ) => NewlineDelimitedEncoder::new().into(),
         };
@@ -71,7 +71,8 @@ impl SinkConfig for ConsoleSinkConfig {
                 | Serializer::Json(_)
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
-                | Serializer::RawMessage(_),
+                | Serializer::RawMessage(_)
+                | Serializer::Syslog(_),
             ) => NewlineDelimitedEncoder::new().into(),
             (None, Serializer::Native(_)) => LengthDelimitedEncoder::new().into(),
         };
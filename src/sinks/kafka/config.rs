@@ -45,6 +45,9 @@ pub(crate) struct KafkaSinkConfig {
     pub librdkafka_options: HashMap<String, String>,
     #[serde(alias = "headers_field")] // accidentally released as `headers_field` in 0.18
     pub headers_key: Option<String>,
+    /// When set, each batch is produced within a Kafka transaction.
+    #[serde(default)]
+    pub transaction: Option<KafkaTransactionConfig>,
     #[serde(
         default,
         deserialize_with = "crate::serde::bool_or_struct",
@@ -53,6 +56,38 @@ pub(crate) struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Options for producing each batch of events within a Kafka transaction, so that consumers
+/// using the `read_committed` isolation level see either the whole batch or none of it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct KafkaTransactionConfig {
+    /// The prefix of the `transactional.id`, which is completed with the host name. The
+    /// resulting ID must be unique to this sink and stable across restarts.
+    pub transactional_id_prefix: String,
+    #[serde(default = "default_transaction_timeout_ms")]
+    pub timeout_ms: u64,
+    #[serde(default = "default_transaction_max_retries")]
+    pub max_retries: usize,
+}
+
+impl KafkaTransactionConfig {
+    pub(crate) fn transactional_id(&self) -> crate::Result<String> {
+        Ok(format!(
+            "{}-{}",
+            self.transactional_id_prefix,
+            crate::get_hostname()?
+        ))
+    }
+}
+
+const fn default_transaction_timeout_ms() -> u64 {
+    60000 // default in librdkafka
+}
+
+const fn default_transaction_max_retries() -> usize {
+    3
+}
+
 const fn default_socket_timeout_ms() -> u64 {
     60000 // default in librdkafka
 }
@@ -86,6 +121,23 @@ impl KafkaSinkConfig {
                     .set("compression.codec", &to_string(self.compression))
                     .set("message.timeout.ms", &self.message_timeout_ms.to_string());
 
+                if let Some(transaction) = &self.transaction {
+                    // Setting a transactional ID implies `enable.idempotence`.
+                    for key in ["transactional.id", "transaction.timeout.ms"] {
+                        if let Some(val) = self.librdkafka_options.get(key) {
+                            return Err(format!("Transaction settings set `librdkafka_options.{}`.\
+                                            The config already sets this as `librdkafka_options.{}={}`.\
+                                            Please delete one.", key, key, val).into());
+                        }
+                    }
+                    client_config
+                        .set("transactional.id", &transaction.transactional_id()?)
+                        .set(
+                            "transaction.timeout.ms",
+                            &transaction.timeout_ms.to_string(),
+                        );
+                }
+
                 if let Some(value) = self.batch.timeout_secs {
                     // Delay in milliseconds to wait for messages in the producer queue to accumulate before
                     // constructing message batches (MessageSets) to transmit to brokers. A higher value
@@ -175,6 +227,7 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            transaction: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -211,4 +264,37 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    fn transactional_config(librdkafka_options: &str) -> KafkaSinkConfig {
+        toml::from_str(&format!(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            encoding.codec = "json"
+            transaction.transactional_id_prefix = "vector"
+            {}
+            "#,
+            librdkafka_options
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn transaction_sets_transactional_id() {
+        let config = transactional_config("")
+            .to_rdkafka(KafkaRole::Producer)
+            .unwrap();
+
+        let transactional_id = config.get("transactional.id").unwrap();
+        assert!(transactional_id.starts_with("vector-"));
+        assert_eq!(config.get("transaction.timeout.ms"), Some("60000"));
+    }
+
+    #[test]
+    fn transaction_errors_on_double_set() {
+        let config = transactional_config(r#"librdkafka_options."transactional.id" = "other""#);
+
+        assert!(config.to_rdkafka(KafkaRole::Producer).is_err());
+        assert!(config.to_rdkafka(KafkaRole::Consumer).is_ok());
+    }
 }
@@ -1,11 +1,14 @@
-use std::task::{Context, Poll};
+use std::{
+    task::{Context, Poll},
+    time::Duration,
+};
 
 use bytes::Bytes;
-use futures::future::BoxFuture;
+use futures::{future::BoxFuture, stream::FuturesUnordered, TryStreamExt};
 use rdkafka::{
     error::KafkaError,
     message::OwnedHeaders,
-    producer::{FutureProducer, FutureRecord},
+    producer::{FutureProducer, FutureRecord, Producer},
     util::Timeout,
 };
 use tower::Service;
@@ -13,11 +16,14 @@ use vector_core::{
     buffers::Ackable,
     internal_event::{BytesSent, EventsSent},
     stream::DriverResponse,
+    ByteSizeOf,
 };
 
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
+    internal_events::KafkaTransactionAborted,
     kafka::KafkaStatisticsContext,
+    sinks::util::retries::ExponentialBackoff,
 };
 
 pub struct KafkaRequest {
@@ -65,6 +71,38 @@ impl Finalizable for KafkaRequest {
     }
 }
 
+impl ByteSizeOf for KafkaRequest {
+    fn size_of(&self) -> usize {
+        // Used by the batcher in transactional mode, so that `batch.max_bytes` limits the size
+        // of the messages produced in a single transaction.
+        self.encoded_length()
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
+impl KafkaRequest {
+    fn encoded_length(&self) -> usize {
+        self.body.len() + self.metadata.key.as_ref().map_or(0, |key| key.len())
+    }
+
+    fn to_record(&self) -> FutureRecord<'_, [u8], [u8]> {
+        let mut record = FutureRecord::to(&self.metadata.topic).payload(self.body.as_ref());
+        if let Some(key) = &self.metadata.key {
+            record = record.key(&key[..]);
+        }
+        if let Some(timestamp) = self.metadata.timestamp_millis {
+            record = record.timestamp(timestamp);
+        }
+        if let Some(headers) = &self.metadata.headers {
+            record = record.headers(headers.clone());
+        }
+        record
+    }
+}
+
 pub struct KafkaService {
     kafka_producer: FutureProducer<KafkaStatisticsContext>,
 }
@@ -90,24 +128,13 @@ impl Service<KafkaRequest> for KafkaService {
         let kafka_producer = self.kafka_producer.clone();
 
         Box::pin(async move {
-            let mut record =
-                FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
-            if let Some(key) = &request.metadata.key {
-                record = record.key(&key[..]);
-            }
-            if let Some(timestamp) = request.metadata.timestamp_millis {
-                record = record.timestamp(timestamp);
-            }
-            if let Some(headers) = request.metadata.headers {
-                record = record.headers(headers);
-            }
+            let record = request.to_record();
 
             //rdkafka will internally retry forever if the queue is full
             let result = match kafka_producer.send(record, Timeout::Never).await {
                 Ok((_partition, _offset)) => {
                     emit!(BytesSent {
-                        byte_size: request.body.len()
-                            + request.metadata.key.map(|x| x.len()).unwrap_or(0),
+                        byte_size: request.encoded_length(),
                         protocol: "kafka"
                     });
                     Ok(KafkaResponse {
@@ -120,3 +147,149 @@ impl Service<KafkaRequest> for KafkaService {
         })
     }
 }
+
+pub struct KafkaTransactionResponse {
+    count: usize,
+    event_byte_size: usize,
+}
+
+impl DriverResponse for KafkaTransactionResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.count,
+            byte_size: self.event_byte_size,
+            output: None,
+        }
+    }
+}
+
+/// Produces each batch of requests within a single Kafka transaction, which is committed only
+/// once every message of the batch has been delivered. Failed transactions are aborted and
+/// retried as a whole.
+///
+/// A transactional producer can only have one transaction in flight, so this service must not be
+/// called concurrently.
+#[derive(Clone)]
+pub struct KafkaTransactionService {
+    kafka_producer: FutureProducer<KafkaStatisticsContext>,
+    timeout: Duration,
+    max_retries: usize,
+}
+
+impl KafkaTransactionService {
+    /// Creates the service, initializing the transactions of the given producer. This fences
+    /// off any previous producer using the same `transactional.id`, and blocks until done.
+    pub(crate) async fn new(
+        kafka_producer: FutureProducer<KafkaStatisticsContext>,
+        timeout: Duration,
+        max_retries: usize,
+    ) -> Result<Self, KafkaError> {
+        let producer = kafka_producer.clone();
+        tokio::task::spawn_blocking(move || producer.init_transactions(timeout))
+            .await
+            .expect("init_transactions panicked")?;
+
+        Ok(Self {
+            kafka_producer,
+            timeout,
+            max_retries,
+        })
+    }
+}
+
+impl Service<Vec<KafkaRequest>> for KafkaTransactionService {
+    type Response = KafkaTransactionResponse;
+    type Error = KafkaError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, requests: Vec<KafkaRequest>) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let mut backoff = ExponentialBackoff::from_millis(2)
+                .factor(250)
+                .max_delay(Duration::from_secs(60));
+            let mut retries = 0;
+            loop {
+                match service.send_transaction(&requests).await {
+                    Ok(()) => break,
+                    Err(error) => {
+                        let will_retry = retries < service.max_retries && !is_fatal(&error);
+                        emit!(KafkaTransactionAborted {
+                            error: &error,
+                            count: requests.len(),
+                            will_retry,
+                        });
+                        if !will_retry {
+                            return Err(error);
+                        }
+                        retries += 1;
+                        tokio::time::sleep(backoff.next().unwrap()).await;
+                    }
+                }
+            }
+
+            emit!(BytesSent {
+                byte_size: requests.iter().map(KafkaRequest::encoded_length).sum(),
+                protocol: "kafka"
+            });
+            Ok(KafkaTransactionResponse {
+                count: requests.len(),
+                event_byte_size: requests.iter().map(|request| request.event_byte_size).sum(),
+            })
+        })
+    }
+}
+
+impl KafkaTransactionService {
+    async fn send_transaction(&self, requests: &[KafkaRequest]) -> Result<(), KafkaError> {
+        self.kafka_producer.begin_transaction()?;
+
+        //rdkafka will internally retry forever if the queue is full
+        let result = requests
+            .iter()
+            .map(|request| {
+                self.kafka_producer
+                    .send(request.to_record(), Timeout::Never)
+            })
+            .collect::<FuturesUnordered<_>>()
+            .map_err(|(error, _original_record)| error)
+            .try_for_each(|_| futures::future::ready(Ok(())))
+            .await;
+
+        let result = match result {
+            Ok(()) => {
+                let (producer, timeout) = (self.kafka_producer.clone(), self.timeout);
+                tokio::task::spawn_blocking(move || producer.commit_transaction(timeout))
+                    .await
+                    .expect("commit_transaction panicked")
+            }
+            Err(error) => Err(error),
+        };
+
+        if let Err(error) = result {
+            if !is_fatal(&error) {
+                let (producer, timeout) = (self.kafka_producer.clone(), self.timeout);
+                tokio::task::spawn_blocking(move || producer.abort_transaction(timeout))
+                    .await
+                    .expect("abort_transaction panicked")?;
+            }
+            return Err(error);
+        }
+        Ok(())
+    }
+}
+
+/// A fatal error leaves the transactional producer unusable, so the transaction can be neither
+/// aborted nor retried.
+fn is_fatal(error: &KafkaError) -> bool {
+    matches!(error, KafkaError::Transaction(error) if error.is_fatal())
+}
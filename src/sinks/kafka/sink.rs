@@ -11,7 +11,7 @@ use rdkafka::{
 use snafu::{ResultExt, Snafu};
 use tokio::time::Duration;
 use tower::limit::ConcurrencyLimit;
-use vector_core::{buffers::Acker, config::log_schema};
+use vector_core::{buffers::Acker, config::log_schema, stream::BatcherSettings};
 
 use super::config::{KafkaRole, KafkaSinkConfig};
 use crate::{
@@ -20,8 +20,9 @@ use crate::{
     kafka::KafkaStatisticsContext,
     sinks::{
         kafka::{
-            config::QUEUED_MIN_MESSAGES, request_builder::KafkaRequestBuilder,
-            service::KafkaService,
+            config::QUEUED_MIN_MESSAGES,
+            request_builder::KafkaRequestBuilder,
+            service::{KafkaService, KafkaTransactionService},
         },
         util::{builder::SinkBuilderExt, encoding::Transformer, StreamSink},
     },
@@ -36,11 +37,19 @@ pub(super) enum BuildError {
     TopicTemplate { source: TemplateParseError },
 }
 
+/// The settings of the transactional producer mode.
+struct TransactionSettings {
+    batch_settings: BatcherSettings,
+    timeout: Duration,
+    max_retries: usize,
+}
+
 pub struct KafkaSink {
     transformer: Transformer,
     encoder: Encoder<()>,
     acker: Acker,
-    service: KafkaService,
+    producer: FutureProducer<KafkaStatisticsContext>,
+    transaction: Option<TransactionSettings>,
     topic: Template,
     key_field: Option<String>,
     headers_key: Option<String>,
@@ -62,21 +71,28 @@ impl KafkaSink {
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.encoding();
         let encoder = Encoder::<()>::new(serializer);
+        let transaction = match &config.transaction {
+            Some(transaction) => Some(TransactionSettings {
+                batch_settings: config.batch.into_batcher_settings()?,
+                timeout: Duration::from_millis(transaction.timeout_ms),
+                max_retries: transaction.max_retries,
+            }),
+            None => None,
+        };
 
         Ok(KafkaSink {
             headers_key: config.headers_key,
             transformer,
             encoder,
             acker,
-            service: KafkaService::new(producer),
+            producer,
+            transaction,
             topic: Template::try_from(config.topic).context(TopicTemplateSnafu)?,
             key_field: config.key_field,
         })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
-        // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
-        let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
         let mut request_builder = KafkaRequestBuilder {
             key_field: self.key_field,
             headers_key: self.headers_key,
@@ -85,10 +101,37 @@ impl KafkaSink {
             encoder: self.encoder,
             log_schema: log_schema(),
         };
-        let sink = input
-            .filter_map(|event| future::ready(request_builder.build_request(event)))
-            .into_driver(service, self.acker);
-        sink.run().await
+        let requests =
+            input.filter_map(move |event| future::ready(request_builder.build_request(event)));
+
+        match self.transaction {
+            None => {
+                // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
+                let service = ConcurrencyLimit::new(
+                    KafkaService::new(self.producer),
+                    QUEUED_MIN_MESSAGES as usize,
+                );
+                requests.into_driver(service, self.acker).run().await
+            }
+            Some(transaction) => {
+                let service = KafkaTransactionService::new(
+                    self.producer,
+                    transaction.timeout,
+                    transaction.max_retries,
+                )
+                .await
+                .map_err(|error| {
+                    error!(message = "Failed to initialize Kafka transactions.", %error);
+                })?;
+                // Only one transaction can be in flight at a time.
+                let service = ConcurrencyLimit::new(service, 1);
+                requests
+                    .batched(transaction.batch_settings.into_byte_size_config())
+                    .into_driver(service, self.acker)
+                    .run()
+                    .await
+            }
+        }
     }
 }
 
@@ -27,7 +27,7 @@ mod integration_test {
         kafka::{KafkaAuthConfig, KafkaCompression, KafkaSaslConfig},
         sinks::{
             kafka::{
-                config::{KafkaRole, KafkaSinkConfig},
+                config::{KafkaRole, KafkaSinkConfig, KafkaTransactionConfig},
                 sink::KafkaSink,
                 *,
             },
@@ -70,6 +70,7 @@ mod integration_test {
             message_timeout_ms: 300000,
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
@@ -105,6 +106,79 @@ mod integration_test {
         kafka_happy_path(kafka_address(9091), None, None, KafkaCompression::Zstd).await;
     }
 
+    #[tokio::test]
+    async fn kafka_happy_path_transactional() {
+        crate::test_util::trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let server = kafka_address(9091);
+        let mut batch = BatchConfig::default();
+        batch.max_events = Some(10);
+        let config = KafkaSinkConfig {
+            bootstrap_servers: server.clone(),
+            topic: topic.clone(),
+            key_field: None,
+            encoding: EncodingConfig::from(StandardEncodings::Text).into(),
+            batch,
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: 60000,
+            message_timeout_ms: 300000,
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            transaction: Some(KafkaTransactionConfig {
+                transactional_id_prefix: random_string(10),
+                timeout_ms: 60000,
+                max_retries: 3,
+            }),
+            acknowledgements: Default::default(),
+        };
+        let (acker, ack_counter) = Acker::basic();
+        let sink = KafkaSink::new(config, acker).unwrap();
+        let sink = VectorSink::from_event_streamsink(sink);
+
+        let num_events = 100;
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
+        run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        // only committed messages are visible to a `read_committed` consumer
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", server.as_str());
+        client_config.set("group.id", &random_string(10));
+        client_config.set("isolation.level", "read_committed");
+
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(&topic, 0)
+            .set_offset(Offset::Beginning)
+            .unwrap();
+
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        consumer.assign(&tpl).unwrap();
+
+        let mut failures = 0;
+        let mut out = Vec::new();
+        while failures < 100 && out.len() < input.len() {
+            match consumer.poll(Duration::from_secs(3)) {
+                Some(Ok(msg)) => {
+                    let s: &str = msg.payload_view().unwrap().unwrap();
+                    out.push(s.to_owned());
+                }
+                _ => {
+                    failures += 1;
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+        }
+
+        assert_eq!(out, input);
+        assert_eq!(
+            ack_counter.load(std::sync::atomic::Ordering::Relaxed),
+            num_events
+        );
+    }
+
     async fn kafka_batch_options_overrides(
         batch: BatchConfig<NoDefaultsBatchSettings>,
         librdkafka_options: HashMap<String, String>,
@@ -125,6 +199,7 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         let (acker, _ack_counter) = Acker::basic();
@@ -257,6 +332,7 @@ mod integration_test {
             message_timeout_ms: 300000,
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            transaction: None,
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
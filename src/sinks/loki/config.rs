@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, num::NonZeroUsize};
 
 use codecs::encoding::SerializerConfig;
 use codecs::{JsonSerializerConfig, LogfmtSerializerConfig, TextSerializerConfig};
@@ -51,6 +51,8 @@ pub struct LokiConfig {
     pub compression: Compression,
     #[serde(default)]
     pub out_of_order_action: OutOfOrderAction,
+    #[serde(default)]
+    pub cardinality: CardinalityConfig,
     pub auth: Option<Auth>,
     #[serde(default)]
     pub request: TowerRequestConfig,
@@ -84,6 +86,30 @@ pub enum OutOfOrderAction {
     Accept,
 }
 
+/// Limits on the number of distinct streams and label values produced by the `labels` templates.
+///
+/// Labels rendered from templates that would exceed a limit are removed from the stream and moved
+/// into the event instead, either at the top level or under `fallback_field`. Static labels are
+/// never moved. The limits are counted over a time window as long as the batch timeout, which
+/// starts over independently of when batches are flushed. The stream holding only the static
+/// labels, which receives the events whose labels are moved, counts toward `max_streams_per_batch`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CardinalityConfig {
+    /// The maximum number of distinct streams in a batch window.
+    pub max_streams_per_batch: Option<NonZeroUsize>,
+    /// The maximum number of distinct values of a single label in a batch window.
+    pub max_label_values: Option<NonZeroUsize>,
+    /// The field to move offending labels into.
+    pub fallback_field: Option<String>,
+}
+
+impl CardinalityConfig {
+    pub const fn is_enabled(&self) -> bool {
+        self.max_streams_per_batch.is_some() || self.max_label_values.is_some()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum Encoding {
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, num::NonZeroUsize};
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroUsize,
+    time::{Duration, Instant},
+};
 
 use bytes::{Bytes, BytesMut};
 use futures::{stream::BoxStream, StreamExt};
@@ -16,8 +20,8 @@ use vector_core::{
 };
 
 use super::{
-    config::{LokiConfig, OutOfOrderAction},
-    event::{LokiBatchEncoder, LokiEvent, LokiRecord, PartitionKey},
+    config::{CardinalityConfig, LokiConfig, OutOfOrderAction},
+    event::{Labels, LokiBatchEncoder, LokiEvent, LokiRecord, PartitionKey},
     service::{LokiRequest, LokiRetryLogic, LokiService},
 };
 use crate::{
@@ -25,8 +29,8 @@ use crate::{
     config::{log_schema, SinkContext},
     http::HttpClient,
     internal_events::{
-        LokiEventUnlabeled, LokiOutOfOrderEventDropped, LokiOutOfOrderEventRewritten,
-        TemplateRenderingError,
+        LokiEventUnlabeled, LokiLabelsDemoted, LokiOutOfOrderEventDropped,
+        LokiOutOfOrderEventRewritten, TemplateRenderingError,
     },
     sinks::util::{
         builder::SinkBuilderExt,
@@ -153,6 +157,7 @@ pub(super) struct EventEncoder {
     labels: HashMap<Template, Template>,
     remove_label_fields: bool,
     remove_timestamp: bool,
+    cardinality_guard: Option<CardinalityGuard>,
 }
 
 impl EventEncoder {
@@ -202,6 +207,9 @@ impl EventEncoder {
         let finalizers = event.take_finalizers();
         let mut labels = self.build_labels(&event);
         self.remove_label_fields(&mut event);
+        if let Some(guard) = self.cardinality_guard.as_mut() {
+            guard.apply(&mut labels, &mut event);
+        }
 
         let schema = log_schema();
         let timestamp_key = schema.timestamp_key();
@@ -240,6 +248,145 @@ impl EventEncoder {
     }
 }
 
+/// Why a label was moved out of the stream and into the event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DemotionReason {
+    MaxLabelValues,
+    MaxStreams,
+}
+
+impl DemotionReason {
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::MaxLabelValues => "max_label_values",
+            Self::MaxStreams => "max_streams_per_batch",
+        }
+    }
+}
+
+/// Keeps the labels produced by dynamic templates within the configured cardinality limits.
+///
+/// Distinct streams and label values are counted over a time window as long as the batch timeout,
+/// after which the counts start over. The window isn't tied to when batches are flushed.
+#[derive(Clone)]
+pub(super) struct CardinalityGuard {
+    max_streams: Option<NonZeroUsize>,
+    max_label_values: Option<NonZeroUsize>,
+    fallback_field: Option<String>,
+    static_labels: HashSet<String>,
+    window: Duration,
+    window_start: Instant,
+    streams: HashSet<Labels>,
+    label_values: HashMap<String, HashSet<String>>,
+}
+
+impl CardinalityGuard {
+    pub(super) fn new(
+        config: &CardinalityConfig,
+        labels: &HashMap<Template, Template>,
+        window: Duration,
+    ) -> Option<Self> {
+        config.is_enabled().then(|| Self {
+            max_streams: config.max_streams_per_batch,
+            max_label_values: config.max_label_values,
+            fallback_field: config.fallback_field.clone(),
+            static_labels: labels
+                .iter()
+                .filter(|(key, value)| {
+                    !key.is_dynamic() && !value.is_dynamic() && !key.get_ref().ends_with('*')
+                })
+                .map(|(key, _)| key.get_ref().trim().to_owned())
+                .collect(),
+            window,
+            window_start: Instant::now(),
+            streams: HashSet::new(),
+            label_values: HashMap::new(),
+        })
+    }
+
+    fn apply(&mut self, labels: &mut Labels, event: &mut Event) {
+        if self.window_start.elapsed() >= self.window {
+            self.streams.clear();
+            self.label_values.clear();
+            self.window_start = Instant::now();
+        }
+
+        let mut demoted = Vec::new();
+
+        // New label values are only recorded once their stream is accepted, so that demoted
+        // labels don't count toward the limit.
+        let mut new_values = Vec::new();
+        if let Some(max_label_values) = self.max_label_values {
+            let static_labels = &self.static_labels;
+            let label_values = &self.label_values;
+            labels.retain(|(name, value)| {
+                if static_labels.contains(name) {
+                    return true;
+                }
+                let values = label_values.get(name);
+                if values.map_or(false, |values| values.contains(value)) {
+                    return true;
+                }
+                let count = values.map_or(0, HashSet::len)
+                    + new_values.iter().filter(|(new, _)| new == name).count();
+                if count < max_label_values.get() {
+                    new_values.push((name.clone(), value.clone()));
+                    true
+                } else {
+                    demoted.push((name.clone(), value.clone()));
+                    false
+                }
+            });
+            if !demoted.is_empty() {
+                emit!(LokiLabelsDemoted {
+                    count: demoted.len(),
+                    reason: DemotionReason::MaxLabelValues.as_str(),
+                });
+            }
+        }
+
+        if let Some(max_streams) = self.max_streams {
+            labels.sort();
+            if !self.streams.contains(labels) {
+                // The stream of the events whose labels are demoted, holding only the static
+                // labels, counts toward the limit as well, so room is kept for it.
+                let mut fallback = labels.clone();
+                fallback.retain(|(name, _)| self.static_labels.contains(name));
+                let reserved = usize::from(!self.streams.contains(&fallback));
+                if fallback != *labels && self.streams.len() + reserved >= max_streams.get() {
+                    let before = demoted.len();
+                    demoted.extend(
+                        labels
+                            .iter()
+                            .filter(|(name, _)| !self.static_labels.contains(name))
+                            .cloned(),
+                    );
+                    emit!(LokiLabelsDemoted {
+                        count: demoted.len() - before,
+                        reason: DemotionReason::MaxStreams.as_str(),
+                    });
+                    *labels = fallback;
+                    new_values.clear();
+                }
+                self.streams.insert(labels.clone());
+            }
+        }
+
+        for (name, value) in new_values {
+            self.label_values.entry(name).or_default().insert(value);
+        }
+
+        let log = event.as_mut_log();
+        for (name, value) in demoted {
+            let path = match &self.fallback_field {
+                Some(field) => format!("{}.\"{}\"", field, name),
+                None => format!("\"{}\"", name),
+            };
+            log.insert(path.as_str(), value);
+        }
+    }
+}
+
 struct FilteredRecord {
     pub rewritten: bool,
     pub inner: LokiRecord,
@@ -351,6 +498,9 @@ impl LokiSink {
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.encoding();
         let encoder = Encoder::<()>::new(serializer);
+        let batch_settings = config.batch.into_batcher_settings()?;
+        let cardinality_guard =
+            CardinalityGuard::new(&config.cardinality, &config.labels, batch_settings.timeout);
 
         Ok(Self {
             acker: cx.acker(),
@@ -365,8 +515,9 @@ impl LokiSink {
                 labels: config.labels,
                 remove_label_fields: config.remove_label_fields,
                 remove_timestamp: config.remove_timestamp,
+                cardinality_guard,
             },
-            batch_settings: config.batch.into_batcher_settings()?,
+            batch_settings,
             out_of_order_action: config.out_of_order_action,
             service,
         })
@@ -445,18 +596,23 @@ fn slugify_text(input: String) -> String {
 #[cfg(test)]
 mod tests {
     use std::{
-        collections::{BTreeMap, HashMap},
+        collections::{BTreeMap, HashMap, HashSet},
         convert::TryFrom,
+        num::NonZeroUsize,
+        time::Duration,
     };
 
     use codecs::JsonSerializer;
     use futures::stream::StreamExt;
     use vector_core::event::{Event, Value};
 
-    use super::{EventEncoder, KeyPartitioner, RecordFilter};
+    use super::{CardinalityGuard, EventEncoder, KeyPartitioner, RecordFilter};
     use crate::{
-        codecs::Encoder, config::log_schema, sinks::loki::config::OutOfOrderAction,
-        template::Template, test_util::random_lines,
+        codecs::Encoder,
+        config::log_schema,
+        sinks::loki::config::{CardinalityConfig, OutOfOrderAction},
+        template::Template,
+        test_util::random_lines,
     };
 
     #[test]
@@ -468,6 +624,7 @@ mod tests {
             labels: HashMap::default(),
             remove_label_fields: false,
             remove_timestamp: false,
+            cardinality_guard: None,
         };
         let mut event = Event::from("hello world");
         let log = event.as_mut_log();
@@ -507,6 +664,7 @@ mod tests {
             labels,
             remove_label_fields: false,
             remove_timestamp: false,
+            cardinality_guard: None,
         };
         let mut event = Event::from("hello world");
         let log = event.as_mut_log();
@@ -539,6 +697,7 @@ mod tests {
             labels: HashMap::default(),
             remove_label_fields: false,
             remove_timestamp: true,
+            cardinality_guard: None,
         };
         let mut event = Event::from("hello world");
         let log = event.as_mut_log();
@@ -567,6 +726,7 @@ mod tests {
             labels,
             remove_label_fields: true,
             remove_timestamp: false,
+            cardinality_guard: None,
         };
         let mut event = Event::from("hello world");
         let log = event.as_mut_log();
@@ -577,6 +737,109 @@ mod tests {
         assert!(!String::from_utf8_lossy(&record.event.event).contains("value"));
     }
 
+    fn guarded_encoder(cardinality: CardinalityConfig) -> EventEncoder {
+        let mut labels = HashMap::default();
+        labels.insert(
+            Template::try_from("static").unwrap(),
+            Template::try_from("value").unwrap(),
+        );
+        labels.insert(
+            Template::try_from("user").unwrap(),
+            Template::try_from("{{ user }}").unwrap(),
+        );
+        let cardinality_guard =
+            CardinalityGuard::new(&cardinality, &labels, Duration::from_secs(3600));
+        EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializer::new().into()),
+            labels,
+            remove_label_fields: true,
+            remove_timestamp: false,
+            cardinality_guard,
+        }
+    }
+
+    fn user_event(user: &str) -> Event {
+        let mut event = Event::from("hello world");
+        event.as_mut_log().insert("user", user);
+        event
+    }
+
+    #[test]
+    fn encoder_demotes_excess_label_values() {
+        let mut encoder = guarded_encoder(CardinalityConfig {
+            max_label_values: NonZeroUsize::new(2),
+            ..Default::default()
+        });
+
+        for user in ["a", "b", "a"] {
+            let record = encoder.encode_event(user_event(user)).unwrap();
+            assert_eq!(record.labels.len(), 2);
+        }
+
+        let record = encoder.encode_event(user_event("c")).unwrap();
+        assert_eq!(
+            record.labels,
+            vec![("static".to_string(), "value".to_string())]
+        );
+        let line: serde_json::Value = serde_json::from_slice(&record.event.event).unwrap();
+        assert_eq!(line["user"], "c");
+    }
+
+    #[test]
+    fn encoder_demotes_excess_streams_into_fallback_field() {
+        let mut encoder = guarded_encoder(CardinalityConfig {
+            max_streams_per_batch: NonZeroUsize::new(2),
+            fallback_field: Some("labels".to_string()),
+            ..Default::default()
+        });
+
+        let record = encoder.encode_event(user_event("a")).unwrap();
+        assert_eq!(record.labels.len(), 2);
+
+        let record = encoder.encode_event(user_event("b")).unwrap();
+        assert_eq!(
+            record.labels,
+            vec![("static".to_string(), "value".to_string())]
+        );
+        let line: serde_json::Value = serde_json::from_slice(&record.event.event).unwrap();
+        assert_eq!(line["labels"]["user"], "b");
+        assert!(line.get("user").is_none());
+    }
+
+    #[test]
+    fn encoder_counts_fallback_stream_toward_max_streams() {
+        let mut encoder = guarded_encoder(CardinalityConfig {
+            max_streams_per_batch: NonZeroUsize::new(2),
+            ..Default::default()
+        });
+
+        let streams = ["a", "b", "c", "a"]
+            .into_iter()
+            .map(|user| encoder.encode_event(user_event(user)).unwrap().labels)
+            .collect::<HashSet<_>>();
+
+        assert_eq!(streams.len(), 2);
+    }
+
+    #[test]
+    fn encoder_ignores_label_values_of_demoted_streams() {
+        let mut encoder = guarded_encoder(CardinalityConfig {
+            max_streams_per_batch: NonZeroUsize::new(2),
+            max_label_values: NonZeroUsize::new(2),
+            ..Default::default()
+        });
+
+        for user in ["a", "b", "c"] {
+            encoder.encode_event(user_event(user)).unwrap();
+        }
+
+        let guard = encoder.cardinality_guard.as_ref().unwrap();
+        assert_eq!(guard.label_values["user"].len(), 1);
+        assert_eq!(guard.streams.len(), 2);
+    }
+
     #[tokio::test]
     async fn filter_encoder_drop() {
         let mut encoder = EventEncoder {
@@ -586,6 +849,7 @@ mod tests {
             labels: HashMap::default(),
             remove_label_fields: false,
             remove_timestamp: false,
+            cardinality_guard: None,
         };
         let base = chrono::Utc::now();
         let events = random_lines(100)
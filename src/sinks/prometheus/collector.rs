@@ -3,8 +3,9 @@ use std::{collections::BTreeMap, fmt::Write as _};
 use chrono::Utc;
 use indexmap::map::IndexMap;
 use prometheus_parser::{proto, METRIC_NAME_LABEL};
-use vector_core::event::metric::{samples_to_buckets, MetricSketch, Quantile};
+use vector_core::event::metric::{samples_to_buckets, MetricSketch, Quantile, Sample};
 
+use super::metadata::{Exemplar, PrometheusMetadata};
 use crate::{
     event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
     sinks::util::{encode_namespace, statistic::DistributionStatistic},
@@ -15,7 +16,13 @@ pub(super) trait MetricCollector {
 
     fn new() -> Self;
 
-    fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue);
+    fn emit_metadata(
+        &mut self,
+        name: &str,
+        fullname: &str,
+        value: &MetricValue,
+        metadata: &PrometheusMetadata,
+    );
 
     fn emit_value(
         &mut self,
@@ -27,6 +34,34 @@ pub(super) trait MetricCollector {
         extra: Option<(&str, String)>,
     );
 
+    /// Attach the exemplars belonging to the bucket with the given upper limit to the
+    /// previously emitted `_bucket` series. Collectors that cannot represent exemplars
+    /// ignore them.
+    fn emit_exemplars(
+        &mut self,
+        _timestamp_millis: Option<i64>,
+        _name: &str,
+        _tags: Option<&BTreeMap<String, String>>,
+        _upper_limit: f64,
+        _exemplars: &[Exemplar],
+    ) {
+    }
+
+    /// The schema to encode histogram distributions with as native histograms, or `None`
+    /// if they should be encoded as classic histograms with fixed buckets.
+    fn native_histogram_schema(&self) -> Option<i32> {
+        None
+    }
+
+    fn emit_native_histogram(
+        &mut self,
+        _timestamp_millis: Option<i64>,
+        _name: &str,
+        _tags: Option<&BTreeMap<String, String>>,
+        _samples: &[Sample],
+    ) {
+    }
+
     fn finish(self) -> Self::Output;
 
     fn encode_metric(
@@ -42,7 +77,8 @@ pub(super) trait MetricCollector {
 
         if metric.kind() == MetricKind::Absolute {
             let tags = metric.tags();
-            self.emit_metadata(metric.name(), name, metric.value());
+            let metadata = PrometheusMetadata::from_metric(metric);
+            self.emit_metadata(metric.name(), name, metric.value(), &metadata);
 
             match metric.value() {
                 MetricValue::Counter { value } => {
@@ -54,6 +90,12 @@ pub(super) trait MetricCollector {
                 MetricValue::Set { values } => {
                     self.emit_value(timestamp, name, "", values.len() as f64, tags, None);
                 }
+                MetricValue::Distribution {
+                    samples,
+                    statistic: StatisticKind::Histogram,
+                } if self.native_histogram_schema().is_some() => {
+                    self.emit_native_histogram(timestamp, name, tags, samples);
+                }
                 MetricValue::Distribution {
                     samples,
                     statistic: StatisticKind::Histogram,
@@ -149,6 +191,13 @@ pub(super) trait MetricCollector {
                             tags,
                             Some(("le", bucket.upper_limit.to_string())),
                         );
+                        self.emit_exemplars(
+                            timestamp,
+                            name,
+                            tags,
+                            bucket.upper_limit,
+                            &metadata.exemplars,
+                        );
                     }
                     self.emit_value(
                         timestamp,
@@ -158,6 +207,7 @@ pub(super) trait MetricCollector {
                         tags,
                         Some(("le", "+Inf".to_string())),
                     );
+                    self.emit_exemplars(timestamp, name, tags, f64::INFINITY, &metadata.exemplars);
                     self.emit_value(timestamp, name, "_sum", *sum, tags, None);
                     self.emit_value(timestamp, name, "_count", *count as f64, tags, None);
                 }
@@ -231,9 +281,19 @@ impl MetricCollector for StringCollector {
         Self { processed }
     }
 
-    fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue) {
+    fn emit_metadata(
+        &mut self,
+        name: &str,
+        fullname: &str,
+        value: &MetricValue,
+        metadata: &PrometheusMetadata,
+    ) {
         if !self.processed.contains_key(fullname) {
-            let header = Self::encode_header(name, fullname, value);
+            let help = match &metadata.help {
+                Some(help) => help.replace('\\', "\\\\").replace('\n', "\\n"),
+                None => name.to_owned(),
+            };
+            let header = Self::encode_header(&help, fullname, value);
             self.processed.insert(fullname.into(), header);
         }
     }
@@ -292,11 +352,11 @@ impl StringCollector {
         .ok();
     }
 
-    fn encode_header(name: &str, fullname: &str, value: &MetricValue) -> String {
+    fn encode_header(help: &str, fullname: &str, value: &MetricValue) -> String {
         let r#type = prometheus_metric_type(value).as_str();
         format!(
             "# HELP {} {}\n# TYPE {} {}\n",
-            fullname, name, fullname, r#type
+            fullname, help, fullname, r#type
         )
     }
 
@@ -320,13 +380,28 @@ impl StringCollector {
 
 type Labels = Vec<proto::Label>;
 
+/// The data collected for a single series, identified by its labels.
+#[derive(Default)]
+struct Series {
+    samples: Vec<proto::Sample>,
+    exemplars: Vec<proto::Exemplar>,
+    histograms: Vec<proto::Histogram>,
+}
+
 pub(super) struct TimeSeries {
-    buffer: IndexMap<Labels, Vec<proto::Sample>>,
+    buffer: IndexMap<Labels, Series>,
     metadata: IndexMap<String, proto::MetricMetadata>,
     timestamp: Option<i64>,
+    native_histogram_schema: Option<i32>,
 }
 
 impl TimeSeries {
+    /// Encode histogram distributions as native histograms using the given schema.
+    pub(super) const fn with_native_histogram_schema(mut self, schema: Option<i32>) -> Self {
+        self.native_histogram_schema = schema;
+        self
+    }
+
     fn make_labels(
         tags: Option<&BTreeMap<String, String>>,
         name: &str,
@@ -368,17 +443,24 @@ impl MetricCollector for TimeSeries {
             buffer: Default::default(),
             metadata: Default::default(),
             timestamp: None,
+            native_histogram_schema: None,
         }
     }
 
-    fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue) {
+    fn emit_metadata(
+        &mut self,
+        name: &str,
+        fullname: &str,
+        value: &MetricValue,
+        metadata: &PrometheusMetadata,
+    ) {
         if !self.metadata.contains_key(name) {
             let r#type = prometheus_metric_type(value);
             let metadata = proto::MetricMetadata {
                 r#type: r#type as i32,
                 metric_family_name: fullname.into(),
-                help: name.into(),
-                unit: String::new(),
+                help: metadata.help.clone().unwrap_or_else(|| name.into()),
+                unit: metadata.unit.clone().unwrap_or_default(),
             };
             self.metadata.insert(name.into(), metadata);
         }
@@ -397,14 +479,86 @@ impl MetricCollector for TimeSeries {
         self.buffer
             .entry(Self::make_labels(tags, name, suffix, extra))
             .or_default()
+            .samples
             .push(proto::Sample { value, timestamp });
     }
 
+    fn emit_exemplars(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        tags: Option<&BTreeMap<String, String>>,
+        upper_limit: f64,
+        exemplars: &[Exemplar],
+    ) {
+        let mut exemplars = exemplars
+            .iter()
+            .filter(|exemplar| exemplar.bucket == upper_limit)
+            .peekable();
+        if exemplars.peek().is_none() {
+            return;
+        }
+
+        let default_timestamp = timestamp_millis.unwrap_or_else(|| self.default_timestamp());
+        let le = if upper_limit.is_infinite() {
+            "+Inf".to_string()
+        } else {
+            upper_limit.to_string()
+        };
+        let exemplars = exemplars
+            .map(|exemplar| proto::Exemplar {
+                labels: exemplar
+                    .labels
+                    .iter()
+                    .map(|(name, value)| proto::Label {
+                        name: name.clone(),
+                        value: value.clone(),
+                    })
+                    .collect(),
+                value: exemplar.value,
+                timestamp: exemplar.timestamp.unwrap_or(default_timestamp),
+            })
+            .collect::<Vec<_>>();
+        self.buffer
+            .entry(Self::make_labels(tags, name, "_bucket", Some(("le", le))))
+            .or_default()
+            .exemplars
+            .extend(exemplars);
+    }
+
+    fn native_histogram_schema(&self) -> Option<i32> {
+        self.native_histogram_schema
+    }
+
+    fn emit_native_histogram(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        tags: Option<&BTreeMap<String, String>>,
+        samples: &[Sample],
+    ) {
+        let schema = self
+            .native_histogram_schema
+            .expect("native histogram schema not set");
+        let timestamp = timestamp_millis.unwrap_or_else(|| self.default_timestamp());
+        let histogram = encode_native_histogram(schema, timestamp, samples);
+        self.buffer
+            .entry(Self::make_labels(tags, name, "", None))
+            .or_default()
+            .histograms
+            .push(histogram);
+    }
+
     fn finish(self) -> proto::WriteRequest {
         let timeseries = self
             .buffer
             .into_iter()
-            .map(|(labels, samples)| proto::TimeSeries { labels, samples })
+            .map(|(labels, series)| proto::TimeSeries {
+                labels,
+                samples: series.samples,
+                exemplars: series.exemplars,
+                histograms: series.histograms,
+            })
             .collect::<Vec<_>>();
         let metadata = self
             .metadata
@@ -418,6 +572,74 @@ impl MetricCollector for TimeSeries {
     }
 }
 
+/// Encode distribution samples as a native histogram with exponential buckets. With the given
+/// schema, each power of two is divided into `2^schema` buckets, and bucket `index` has the
+/// upper boundary `2^(index * 2^-schema)`. Zero values are counted in the zero bucket.
+fn encode_native_histogram(schema: i32, timestamp: i64, samples: &[Sample]) -> proto::Histogram {
+    use proto::histogram::{Count, ZeroCount};
+
+    let scale = 2_f64.powi(schema);
+    let index = |value: f64| (value.log2() * scale).ceil() as i32;
+
+    let mut positive = BTreeMap::<i32, u64>::new();
+    let mut negative = BTreeMap::<i32, u64>::new();
+    let mut zero_count = 0;
+    let mut count = 0;
+    let mut sum = 0.0;
+    for sample in samples {
+        let rate = u64::from(sample.rate);
+        count += rate;
+        sum += sample.value * sample.rate as f64;
+        if sample.value > 0.0 {
+            *positive.entry(index(sample.value)).or_default() += rate;
+        } else if sample.value < 0.0 {
+            *negative.entry(index(-sample.value)).or_default() += rate;
+        } else {
+            zero_count += rate;
+        }
+    }
+
+    let (positive_spans, positive_deltas) = encode_native_buckets(&positive);
+    let (negative_spans, negative_deltas) = encode_native_buckets(&negative);
+    proto::Histogram {
+        count: Some(Count::CountInt(count)),
+        sum,
+        schema,
+        zero_threshold: 0.0,
+        zero_count: Some(ZeroCount::ZeroCountInt(zero_count)),
+        negative_spans,
+        negative_deltas,
+        negative_counts: vec![],
+        positive_spans,
+        positive_deltas,
+        positive_counts: vec![],
+        reset_hint: proto::histogram::ResetHint::Unknown as i32,
+        timestamp,
+    }
+}
+
+/// Encode sparse bucket counts as spans of consecutive buckets and the deltas between the counts
+/// of subsequent buckets.
+fn encode_native_buckets(buckets: &BTreeMap<i32, u64>) -> (Vec<proto::BucketSpan>, Vec<i64>) {
+    let mut spans = Vec::<proto::BucketSpan>::new();
+    let mut deltas = Vec::with_capacity(buckets.len());
+    let mut next_index = None;
+    let mut previous_count = 0;
+    for (&index, &count) in buckets {
+        match (next_index, spans.last_mut()) {
+            (Some(next), Some(span)) if next == index => span.length += 1,
+            _ => spans.push(proto::BucketSpan {
+                offset: next_index.map_or(index, |next| index - next),
+                length: 1,
+            }),
+        }
+        next_index = Some(index + 1);
+        deltas.push(count as i64 - previous_count as i64);
+        previous_count = count;
+    }
+    (spans, deltas)
+}
+
 const fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType {
     use proto::MetricType;
     match metric_value {
@@ -495,6 +717,8 @@ mod tests {
                                 value: $svalue,
                                 timestamp: $timestamp,
                             }],
+                            exemplars: vec![],
+                            histograms: vec![],
                         },
                     )*
                 ],
@@ -916,6 +1140,122 @@ mod tests {
         assert!(encoded.timeseries[0].samples[0].timestamp >= now);
     }
 
+    #[test]
+    fn encodes_metadata_and_exemplars_request() {
+        let mut metric = Metric::new(
+            "requests".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vector_core::buckets![1.0 => 1, 2.0 => 2],
+                count: 4,
+                sum: 5.0,
+            },
+        )
+        .with_timestamp(Some(timestamp()));
+        PrometheusMetadata {
+            help: Some("Request duration.".to_owned()),
+            unit: Some("seconds".to_owned()),
+            exemplars: vec![
+                Exemplar {
+                    bucket: 2.0,
+                    value: 1.5,
+                    timestamp: Some(1612325106000),
+                    labels: BTreeMap::from([("trace_id".to_owned(), "abc".to_owned())]),
+                },
+                Exemplar {
+                    bucket: f64::INFINITY,
+                    value: 7.0,
+                    timestamp: None,
+                    labels: BTreeMap::new(),
+                },
+            ],
+        }
+        .attach(&mut metric);
+
+        let encoded = encode_one::<TimeSeries>(None, &[], &[], &metric);
+        assert_eq!(encoded.metadata[0].help, "Request duration.");
+        assert_eq!(encoded.metadata[0].unit, "seconds");
+
+        let exemplars = encoded
+            .timeseries
+            .iter()
+            .map(|series| series.exemplars.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            exemplars,
+            vec![
+                vec![],
+                vec![proto::Exemplar {
+                    labels: vec![proto::Label {
+                        name: "trace_id".into(),
+                        value: "abc".into(),
+                    }],
+                    value: 1.5,
+                    timestamp: 1612325106000,
+                }],
+                vec![proto::Exemplar {
+                    labels: vec![],
+                    value: 7.0,
+                    timestamp: 1612325106789,
+                }],
+                vec![],
+                vec![],
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_native_histogram_request() {
+        use proto::histogram::{Count, ZeroCount};
+
+        let metric = Metric::new(
+            "requests".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Distribution {
+                samples: vector_core::samples![0.0 => 1, 1.0 => 2, 2.0 => 1, 8.0 => 1, -1.0 => 1],
+                statistic: StatisticKind::Histogram,
+            },
+        )
+        .with_timestamp(Some(timestamp()));
+
+        let mut collector = TimeSeries::new().with_native_histogram_schema(Some(0));
+        collector.encode_metric(None, &[], &[], &metric);
+        let encoded = collector.finish();
+
+        assert_eq!(encoded.timeseries.len(), 1);
+        assert!(encoded.timeseries[0].samples.is_empty());
+        assert_eq!(
+            encoded.timeseries[0].histograms,
+            vec![proto::Histogram {
+                count: Some(Count::CountInt(6)),
+                sum: 11.0,
+                schema: 0,
+                zero_threshold: 0.0,
+                zero_count: Some(ZeroCount::ZeroCountInt(1)),
+                negative_spans: vec![proto::BucketSpan {
+                    offset: 0,
+                    length: 1
+                }],
+                negative_deltas: vec![1],
+                negative_counts: vec![],
+                positive_spans: vec![
+                    proto::BucketSpan {
+                        offset: 0,
+                        length: 2
+                    },
+                    proto::BucketSpan {
+                        offset: 1,
+                        length: 1
+                    },
+                ],
+                positive_deltas: vec![2, -1, 0],
+                positive_counts: vec![],
+                reset_hint: 0,
+                timestamp: 1612325106789,
+            }]
+        );
+    }
+
     fn timestamp() -> DateTime<Utc> {
         Utc.ymd(2021, 2, 3).and_hms_milli(4, 5, 6, 789)
     }
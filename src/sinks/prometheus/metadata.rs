@@ -0,0 +1,166 @@
+//! Prometheus-specific metric information that has no place in Vector's metric data model.
+//!
+//! The help text, unit and exemplars of a metric are carried in the event metadata under the
+//! `prometheus` key, so that they survive a trip through a topology from the
+//! `prometheus_remote_write` source to the `prometheus_remote_write` sink. Like all event
+//! metadata, they are not persisted in disk buffers.
+
+use std::collections::BTreeMap;
+
+use vector_core::event::{Metric, Value};
+
+const METADATA_KEY: &str = "prometheus";
+
+/// An exemplar attached to a histogram bucket.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Exemplar {
+    /// The upper limit of the bucket the exemplar belongs to.
+    pub(crate) bucket: f64,
+    pub(crate) value: f64,
+    pub(crate) timestamp: Option<i64>,
+    pub(crate) labels: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct PrometheusMetadata {
+    pub(crate) help: Option<String>,
+    pub(crate) unit: Option<String>,
+    pub(crate) exemplars: Vec<Exemplar>,
+}
+
+impl PrometheusMetadata {
+    /// Read the Prometheus metadata from the event metadata of the given metric.
+    pub(crate) fn from_metric(metric: &Metric) -> Self {
+        let object = match metric
+            .metadata()
+            .value()
+            .as_object()
+            .and_then(|object| object.get(METADATA_KEY))
+            .and_then(Value::as_object)
+        {
+            Some(object) => object,
+            None => return Self::default(),
+        };
+
+        let string = |key: &str| {
+            object
+                .get(key)
+                .and_then(Value::as_str)
+                .map(|value| value.into_owned())
+        };
+        let exemplars = object
+            .get("exemplars")
+            .and_then(Value::as_array)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|exemplar| {
+                let exemplar = exemplar.as_object()?;
+                let float = |key: &str| match exemplar.get(key)? {
+                    Value::Float(value) => Some(value.into_inner()),
+                    Value::Integer(value) => Some(*value as f64),
+                    _ => None,
+                };
+                Some(Exemplar {
+                    bucket: float("bucket").unwrap_or(f64::INFINITY),
+                    value: float("value")?,
+                    timestamp: exemplar.get("timestamp").and_then(Value::as_integer),
+                    labels: exemplar
+                        .get("labels")
+                        .and_then(Value::as_object)
+                        .map(|labels| {
+                            labels
+                                .iter()
+                                .map(|(name, value)| (name.clone(), value.to_string_lossy()))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                })
+            })
+            .collect();
+
+        Self {
+            help: string("help"),
+            unit: string("unit"),
+            exemplars,
+        }
+    }
+
+    /// Store the Prometheus metadata in the event metadata of the given metric.
+    pub(crate) fn attach(self, metric: &mut Metric) {
+        if self == Self::default() {
+            return;
+        }
+
+        let mut object = BTreeMap::new();
+        if let Some(help) = self.help {
+            object.insert("help".to_owned(), Value::from(help));
+        }
+        if let Some(unit) = self.unit {
+            object.insert("unit".to_owned(), Value::from(unit));
+        }
+        if !self.exemplars.is_empty() {
+            let exemplars = self
+                .exemplars
+                .into_iter()
+                .map(|exemplar| {
+                    let labels = exemplar
+                        .labels
+                        .into_iter()
+                        .map(|(name, value)| (name, Value::from(value)))
+                        .collect::<BTreeMap<_, _>>();
+                    let mut object = BTreeMap::new();
+                    object.insert("bucket".to_owned(), Value::from(exemplar.bucket));
+                    object.insert("value".to_owned(), Value::from(exemplar.value));
+                    if let Some(timestamp) = exemplar.timestamp {
+                        object.insert("timestamp".to_owned(), Value::from(timestamp));
+                    }
+                    object.insert("labels".to_owned(), Value::from(labels));
+                    Value::from(object)
+                })
+                .collect::<Vec<_>>();
+            object.insert("exemplars".to_owned(), Value::from(exemplars));
+        }
+
+        let value = metric.metadata_mut().value_mut();
+        if !value.is_object() {
+            *value = Value::from(BTreeMap::new());
+        }
+        value
+            .as_object_mut_unwrap()
+            .insert(METADATA_KEY.to_owned(), Value::from(object));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{MetricKind, MetricValue};
+
+    use super::*;
+
+    #[test]
+    fn roundtrips_through_event_metadata() {
+        let mut metric = Metric::new(
+            "requests",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        );
+        assert_eq!(
+            PrometheusMetadata::from_metric(&metric),
+            PrometheusMetadata::default()
+        );
+
+        let metadata = PrometheusMetadata {
+            help: Some("Number of requests.".to_owned()),
+            unit: None,
+            exemplars: vec![Exemplar {
+                bucket: 0.5,
+                value: 0.25,
+                timestamp: Some(1612325106789),
+                labels: BTreeMap::from([("trace_id".to_owned(), "abc".to_owned())]),
+            }],
+        };
+        metadata.clone().attach(&mut metric);
+
+        assert_eq!(PrometheusMetadata::from_metric(&metric), metadata);
+    }
+}
@@ -41,6 +41,24 @@ impl SinkBatchSettings for PrometheusRemoteWriteDefaultBatchSettings {
 enum Errors {
     #[snafu(display(r#"Prometheus remote_write sink cannot accept "set" metrics"#))]
     SetMetricInvalid,
+    #[snafu(display("Native histogram schema must be between -4 and 8, got {}", schema))]
+    InvalidNativeHistogramSchema { schema: i32 },
+}
+
+/// How histogram distributions are encoded.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HistogramFormat {
+    /// Classic histograms with the fixed `buckets`.
+    #[derivative(Default)]
+    Classic,
+    /// Native histograms with exponential buckets.
+    Native,
+}
+
+const fn default_native_histogram_schema() -> i32 {
+    3
 }
 
 #[derive(Debug, Default, Deserialize, Serialize)]
@@ -55,6 +73,11 @@ pub struct RemoteWriteConfig {
     #[serde(default = "super::default_summary_quantiles")]
     pub quantiles: Vec<f64>,
 
+    #[serde(default)]
+    pub histogram_format: HistogramFormat,
+    #[serde(default = "default_native_histogram_schema")]
+    pub native_histogram_schema: i32,
+
     #[serde(default)]
     pub batch: BatchConfig<PrometheusRemoteWriteDefaultBatchSettings>,
     #[serde(default)]
@@ -87,6 +110,16 @@ impl SinkConfig for RemoteWriteConfig {
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let buckets = self.buckets.clone();
         let quantiles = self.quantiles.clone();
+        let native_histogram_schema = match self.histogram_format {
+            HistogramFormat::Classic => None,
+            HistogramFormat::Native => {
+                let schema = self.native_histogram_schema;
+                if !(-4..=8).contains(&schema) {
+                    return Err(Errors::InvalidNativeHistogramSchema { schema }.into());
+                }
+                Some(schema)
+            }
+        };
 
         let client = HttpClient::new(tls_settings, cx.proxy())?;
         let tenant_id = self.tenant_id.clone();
@@ -99,6 +132,7 @@ impl SinkConfig for RemoteWriteConfig {
             client,
             buckets,
             quantiles,
+            native_histogram_schema,
             auth,
         };
 
@@ -185,12 +219,14 @@ struct RemoteWriteService {
     client: HttpClient,
     buckets: Vec<f64>,
     quantiles: Vec<f64>,
+    native_histogram_schema: Option<i32>,
     auth: Option<Auth>,
 }
 
 impl RemoteWriteService {
     fn encode_events(&self, metrics: Vec<Metric>) -> Bytes {
-        let mut time_series = collector::TimeSeries::new();
+        let mut time_series =
+            collector::TimeSeries::new().with_native_histogram_schema(self.native_histogram_schema);
         for metric in metrics {
             time_series.encode_metric(
                 self.default_namespace.as_deref(),
@@ -260,7 +296,7 @@ mod tests {
     use super::*;
     use crate::{
         config::SinkContext,
-        event::{MetricKind, MetricValue},
+        event::{MetricKind, MetricValue, StatisticKind},
         sinks::util::test::build_test_server,
         test_util,
     };
@@ -386,6 +422,51 @@ mod tests {
         check_output(2, "counter-1", 26.0);
     }
 
+    #[tokio::test]
+    async fn sends_native_histograms() {
+        let event = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 2, 4.0 => 1],
+                statistic: StatisticKind::Histogram,
+            },
+        )
+        .with_timestamp(Some(chrono::Utc::now()))
+        .into();
+        let outputs = send_request(
+            indoc! {r#"
+                histogram_format = "native"
+                native_histogram_schema = 0
+            "#},
+            vec![event],
+        )
+        .await;
+
+        assert_eq!(outputs.len(), 1);
+        let (_, req) = &outputs[0];
+        assert_eq!(req.timeseries.len(), 1);
+        assert_eq!(req.timeseries[0].labels, labels!("__name__" => "latency"));
+        assert!(req.timeseries[0].samples.is_empty());
+        let histogram = &req.timeseries[0].histograms[0];
+        assert_eq!(histogram.schema, 0);
+        assert_eq!(histogram.sum, 6.0);
+        assert_eq!(histogram.positive_deltas, vec![2, -1]);
+        assert_eq!(req.metadata[0].r#type, proto::MetricType::Histogram as i32);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_native_histogram_schema() {
+        let config: RemoteWriteConfig = toml::from_str(indoc! {r#"
+            endpoint = "http://localhost:9090/write"
+            histogram_format = "native"
+            native_histogram_schema = 9
+        "#})
+        .unwrap();
+
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+
     async fn send_request(
         config: &str,
         events: Vec<Event>,
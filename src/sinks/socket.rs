@@ -1,6 +1,7 @@
 use codecs::{
-    encoding::{Framer, FramingConfig, SerializerConfig},
-    BytesEncoder, JsonSerializerConfig, NewlineDelimitedEncoder, TextSerializerConfig,
+    encoding::{Framer, FramingConfig, Serializer, SerializerConfig},
+    BytesEncoder, JsonSerializerConfig, NewlineDelimitedEncoder, OctetCountingEncoder,
+    TextSerializerConfig,
 };
 use serde::{Deserialize, Serialize};
 
@@ -96,11 +97,14 @@ impl SinkConfig for SocketSinkConfig {
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let transformer = self.encoding.transformer();
         let (framer, serializer) = self.encoding.encoding();
-        let framer = framer.unwrap_or_else(|| match self.mode {
-            Mode::Tcp(_) => NewlineDelimitedEncoder::new().into(),
-            Mode::Udp(_) => BytesEncoder::new().into(),
+        let framer = framer.unwrap_or_else(|| match (&self.mode, &serializer) {
+            // Syslog over TCP is conventionally framed by octet counting, see
+            // https://tools.ietf.org/html/rfc6587#section-3.4.1.
+            (Mode::Tcp(_), Serializer::Syslog(_)) => OctetCountingEncoder::new().into(),
+            (Mode::Tcp(_), _) => NewlineDelimitedEncoder::new().into(),
+            (Mode::Udp(_), _) => BytesEncoder::new().into(),
             #[cfg(unix)]
-            Mode::Unix(_) => NewlineDelimitedEncoder::new().into(),
+            (Mode::Unix(_), _) => NewlineDelimitedEncoder::new().into(),
         });
         let encoder = Encoder::<Framer>::new(framer, serializer);
         match &self.mode {
@@ -232,6 +236,43 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn tcp_stream_syslog_octet_counting() {
+        use tokio::io::AsyncReadExt;
+
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(&addr).await.unwrap();
+        let config = SocketSinkConfig {
+            mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
+            encoding: EncodingConfigWithFramingAdapter::new(
+                None,
+                codecs::SyslogSerializerConfig::default().into(),
+            ),
+        };
+
+        let context = SinkContext::new_test();
+        let (sink, _healthcheck) = config.build(context).await.unwrap();
+
+        let events = vec![Event::from("first"), Event::from("second")];
+        run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut output = String::new();
+        socket.read_to_string(&mut output).await.unwrap();
+
+        let mut rest = output.as_str();
+        for message in ["first", "second"] {
+            let (length, tail) = rest.split_once(' ').expect("Missing octet count");
+            let (frame, tail) = tail.split_at(length.parse().unwrap());
+            assert!(frame.starts_with("<14>1 "));
+            assert!(frame.ends_with(&format!(" {}", message)));
+            rest = tail;
+        }
+        assert!(rest.is_empty());
+    }
+
     // This is a test that checks that we properly receive all events in the
     // case of a proper server side write side shutdown.
     //
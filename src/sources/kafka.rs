@@ -2,6 +2,7 @@ use std::{
     collections::{BTreeMap, HashMap, HashSet},
     io::Cursor,
     sync::Arc,
+    time::Duration,
 };
 
 use async_stream::stream;
@@ -16,6 +17,7 @@ use rdkafka::{
     config::ClientConfig,
     consumer::{Consumer, StreamConsumer},
     message::{BorrowedMessage, Headers, Message},
+    Offset, TopicPartitionList,
 };
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
@@ -45,6 +47,12 @@ enum BuildError {
     KafkaCreateError { source: rdkafka::error::KafkaError },
     #[snafu(display("Could not subscribe to Kafka topics: {}", source))]
     KafkaSubscribeError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Could not assign Kafka partitions: {}", source))]
+    KafkaAssignError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Could not look up Kafka offsets for timestamps: {}", source))]
+    KafkaOffsetsForTimesError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Exactly one of `topics` and `partitions` must be set"))]
+    TopicsOrPartitions,
 }
 
 /// Configuration for the `kafka` source.
@@ -64,8 +72,15 @@ pub struct KafkaSourceConfig {
     /// The Kafka topics names to read events from.
     ///
     /// Regular expression syntax is supported if the topic begins with `^`.
+    #[serde(default)]
     topics: Vec<String>,
 
+    /// The Kafka partitions to read events from, assigned statically instead of subscribing to `topics`.
+    ///
+    /// The consumer group is not rebalanced, but is still used to store the consumer offsets.
+    #[serde(default)]
+    partitions: Vec<KafkaPartitionAssignment>,
+
     /// The consumer group name to be used to consume events from Kafka.
     group_id: String,
 
@@ -154,6 +169,45 @@ pub struct KafkaSourceConfig {
     acknowledgements: AcknowledgementsConfig,
 }
 
+/// A statically assigned Kafka partition.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaPartitionAssignment {
+    /// The topic of the partition.
+    topic: String,
+
+    /// The partition number.
+    partition: i32,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    start_offset: KafkaStartOffset,
+}
+
+/// Where to start reading a statically assigned partition.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case", tag = "from")]
+pub enum KafkaStartOffset {
+    /// The offset stored for the consumer group, or the one given by `auto_offset_reset` if there is none.
+    #[derivative(Default)]
+    Stored,
+
+    /// The earliest offset in the partition.
+    Earliest,
+
+    /// The end of the partition, so that only new messages are read.
+    Latest,
+
+    /// The earliest offset whose timestamp is equal to or greater than the given one.
+    Timestamp {
+        /// The timestamp, in milliseconds since the Unix epoch.
+        timestamp_ms: i64,
+    },
+}
+
 const fn default_session_timeout_ms() -> u64 {
     10000 // default in librdkafka
 }
@@ -550,15 +604,68 @@ fn create_consumer(
         }
     }
 
+    if config.topics.is_empty() == config.partitions.is_empty() {
+        return Err(BuildError::TopicsOrPartitions.into());
+    }
+
     let consumer = client_config
         .create_with_context::<_, StreamConsumer<_>>(KafkaStatisticsContext)
         .context(KafkaCreateSnafu)?;
-    let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
-    consumer.subscribe(&topics).context(KafkaSubscribeSnafu)?;
+    if config.partitions.is_empty() {
+        let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
+        consumer.subscribe(&topics).context(KafkaSubscribeSnafu)?;
+    } else {
+        let assignment = partition_assignment(&consumer, config)?;
+        consumer.assign(&assignment).context(KafkaAssignSnafu)?;
+    }
 
     Ok(consumer)
 }
 
+fn partition_assignment(
+    consumer: &StreamConsumer<KafkaStatisticsContext>,
+    config: &KafkaSourceConfig,
+) -> crate::Result<TopicPartitionList> {
+    let mut assignment = TopicPartitionList::new();
+    let mut timestamps = TopicPartitionList::new();
+    for partition in &config.partitions {
+        let offset = match partition.start_offset {
+            KafkaStartOffset::Stored => Offset::Stored,
+            KafkaStartOffset::Earliest => Offset::Beginning,
+            KafkaStartOffset::Latest => Offset::End,
+            KafkaStartOffset::Timestamp { timestamp_ms } => {
+                // Resolved to an offset below.
+                timestamps
+                    .add_partition_offset(
+                        &partition.topic,
+                        partition.partition,
+                        Offset::Offset(timestamp_ms),
+                    )
+                    .context(KafkaAssignSnafu)?;
+                continue;
+            }
+        };
+        assignment
+            .add_partition_offset(&partition.topic, partition.partition, offset)
+            .context(KafkaAssignSnafu)?;
+    }
+
+    if timestamps.count() > 0 {
+        let timeout = Duration::from_millis(config.socket_timeout_ms);
+        let offsets = consumer
+            .offsets_for_times(timestamps, timeout)
+            .context(KafkaOffsetsForTimesSnafu)?;
+        for element in offsets.elements() {
+            element.error().context(KafkaOffsetsForTimesSnafu)?;
+            assignment
+                .add_partition_offset(element.topic(), element.partition(), element.offset())
+                .context(KafkaAssignSnafu)?;
+        }
+    }
+
+    Ok(assignment)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -601,6 +708,47 @@ mod test {
         assert!(create_consumer(&config).is_ok());
     }
 
+    #[tokio::test]
+    async fn consumer_create_with_partitions_ok() {
+        let config: KafkaSourceConfig = toml::from_str(&format!(
+            r#"
+            bootstrap_servers = "{}"
+            group_id = "group"
+
+            [[partitions]]
+            topic = "topic"
+            partition = 0
+
+            [[partitions]]
+            topic = "topic"
+            partition = 1
+            start_offset.from = "earliest"
+            "#,
+            kafka_address(9091)
+        ))
+        .unwrap();
+        assert!(create_consumer(&config).is_ok());
+    }
+
+    #[tokio::test]
+    async fn consumer_create_requires_topics_or_partitions() {
+        let config = KafkaSourceConfig {
+            topics: vec![],
+            ..make_config("topic", "group")
+        };
+        assert!(create_consumer(&config).is_err());
+
+        let config = KafkaSourceConfig {
+            partitions: vec![KafkaPartitionAssignment {
+                topic: "topic".into(),
+                partition: 0,
+                start_offset: KafkaStartOffset::Latest,
+            }],
+            ..make_config("topic", "group")
+        };
+        assert!(create_consumer(&config).is_err());
+    }
+
     #[tokio::test]
     async fn consumer_create_incorrect_auto_offset_reset() {
         let config = KafkaSourceConfig {
@@ -685,6 +833,50 @@ mod integration_test {
         send_receive(true, 2).await;
     }
 
+    #[tokio::test]
+    async fn consumes_assigned_partition_from_timestamp() {
+        let topic = format!("test-topic-{}", random_string(10));
+        let group_id = format!("test-group-{}", random_string(10));
+        let now = Utc::now().timestamp_millis();
+
+        send_events(&topic, 5, "key", "old", now - 60_000, "header", "value").await;
+        send_events(&topic, 5, "key", "new", now, "header", "value").await;
+
+        let config = KafkaSourceConfig {
+            topics: vec![],
+            partitions: vec![KafkaPartitionAssignment {
+                topic: topic.clone(),
+                partition: 0,
+                start_offset: KafkaStartOffset::Timestamp {
+                    timestamp_ms: now - 30_000,
+                },
+            }],
+            ..make_config(&topic, &group_id)
+        };
+
+        let (trigger_shutdown, shutdown, shutdown_done) = ShutdownSignal::new_wired();
+        let (tx, rx) = SourceSender::new_test();
+        let consumer = create_consumer(&config).unwrap();
+        tokio::spawn(kafka_source(
+            config,
+            consumer,
+            crate::codecs::Decoder::default(),
+            shutdown,
+            tx,
+            false,
+        ));
+        let events = collect_n(rx, 5).await;
+        drop(trigger_shutdown);
+        shutdown_done.await;
+
+        for (i, event) in events.into_iter().enumerate() {
+            assert_eq!(
+                event.as_log()[log_schema().message_key()],
+                format!("new {}", i).into()
+            );
+        }
+    }
+
     async fn send_receive(acknowledgements: bool, receive_count: usize) {
         const SEND_COUNT: usize = 10;
 
@@ -3,9 +3,12 @@ use std::{cmp::Ordering, collections::BTreeMap};
 use chrono::{DateTime, TimeZone, Utc};
 use prometheus_parser::{proto, GroupKind, MetricGroup, ParserError};
 
-use crate::event::{
-    metric::{Bucket, Metric, MetricKind, MetricValue, Quantile},
-    Event,
+use crate::{
+    event::{
+        metric::{Bucket, Metric, MetricKind, MetricValue, Quantile},
+        Event,
+    },
+    sinks::prometheus::metadata::{Exemplar, PrometheusMetadata},
 };
 
 fn has_values_or_none(tags: BTreeMap<String, String>) -> Option<BTreeMap<String, String>> {
@@ -33,15 +36,35 @@ pub(super) fn parse_request(request: proto::WriteRequest) -> Result<Vec<Event>,
     prometheus_parser::parse_request(request).map(reparse_groups)
 }
 
+fn convert_exemplars(exemplars: Vec<prometheus_parser::Exemplar>) -> Vec<Exemplar> {
+    exemplars
+        .into_iter()
+        .map(|exemplar| Exemplar {
+            bucket: exemplar.bucket,
+            value: exemplar.value,
+            timestamp: exemplar.timestamp,
+            labels: exemplar.labels,
+        })
+        .collect()
+}
+
 fn reparse_groups(groups: Vec<MetricGroup>) -> Vec<Event> {
     let mut result = Vec::new();
     let start = Utc::now();
 
     for group in groups {
+        // The help text and unit have no place in the metric data model, so they are carried
+        // in the event metadata along with any exemplars.
+        let metadata = PrometheusMetadata {
+            help: group.help,
+            unit: group.unit,
+            exemplars: Vec::new(),
+        };
+
         match group.metrics {
             GroupKind::Counter(metrics) => {
                 for (key, metric) in metrics {
-                    let counter = Metric::new(
+                    let mut counter = Metric::new(
                         group.name.clone(),
                         MetricKind::Absolute,
                         MetricValue::Counter {
@@ -51,12 +74,14 @@ fn reparse_groups(groups: Vec<MetricGroup>) -> Vec<Event> {
                     .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
                     .with_tags(has_values_or_none(key.labels));
 
+                    metadata.clone().attach(&mut counter);
+
                     result.push(counter.into());
                 }
             }
             GroupKind::Gauge(metrics) | GroupKind::Untyped(metrics) => {
                 for (key, metric) in metrics {
-                    let gauge = Metric::new(
+                    let mut gauge = Metric::new(
                         group.name.clone(),
                         MetricKind::Absolute,
                         MetricValue::Gauge {
@@ -66,6 +91,8 @@ fn reparse_groups(groups: Vec<MetricGroup>) -> Vec<Event> {
                     .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
                     .with_tags(has_values_or_none(key.labels));
 
+                    metadata.clone().attach(&mut gauge);
+
                     result.push(gauge.into());
                 }
             }
@@ -83,51 +110,57 @@ fn reparse_groups(groups: Vec<MetricGroup>) -> Vec<Event> {
                         buckets.pop();
                     }
 
-                    result.push(
-                        Metric::new(
-                            group.name.clone(),
-                            MetricKind::Absolute,
-                            MetricValue::AggregatedHistogram {
-                                buckets: buckets
-                                    .into_iter()
-                                    .map(|b| Bucket {
-                                        upper_limit: b.bucket,
-                                        count: b.count,
-                                    })
-                                    .collect(),
-                                count: metric.count,
-                                sum: metric.sum,
-                            },
-                        )
-                        .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
-                        .with_tags(has_values_or_none(key.labels))
-                        .into(),
-                    );
+                    let mut histogram = Metric::new(
+                        group.name.clone(),
+                        MetricKind::Absolute,
+                        MetricValue::AggregatedHistogram {
+                            buckets: buckets
+                                .into_iter()
+                                .map(|b| Bucket {
+                                    upper_limit: b.bucket,
+                                    count: b.count,
+                                })
+                                .collect(),
+                            count: metric.count,
+                            sum: metric.sum,
+                        },
+                    )
+                    .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
+                    .with_tags(has_values_or_none(key.labels));
+
+                    PrometheusMetadata {
+                        exemplars: convert_exemplars(metric.exemplars),
+                        ..metadata.clone()
+                    }
+                    .attach(&mut histogram);
+
+                    result.push(histogram.into());
                 }
             }
             GroupKind::Summary(metrics) => {
                 for (key, metric) in metrics {
-                    result.push(
-                        Metric::new(
-                            group.name.clone(),
-                            MetricKind::Absolute,
-                            MetricValue::AggregatedSummary {
-                                quantiles: metric
-                                    .quantiles
-                                    .into_iter()
-                                    .map(|q| Quantile {
-                                        quantile: q.quantile,
-                                        value: q.value,
-                                    })
-                                    .collect(),
-                                count: metric.count,
-                                sum: metric.sum,
-                            },
-                        )
-                        .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
-                        .with_tags(has_values_or_none(key.labels))
-                        .into(),
-                    );
+                    let mut summary = Metric::new(
+                        group.name.clone(),
+                        MetricKind::Absolute,
+                        MetricValue::AggregatedSummary {
+                            quantiles: metric
+                                .quantiles
+                                .into_iter()
+                                .map(|q| Quantile {
+                                    quantile: q.quantile,
+                                    value: q.value,
+                                })
+                                .collect(),
+                            count: metric.count,
+                            sum: metric.sum,
+                        },
+                    )
+                    .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
+                    .with_tags(has_values_or_none(key.labels));
+
+                    metadata.clone().attach(&mut summary);
+
+                    result.push(summary.into());
                 }
             }
         }
@@ -153,6 +186,78 @@ mod test {
         super::parse_text(text).map(|events| events.into_iter().map(Event::into_metric).collect())
     }
 
+    #[test]
+    fn attaches_request_metadata_and_exemplars() {
+        let label = |name: &str, value: &str| proto::Label {
+            name: name.into(),
+            value: value.into(),
+        };
+        let series = |name: &str, le: &str, value: f64, exemplars| proto::TimeSeries {
+            labels: vec![label("__name__", name), label("le", le)],
+            samples: vec![proto::Sample {
+                value,
+                timestamp: 1612411506789,
+            }],
+            exemplars,
+            histograms: vec![],
+        };
+        let request = proto::WriteRequest {
+            metadata: vec![proto::MetricMetadata {
+                r#type: proto::MetricType::Histogram as i32,
+                metric_family_name: "latency".into(),
+                help: "Request latency.".into(),
+                unit: "seconds".into(),
+            }],
+            timeseries: vec![
+                series(
+                    "latency_bucket",
+                    "1",
+                    2.0,
+                    vec![proto::Exemplar {
+                        labels: vec![label("trace_id", "abc")],
+                        value: 0.5,
+                        timestamp: 1612411506000,
+                    }],
+                ),
+                series("latency_bucket", "+Inf", 3.0, vec![]),
+                proto::TimeSeries {
+                    labels: vec![label("__name__", "latency_sum")],
+                    samples: vec![proto::Sample {
+                        value: 4.5,
+                        timestamp: 1612411506789,
+                    }],
+                    exemplars: vec![],
+                    histograms: vec![],
+                },
+                proto::TimeSeries {
+                    labels: vec![label("__name__", "latency_count")],
+                    samples: vec![proto::Sample {
+                        value: 3.0,
+                        timestamp: 1612411506789,
+                    }],
+                    exemplars: vec![],
+                    histograms: vec![],
+                },
+            ],
+        };
+
+        let events = parse_request(request).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            PrometheusMetadata::from_metric(events[0].as_metric()),
+            PrometheusMetadata {
+                help: Some("Request latency.".into()),
+                unit: Some("seconds".into()),
+                exemplars: vec![Exemplar {
+                    bucket: 1.0,
+                    value: 0.5,
+                    timestamp: Some(1612411506000),
+                    labels: btreemap! { "trace_id" => "abc" },
+                }],
+            }
+        );
+    }
+
     #[test]
     fn adds_timestamp_if_missing() {
         let now = Utc::now();
@@ -38,12 +38,15 @@ use crate::{
         ComponentKey, DataType, Input, Output, OutputId, ProxyConfig, SinkContext, SourceContext,
         TransformContext, TransformOuter,
     },
-    event::{EventArray, EventContainer},
+    event::{into_event_stream, EventArray, EventContainer},
     internal_events::EventsReceived,
     shutdown::SourceShutdownCoordinator,
     source_sender::CHUNK_SIZE,
     spawn_named,
-    transforms::{SyncTransform, TaskTransform, Transform, TransformOutputs, TransformOutputsBuf},
+    transforms::{
+        MultiOutputTaskTransform, SyncTransform, TaskTransform, Transform, TransformOutputs,
+        TransformOutputsBuf,
+    },
     utilization::wrap,
     SourceSender,
 };
@@ -553,6 +556,7 @@ fn build_transform(
             node.typetag,
             &node.key,
         ),
+        Transform::MultiOutputTask(t) => build_multi_output_task_transform(t, node, input_rx),
     }
 }
 
@@ -583,6 +587,48 @@ fn build_sync_transform(
     (task, output_controls)
 }
 
+fn build_multi_output_task_transform(
+    t: Box<dyn MultiOutputTaskTransform>,
+    node: TransformNode,
+    input_rx: BufferReceiver<EventArray>,
+) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
+    let (mut outputs, controls) = TransformOutputs::new(node.outputs);
+
+    let input_rx = crate::utilization::wrap(input_rx.into_stream());
+    let input_type = node.input_details.data_type();
+
+    let filtered = input_rx
+        .filter(move |events| ready(filter_events_type(events, input_type)))
+        .inspect(|events| {
+            emit!(EventsReceived {
+                count: events.len(),
+                byte_size: events.size_of(),
+            })
+        })
+        .flat_map(into_event_stream);
+    let mut stream = t.transform(Box::pin(filtered));
+    let transform = async move {
+        while let Some(mut buf) = stream.next().await {
+            outputs.send(&mut buf).await;
+        }
+        debug!("Finished.");
+        Ok(TaskOutput::Transform)
+    }
+    .boxed();
+
+    let mut output_controls = HashMap::new();
+    for (name, control) in controls {
+        let id = name
+            .map(|name| OutputId::from((&node.key, name)))
+            .unwrap_or_else(|| OutputId::from(&node.key));
+        output_controls.insert(id, control);
+    }
+
+    let task = Task::new(node.key.clone(), node.typetag, transform);
+
+    (task, output_controls)
+}
+
 struct Runner {
     transform: Box<dyn SyncTransform>,
     input_rx: Option<BufferReceiver<EventArray>>,
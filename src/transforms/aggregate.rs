@@ -1,17 +1,21 @@
 use std::{
-    collections::{btree_map::Entry, BTreeMap},
+    collections::{btree_map::Entry, BTreeMap, VecDeque},
     pin::Pin,
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use async_stream::stream;
+use chrono::{DateTime, Utc};
 use futures::{Stream, StreamExt};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
 
 use crate::{
     config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
     event::{metric, Event, EventMetadata},
     internal_events::{AggregateEventRecorded, AggregateFlushed, AggregateUpdateFailed},
+    metrics::AgentDDSketch,
     schema,
     transforms::{TaskTransform, Transform},
 };
@@ -22,12 +26,125 @@ pub struct AggregateConfig {
     /// The interval between flushes in milliseconds.
     #[serde(default = "default_interval_ms")]
     pub interval_ms: u64,
+
+    /// The window metrics are aggregated over.
+    pub window: WindowConfig,
+
+    /// Align the flushes to multiples of the interval since the Unix epoch,
+    /// so that windows start and end on wall-clock boundaries.
+    pub align_to_wall_clock: bool,
+
+    /// The tags removed from metrics before they are aggregated, rolling up
+    /// the series only differing by those tags.
+    pub drop_tags: Vec<String>,
+
+    /// The function aggregating each metric, by metric name. Metrics not
+    /// listed here are aggregated according to their kind.
+    pub functions: IndexMap<String, AggregationFunction>,
 }
 
 const fn default_interval_ms() -> u64 {
     10 * 1000
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum WindowConfig {
+    /// Each flush emits the metrics received since the previous flush.
+    Tumbling,
+    /// Each flush emits the metrics received over the last `length_ms`
+    /// milliseconds, which must be a multiple of the interval.
+    Sliding { length_ms: u64 },
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self::Tumbling
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "function", rename_all = "snake_case", deny_unknown_fields)]
+pub enum AggregationFunction {
+    /// The sum of the values, as a counter.
+    Sum,
+    /// The number of values, as a counter.
+    Count,
+    /// The smallest value, as a gauge.
+    Min,
+    /// The largest value, as a gauge.
+    Max,
+    /// The mean of the values, as a gauge.
+    Mean,
+    /// The last value, as a gauge.
+    Last,
+    /// The latest metric, whatever its type, as an absolute metric.
+    LatestAbsolute,
+    /// The given percentiles of the values, as a summary.
+    Percentiles { percentiles: Vec<f64> },
+}
+
+impl AggregationFunction {
+    /// Compute the value of the function, or `None` if there were no values.
+    fn finish(&self, stats: &Statistics) -> Option<(metric::MetricKind, metric::MetricValue)> {
+        use metric::{MetricKind::*, MetricValue::*};
+
+        if stats.count == 0 {
+            return None;
+        }
+        Some(match self {
+            Self::Sum => (Incremental, Counter { value: stats.sum }),
+            Self::Count => (
+                Incremental,
+                Counter {
+                    value: stats.count as f64,
+                },
+            ),
+            Self::Min => (Absolute, Gauge { value: stats.min }),
+            Self::Max => (Absolute, Gauge { value: stats.max }),
+            Self::Mean => (
+                Absolute,
+                Gauge {
+                    value: stats.sum / stats.count as f64,
+                },
+            ),
+            Self::Last => (Absolute, Gauge { value: stats.last }),
+            Self::Percentiles { percentiles } => {
+                let sketch = stats.sketch.as_ref()?;
+                let quantiles = percentiles
+                    .iter()
+                    .filter_map(|quantile| {
+                        sketch.quantile(*quantile).map(|value| metric::Quantile {
+                            quantile: *quantile,
+                            value,
+                        })
+                    })
+                    .collect();
+                (
+                    Absolute,
+                    AggregatedSummary {
+                        quantiles,
+                        count: stats.count,
+                        sum: stats.sum,
+                    },
+                )
+            }
+            // The latest metric is kept as is rather than as statistics.
+            Self::LatestAbsolute => return None,
+        })
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "The length of a sliding window must be a non-zero multiple of `interval_ms`"
+    ))]
+    InvalidWindowLength,
+    #[snafu(display("The percentiles of metric {:?} must be between 0 and 1", metric))]
+    InvalidPercentiles { metric: String },
+}
+
 inventory::submit! {
     TransformDescription::new::<AggregateConfig>("aggregate")
 }
@@ -54,57 +171,290 @@ impl TransformConfig for AggregateConfig {
     }
 }
 
-type MetricEntry = (metric::MetricData, EventMetadata);
+/// The statistics of the values of a metric aggregated by a function.
+#[derive(Clone, Debug)]
+struct Statistics {
+    count: u32,
+    sum: f64,
+    min: f64,
+    max: f64,
+    last: f64,
+    /// Only kept for the percentiles function.
+    sketch: Option<AgentDDSketch>,
+}
+
+impl Statistics {
+    /// Collect the values of a counter, gauge or distribution, or `None` for
+    /// other metric types.
+    fn from_value(value: &metric::MetricValue, with_sketch: bool) -> Option<Self> {
+        let mut stats = Self {
+            count: 0,
+            sum: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            last: 0.0,
+            sketch: with_sketch.then(AgentDDSketch::with_agent_defaults),
+        };
+        match value {
+            metric::MetricValue::Counter { value } | metric::MetricValue::Gauge { value } => {
+                stats.insert(*value, 1)
+            }
+            metric::MetricValue::Distribution { samples, .. } => {
+                for sample in samples {
+                    stats.insert(sample.value, sample.rate);
+                }
+            }
+            _ => return None,
+        }
+        Some(stats)
+    }
+
+    fn insert(&mut self, value: f64, count: u32) {
+        if count == 0 {
+            return;
+        }
+        self.count += count;
+        self.sum += value * count as f64;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.last = value;
+        if let Some(sketch) = self.sketch.as_mut() {
+            sketch.insert_n(value, count);
+        }
+    }
+
+    fn merge(&mut self, other: &Self) -> bool {
+        if other.count == 0 {
+            return true;
+        }
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.last = other.last;
+        match (self.sketch.as_mut(), other.sketch.as_ref()) {
+            (Some(sketch), Some(other)) => sketch.merge(other).is_ok(),
+            _ => true,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum AggregateValue {
+    /// Incremental values are added, and absolute values replace the
+    /// previous one.
+    Merged(metric::MetricData),
+    /// Each value replaces the previous one, whatever its kind.
+    Latest(metric::MetricData),
+    /// The values are collected for an aggregation function.
+    Statistics(Statistics),
+}
+
+#[derive(Clone, Debug)]
+struct MetricEntry {
+    value: AggregateValue,
+    metadata: EventMetadata,
+}
+
+impl MetricEntry {
+    /// Merge a newer entry into this one, returning `false` if the entries
+    /// couldn't be combined and the newer one replaced this one instead.
+    fn merge(&mut self, other: Self) -> bool {
+        match other.value {
+            AggregateValue::Merged(data) if data.kind == metric::MetricKind::Incremental => {
+                if let AggregateValue::Merged(existing) = &mut self.value {
+                    // In order to update (add) the new and old kind's must match
+                    if existing.kind == data.kind && existing.update(&data) {
+                        self.metadata.merge(other.metadata);
+                        return true;
+                    }
+                }
+                *self = Self {
+                    value: AggregateValue::Merged(data),
+                    metadata: other.metadata,
+                };
+                false
+            }
+            AggregateValue::Statistics(stats) => {
+                if let AggregateValue::Statistics(existing) = &mut self.value {
+                    self.metadata.merge(other.metadata);
+                    return existing.merge(&stats);
+                }
+                *self = Self {
+                    value: AggregateValue::Statistics(stats),
+                    metadata: other.metadata,
+                };
+                true
+            }
+            value => {
+                // Always replace/store
+                *self = Self {
+                    value,
+                    metadata: other.metadata,
+                };
+                true
+            }
+        }
+    }
+}
+
+fn merge_into(
+    map: &mut BTreeMap<metric::MetricSeries, MetricEntry>,
+    series: metric::MetricSeries,
+    entry: MetricEntry,
+) -> bool {
+    match map.entry(series) {
+        Entry::Occupied(mut existing) => existing.get_mut().merge(entry),
+        Entry::Vacant(vacant) => {
+            vacant.insert(entry);
+            true
+        }
+    }
+}
+
+/// The time from `since_epoch` until the next multiple of `interval` since
+/// the Unix epoch.
+fn time_to_boundary(since_epoch: Duration, interval: Duration) -> Duration {
+    let interval_ms = interval.as_millis().max(1);
+    let elapsed_ms = since_epoch.as_millis() % interval_ms;
+    Duration::from_millis((interval_ms - elapsed_ms) as u64)
+}
 
 //------------------------------------------------------------------------------
 
 #[derive(Debug)]
 pub struct Aggregate {
     interval: Duration,
-    map: BTreeMap<metric::MetricSeries, MetricEntry>,
+    align_to_wall_clock: bool,
+    sliding: bool,
+    /// The number of intervals making up a window.
+    window_intervals: usize,
+    drop_tags: Vec<String>,
+    functions: IndexMap<String, AggregationFunction>,
+    /// The metrics aggregated over each interval of the current window, the
+    /// last one being the current interval.
+    intervals: VecDeque<BTreeMap<metric::MetricSeries, MetricEntry>>,
 }
 
 impl Aggregate {
     pub fn new(config: &AggregateConfig) -> crate::Result<Self> {
+        let (sliding, window_intervals) = match config.window {
+            WindowConfig::Tumbling => (false, 1),
+            WindowConfig::Sliding { length_ms } => {
+                if length_ms == 0 || config.interval_ms == 0 || length_ms % config.interval_ms != 0
+                {
+                    return Err(BuildError::InvalidWindowLength.into());
+                }
+                (true, (length_ms / config.interval_ms) as usize)
+            }
+        };
+
+        for (metric, function) in &config.functions {
+            if let AggregationFunction::Percentiles { percentiles } = function {
+                if percentiles.is_empty()
+                    || percentiles
+                        .iter()
+                        .any(|percentile| !(0.0..=1.0).contains(percentile))
+                {
+                    return Err(BuildError::InvalidPercentiles {
+                        metric: metric.clone(),
+                    }
+                    .into());
+                }
+            }
+        }
+
         Ok(Self {
             interval: Duration::from_millis(config.interval_ms),
-            map: BTreeMap::new(),
+            align_to_wall_clock: config.align_to_wall_clock,
+            sliding,
+            window_intervals,
+            drop_tags: config.drop_tags.clone(),
+            functions: config.functions.clone(),
+            intervals: VecDeque::from(vec![BTreeMap::new()]),
         })
     }
 
     fn record(&mut self, event: Event) {
-        let (series, data, metadata) = event.into_metric().into_parts();
+        let (mut series, data, metadata) = event.into_metric().into_parts();
+        for tag in &self.drop_tags {
+            series.remove_tag(tag);
+        }
 
-        match data.kind {
-            metric::MetricKind::Incremental => match self.map.entry(series) {
-                Entry::Occupied(mut entry) => {
-                    let existing = entry.get_mut();
-                    // In order to update (add) the new and old kind's must match
-                    if existing.0.kind == data.kind && existing.0.update(&data) {
-                        existing.1.merge(metadata);
-                    } else {
+        let value = match self.functions.get(&series.name.name) {
+            None => AggregateValue::Merged(data),
+            Some(AggregationFunction::LatestAbsolute) => AggregateValue::Latest(data),
+            Some(function) => {
+                let with_sketch = matches!(function, AggregationFunction::Percentiles { .. });
+                match Statistics::from_value(&data.value, with_sketch) {
+                    Some(stats) => AggregateValue::Statistics(stats),
+                    None => {
                         emit!(AggregateUpdateFailed);
-                        *existing = (data, metadata);
+                        return;
                     }
                 }
-                Entry::Vacant(entry) => {
-                    entry.insert((data, metadata));
-                }
-            },
-            metric::MetricKind::Absolute => {
-                // Always replace/store
-                self.map.insert(series, (data, metadata));
             }
         };
 
+        let current = self
+            .intervals
+            .back_mut()
+            .expect("there is always a current interval");
+        if !merge_into(current, series, MetricEntry { value, metadata }) {
+            emit!(AggregateUpdateFailed);
+        }
+
         emit!(AggregateEventRecorded);
     }
 
+    fn finish(
+        &self,
+        series: metric::MetricSeries,
+        entry: MetricEntry,
+        timestamp: DateTime<Utc>,
+    ) -> Option<metric::Metric> {
+        let data = match entry.value {
+            // Over sliding windows, values are emitted several times, so they
+            // can only be absolute.
+            AggregateValue::Merged(data) if self.sliding => data.into_absolute(),
+            AggregateValue::Merged(data) => data,
+            AggregateValue::Latest(data) => data.into_absolute(),
+            AggregateValue::Statistics(stats) => {
+                let function = self.functions.get(&series.name.name)?;
+                let (kind, value) = function.finish(&stats)?;
+                let kind = if self.sliding {
+                    metric::MetricKind::Absolute
+                } else {
+                    kind
+                };
+                metric::MetricData::from_parts(Some(timestamp), kind, value)
+            }
+        };
+        Some(metric::Metric::from_parts(series, data, entry.metadata))
+    }
+
     fn flush_into(&mut self, output: &mut Vec<Event>) {
-        let map = std::mem::take(&mut self.map);
+        let map = if self.window_intervals == 1 {
+            self.intervals.pop_front().unwrap_or_default()
+        } else {
+            let mut map = BTreeMap::new();
+            for interval in &self.intervals {
+                for (series, entry) in interval {
+                    merge_into(&mut map, series.clone(), entry.clone());
+                }
+            }
+            map
+        };
+        self.intervals.push_back(BTreeMap::new());
+        while self.intervals.len() > self.window_intervals {
+            self.intervals.pop_front();
+        }
+
+        let timestamp = Utc::now();
         for (series, entry) in map.into_iter() {
-            let metric = metric::Metric::from_parts(series, entry.0, entry.1);
-            output.push(Event::Metric(metric));
+            if let Some(metric) = self.finish(series, entry, timestamp) {
+                output.push(Event::Metric(metric));
+            }
         }
 
         emit!(AggregateFlushed);
@@ -119,7 +469,15 @@ impl TaskTransform<Event> for Aggregate {
     where
         Self: 'static,
     {
-        let mut flush_stream = tokio::time::interval(self.interval);
+        let mut flush_stream = if self.align_to_wall_clock {
+            let since_epoch = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            let start = tokio::time::Instant::now() + time_to_boundary(since_epoch, self.interval);
+            tokio::time::interval_at(start, self.interval)
+        } else {
+            tokio::time::interval(self.interval)
+        };
 
         Box::pin(stream! {
             let mut output = Vec::new();
@@ -173,6 +531,7 @@ mod tests {
     fn incremental() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -243,6 +602,7 @@ mod tests {
     fn absolute() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -308,6 +668,7 @@ mod tests {
     fn conflicting_value_type() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -365,6 +726,7 @@ mod tests {
     fn conflicting_kinds() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -415,6 +777,227 @@ mod tests {
         assert_eq!(&summed, &out[0]);
     }
 
+    fn build(config: &str) -> Aggregate {
+        Aggregate::new(&toml::from_str::<AggregateConfig>(config).unwrap()).unwrap()
+    }
+
+    fn gauge(name: &'static str, value: f64) -> Event {
+        make_metric(
+            name,
+            metric::MetricKind::Absolute,
+            metric::MetricValue::Gauge { value },
+        )
+    }
+
+    fn counter(name: &'static str, value: f64) -> Event {
+        make_metric(
+            name,
+            metric::MetricKind::Incremental,
+            metric::MetricValue::Counter { value },
+        )
+    }
+
+    #[test]
+    fn aggregation_functions() {
+        let mut agg = build(
+            r#"
+[functions]
+count = { function = "count" }
+last = { function = "last" }
+max = { function = "max" }
+mean = { function = "mean" }
+min = { function = "min" }
+sum = { function = "sum" }
+"#,
+        );
+
+        for name in ["count", "last", "max", "mean", "min", "sum"] {
+            for value in [1.0, 4.0, 2.5] {
+                agg.record(gauge(name, value));
+            }
+        }
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+
+        let values = out
+            .iter()
+            .map(|event| {
+                let metric = event.as_metric();
+                (metric.name(), metric.kind(), metric.value().clone())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                (
+                    "count",
+                    metric::MetricKind::Incremental,
+                    metric::MetricValue::Counter { value: 3.0 }
+                ),
+                (
+                    "last",
+                    metric::MetricKind::Absolute,
+                    metric::MetricValue::Gauge { value: 2.5 }
+                ),
+                (
+                    "max",
+                    metric::MetricKind::Absolute,
+                    metric::MetricValue::Gauge { value: 4.0 }
+                ),
+                (
+                    "mean",
+                    metric::MetricKind::Absolute,
+                    metric::MetricValue::Gauge { value: 2.5 }
+                ),
+                (
+                    "min",
+                    metric::MetricKind::Absolute,
+                    metric::MetricValue::Gauge { value: 1.0 }
+                ),
+                (
+                    "sum",
+                    metric::MetricKind::Incremental,
+                    metric::MetricValue::Counter { value: 7.5 }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn percentiles_and_latest_absolute() {
+        let mut agg = build(
+            r#"
+[functions.latency]
+function = "percentiles"
+percentiles = [0.5, 0.99]
+
+[functions.queue]
+function = "latest_absolute"
+"#,
+        );
+
+        let samples = (1..=100)
+            .map(|value| metric::Sample {
+                value: value as f64,
+                rate: 1,
+            })
+            .collect();
+        agg.record(make_metric(
+            "latency",
+            metric::MetricKind::Incremental,
+            metric::MetricValue::Distribution {
+                samples,
+                statistic: metric::StatisticKind::Histogram,
+            },
+        ));
+        agg.record(counter("queue", 3.0));
+        agg.record(counter("queue", 5.0));
+
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(2, out.len());
+
+        let latency = out[0].as_metric();
+        assert_eq!(latency.kind(), metric::MetricKind::Absolute);
+        match latency.value() {
+            metric::MetricValue::AggregatedSummary {
+                quantiles,
+                count,
+                sum,
+            } => {
+                assert_eq!(*count, 100);
+                assert_eq!(*sum, 5050.0);
+                assert_eq!(quantiles.len(), 2);
+                for (quantile, expected) in quantiles.iter().zip([50.0, 99.0]) {
+                    assert!((quantile.value - expected).abs() / expected < 0.02);
+                }
+            }
+            value => panic!("Unexpected latency value {:?}", value),
+        }
+
+        let queue = out[1].as_metric();
+        assert_eq!(queue.kind(), metric::MetricKind::Absolute);
+        assert_eq!(queue.value(), &metric::MetricValue::Counter { value: 5.0 });
+    }
+
+    #[test]
+    fn drop_tags() {
+        let mut agg = build(r#"drop_tags = ["host"]"#);
+
+        for host in ["a", "b"] {
+            let mut event = counter("requests", 2.0);
+            let metric = event.as_mut_metric();
+            metric.insert_tag("host".into(), host.into());
+            metric.insert_tag("path".into(), "/".into());
+            agg.record(event);
+        }
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+
+        assert_eq!(1, out.len());
+        let metric = out[0].as_metric();
+        assert_eq!(metric.value(), &metric::MetricValue::Counter { value: 4.0 });
+        assert_eq!(metric.tag_value("host"), None);
+        assert!(metric.tag_matches("path", "/"));
+    }
+
+    #[test]
+    fn sliding_window() {
+        assert!(Aggregate::new(
+            &toml::from_str::<AggregateConfig>(
+                r#"
+interval_ms = 1000
+window = { type = "sliding", length_ms = 2500 }
+"#
+            )
+            .unwrap()
+        )
+        .is_err());
+
+        let mut agg = build(
+            r#"
+interval_ms = 1000
+window = { type = "sliding", length_ms = 3000 }
+"#,
+        );
+
+        let flush = |agg: &mut Aggregate| {
+            let mut out = vec![];
+            agg.flush_into(&mut out);
+            out.into_iter()
+                .map(|event| {
+                    assert_eq!(event.as_metric().kind(), metric::MetricKind::Absolute);
+                    event.as_metric().value().clone()
+                })
+                .collect::<Vec<_>>()
+        };
+        let counter_value = |value| vec![metric::MetricValue::Counter { value }];
+
+        agg.record(counter("requests", 1.0));
+        assert_eq!(flush(&mut agg), counter_value(1.0));
+        agg.record(counter("requests", 2.0));
+        assert_eq!(flush(&mut agg), counter_value(3.0));
+        agg.record(counter("requests", 4.0));
+        assert_eq!(flush(&mut agg), counter_value(7.0));
+        // The first interval slid out of the window.
+        assert_eq!(flush(&mut agg), counter_value(6.0));
+        assert_eq!(flush(&mut agg), counter_value(4.0));
+        assert!(flush(&mut agg).is_empty());
+    }
+
+    #[test]
+    fn wall_clock_boundaries() {
+        let interval = Duration::from_secs(10);
+        assert_eq!(
+            time_to_boundary(Duration::from_millis(12_345), interval),
+            Duration::from_millis(7_655)
+        );
+        assert_eq!(
+            time_to_boundary(Duration::from_secs(20), interval),
+            interval
+        );
+    }
+
     #[tokio::test]
     async fn transform_shutdown() {
         let agg = toml::from_str::<AggregateConfig>(
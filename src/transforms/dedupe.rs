@@ -1,9 +1,18 @@
-use std::{future::ready, pin::Pin};
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    pin::Pin,
+    time::{Duration, Instant},
+};
 
+use async_stream::stream;
 use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use futures::{Stream, StreamExt};
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
 
 use crate::{
     config::{
@@ -11,11 +20,16 @@ use crate::{
         TransformDescription,
     },
     event::{Event, Value},
-    internal_events::DedupeEventDiscarded,
+    internal_events::{DedupeEventDiscarded, DedupeLoadError, DedupePersistError},
     schema,
-    transforms::{TaskTransform, Transform},
+    transforms::{MultiOutputTaskTransform, Transform, TransformOutputsBuf},
 };
 
+/// The name of the output duplicate events are routed to.
+pub const DUPLICATES_OUTPUT: &str = "duplicates";
+
+const STATE_FILE: &str = "dedupe.json";
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub enum FieldMatchConfig {
@@ -28,7 +42,11 @@ pub enum FieldMatchConfig {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct CacheConfig {
+    #[serde(default = "default_num_events")]
     pub num_events: usize,
+    /// How long an event is remembered. Events are remembered until they
+    /// are evicted from the cache if not set.
+    pub ttl_secs: Option<u64>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -38,10 +56,41 @@ pub struct DedupeConfig {
     pub fields: Option<FieldMatchConfig>,
     #[serde(default = "default_cache_config")]
     pub cache: CacheConfig,
+    /// Persist the cache in the data directory, so that duplicates of
+    /// events seen before a restart are still detected.
+    #[serde(default)]
+    pub persist: bool,
+    /// How often the cache is persisted.
+    #[serde(default = "default_persist_interval_secs")]
+    pub persist_interval_secs: u64,
+    /// The directory the cache is persisted in. Defaults to the global
+    /// `data_dir` option.
+    pub data_dir: Option<PathBuf>,
+}
+
+const fn default_num_events() -> usize {
+    5000
 }
 
 const fn default_cache_config() -> CacheConfig {
-    CacheConfig { num_events: 5000 }
+    CacheConfig {
+        num_events: default_num_events(),
+        ttl_secs: None,
+    }
+}
+
+const fn default_persist_interval_secs() -> u64 {
+    60
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`cache.ttl_secs` must be non-zero"))]
+    ZeroTtl,
+    #[snafu(display("`persist_interval_secs` must be non-zero"))]
+    ZeroPersistInterval,
+    #[snafu(display("Persisting the cache requires a component ID"))]
+    MissingComponentKey,
 }
 
 impl DedupeConfig {
@@ -63,7 +112,12 @@ impl DedupeConfig {
 
 pub struct Dedupe {
     fields: FieldMatchConfig,
-    cache: LruCache<CacheEntry, bool>,
+    /// The entries of the events seen, along with when they were first seen.
+    cache: LruCache<CacheEntry, Instant>,
+    ttl: Option<Duration>,
+    state_path: Option<PathBuf>,
+    persist_interval: Duration,
+    outputs: Vec<Output>,
 }
 
 inventory::submit! {
@@ -75,6 +129,9 @@ impl GenerateConfig for DedupeConfig {
         toml::Value::try_from(Self {
             fields: None,
             cache: default_cache_config(),
+            persist: false,
+            persist_interval_secs: default_persist_interval_secs(),
+            data_dir: None,
         })
         .unwrap()
     }
@@ -83,8 +140,31 @@ impl GenerateConfig for DedupeConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "dedupe")]
 impl TransformConfig for DedupeConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::event_task(Dedupe::new(self.clone())))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        if self.cache.ttl_secs == Some(0) {
+            return Err(BuildError::ZeroTtl.into());
+        }
+        if self.persist_interval_secs == 0 {
+            return Err(BuildError::ZeroPersistInterval.into());
+        }
+
+        let state_path = if self.persist {
+            let key = context
+                .key
+                .as_ref()
+                .ok_or(BuildError::MissingComponentKey)?;
+            let data_dir = context
+                .globals
+                .resolve_and_make_data_subdir(self.data_dir.as_ref(), key.id())?;
+            Some(data_dir.join(STATE_FILE))
+        } else {
+            None
+        };
+
+        Ok(Transform::multi_output_task(Dedupe::new(
+            self.clone(),
+            state_path,
+        )))
     }
 
     fn input(&self) -> Input {
@@ -92,7 +172,10 @@ impl TransformConfig for DedupeConfig {
     }
 
     fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
-        vec![Output::default(DataType::Log)]
+        vec![
+            Output::default(DataType::Log),
+            Output::default(DataType::Log).with_port(DUPLICATES_OUTPUT),
+        ]
     }
 
     fn transform_type(&self) -> &'static str {
@@ -125,7 +208,7 @@ type TypeId = u8;
 /// iterating over the fields of the incoming Events, we know that the
 /// CacheEntries for 2 equivalent events will always contain the fields in the
 /// same order.
-#[derive(PartialEq, Eq, Hash)]
+#[derive(PartialEq, Eq, Hash, Deserialize, Serialize)]
 enum CacheEntry {
     Match(Vec<Option<(TypeId, Bytes)>>),
     Ignore(Vec<(String, TypeId, Bytes)>),
@@ -147,26 +230,105 @@ const fn type_id_for_value(val: &Value) -> TypeId {
 }
 
 impl Dedupe {
-    pub fn new(config: DedupeConfig) -> Self {
+    pub fn new(config: DedupeConfig, state_path: Option<PathBuf>) -> Self {
         let num_entries = config.cache.num_events;
         let fields = config.fill_default_fields_match();
-        Self {
+        let mut dedupe = Self {
             fields,
             cache: LruCache::new(num_entries),
+            ttl: config.cache.ttl_secs.map(Duration::from_secs),
+            state_path,
+            persist_interval: Duration::from_secs(config.persist_interval_secs),
+            outputs: config.outputs(&schema::Definition::empty()),
+        };
+        if let Some(path) = &dedupe.state_path {
+            match load_state(path) {
+                Ok(Some(state)) => dedupe.restore(state, Instant::now()),
+                Ok(None) => {}
+                Err(error) => emit!(DedupeLoadError { error, path }),
+            }
         }
+        dedupe
     }
 
-    fn transform_one(&mut self, event: Event) -> Option<Event> {
+    fn is_expired(&self, seen_at: Instant, now: Instant) -> bool {
+        self.ttl
+            .map_or(false, |ttl| now.saturating_duration_since(seen_at) >= ttl)
+    }
+
+    /// Returns the event, or the event as an error if it is a duplicate of
+    /// an event seen less than `ttl` ago.
+    fn transform_one(&mut self, event: Event, now: Instant) -> Result<Event, Event> {
         let cache_entry = build_cache_entry(&event, &self.fields);
-        if self.cache.put(cache_entry, true).is_some() {
-            emit!(DedupeEventDiscarded { event });
-            None
+        let duplicate = self
+            .cache
+            .get(&cache_entry)
+            .copied()
+            .map_or(false, |seen_at| !self.is_expired(seen_at, now));
+        if duplicate {
+            emit!(DedupeEventDiscarded { event: &event });
+            Err(event)
         } else {
-            Some(event)
+            self.cache.put(cache_entry, now);
+            Ok(event)
+        }
+    }
+
+    /// The unexpired entries of the cache, from the least to the most
+    /// recently used, along with the time they were first seen.
+    fn state(&self, now: Instant) -> Vec<(&CacheEntry, DateTime<Utc>)> {
+        let wall_now = Utc::now();
+        self.cache
+            .iter()
+            .rev()
+            .filter(|(_, seen_at)| !self.is_expired(**seen_at, now))
+            .map(|(entry, seen_at)| {
+                let age = chrono::Duration::from_std(now.saturating_duration_since(*seen_at))
+                    .unwrap_or_else(|_| chrono::Duration::zero());
+                (entry, wall_now - age)
+            })
+            .collect()
+    }
+
+    fn restore(&mut self, state: Vec<(CacheEntry, DateTime<Utc>)>, now: Instant) {
+        let wall_now = Utc::now();
+        for (entry, seen_at) in state {
+            let age = (wall_now - seen_at).to_std().unwrap_or_default();
+            if self.ttl.map_or(false, |ttl| age >= ttl) {
+                continue;
+            }
+            self.cache.put(entry, now.checked_sub(age).unwrap_or(now));
+        }
+    }
+
+    fn persist(&self) {
+        if let Some(path) = &self.state_path {
+            if let Err(error) = save_state(path, &self.state(Instant::now())) {
+                emit!(DedupePersistError { error, path });
+            }
         }
     }
 }
 
+fn load_state(path: &Path) -> io::Result<Option<Vec<(CacheEntry, DateTime<Utc>)>>> {
+    match fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map(Some).map_err(Into::into),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Write the state to a temporary file first, so that a crash while
+/// persisting doesn't lose the previous state.
+fn save_state(path: &Path, state: &[(&CacheEntry, DateTime<Utc>)]) -> io::Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+    let mut file = fs::File::create(&tmp_path)?;
+    serde_json::to_writer(&mut file, state)?;
+    file.flush()?;
+    file.sync_all()?;
+    fs::rename(tmp_path, path)
+}
+
 /// Takes in an Event and returns a CacheEntry to place into the LRU cache
 /// containing all relevant information for the fields that need matching
 /// against according to the specified FieldMatchConfig.
@@ -203,16 +365,51 @@ fn build_cache_entry(event: &Event, fields: &FieldMatchConfig) -> CacheEntry {
     }
 }
 
-impl TaskTransform<Event> for Dedupe {
+impl MultiOutputTaskTransform for Dedupe {
     fn transform(
         self: Box<Self>,
-        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
-    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
-    where
-        Self: 'static,
-    {
-        let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = TransformOutputsBuf> + Send>> {
+        let mut me = self;
+
+        // The interval is only created when persisting, so that the transform
+        // can run without a timer otherwise.
+        let mut persist_stream = me
+            .state_path
+            .is_some()
+            .then(|| tokio::time::interval(me.persist_interval));
+
+        Box::pin(stream! {
+            loop {
+                let mut output = TransformOutputsBuf::new_with_capacity(me.outputs.clone(), 1);
+                let done = tokio::select! {
+                    _ = async { persist_stream.as_mut().unwrap().tick().await },
+                        if persist_stream.is_some() => {
+                        me.persist();
+                        false
+                    }
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                me.persist();
+                                true
+                            }
+                            Some(event) => {
+                                match me.transform_one(event, Instant::now()) {
+                                    Ok(event) => output.push(event),
+                                    Err(event) => output.push_named(DUPLICATES_OUTPUT, event),
+                                }
+                                false
+                            }
+                        }
+                    }
+                };
+                if !output.is_empty() {
+                    yield output;
+                }
+                if done { break }
+            }
+        })
     }
 }
 
@@ -232,10 +429,19 @@ mod tests {
     }
 
     fn make_match_transform(num_events: usize, fields: Vec<String>) -> Dedupe {
-        Dedupe::new(DedupeConfig {
-            cache: CacheConfig { num_events },
-            fields: Some(FieldMatchConfig::MatchFields(fields)),
-        })
+        Dedupe::new(
+            DedupeConfig {
+                cache: CacheConfig {
+                    num_events,
+                    ttl_secs: None,
+                },
+                fields: Some(FieldMatchConfig::MatchFields(fields)),
+                persist: false,
+                persist_interval_secs: default_persist_interval_secs(),
+                data_dir: None,
+            },
+            None,
+        )
     }
 
     fn make_ignore_transform(num_events: usize, given_fields: Vec<String>) -> Dedupe {
@@ -243,10 +449,19 @@ mod tests {
         let mut fields = vec!["message".into(), "timestamp".into()];
         fields.extend(given_fields);
 
-        Dedupe::new(DedupeConfig {
-            cache: CacheConfig { num_events },
-            fields: Some(FieldMatchConfig::IgnoreFields(fields)),
-        })
+        Dedupe::new(
+            DedupeConfig {
+                cache: CacheConfig {
+                    num_events,
+                    ttl_secs: None,
+                },
+                fields: Some(FieldMatchConfig::IgnoreFields(fields)),
+                persist: false,
+                persist_interval_secs: default_persist_interval_secs(),
+                data_dir: None,
+            },
+            None,
+        )
     }
 
     #[test]
@@ -277,16 +492,20 @@ mod tests {
         event3.as_mut_log().insert("unmatched", "another value2");
 
         // First event should always be passed through as-is.
-        let new_event = transform.transform_one(event1.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event1.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event1);
 
         // Second event differs in matched field so should be outputted even though it
         // has the same value for unmatched field.
-        let new_event = transform.transform_one(event2.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event2.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event2);
 
         // Third event has the same value for "matched" as first event, so it should be dropped.
-        assert_eq!(None, transform.transform_one(event3));
+        assert!(transform.transform_one(event3, Instant::now()).is_err());
     }
 
     #[test]
@@ -309,12 +528,16 @@ mod tests {
         event2.as_mut_log().insert("matched2", "some value");
 
         // First event should always be passed through as-is.
-        let new_event = transform.transform_one(event1.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event1.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event1);
 
         // Second event has a different matched field name with the same value,
         // so it should not be considered a dupe
-        let new_event = transform.transform_one(event2.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event2.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event2);
     }
 
@@ -344,12 +567,14 @@ mod tests {
         event2.as_mut_log().insert("matched1", "value1");
 
         // First event should always be passed through as-is.
-        let new_event = transform.transform_one(event1.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event1.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event1);
 
         // Second event is the same just with different field order, so it
         // shouldn't be outputted.
-        assert_eq!(None, transform.transform_one(event2));
+        assert!(transform.transform_one(event2, Instant::now()).is_err());
     }
 
     #[test]
@@ -375,17 +600,23 @@ mod tests {
         event2.as_mut_log().insert("matched", "some value2");
 
         // First event should always be passed through as-is.
-        let new_event = transform.transform_one(event1.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event1.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event1);
 
         // Second event gets outputted because it's not a dupe.  This causes the first
         // Event to be evicted from the cache.
-        let new_event = transform.transform_one(event2.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event2.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event2);
 
         // Third event is a dupe but gets outputted anyway because the first
         // event has aged out of the cache.
-        let new_event = transform.transform_one(event1.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event1.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event1);
     }
 
@@ -412,12 +643,16 @@ mod tests {
         event2.as_mut_log().insert("matched", 123);
 
         // First event should always be passed through as-is.
-        let new_event = transform.transform_one(event1.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event1.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event1);
 
         // Second event should also get passed through even though the string
         // representations of "matched" are the same.
-        let new_event = transform.transform_one(event2.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event2.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event2);
     }
 
@@ -448,12 +683,16 @@ mod tests {
         event2.as_mut_log().insert("matched", map2);
 
         // First event should always be passed through as-is.
-        let new_event = transform.transform_one(event1.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event1.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event1);
 
         // Second event should also get passed through even though the string
         // representations of "matched" are the same.
-        let new_event = transform.transform_one(event2.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event2.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event2);
     }
 
@@ -477,12 +716,107 @@ mod tests {
         let event2 = Event::from("message");
 
         // First event should always be passed through as-is.
-        let new_event = transform.transform_one(event1.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event1.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event1);
 
         // Second event should also get passed through as null is different than
         // missing
-        let new_event = transform.transform_one(event2.clone()).unwrap();
+        let new_event = transform
+            .transform_one(event2.clone(), Instant::now())
+            .unwrap();
         assert_eq!(new_event, event2);
     }
+
+    fn make_config(config: &str) -> DedupeConfig {
+        toml::from_str(config).unwrap()
+    }
+
+    fn event_with(value: &str) -> Event {
+        let mut event = Event::from("message");
+        event.as_mut_log().insert("matched", value);
+        event
+    }
+
+    #[test]
+    fn dedupe_ttl() {
+        let mut transform = Dedupe::new(
+            make_config(
+                r#"
+cache.ttl_secs = 10
+fields.match = ["matched"]
+"#,
+            ),
+            None,
+        );
+        let now = Instant::now();
+
+        assert!(transform.transform_one(event_with("a"), now).is_ok());
+        assert!(transform
+            .transform_one(event_with("a"), now + Duration::from_secs(5))
+            .is_err());
+
+        // The first event expired, so the window restarts with this one.
+        assert!(transform
+            .transform_one(event_with("a"), now + Duration::from_secs(10))
+            .is_ok());
+        assert!(transform
+            .transform_one(event_with("a"), now + Duration::from_secs(15))
+            .is_err());
+    }
+
+    /// Runs the transform, returning the `matched` field of the events passed
+    /// and of the duplicates.
+    async fn run(transform: Dedupe, events: Vec<Event>) -> (Vec<Value>, Vec<Value>) {
+        let mut outputs = Box::new(transform)
+            .transform(Box::pin(futures::stream::iter(events)))
+            .collect::<Vec<_>>()
+            .await;
+        let passed = outputs
+            .iter_mut()
+            .flat_map(|output| output.drain())
+            .collect();
+        let duplicates = outputs
+            .iter_mut()
+            .flat_map(|output| output.drain_named(DUPLICATES_OUTPUT))
+            .collect();
+        (passed, duplicates)
+    }
+
+    #[tokio::test]
+    async fn dedupe_duplicates_output() {
+        let transform = Dedupe::new(make_config(r#"fields.match = ["matched"]"#), None);
+
+        let (passed, duplicates) = run(
+            transform,
+            vec![event_with("a"), event_with("a"), event_with("b")],
+        )
+        .await;
+
+        assert_eq!(passed, vec![Value::from("a"), Value::from("b")]);
+        assert_eq!(duplicates, vec![Value::from("a")]);
+    }
+
+    #[tokio::test]
+    async fn dedupe_persists_cache_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_path = dir.path().join(STATE_FILE);
+        let config = make_config(
+            r#"
+cache.ttl_secs = 3600
+fields.match = ["matched"]
+"#,
+        );
+
+        let transform = Dedupe::new(config.clone(), Some(state_path.clone()));
+        let (passed, _) = run(transform, vec![event_with("a"), event_with("b")]).await;
+        assert_eq!(passed.len(), 2);
+        assert!(state_path.exists());
+
+        let transform = Dedupe::new(config, Some(state_path));
+        let (passed, duplicates) = run(transform, vec![event_with("b"), event_with("c")]).await;
+        assert_eq!(passed, vec![Value::from("c")]);
+        assert_eq!(duplicates, vec![Value::from("b")]);
+    }
 }
@@ -0,0 +1,431 @@
+use std::{pin::Pin, time::Duration};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{
+        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{Event, EventMetadata, LogEvent},
+    expiring_hash_map::ExpiringHashMap,
+    internal_events::{JoinPartialEventFlushed, TemplateRenderingError},
+    schema,
+    template::Template,
+    transforms::{MultiOutputTaskTransform, Transform, TransformOutputsBuf},
+};
+
+//------------------------------------------------------------------------------
+
+pub(crate) const UNMATCHED_OUTPUT: &str = "unmatched";
+
+const fn default_timeout_ms() -> u64 {
+    30000
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct JoinConfig {
+    /// The template rendering the key events are correlated by, such as
+    /// `{{ request_id }}`.
+    pub key: Template,
+
+    /// The sides of the join, each identified by a condition. An event is
+    /// assigned to the first side whose condition it matches, regardless of
+    /// which of the transform's inputs it was received from.
+    pub sides: IndexMap<String, AnyCondition>,
+
+    /// How long to wait for the missing sides of a key before flushing the
+    /// partially joined event to the `unmatched` output.
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+inventory::submit! {
+    TransformDescription::new::<JoinConfig>("join")
+}
+
+impl GenerateConfig for JoinConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            key: Template::try_from("{{ request_id }}").unwrap(),
+            sides: IndexMap::new(),
+            timeout_ms: default_timeout_ms(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "join")]
+impl TransformConfig for JoinConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Join::new(self, &context.enrichment_tables).map(Transform::multi_output_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn validate(&self, _: &schema::Definition) -> Result<(), Vec<String>> {
+        if self.sides.len() < 2 {
+            Err(vec!["a join must have at least two `sides`".to_owned()])
+        } else {
+            Ok(())
+        }
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![
+            Output::default(DataType::Log),
+            Output::default(DataType::Log).with_port(UNMATCHED_OUTPUT),
+        ]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "join"
+    }
+}
+
+/// The events received so far for a key, indexed by side.
+#[derive(Debug)]
+struct JoinState {
+    events: Vec<Option<LogEvent>>,
+}
+
+impl JoinState {
+    fn new(sides: usize) -> Self {
+        Self {
+            events: (0..sides).map(|_| None).collect(),
+        }
+    }
+
+    fn missing(&self) -> usize {
+        self.events.iter().filter(|event| event.is_none()).count()
+    }
+
+    /// Merge the received events into one, nesting each event under the name
+    /// of its side.
+    fn flush(self, sides: &[(String, Condition)]) -> LogEvent {
+        let mut metadata: Option<EventMetadata> = None;
+        let mut fields = Vec::with_capacity(sides.len());
+        for ((name, _), event) in sides.iter().zip(self.events) {
+            if let Some(event) = event {
+                let (value, event_metadata) = event.into_parts();
+                match metadata.as_mut() {
+                    Some(metadata) => metadata.merge(event_metadata),
+                    None => metadata = Some(event_metadata),
+                }
+                fields.push((name, value));
+            }
+        }
+
+        let mut merged = LogEvent::new_with_metadata(metadata.unwrap_or_default());
+        for (name, value) in fields {
+            merged.insert(name.as_str(), value);
+        }
+        merged
+    }
+}
+
+//------------------------------------------------------------------------------
+
+pub struct Join {
+    key: Template,
+    sides: Vec<(String, Condition)>,
+    timeout: Duration,
+    outputs: Vec<Output>,
+    pending: ExpiringHashMap<String, JoinState>,
+}
+
+impl Join {
+    pub fn new(
+        config: &JoinConfig,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        let mut sides = Vec::with_capacity(config.sides.len());
+        for (name, condition) in &config.sides {
+            sides.push((name.clone(), condition.build(enrichment_tables)?));
+        }
+
+        Ok(Join {
+            key: config.key.clone(),
+            sides,
+            timeout: Duration::from_millis(config.timeout_ms),
+            outputs: config.outputs(&schema::Definition::empty()),
+            pending: ExpiringHashMap::default(),
+        })
+    }
+
+    fn new_buf(&self) -> TransformOutputsBuf {
+        TransformOutputsBuf::new_with_capacity(self.outputs.clone(), 1)
+    }
+
+    fn side_of(&self, mut event: Event) -> (Option<usize>, Event) {
+        for (index, (_, condition)) in self.sides.iter().enumerate() {
+            let (matched, checked) = condition.check(event);
+            if matched {
+                return (Some(index), checked);
+            }
+            event = checked;
+        }
+        (None, event)
+    }
+
+    fn flush_partial(&self, state: JoinState, output: &mut TransformOutputsBuf) {
+        emit!(JoinPartialEventFlushed {
+            missing: state.missing()
+        });
+        output.push_named(UNMATCHED_OUTPUT, state.flush(&self.sides).into());
+    }
+
+    fn flush_all(&mut self, output: &mut TransformOutputsBuf) {
+        let states = self
+            .pending
+            .drain()
+            .map(|(_, state)| state)
+            .collect::<Vec<_>>();
+        for state in states {
+            self.flush_partial(state, output);
+        }
+    }
+
+    fn transform_one(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let (side, event) = self.side_of(event);
+        let side = match side {
+            Some(side) => side,
+            None => {
+                output.push_named(UNMATCHED_OUTPUT, event);
+                return;
+            }
+        };
+
+        let key = match self.key.render_string(&event) {
+            Ok(key) => key,
+            Err(error) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("key"),
+                    drop_event: false,
+                });
+                output.push_named(UNMATCHED_OUTPUT, event);
+                return;
+            }
+        };
+
+        if self.pending.get(&key).is_none() {
+            self.pending
+                .insert(key.clone(), JoinState::new(self.sides.len()), self.timeout);
+        }
+        let state = self.pending.get_mut(&key).expect("state was just inserted");
+        if state.events[side].is_some() {
+            // The side already arrived for this key, so there is nothing to
+            // join this event with.
+            output.push_named(UNMATCHED_OUTPUT, event);
+            return;
+        }
+        state.events[side] = Some(event.into_log());
+
+        if state.missing() == 0 {
+            if let Some((state, _)) = self.pending.remove(&key) {
+                output.push(state.flush(&self.sides).into());
+            }
+        }
+    }
+}
+
+impl MultiOutputTaskTransform for Join {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = TransformOutputsBuf> + Send>> {
+        let mut me = self;
+
+        Box::pin(stream! {
+            loop {
+                let mut output = me.new_buf();
+                let done = tokio::select! {
+                    expired = me.pending.next_expired(), if !me.pending.is_empty() => {
+                        if let Some((state, _)) = expired {
+                            me.flush_partial(state, &mut output);
+                        }
+                        false
+                    }
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                me.flush_all(&mut output);
+                                true
+                            }
+                            Some(event) => {
+                                me.transform_one(event, &mut output);
+                                false
+                            }
+                        }
+                    }
+                };
+                if !output.is_empty() {
+                    yield output;
+                }
+                if done { break }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::task::Poll;
+
+    use futures::{stream, SinkExt};
+
+    use super::*;
+    use crate::event::Value;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<JoinConfig>();
+    }
+
+    async fn build(config: &str) -> Box<dyn MultiOutputTaskTransform> {
+        let join = toml::from_str::<JoinConfig>(config)
+            .unwrap()
+            .build(&TransformContext::default())
+            .await
+            .unwrap();
+        match join {
+            Transform::MultiOutputTask(join) => join,
+            _ => panic!("join is not a multi-output task transform"),
+        }
+    }
+
+    const CONFIG: &str = r#"
+key = "{{ request_id }}"
+timeout_ms = 5000
+
+[sides.request]
+  type = "check_fields"
+  "kind.eq" = "request"
+
+[sides.response]
+  type = "check_fields"
+  "kind.eq" = "response"
+"#;
+
+    fn make_event(kind: &str, request_id: &str, message: &str) -> Event {
+        let mut event = LogEvent::from(message);
+        event.insert("kind", kind);
+        event.insert("request_id", request_id);
+        event.into()
+    }
+
+    #[tokio::test]
+    async fn joins_events_by_key() {
+        let join = build(CONFIG).await;
+
+        let inputs = vec![
+            make_event("request", "1", "GET /a"),
+            make_event("request", "2", "GET /b"),
+            make_event("response", "2", "404"),
+            make_event("response", "1", "200"),
+        ];
+        let mut out_stream = join.transform(Box::pin(stream::iter(inputs)));
+
+        let mut output = out_stream.next().await.unwrap();
+        let joined = output.drain().next().unwrap().into_log();
+        assert_eq!(joined["request.message"], Value::from("GET /b"));
+        assert_eq!(joined["response.message"], Value::from("404"));
+
+        let mut output = out_stream.next().await.unwrap();
+        let joined = output.drain().next().unwrap().into_log();
+        assert_eq!(joined["request.request_id"], Value::from("1"));
+        assert_eq!(joined["request.message"], Value::from("GET /a"));
+        assert_eq!(joined["response.message"], Value::from("200"));
+
+        assert!(out_stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn routes_unmatched_events() {
+        let join = build(CONFIG).await;
+
+        let mut no_key = LogEvent::from("no key");
+        no_key.insert("kind", "request");
+        let inputs = vec![
+            make_event("other", "1", "no side"),
+            no_key.into(),
+            make_event("request", "1", "first"),
+            make_event("request", "1", "duplicate"),
+        ];
+        let mut out_stream = join.transform(Box::pin(stream::iter(inputs)));
+
+        let mut unmatched = Vec::new();
+        while let Some(mut output) = out_stream.next().await {
+            assert_eq!(output.drain().count(), 0);
+            unmatched.extend(output.drain_named(UNMATCHED_OUTPUT));
+        }
+
+        let messages = unmatched
+            .into_iter()
+            .map(|event| event.into_log())
+            .map(|log| {
+                log.get("message")
+                    .or_else(|| log.get("request.message"))
+                    .cloned()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        // The partially joined event is flushed when the input ends.
+        assert_eq!(
+            messages,
+            vec![
+                Value::from("no side"),
+                Value::from("no key"),
+                Value::from("duplicate"),
+                Value::from("first"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn flushes_partial_events_after_timeout() {
+        tokio::time::pause();
+
+        let join = build(CONFIG).await;
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        let mut out_stream = join.transform(Box::pin(rx));
+
+        tx.send(make_event("request", "1", "GET /a")).await.unwrap();
+        // Nothing is emitted until the timeout elapses.
+        assert!(matches!(futures::poll!(out_stream.next()), Poll::Pending));
+
+        tokio::time::advance(Duration::from_secs(6)).await;
+
+        let mut output = out_stream.next().await.unwrap();
+        assert_eq!(output.drain().count(), 0);
+        let partial = output
+            .drain_named(UNMATCHED_OUTPUT)
+            .next()
+            .unwrap()
+            .into_log();
+        assert_eq!(partial["request.message"], Value::from("GET /a"));
+        assert!(partial.get("response").is_none());
+
+        // A late response no longer has anything to join with.
+        tx.send(make_event("response", "1", "200")).await.unwrap();
+        tx.disconnect();
+        let mut output = out_stream.next().await.unwrap();
+        let partial = output
+            .drain_named(UNMATCHED_OUTPUT)
+            .next()
+            .unwrap()
+            .into_log();
+        assert_eq!(partial["response.message"], Value::from("200"));
+        assert!(out_stream.next().await.is_none());
+    }
+}
@@ -0,0 +1,269 @@
+//! A Drain-style online log clustering algorithm.
+//!
+//! Messages are split into tokens and routed through a fixed-depth prefix tree, keyed by the
+//! number of tokens and then by their first tokens, to a leaf holding candidate clusters. The
+//! message joins the most similar cluster if the similarity reaches the threshold, replacing
+//! the tokens of the cluster template that differ with a wildcard, and starts a new cluster
+//! otherwise.
+//!
+//! See <https://jiemingzhu.github.io/pub/pjhe_icws2017.pdf>.
+use std::collections::HashMap;
+
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+
+pub(super) const WILDCARD: &str = "<*>";
+
+/// A cluster of similar messages.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(super) struct Cluster {
+    pub(super) id: u64,
+    pub(super) tokens: Vec<String>,
+    pub(super) size: u64,
+}
+
+impl Cluster {
+    pub(super) fn template(&self) -> String {
+        self.tokens.join(" ")
+    }
+}
+
+/// The persisted state of [`Drain`].
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub(super) struct DrainState {
+    pub(super) next_id: u64,
+    /// The clusters, from the least to the most recently used.
+    pub(super) clusters: Vec<Cluster>,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    children: HashMap<String, Node>,
+    clusters: Vec<u64>,
+}
+
+pub(super) struct Drain {
+    /// The number of leading tokens routing messages through the tree.
+    prefix_tokens: usize,
+    similarity_threshold: f64,
+    max_children: usize,
+    root: HashMap<usize, Node>,
+    clusters: LruCache<u64, Cluster>,
+    next_id: u64,
+}
+
+impl Drain {
+    pub(super) fn new(
+        depth: usize,
+        similarity_threshold: f64,
+        max_children: usize,
+        max_clusters: usize,
+    ) -> Self {
+        Self {
+            // The depth includes the root and the token count layers of the tree.
+            prefix_tokens: depth.saturating_sub(2),
+            similarity_threshold,
+            max_children,
+            root: HashMap::new(),
+            clusters: LruCache::new(max_clusters),
+            next_id: 1,
+        }
+    }
+
+    /// Restore the clusters of a previous run.
+    pub(super) fn restore(&mut self, state: DrainState) {
+        self.next_id = self.next_id.max(state.next_id);
+        for cluster in state.clusters {
+            self.next_id = self.next_id.max(cluster.id + 1);
+            let leaf = leaf(
+                &mut self.root,
+                &cluster.tokens,
+                self.prefix_tokens,
+                self.max_children,
+            );
+            leaf.clusters.push(cluster.id);
+            self.clusters.put(cluster.id, cluster);
+        }
+    }
+
+    pub(super) fn state(&self) -> DrainState {
+        DrainState {
+            next_id: self.next_id,
+            clusters: self
+                .clusters
+                .iter()
+                .rev()
+                .map(|(_, cluster)| cluster.clone())
+                .collect(),
+        }
+    }
+
+    /// Add a message, returning the cluster it joined.
+    pub(super) fn add(&mut self, message: &str) -> &Cluster {
+        let tokens = message
+            .split_whitespace()
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>();
+
+        let leaf = leaf(
+            &mut self.root,
+            &tokens,
+            self.prefix_tokens,
+            self.max_children,
+        );
+        // Clusters evicted from the cache are only removed from the tree lazily.
+        let clusters = &mut self.clusters;
+        leaf.clusters.retain(|id| clusters.contains(id));
+
+        let mut best: Option<(u64, f64, usize)> = None;
+        for id in &leaf.clusters {
+            if let Some(cluster) = clusters.peek(id) {
+                let (similarity, wildcards) = similarity(&cluster.tokens, &tokens);
+                let better = match best {
+                    None => true,
+                    Some((_, best_similarity, best_wildcards)) => {
+                        similarity > best_similarity
+                            || (similarity == best_similarity && wildcards > best_wildcards)
+                    }
+                };
+                if better {
+                    best = Some((*id, similarity, wildcards));
+                }
+            }
+        }
+
+        let id = match best {
+            Some((id, similarity, _)) if similarity >= self.similarity_threshold => {
+                let cluster = clusters.get_mut(&id).expect("cluster exists");
+                for (template, token) in cluster.tokens.iter_mut().zip(&tokens) {
+                    if template != token {
+                        *template = WILDCARD.to_owned();
+                    }
+                }
+                cluster.size += 1;
+                id
+            }
+            _ => {
+                let id = self.next_id;
+                self.next_id += 1;
+                clusters.put(
+                    id,
+                    Cluster {
+                        id,
+                        tokens,
+                        size: 1,
+                    },
+                );
+                leaf.clusters.push(id);
+                id
+            }
+        };
+
+        self.clusters.peek(&id).expect("cluster exists")
+    }
+
+    #[cfg(test)]
+    pub(super) fn len(&self) -> usize {
+        self.clusters.len()
+    }
+}
+
+/// Find the leaf of the prefix tree holding the candidate clusters of the tokens, creating it if
+/// needed. Tokens containing digits are likely variables, so they are routed through a wildcard
+/// node, as are tokens beyond `max_children` distinct ones at a node.
+fn leaf<'a>(
+    root: &'a mut HashMap<usize, Node>,
+    tokens: &[String],
+    prefix_tokens: usize,
+    max_children: usize,
+) -> &'a mut Node {
+    let mut node = root.entry(tokens.len()).or_default();
+    for token in tokens.iter().take(prefix_tokens) {
+        let mut key = if token.chars().any(|c| c.is_ascii_digit()) {
+            WILDCARD
+        } else {
+            token.as_str()
+        };
+        // Keep room for the wildcard node.
+        if !node.children.contains_key(key) && node.children.len() + 1 >= max_children {
+            key = WILDCARD;
+        }
+        node = node.children.entry(key.to_owned()).or_default();
+    }
+    node
+}
+
+/// The share of the template tokens equal to the message tokens, along with the number of
+/// wildcards in the template.
+fn similarity(template: &[String], tokens: &[String]) -> (f64, usize) {
+    if template.is_empty() {
+        return (1.0, 0);
+    }
+
+    let mut same = 0;
+    let mut wildcards = 0;
+    for (template, token) in template.iter().zip(tokens) {
+        if template == WILDCARD {
+            wildcards += 1;
+        } else if template == token {
+            same += 1;
+        }
+    }
+    (same as f64 / template.len() as f64, wildcards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain() -> Drain {
+        Drain::new(4, 0.4, 100, 1000)
+    }
+
+    #[test]
+    fn clusters_similar_messages() {
+        let mut drain = drain();
+
+        let first = drain.add("connected to 10.0.0.1 in 5 ms").clone();
+        assert_eq!(first.template(), "connected to 10.0.0.1 in 5 ms");
+
+        let second = drain.add("connected to 10.0.0.2 in 12 ms").clone();
+        assert_eq!(second.id, first.id);
+        assert_eq!(second.template(), "connected to <*> in <*> ms");
+        assert_eq!(second.size, 2);
+
+        let other = drain.add("user alice logged out").clone();
+        assert_ne!(other.id, first.id);
+        assert_eq!(drain.len(), 2);
+    }
+
+    #[test]
+    fn evicts_least_recently_used_clusters() {
+        let mut drain = Drain::new(4, 0.4, 100, 2);
+
+        let first = drain.add("disk full on sda").id;
+        drain.add("user alice logged out");
+        drain.add("disk full on sdb");
+        let evicting = drain.add("cache warmed up successfully").id;
+
+        assert_eq!(drain.len(), 2);
+        assert_ne!(evicting, first);
+        // The disk cluster was used recently, so it survived.
+        assert_eq!(drain.add("disk full on sdc").id, first);
+    }
+
+    #[test]
+    fn restores_state() {
+        let mut drain = drain();
+        let id = drain.add("connected to 10.0.0.1 in 5 ms").id;
+        drain.add("connected to 10.0.0.2 in 12 ms");
+
+        let mut restored = Drain::new(4, 0.4, 100, 1000);
+        restored.restore(drain.state());
+
+        let cluster = restored.add("connected to 10.0.0.3 in 1 ms");
+        assert_eq!(cluster.id, id);
+        assert_eq!(cluster.size, 3);
+        assert_ne!(restored.add("something else entirely").id, id);
+    }
+}
@@ -0,0 +1,394 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use futures::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::Event,
+    internal_events::{LogPatternsLoadError, LogPatternsPersistError},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+mod drain;
+
+use drain::{Drain, DrainState};
+
+const STATE_FILE: &str = "log_patterns.json";
+
+const fn default_similarity_threshold() -> f64 {
+    0.4
+}
+
+const fn default_depth() -> usize {
+    4
+}
+
+const fn default_max_children() -> usize {
+    100
+}
+
+const fn default_max_clusters() -> usize {
+    1000
+}
+
+fn default_pattern_id_field() -> String {
+    "pattern_id".to_owned()
+}
+
+fn default_pattern_field() -> String {
+    "pattern".to_owned()
+}
+
+const fn default_persist_interval_secs() -> u64 {
+    60
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct LogPatternsConfig {
+    /// The field holding the message to cluster. Defaults to the message
+    /// key of the log schema.
+    pub field: Option<String>,
+
+    /// The share of the tokens of a message that must be equal to those of
+    /// a pattern for the message to join it.
+    #[serde(default = "default_similarity_threshold")]
+    pub similarity_threshold: f64,
+
+    /// The depth of the prefix tree, including its root and token count
+    /// layers. Messages only share a pattern if their first `depth - 2`
+    /// tokens are equal, or all contain digits.
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+
+    /// The maximum number of children of a node of the prefix tree.
+    #[serde(default = "default_max_children")]
+    pub max_children: usize,
+
+    /// The maximum number of patterns to keep. The least recently used
+    /// pattern is forgotten when the limit is reached.
+    #[serde(default = "default_max_clusters")]
+    pub max_clusters: usize,
+
+    /// The field the ID of the pattern of the message is written to.
+    #[serde(default = "default_pattern_id_field")]
+    pub pattern_id_field: String,
+
+    /// The field the template of the pattern of the message is written to.
+    #[serde(default = "default_pattern_field")]
+    pub pattern_field: String,
+
+    /// Persist the patterns in the data directory, so that their IDs are
+    /// kept across restarts.
+    #[serde(default)]
+    pub persist: bool,
+
+    /// How often the patterns are persisted.
+    #[serde(default = "default_persist_interval_secs")]
+    pub persist_interval_secs: u64,
+
+    /// The directory the patterns are persisted in. Defaults to the global
+    /// `data_dir` option.
+    pub data_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`similarity_threshold` must be between 0 and 1"))]
+    InvalidSimilarityThreshold,
+    #[snafu(display("`depth` must be at least 3"))]
+    InvalidDepth,
+    #[snafu(display("`max_children` and `max_clusters` must be at least 2"))]
+    InvalidLimits,
+    #[snafu(display("`persist_interval_secs` must be non-zero"))]
+    ZeroPersistInterval,
+    #[snafu(display("Persisting patterns requires a component ID"))]
+    MissingComponentKey,
+}
+
+inventory::submit! {
+    TransformDescription::new::<LogPatternsConfig>("log_patterns")
+}
+
+impl GenerateConfig for LogPatternsConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            field: None,
+            similarity_threshold: default_similarity_threshold(),
+            depth: default_depth(),
+            max_children: default_max_children(),
+            max_clusters: default_max_clusters(),
+            pattern_id_field: default_pattern_id_field(),
+            pattern_field: default_pattern_field(),
+            persist: false,
+            persist_interval_secs: default_persist_interval_secs(),
+            data_dir: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "log_patterns")]
+impl TransformConfig for LogPatternsConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        if !(0.0..=1.0).contains(&self.similarity_threshold) {
+            return Err(BuildError::InvalidSimilarityThreshold.into());
+        }
+        if self.depth < 3 {
+            return Err(BuildError::InvalidDepth.into());
+        }
+        if self.max_children < 2 || self.max_clusters < 2 {
+            return Err(BuildError::InvalidLimits.into());
+        }
+        if self.persist_interval_secs == 0 {
+            return Err(BuildError::ZeroPersistInterval.into());
+        }
+
+        let state_path = if self.persist {
+            let key = context
+                .key
+                .as_ref()
+                .ok_or(BuildError::MissingComponentKey)?;
+            let data_dir = context
+                .globals
+                .resolve_and_make_data_subdir(self.data_dir.as_ref(), key.id())?;
+            Some(data_dir.join(STATE_FILE))
+        } else {
+            None
+        };
+
+        Ok(Transform::event_task(LogPatterns::new(self, state_path)))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "log_patterns"
+    }
+}
+
+//------------------------------------------------------------------------------
+
+pub struct LogPatterns {
+    field: String,
+    pattern_id_field: String,
+    pattern_field: String,
+    drain: Drain,
+    state_path: Option<PathBuf>,
+    persist_interval: Duration,
+}
+
+impl LogPatterns {
+    fn new(config: &LogPatternsConfig, state_path: Option<PathBuf>) -> Self {
+        let mut drain = Drain::new(
+            config.depth,
+            config.similarity_threshold,
+            config.max_children,
+            config.max_clusters,
+        );
+        if let Some(path) = &state_path {
+            match load_state(path) {
+                Ok(Some(state)) => drain.restore(state),
+                Ok(None) => {}
+                Err(error) => emit!(LogPatternsLoadError { error, path }),
+            }
+        }
+
+        Self {
+            field: config
+                .field
+                .clone()
+                .unwrap_or_else(|| log_schema().message_key().to_owned()),
+            pattern_id_field: config.pattern_id_field.clone(),
+            pattern_field: config.pattern_field.clone(),
+            drain,
+            state_path,
+            persist_interval: Duration::from_secs(config.persist_interval_secs),
+        }
+    }
+
+    fn transform_one(&mut self, mut event: Event) -> Event {
+        let log = event.as_mut_log();
+        let message = match log.get(self.field.as_str()) {
+            Some(message) => message.to_string_lossy(),
+            None => return event,
+        };
+
+        let cluster = self.drain.add(&message);
+        let (id, template) = (cluster.id, cluster.template());
+        log.insert(self.pattern_id_field.as_str(), id as i64);
+        log.insert(self.pattern_field.as_str(), template);
+        event
+    }
+
+    fn persist(&self) {
+        if let Some(path) = &self.state_path {
+            if let Err(error) = save_state(path, &self.drain.state()) {
+                emit!(LogPatternsPersistError { error, path });
+            }
+        }
+    }
+}
+
+fn load_state(path: &Path) -> io::Result<Option<DrainState>> {
+    match fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map(Some).map_err(Into::into),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Write the state to a temporary file first, so that a crash while
+/// persisting doesn't lose the previous state.
+fn save_state(path: &Path, state: &DrainState) -> io::Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+    let mut file = fs::File::create(&tmp_path)?;
+    serde_json::to_writer(&mut file, state)?;
+    file.flush()?;
+    file.sync_all()?;
+    fs::rename(tmp_path, path)
+}
+
+impl TaskTransform<Event> for LogPatterns {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+
+        let mut persist_stream = tokio::time::interval(me.persist_interval);
+
+        Box::pin(
+            stream! {
+              loop {
+                let mut output = Vec::new();
+                let done = tokio::select! {
+                    _ = persist_stream.tick(), if me.state_path.is_some() => {
+                      me.persist();
+                      false
+                    }
+                    maybe_event = input_rx.next() => {
+                      match maybe_event {
+                        None => {
+                          me.persist();
+                          true
+                        }
+                        Some(event) => {
+                          output.push(me.transform_one(event));
+                          false
+                        }
+                      }
+                    }
+                };
+                yield stream::iter(output.into_iter());
+                if done { break }
+              }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::event::{LogEvent, Value};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LogPatternsConfig>();
+    }
+
+    fn transform(config: &LogPatternsConfig, state_path: Option<PathBuf>) -> Box<LogPatterns> {
+        Box::new(LogPatterns::new(config, state_path))
+    }
+
+    async fn run(transform: Box<LogPatterns>, messages: &[&str]) -> Vec<LogEvent> {
+        let inputs = messages
+            .iter()
+            .map(|message| Event::from(LogEvent::from(*message)))
+            .collect::<Vec<_>>();
+        transform
+            .transform_events(Box::pin(stream::iter(inputs)))
+            .map(Event::into_log)
+            .collect()
+            .await
+    }
+
+    fn default_config() -> LogPatternsConfig {
+        toml::from_str("").unwrap()
+    }
+
+    #[tokio::test]
+    async fn assigns_patterns() {
+        let output = run(
+            transform(&default_config(), None),
+            &[
+                "connected to 10.0.0.1 in 5 ms",
+                "user alice logged out",
+                "connected to 10.0.0.2 in 12 ms",
+            ],
+        )
+        .await;
+
+        assert_eq!(output[0]["pattern_id"], output[2]["pattern_id"]);
+        assert_ne!(output[0]["pattern_id"], output[1]["pattern_id"]);
+        assert_eq!(output[1]["pattern"], Value::from("user alice logged out"));
+        assert_eq!(
+            output[2]["pattern"],
+            Value::from("connected to <*> in <*> ms")
+        );
+        assert_eq!(
+            output[2]["message"],
+            Value::from("connected to 10.0.0.2 in 12 ms")
+        );
+    }
+
+    #[tokio::test]
+    async fn persists_patterns_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_path = dir.path().join(STATE_FILE);
+        let config = default_config();
+
+        let first = run(
+            transform(&config, Some(state_path.clone())),
+            &["user alice logged out", "connected to 10.0.0.1 in 5 ms"],
+        )
+        .await;
+        assert!(state_path.exists());
+
+        let second = run(
+            transform(&config, Some(state_path)),
+            &["connected to 10.0.0.2 in 12 ms", "disk full on sda"],
+        )
+        .await;
+        assert_eq!(second[0]["pattern_id"], first[1]["pattern_id"]);
+        assert_ne!(second[1]["pattern_id"], first[0]["pattern_id"]);
+        assert_ne!(second[1]["pattern_id"], first[1]["pattern_id"]);
+    }
+}
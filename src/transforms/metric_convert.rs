@@ -0,0 +1,526 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{
+        metric::{Metric, MetricData, MetricKind, MetricSeries, MetricValue, Quantile},
+        Event,
+    },
+    metrics::AgentDDSketch,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+const fn default_expire_after_secs() -> u64 {
+    300
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MetricConvertConfig {
+    /// The kind metrics are converted to. Absolute metrics are converted to
+    /// incremental ones by subtracting the previous value of their series,
+    /// and incremental metrics to absolute ones by accumulating the values of
+    /// their series.
+    pub kind: Option<MetricKind>,
+
+    /// Convert gauges to the per-second rate of change between consecutive
+    /// values of their series.
+    #[serde(default)]
+    pub gauges_to_rates: bool,
+
+    /// Convert distributions to aggregated histograms or summaries.
+    pub distributions: Option<DistributionConversion>,
+
+    /// How long the state of a series is kept after the series was last
+    /// seen.
+    #[serde(default = "default_expire_after_secs")]
+    pub expire_after_secs: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum DistributionConversion {
+    /// Count the samples into buckets with the given upper limits.
+    AggregatedHistogram { buckets: Vec<f64> },
+    /// Compute the given quantiles of the samples.
+    AggregatedSummary { quantiles: Vec<f64> },
+}
+
+impl DistributionConversion {
+    /// Convert a distribution, or return `None` for other values.
+    fn convert(&self, value: &MetricValue) -> Option<MetricValue> {
+        match self {
+            Self::AggregatedHistogram { buckets } => value.distribution_to_agg_histogram(buckets),
+            Self::AggregatedSummary { quantiles } => match value {
+                MetricValue::Distribution { samples, .. } => {
+                    let mut sketch = AgentDDSketch::with_agent_defaults();
+                    for sample in samples {
+                        sketch.insert_n(sample.value, sample.rate);
+                    }
+                    Some(MetricValue::AggregatedSummary {
+                        quantiles: quantiles
+                            .iter()
+                            .filter_map(|quantile| {
+                                sketch.quantile(*quantile).map(|value| Quantile {
+                                    quantile: *quantile,
+                                    value,
+                                })
+                            })
+                            .collect(),
+                        count: sketch.count(),
+                        sum: sketch.sum().unwrap_or_default(),
+                    })
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`expire_after_secs` must be non-zero"))]
+    ZeroExpiry,
+    #[snafu(display("The quantiles of `distributions` must be between 0 and 1"))]
+    InvalidQuantiles,
+}
+
+inventory::submit! {
+    TransformDescription::new::<MetricConvertConfig>("metric_convert")
+}
+
+impl GenerateConfig for MetricConvertConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            kind: Some(MetricKind::Absolute),
+            gauges_to_rates: false,
+            distributions: None,
+            expire_after_secs: default_expire_after_secs(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "metric_convert")]
+impl TransformConfig for MetricConvertConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.expire_after_secs == 0 {
+            return Err(BuildError::ZeroExpiry.into());
+        }
+        if let Some(DistributionConversion::AggregatedSummary { quantiles }) = &self.distributions {
+            if quantiles
+                .iter()
+                .any(|quantile| !(0.0..=1.0).contains(quantile))
+            {
+                return Err(BuildError::InvalidQuantiles.into());
+            }
+        }
+
+        Ok(Transform::function(MetricConvert::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "metric_convert"
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SeriesState {
+    /// The previous value of the series, or its accumulated value when
+    /// converting to absolute metrics.
+    data: MetricData,
+    last_seen: Instant,
+}
+
+#[derive(Clone, Debug)]
+pub struct MetricConvert {
+    kind: Option<MetricKind>,
+    gauges_to_rates: bool,
+    distributions: Option<DistributionConversion>,
+    expire_after: Duration,
+    state: HashMap<MetricSeries, SeriesState>,
+    last_expiry: Instant,
+}
+
+impl MetricConvert {
+    pub fn new(config: &MetricConvertConfig) -> Self {
+        Self {
+            kind: config.kind,
+            gauges_to_rates: config.gauges_to_rates,
+            distributions: config.distributions.clone(),
+            expire_after: Duration::from_secs(config.expire_after_secs),
+            state: HashMap::new(),
+            last_expiry: Instant::now(),
+        }
+    }
+
+    /// Forget the series not seen for `expire_after`. This is only checked
+    /// once per `expire_after`, to avoid scanning the state for each metric.
+    fn expire(&mut self, now: Instant) {
+        if now.saturating_duration_since(self.last_expiry) < self.expire_after {
+            return;
+        }
+        let expire_after = self.expire_after;
+        self.state
+            .retain(|_, state| now.saturating_duration_since(state.last_seen) < expire_after);
+        self.last_expiry = now;
+    }
+
+    fn store(&mut self, series: &MetricSeries, data: MetricData, now: Instant) {
+        self.state.insert(
+            series.clone(),
+            SeriesState {
+                data,
+                last_seen: now,
+            },
+        );
+    }
+
+    fn convert(&mut self, metric: Metric, now: Instant) -> Option<Metric> {
+        self.expire(now);
+
+        let (series, mut data, metadata) = metric.into_parts();
+        if let Some(conversion) = &self.distributions {
+            if let Some(value) = conversion.convert(&data.value) {
+                data.value = value;
+            }
+        }
+
+        let data = if self.gauges_to_rates && matches!(data.value, MetricValue::Gauge { .. }) {
+            self.gauge_to_rate(&series, data, now)?
+        } else {
+            match (self.kind, data.kind) {
+                (Some(MetricKind::Incremental), MetricKind::Absolute) => {
+                    self.absolute_to_incremental(&series, data, now)?
+                }
+                (Some(MetricKind::Absolute), MetricKind::Incremental) => {
+                    self.incremental_to_absolute(&series, data, now)
+                }
+                (Some(MetricKind::Absolute), MetricKind::Absolute) => {
+                    // Absolute values set the total that later increments
+                    // are added to.
+                    self.store(&series, data.clone(), now);
+                    data
+                }
+                _ => data,
+            }
+        };
+        Some(Metric::from_parts(series, data, metadata))
+    }
+
+    /// Compute the increment since the previous value of the series. Nothing
+    /// is emitted for the first value of a series, since the increment is
+    /// unknown.
+    fn absolute_to_incremental(
+        &mut self,
+        series: &MetricSeries,
+        data: MetricData,
+        now: Instant,
+    ) -> Option<MetricData> {
+        let previous = match self.state.get_mut(series) {
+            Some(previous) => previous,
+            None => {
+                self.store(series, data, now);
+                return None;
+            }
+        };
+        previous.last_seen = now;
+
+        let mut increment = data.clone();
+        if increment.subtract(&previous.data) {
+            previous.data = data;
+            Some(increment.into_incremental())
+        } else if increment.value.as_name() == previous.data.value.as_name() {
+            // Counters and histograms only decrease when they are reset, for
+            // example by a restart of their source, so the whole value is the
+            // increment since the reset.
+            previous.data = data.clone();
+            Some(data.into_incremental())
+        } else {
+            // The metric changed type, so it is a new reference value.
+            previous.data = data;
+            None
+        }
+    }
+
+    /// Accumulate the increments of the series into its total value.
+    fn incremental_to_absolute(
+        &mut self,
+        series: &MetricSeries,
+        data: MetricData,
+        now: Instant,
+    ) -> MetricData {
+        if let Some(total) = self.state.get_mut(series) {
+            if total.data.update(&data) {
+                total.last_seen = now;
+                return total.data.clone().into_absolute();
+            }
+        }
+        // This is either the first value of the series, or the metric changed
+        // type, so the total starts over.
+        self.store(series, data.clone(), now);
+        data.into_absolute()
+    }
+
+    /// Compute the per-second rate of change of a gauge since its previous
+    /// value. Nothing is emitted for the first value of a series.
+    fn gauge_to_rate(
+        &mut self,
+        series: &MetricSeries,
+        mut data: MetricData,
+        now: Instant,
+    ) -> Option<MetricData> {
+        let value = match data.value {
+            MetricValue::Gauge { value } => value,
+            _ => return None,
+        };
+        let timestamp = *data.timestamp.get_or_insert_with(Utc::now);
+
+        let previous = match self.state.get_mut(series) {
+            Some(previous) => previous,
+            None => {
+                self.store(series, data, now);
+                return None;
+            }
+        };
+        let previous_value = match previous.data.value {
+            MetricValue::Gauge { value } => Some(value),
+            _ => None,
+        };
+        let delta = match (data.kind, previous_value) {
+            (MetricKind::Incremental, _) => Some(value),
+            (MetricKind::Absolute, previous_value) => {
+                previous_value.map(|previous| value - previous)
+            }
+        };
+        let elapsed = previous
+            .data
+            .timestamp
+            .map(|previous| (timestamp - previous).num_milliseconds() as f64 / 1000.0);
+        previous.data = data;
+        previous.last_seen = now;
+
+        match (delta, elapsed) {
+            (Some(delta), Some(elapsed)) if elapsed > 0.0 => Some(MetricData::from_parts(
+                Some(timestamp),
+                MetricKind::Absolute,
+                MetricValue::Gauge {
+                    value: delta / elapsed,
+                },
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl FunctionTransform for MetricConvert {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        if let Some(metric) = self.convert(event.into_metric(), Instant::now()) {
+            output.push(Event::Metric(metric));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::event::metric::{Bucket, Sample, StatisticKind};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MetricConvertConfig>();
+    }
+
+    fn transform(config: &str) -> MetricConvert {
+        MetricConvert::new(&toml::from_str(config).unwrap())
+    }
+
+    fn counter(kind: MetricKind, value: f64) -> Metric {
+        Metric::new("requests", kind, MetricValue::Counter { value })
+    }
+
+    fn converted_values(
+        transform: &mut MetricConvert,
+        metrics: Vec<Metric>,
+        now: Instant,
+    ) -> Vec<Option<(MetricKind, MetricValue)>> {
+        metrics
+            .into_iter()
+            .map(|metric| {
+                transform
+                    .convert(metric, now)
+                    .map(|metric| (metric.kind(), metric.value().clone()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn absolute_to_incremental_handles_resets() {
+        let mut transform = transform(r#"kind = "incremental""#);
+        let values = converted_values(
+            &mut transform,
+            vec![
+                counter(MetricKind::Absolute, 10.0),
+                counter(MetricKind::Absolute, 15.0),
+                // The source restarted.
+                counter(MetricKind::Absolute, 4.0),
+                counter(MetricKind::Absolute, 6.0),
+                counter(MetricKind::Incremental, 1.0),
+            ],
+            Instant::now(),
+        );
+
+        let incremental = |value| Some((MetricKind::Incremental, MetricValue::Counter { value }));
+        assert_eq!(
+            values,
+            vec![
+                None,
+                incremental(5.0),
+                incremental(4.0),
+                incremental(2.0),
+                incremental(1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn incremental_to_absolute() {
+        let mut transform = transform(r#"kind = "absolute""#);
+        let values = converted_values(
+            &mut transform,
+            vec![
+                counter(MetricKind::Incremental, 1.0),
+                counter(MetricKind::Incremental, 2.0),
+                counter(MetricKind::Absolute, 10.0),
+                counter(MetricKind::Incremental, 3.0),
+            ],
+            Instant::now(),
+        );
+
+        let absolute = |value| Some((MetricKind::Absolute, MetricValue::Counter { value }));
+        assert_eq!(
+            values,
+            vec![absolute(1.0), absolute(3.0), absolute(10.0), absolute(13.0)]
+        );
+    }
+
+    #[test]
+    fn expires_series_state() {
+        let mut transform = transform(
+            r#"
+kind = "incremental"
+expire_after_secs = 60
+"#,
+        );
+        let start = Instant::now();
+
+        assert!(transform
+            .convert(counter(MetricKind::Absolute, 10.0), start)
+            .is_none());
+        assert!(transform
+            .convert(
+                counter(MetricKind::Absolute, 12.0),
+                start + Duration::from_secs(30)
+            )
+            .is_some());
+        // The series wasn't seen for longer than the expiry, so its previous
+        // value was forgotten.
+        assert!(transform
+            .convert(
+                counter(MetricKind::Absolute, 15.0),
+                start + Duration::from_secs(120)
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn gauges_to_rates() {
+        let mut transform = transform("gauges_to_rates = true");
+        let gauge = |value, seconds| {
+            Metric::new("bytes", MetricKind::Absolute, MetricValue::Gauge { value })
+                .with_timestamp(Some(Utc.timestamp(seconds, 0)))
+        };
+
+        let values = converted_values(
+            &mut transform,
+            vec![gauge(100.0, 0), gauge(150.0, 10), gauge(130.0, 20)],
+            Instant::now(),
+        );
+        let rate = |value| Some((MetricKind::Absolute, MetricValue::Gauge { value }));
+        assert_eq!(values, vec![None, rate(5.0), rate(-2.0)]);
+    }
+
+    #[test]
+    fn distributions_to_cumulative_histograms() {
+        let mut transform = transform(
+            r#"
+kind = "absolute"
+distributions = { type = "aggregated_histogram", buckets = [1.0, 10.0] }
+"#,
+        );
+        let distribution = |values: &[f64]| {
+            Metric::new(
+                "latency",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: values
+                        .iter()
+                        .map(|value| Sample {
+                            value: *value,
+                            rate: 1,
+                        })
+                        .collect(),
+                    statistic: StatisticKind::Histogram,
+                },
+            )
+        };
+
+        let values = converted_values(
+            &mut transform,
+            vec![distribution(&[0.5, 2.0]), distribution(&[4.0])],
+            Instant::now(),
+        );
+        assert_eq!(
+            values[1],
+            Some((
+                MetricKind::Absolute,
+                MetricValue::AggregatedHistogram {
+                    buckets: vec![
+                        Bucket {
+                            upper_limit: 1.0,
+                            count: 1
+                        },
+                        Bucket {
+                            upper_limit: 10.0,
+                            count: 2
+                        },
+                    ],
+                    count: 3,
+                    sum: 6.5,
+                }
+            ))
+        );
+    }
+}
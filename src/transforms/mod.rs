@@ -26,10 +26,14 @@ pub mod filter;
 pub mod geoip;
 #[cfg(feature = "transforms-grok_parser")]
 pub mod grok_parser;
+#[cfg(feature = "transforms-join")]
+pub mod join;
 #[cfg(feature = "transforms-json_parser")]
 pub mod json_parser;
 #[cfg(feature = "transforms-key_value_parser")]
 pub mod key_value_parser;
+#[cfg(feature = "transforms-log_patterns")]
+pub mod log_patterns;
 #[cfg(feature = "transforms-log_to_metric")]
 pub mod log_to_metric;
 #[cfg(feature = "transforms-logfmt_parser")]
@@ -38,6 +42,8 @@ pub mod logfmt_parser;
 pub mod lua;
 #[cfg(feature = "transforms-merge")]
 pub mod merge;
+#[cfg(feature = "transforms-metric_convert")]
+pub mod metric_convert;
 #[cfg(feature = "transforms-metric_to_log")]
 pub mod metric_to_log;
 #[cfg(feature = "transforms-pipelines")]
@@ -46,6 +52,8 @@ pub mod pipelines;
 pub mod reduce;
 #[cfg(feature = "transforms-regex_parser")]
 pub mod regex_parser;
+#[cfg(feature = "transforms-relabel")]
+pub mod relabel;
 #[cfg(feature = "transforms-remap")]
 pub mod remap;
 #[cfg(feature = "transforms-remove_fields")]
@@ -58,6 +66,8 @@ pub mod rename_fields;
 pub mod route;
 #[cfg(feature = "transforms-sample")]
 pub mod sample;
+#[cfg(feature = "transforms-span_metrics")]
+pub mod span_metrics;
 #[cfg(feature = "transforms-split")]
 pub mod split;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
@@ -66,10 +76,12 @@ pub mod tag_cardinality_limit;
 pub mod throttle;
 #[cfg(feature = "transforms-tokenizer")]
 pub mod tokenizer;
+#[cfg(feature = "transforms-trace_sampler")]
+pub mod trace_sampler;
 
 pub use vector_core::transform::{
-    FunctionTransform, OutputBuffer, SyncTransform, TaskTransform, Transform, TransformOutputs,
-    TransformOutputsBuf,
+    FunctionTransform, MultiOutputTaskTransform, OutputBuffer, SyncTransform, TaskTransform,
+    Transform, TransformOutputs, TransformOutputsBuf,
 };
 
 #[derive(Debug, Snafu)]
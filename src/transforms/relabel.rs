@@ -0,0 +1,486 @@
+use md5::{Digest, Md5};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{metric::Metric, Event},
+    internal_events::RelabelEventDiscarded,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// The pseudo label holding the name of the metric.
+const NAME_LABEL: &str = "__name__";
+
+fn default_separator() -> String {
+    ";".to_owned()
+}
+
+fn default_regex() -> String {
+    "(.*)".to_owned()
+}
+
+fn default_replacement() -> String {
+    "$1".to_owned()
+}
+
+const fn default_action() -> RelabelAction {
+    RelabelAction::Replace
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RelabelConfig {
+    /// The relabeling rules, applied in order.
+    #[serde(alias = "relabel_configs")]
+    pub rules: Vec<RelabelRuleConfig>,
+}
+
+/// A relabeling rule, following the semantics of the Prometheus
+/// `relabel_config`.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RelabelRuleConfig {
+    /// The labels whose values are concatenated and matched against `regex`.
+    /// The name of the metric is available as `__name__`.
+    #[serde(default)]
+    pub source_labels: Vec<String>,
+
+    /// The separator placed between the concatenated source label values.
+    #[serde(default = "default_separator")]
+    pub separator: String,
+
+    /// The label written by the `replace` and `hashmod` actions.
+    pub target_label: Option<String>,
+
+    /// The regular expression matched against the concatenated source label
+    /// values, or against label names for the `labelmap`, `labeldrop` and
+    /// `labelkeep` actions. It is anchored at both ends.
+    #[serde(default = "default_regex")]
+    pub regex: String,
+
+    /// The modulus of the hash of the source label values for the `hashmod`
+    /// action.
+    pub modulus: Option<u64>,
+
+    /// The value written by the `replace` action, or the name of the label
+    /// written by the `labelmap` action, referencing capture groups of
+    /// `regex` as `$1` or `${name}`.
+    #[serde(default = "default_replacement")]
+    pub replacement: String,
+
+    #[serde(default = "default_action")]
+    pub action: RelabelAction,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelabelAction {
+    /// Set `target_label` to `replacement` if `regex` matches the source
+    /// label values.
+    Replace,
+    /// Drop metrics whose source label values don't match `regex`.
+    Keep,
+    /// Drop metrics whose source label values match `regex`.
+    Drop,
+    /// Set `target_label` to the hash of the source label values modulo
+    /// `modulus`.
+    HashMod,
+    /// Copy the labels whose names match `regex` to the labels named by
+    /// `replacement`.
+    LabelMap,
+    /// Remove the labels whose names match `regex`.
+    LabelDrop,
+    /// Remove the labels whose names don't match `regex`.
+    LabelKeep,
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid regex in relabel rule {}: {}", index, source))]
+    InvalidRegex { index: usize, source: regex::Error },
+    #[snafu(display("Relabel rule {} requires a `target_label`", index))]
+    MissingTargetLabel { index: usize },
+    #[snafu(display("Relabel rule {} requires a non-zero `modulus`", index))]
+    MissingModulus { index: usize },
+}
+
+inventory::submit! {
+    TransformDescription::new::<RelabelConfig>("relabel")
+}
+
+impl GenerateConfig for RelabelConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"[[rules]]
+            source_labels = ["job"]
+            regex = "node_.*"
+            action = "keep""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "relabel")]
+impl TransformConfig for RelabelConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Relabel::new(self).map(Transform::function)
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "relabel"
+    }
+}
+
+#[derive(Clone, Debug)]
+struct RelabelRule {
+    source_labels: Vec<String>,
+    separator: String,
+    target_label: String,
+    regex: Regex,
+    modulus: u64,
+    replacement: String,
+    action: RelabelAction,
+}
+
+impl RelabelRule {
+    fn new(index: usize, config: &RelabelRuleConfig) -> Result<Self, BuildError> {
+        let regex =
+            Regex::new(&format!("^(?:{})$", config.regex)).context(InvalidRegexSnafu { index })?;
+
+        let target_label = match (config.action, &config.target_label) {
+            (_, Some(target_label)) => target_label.clone(),
+            (RelabelAction::Replace | RelabelAction::HashMod, None) => {
+                return Err(BuildError::MissingTargetLabel { index })
+            }
+            (_, None) => String::new(),
+        };
+
+        let modulus = match (config.action, config.modulus) {
+            (RelabelAction::HashMod, None | Some(0)) => {
+                return Err(BuildError::MissingModulus { index })
+            }
+            (_, modulus) => modulus.unwrap_or_default(),
+        };
+
+        Ok(Self {
+            source_labels: config.source_labels.clone(),
+            separator: config.separator.clone(),
+            target_label,
+            regex,
+            modulus,
+            replacement: config.replacement.clone(),
+            action: config.action,
+        })
+    }
+
+    fn source_value(&self, metric: &Metric) -> String {
+        let values = self
+            .source_labels
+            .iter()
+            .map(|label| get_label(metric, label).unwrap_or_default())
+            .collect::<Vec<_>>();
+        values.join(&self.separator)
+    }
+
+    /// Apply the rule, returning `false` if the metric must be dropped.
+    fn apply(&self, metric: &mut Metric) -> bool {
+        match self.action {
+            RelabelAction::Replace => {
+                let value = self.source_value(metric);
+                if let Some(captures) = self.regex.captures(&value) {
+                    let mut target = String::new();
+                    captures.expand(&self.target_label, &mut target);
+                    let mut replacement = String::new();
+                    captures.expand(&self.replacement, &mut replacement);
+                    if !target.is_empty() {
+                        set_label(metric, target, replacement);
+                    }
+                }
+                true
+            }
+            RelabelAction::Keep => self.regex.is_match(&self.source_value(metric)),
+            RelabelAction::Drop => !self.regex.is_match(&self.source_value(metric)),
+            RelabelAction::HashMod => {
+                let digest = Md5::digest(self.source_value(metric).as_bytes());
+                let mut hash = [0; 8];
+                hash.copy_from_slice(&digest[8..]);
+                let value = u64::from_be_bytes(hash) % self.modulus;
+                set_label(metric, self.target_label.clone(), value.to_string());
+                true
+            }
+            RelabelAction::LabelMap => {
+                let mapped = metric
+                    .tags()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(name, value)| {
+                        self.regex.captures(name).map(|captures| {
+                            let mut target = String::new();
+                            captures.expand(&self.replacement, &mut target);
+                            (target, value.clone())
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                for (name, value) in mapped {
+                    set_label(metric, name, value);
+                }
+                true
+            }
+            RelabelAction::LabelDrop | RelabelAction::LabelKeep => {
+                let keep_matching = self.action == RelabelAction::LabelKeep;
+                let removed = metric
+                    .tags()
+                    .into_iter()
+                    .flatten()
+                    .map(|(name, _)| name)
+                    .filter(|name| self.regex.is_match(name) != keep_matching)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                for name in removed {
+                    metric.remove_tag(&name);
+                }
+                true
+            }
+        }
+    }
+}
+
+fn get_label(metric: &Metric, label: &str) -> Option<String> {
+    if label == NAME_LABEL {
+        Some(metric.name().to_owned())
+    } else {
+        metric.tag_value(label)
+    }
+}
+
+/// Set a label, removing it if the value is empty as Prometheus does. The
+/// name of the metric can't be removed.
+fn set_label(metric: &mut Metric, label: String, value: String) {
+    if label == NAME_LABEL {
+        if !value.is_empty() {
+            *metric.series_mut().name_mut().name_mut() = value;
+        }
+    } else if value.is_empty() {
+        metric.remove_tag(&label);
+    } else {
+        metric.insert_tag(label, value);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Relabel {
+    rules: Vec<RelabelRule>,
+}
+
+impl Relabel {
+    pub fn new(config: &RelabelConfig) -> crate::Result<Self> {
+        let rules = config
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| RelabelRule::new(index, rule))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+}
+
+impl FunctionTransform for Relabel {
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        let metric = event.as_mut_metric();
+        for rule in &self.rules {
+            if !rule.apply(metric) {
+                emit!(RelabelEventDiscarded);
+                return;
+            }
+        }
+        output.push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{
+        event::metric::{MetricKind, MetricValue},
+        transforms::test::transform_one,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RelabelConfig>();
+    }
+
+    fn relabel(config: &str, metric: Metric) -> Option<Metric> {
+        let config = toml::from_str::<RelabelConfig>(config).unwrap();
+        let mut transform = Relabel::new(&config).unwrap();
+        transform_one(&mut transform, metric.into()).map(Event::into_metric)
+    }
+
+    fn make_metric(name: &str, tags: &[(&str, &str)]) -> Metric {
+        Metric::new(
+            name,
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(
+            tags.iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        ))
+    }
+
+    fn tags(metric: &Metric) -> BTreeMap<String, String> {
+        metric.tags().cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn replace() {
+        let metric = relabel(
+            r#"
+[[rules]]
+source_labels = ["__name__", "instance"]
+regex = "(.*)_total;(.*):\\d+"
+target_label = "${2}_host"
+replacement = "$1 on $2"
+
+[[rules]]
+source_labels = ["job"]
+regex = "(.*)"
+target_label = "job"
+replacement = ""
+
+[[rules]]
+source_labels = ["__name__"]
+regex = "http_(.*)"
+target_label = "__name__"
+replacement = "web_$1"
+"#,
+            make_metric(
+                "http_requests_total",
+                &[("instance", "web1:8080"), ("job", "api")],
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(metric.name(), "web_requests_total");
+        assert_eq!(
+            tags(&metric),
+            BTreeMap::from([
+                ("instance".to_owned(), "web1:8080".to_owned()),
+                ("web1_host".to_owned(), "http_requests on web1".to_owned()),
+            ])
+        );
+    }
+
+    #[test]
+    fn keep_and_drop() {
+        let config = r#"
+[[rules]]
+source_labels = ["job"]
+regex = "node|api"
+action = "keep"
+
+[[rules]]
+source_labels = ["__name__"]
+regex = "go_.*"
+action = "drop"
+"#;
+        assert!(relabel(config, make_metric("up", &[("job", "node")])).is_some());
+        assert!(relabel(config, make_metric("up", &[("job", "nodes")])).is_none());
+        assert!(relabel(config, make_metric("up", &[])).is_none());
+        assert!(relabel(config, make_metric("go_goroutines", &[("job", "api")])).is_none());
+    }
+
+    #[test]
+    fn hashmod() {
+        let config = r#"
+[[rules]]
+source_labels = ["instance"]
+target_label = "shard"
+modulus = 8
+action = "hashmod"
+"#;
+        let shard = |instance: &str| {
+            relabel(config, make_metric("up", &[("instance", instance)]))
+                .unwrap()
+                .tag_value("shard")
+                .unwrap()
+        };
+        assert_eq!(shard("web1:8080"), shard("web1:8080"));
+        let all = (0..32)
+            .map(|index| shard(&format!("web{}:8080", index)))
+            .collect::<std::collections::BTreeSet<_>>();
+        assert!(all.len() > 1);
+        assert!(all.iter().all(|shard| shard.parse::<u64>().unwrap() < 8));
+    }
+
+    #[test]
+    fn label_actions() {
+        let metric = relabel(
+            r#"
+[[rules]]
+regex = "__meta_kubernetes_pod_label_(.+)"
+action = "labelmap"
+
+[[rules]]
+regex = "__meta_.*"
+action = "labeldrop"
+
+[[rules]]
+regex = "app|instance"
+action = "labelkeep"
+"#,
+            make_metric(
+                "up",
+                &[
+                    ("__meta_kubernetes_pod_label_app", "checkout"),
+                    ("__meta_kubernetes_pod_name", "checkout-1"),
+                    ("instance", "10.0.0.1:9100"),
+                    ("zone", "a"),
+                ],
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            tags(&metric),
+            BTreeMap::from([
+                ("app".to_owned(), "checkout".to_owned()),
+                ("instance".to_owned(), "10.0.0.1:9100".to_owned()),
+            ])
+        );
+    }
+
+    #[test]
+    fn invalid_rules() {
+        for config in [
+            r#"rules = [{ regex = "(" , target_label = "a" }]"#,
+            r#"rules = [{ action = "replace" }]"#,
+            r#"rules = [{ action = "hashmod", target_label = "a" }]"#,
+        ] {
+            let config = toml::from_str::<RelabelConfig>(config).unwrap();
+            assert!(Relabel::new(&config).is_err());
+        }
+    }
+}
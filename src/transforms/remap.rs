@@ -14,7 +14,7 @@ use vector_vrl_functions::set_semantic_meaning::MeaningList;
 use vrl::{
     diagnostic::{Formatter, Note},
     prelude::{DiagnosticMessage, ExpressionError},
-    Program, Runtime, Terminate, VrlRuntime,
+    Program, Runtime, Terminate, Vm, VrlRuntime,
 };
 
 use crate::{
@@ -45,6 +45,10 @@ pub struct RemapConfig {
     pub reroute_dropped: bool,
     #[serde(default)]
     pub runtime: VrlRuntime,
+    /// The directory of the config file defining the transform, which imports of `source`
+    /// programs are relative to.
+    #[serde(skip, default = "crate::config::current_config_dir")]
+    pub(crate) config_dir: Option<PathBuf>,
 }
 
 impl RemapConfig {
@@ -82,6 +86,13 @@ impl RemapConfig {
         );
         state.set_external_context(enrichment_tables);
         state.set_external_context(MeaningList::default());
+        let import_dir = match &self.file {
+            Some(file) => file.parent(),
+            None => self.config_dir.as_deref(),
+        };
+        if let Some(dir) = import_dir {
+            state.set_import_dir(dir);
+        }
 
         vrl::compile_with_state(&source, &functions, &mut state)
             .map_err(|diagnostics| {
@@ -116,6 +127,10 @@ impl TransformConfig for RemapConfig {
                 let (remap, warnings) = Remap::new_ast(self.clone(), context)?;
                 (Transform::synchronous(remap), warnings)
             }
+            VrlRuntime::Vm => {
+                let (remap, warnings) = Remap::new_vm(self.clone(), context)?;
+                (Transform::synchronous(remap), warnings)
+            }
         };
 
         // TODO: We could improve on this by adding support for non-fatal error
@@ -254,6 +269,34 @@ impl VrlRunner for AstRunner {
     }
 }
 
+#[derive(Debug)]
+pub struct VmRunner {
+    pub runtime: Runtime,
+    pub vm: Arc<Vm>,
+}
+
+impl Clone for VmRunner {
+    fn clone(&self) -> Self {
+        Self {
+            runtime: Runtime::default(),
+            vm: Arc::clone(&self.vm),
+        }
+    }
+}
+
+impl VrlRunner for VmRunner {
+    fn run(
+        &mut self,
+        target: &mut VrlTarget,
+        _: &Program,
+        timezone: &TimeZone,
+    ) -> std::result::Result<value::Value, Terminate> {
+        let result = self.runtime.run_vm(target, &self.vm, timezone);
+        self.runtime.clear();
+        result
+    }
+}
+
 impl Remap<AstRunner> {
     pub fn new_ast(
         config: RemapConfig,
@@ -271,6 +314,24 @@ impl Remap<AstRunner> {
     }
 }
 
+impl Remap<VmRunner> {
+    pub fn new_vm(
+        config: RemapConfig,
+        context: &TransformContext,
+    ) -> crate::Result<(Self, String)> {
+        let (program, warnings, _, _) = config.compile_vrl_program(
+            context.enrichment_tables.clone(),
+            context.merged_schema_definition.clone(),
+        )?;
+
+        let runtime = Runtime::default();
+        let vm = Arc::new(program.compile_to_vm());
+        let runner = VmRunner { runtime, vm };
+
+        Self::new(config, context, program, runner).map(|remap| (remap, warnings))
+    }
+}
+
 impl<Runner> Remap<Runner>
 where
     Runner: VrlRunner,
@@ -485,7 +546,7 @@ mod tests {
 
     use super::*;
     use crate::{
-        config::{build_unit_tests, ConfigBuilder},
+        config::{build_unit_tests, load_builder_from_paths, ConfigBuilder, ConfigPath, Format},
         event::{
             metric::{MetricKind, MetricValue},
             LogEvent, Metric, Value,
@@ -556,6 +617,45 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn config_import_in_source() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("normalize.vrl"),
+            "fn normalize_level(level: string) { downcase(strip_whitespace(level)) }",
+        )
+        .unwrap();
+        let path = dir.path().join("vector.toml");
+        std::fs::write(
+            &path,
+            indoc! {r#"
+                [transforms.normalize]
+                type = "remap"
+                inputs = []
+                source = """
+                import "normalize.vrl"
+                .level = normalize_level(string!(.level))
+                """
+            "#},
+        )
+        .unwrap();
+
+        // The import is resolved against the directory of the config file,
+        // not the working directory.
+        let (builder, _) =
+            load_builder_from_paths(&[ConfigPath::File(path, Some(Format::Toml))]).unwrap();
+        let schema_definitions = HashMap::from([
+            (None, test_default_schema_definition()),
+            (Some(DROPPED.to_owned()), test_dropped_schema_definition()),
+        ]);
+        let transform = builder.transforms[&ComponentKey::from("normalize")]
+            .inner
+            .build(&TransformContext::new_test(schema_definitions))
+            .await;
+
+        assert!(transform.is_ok());
+    }
+
     fn get_field_string(event: &Event, field: &str) -> String {
         event.as_log().get(field).unwrap().to_string_lossy()
     }
@@ -637,6 +737,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_remap_vm_runtime() {
+        let event = {
+            let mut event = LogEvent::from("augment me");
+            event.insert("copy_from", "buz");
+            event.insert("count", 2);
+            Event::from(event)
+        };
+
+        let conf = RemapConfig {
+            source: Some(
+                r#"  .foo = "bar"
+  .copy = .copy_from
+  if .count > 1 { .many = true } else { .many = false }
+  .parsed, .err = parse_int(.message)
+  .fallback = parse_int(.message) ?? 7
+"#
+                .to_string(),
+            ),
+            drop_on_error: true,
+            drop_on_abort: false,
+            runtime: VrlRuntime::Vm,
+            ..Default::default()
+        };
+        let schema_definitions = HashMap::from([
+            (None, test_default_schema_definition()),
+            (Some(DROPPED.to_owned()), test_dropped_schema_definition()),
+        ]);
+        let mut tform = Remap::new_vm(conf, &TransformContext::new_test(schema_definitions))
+            .map(|(remap, _)| remap)
+            .unwrap();
+
+        let result = transform_one(&mut tform, event).unwrap();
+        assert_eq!(get_field_string(&result, "message"), "augment me");
+        assert_eq!(get_field_string(&result, "foo"), "bar");
+        assert_eq!(get_field_string(&result, "copy"), "buz");
+        assert_eq!(result.as_log().get("many"), Some(&Value::from(true)));
+        assert_eq!(result.as_log().get("parsed"), Some(&Value::from(0)));
+        assert!(get_field_string(&result, "err").starts_with("could not parse integer"));
+        assert_eq!(result.as_log().get("fallback"), Some(&Value::from(7)));
+        assert!(tform.runner().runtime.is_empty());
+    }
+
     #[test]
     fn check_remap_emits_multiple() {
         let event = {
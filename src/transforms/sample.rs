@@ -1,4 +1,10 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
 
 use crate::{
     conditions::{AnyCondition, Condition},
@@ -7,17 +13,51 @@ use crate::{
         TransformDescription,
     },
     event::Event,
-    internal_events::SampleEventDiscarded,
+    internal_events::{SampleEventDiscarded, TemplateRenderingError},
     schema,
+    template::Template,
     transforms::{FunctionTransform, OutputBuffer, Transform},
 };
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct SampleConfig {
-    pub rate: u64,
+    pub rate: Option<u64>,
     pub key_field: Option<String>,
     pub exclude: Option<AnyCondition>,
+    pub dynamic: Option<DynamicSampleConfig>,
+}
+
+const fn default_window_secs() -> u64 {
+    30
+}
+
+/// Sample each key at a rate adjusted so that the total output stays near a
+/// target throughput, keeping rare keys at 100%.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DynamicSampleConfig {
+    /// The template rendering the key events are sampled by, such as
+    /// `{{ service }}`.
+    pub key: Template,
+
+    /// The number of events per second to forward, across all keys.
+    pub target_events_per_sec: f64,
+
+    /// The window over which the volume of each key is observed before its
+    /// sample rate is adjusted.
+    #[serde(default = "default_window_secs")]
+    pub window_secs: u64,
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Exactly one of `rate` and `dynamic` must be set"))]
+    RateOrDynamic,
+    #[snafu(display(
+        "`dynamic.target_events_per_sec` and `dynamic.window_secs` must be positive"
+    ))]
+    InvalidDynamicBudget,
 }
 
 inventory::submit! {
@@ -31,9 +71,10 @@ inventory::submit! {
 impl GenerateConfig for SampleConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
-            rate: 10,
+            rate: Some(10),
             key_field: None,
             exclude: None::<AnyCondition>,
+            dynamic: None,
         })
         .unwrap()
     }
@@ -43,14 +84,26 @@ impl GenerateConfig for SampleConfig {
 #[typetag::serde(name = "sample")]
 impl TransformConfig for SampleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Sample::new(
-            self.rate,
-            self.key_field.clone(),
-            self.exclude
-                .as_ref()
-                .map(|condition| condition.build(&context.enrichment_tables))
-                .transpose()?,
-        )))
+        let exclude = self
+            .exclude
+            .as_ref()
+            .map(|condition| condition.build(&context.enrichment_tables))
+            .transpose()?;
+
+        match (self.rate, &self.dynamic) {
+            (Some(rate), None) => Ok(Transform::function(Sample::new(
+                rate,
+                self.key_field.clone(),
+                exclude,
+            ))),
+            (None, Some(dynamic)) => {
+                if dynamic.target_events_per_sec <= 0.0 || dynamic.window_secs == 0 {
+                    return Err(BuildError::InvalidDynamicBudget.into());
+                }
+                Ok(Transform::function(DynamicSample::new(dynamic, exclude)))
+            }
+            _ => Err(BuildError::RateOrDynamic.into()),
+        }
     }
 
     fn input(&self) -> Input {
@@ -150,6 +203,107 @@ impl FunctionTransform for Sample {
     }
 }
 
+/// Compute the sample rate of each key from the number of events seen for it
+/// over a window, sharing the budget of the window between the keys. Keys are
+/// visited from the rarest one, so that keys below their fair share of the
+/// remaining budget are kept at 100%, and the budget they leave is shared by
+/// the noisier keys. Each key keeps at least one event per window.
+fn compute_rates(counts: &HashMap<String, u64>, budget: f64) -> HashMap<String, u64> {
+    let mut counts = counts.iter().collect::<Vec<_>>();
+    counts.sort_by_key(|(_, count)| **count);
+
+    let mut remaining_budget = budget;
+    let mut rates = HashMap::with_capacity(counts.len());
+    for (index, (key, count)) in counts.iter().enumerate() {
+        let count = **count as f64;
+        let share = (remaining_budget / (counts.len() - index) as f64).max(1.0);
+        let rate = if count <= share {
+            1
+        } else {
+            (count / share).ceil() as u64
+        };
+        remaining_budget = (remaining_budget - count / rate as f64).max(0.0);
+        rates.insert((*key).clone(), rate);
+    }
+    rates
+}
+
+#[derive(Clone)]
+pub struct DynamicSample {
+    key: Template,
+    /// The number of events to forward per window.
+    budget: f64,
+    window: Duration,
+    exclude: Option<Condition>,
+    window_start: Instant,
+    /// The number of events seen for each key during the current window.
+    counts: HashMap<String, u64>,
+    /// The sample rate of each key, computed from the previous window. Keys
+    /// not seen during the previous window aren't sampled.
+    rates: HashMap<String, u64>,
+}
+
+impl DynamicSample {
+    pub fn new(config: &DynamicSampleConfig, exclude: Option<Condition>) -> Self {
+        Self {
+            key: config.key.clone(),
+            budget: config.target_events_per_sec * config.window_secs as f64,
+            window: Duration::from_secs(config.window_secs),
+            exclude,
+            window_start: Instant::now(),
+            counts: HashMap::new(),
+            rates: HashMap::new(),
+        }
+    }
+
+    fn sample(&mut self, output: &mut OutputBuffer, mut event: Event, now: Instant) {
+        if now.saturating_duration_since(self.window_start) >= self.window {
+            self.rates = compute_rates(&self.counts, self.budget);
+            self.counts.clear();
+            self.window_start = now;
+        }
+
+        let key = self.key.render_string(&event).unwrap_or_else(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some("key"),
+                drop_event: false,
+            });
+            String::new()
+        });
+
+        let rate = self.rates.get(&key).copied().unwrap_or(1);
+        let seen = self.counts.entry(key).or_default();
+        let index = *seen;
+        *seen += 1;
+
+        if index % rate == 0 {
+            event.as_mut_log().insert("sample_rate", rate.to_string());
+            output.push(event);
+        } else {
+            emit!(SampleEventDiscarded);
+        }
+    }
+}
+
+impl FunctionTransform for DynamicSample {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        let event = match self.exclude.as_ref() {
+            Some(condition) => {
+                let (result, event) = condition.check(event);
+                if result {
+                    output.push(event);
+                    return;
+                }
+                event
+            }
+            None => event,
+        };
+
+        self.sample(output, event, Instant::now());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use approx::assert_relative_eq;
@@ -166,7 +320,7 @@ mod tests {
     fn condition_contains(key: &str, needle: &str) -> Condition {
         VrlConfig {
             source: format!(r#"contains!(."{}", "{}")"#, key, needle),
-            runtime: Default::default(),
+            ..Default::default()
         }
         .build(&Default::default())
         .unwrap()
@@ -339,6 +493,75 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn requires_rate_or_dynamic() {
+        for config in [
+            "",
+            r#"
+rate = 10
+dynamic.key = "{{ service }}"
+dynamic.target_events_per_sec = 10.0
+"#,
+        ] {
+            let config = toml::from_str::<SampleConfig>(config).unwrap();
+            assert!(config.build(&TransformContext::default()).await.is_err());
+        }
+    }
+
+    #[test]
+    fn dynamic_rates_keep_rare_keys() {
+        let counts = HashMap::from([
+            ("noisy".to_owned(), 1000),
+            ("medium".to_owned(), 100),
+            ("rare".to_owned(), 5),
+        ]);
+        let rates = compute_rates(&counts, 200.0);
+        assert_eq!(rates["rare"], 1);
+        assert_eq!(rates["medium"], 2);
+        assert_eq!(rates["noisy"], 7);
+    }
+
+    #[test]
+    fn dynamic_sampling_targets_throughput() {
+        let config = toml::from_str::<DynamicSampleConfig>(
+            r#"
+key = "{{ service }}"
+target_events_per_sec = 10.0
+window_secs = 10
+"#,
+        )
+        .unwrap();
+        let mut sampler = DynamicSample::new(&config, None);
+        let start = Instant::now();
+
+        let mut run_window = |now: Instant| {
+            let mut kept = HashMap::<String, Vec<Event>>::new();
+            for (service, count) in [("noisy", 500), ("rare", 5)] {
+                for _ in 0..count {
+                    let mut event = Event::from("message");
+                    event.as_mut_log().insert("service", service);
+                    let mut buf = OutputBuffer::with_capacity(1);
+                    sampler.sample(&mut buf, event, now);
+                    kept.entry(service.to_owned())
+                        .or_default()
+                        .extend(buf.into_events());
+                }
+            }
+            kept
+        };
+
+        // Nothing is known about the keys during the first window.
+        let kept = run_window(start);
+        assert_eq!(kept["noisy"].len(), 500);
+        assert_eq!(kept["rare"].len(), 5);
+
+        let kept = run_window(start + Duration::from_secs(10));
+        assert_eq!(kept["rare"].len(), 5);
+        assert_eq!(kept["rare"][0].as_log()["sample_rate"], "1".into());
+        assert_eq!(kept["noisy"].len(), 84);
+        assert_eq!(kept["noisy"][0].as_log()["sample_rate"], "6".into());
+    }
+
     fn random_events(n: usize) -> Vec<Event> {
         random_lines(10).take(n).map(Event::from).collect()
     }
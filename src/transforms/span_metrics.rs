@@ -0,0 +1,406 @@
+use std::{collections::BTreeMap, pin::Pin, time::Duration};
+
+use async_stream::stream;
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{
+        metric::{Bucket, Metric, MetricKind, MetricTags, MetricValue},
+        Event, TraceEvent, Value,
+    },
+    schema,
+    transforms::{MultiOutputTaskTransform, Transform, TransformOutputsBuf},
+};
+
+//------------------------------------------------------------------------------
+
+pub(crate) const METRICS_OUTPUT: &str = "metrics";
+
+fn default_namespace() -> String {
+    "traces".to_owned()
+}
+
+const fn default_flush_period_ms() -> u64 {
+    10000
+}
+
+fn default_buckets() -> Vec<f64> {
+    vec![
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ]
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SpanMetricsConfig {
+    /// The namespace of the emitted metrics.
+    #[serde(default = "default_namespace")]
+    pub namespace: String,
+
+    /// How often the metrics aggregated from the spans are emitted.
+    #[serde(default = "default_flush_period_ms")]
+    pub flush_period_ms: u64,
+
+    /// The span attributes, read from the `meta` of each span, to add as
+    /// tags to the metrics in addition to the service, resource, operation
+    /// and status.
+    #[serde(default)]
+    pub dimensions: Vec<String>,
+
+    /// The upper limits, in seconds, of the buckets of the duration
+    /// histogram.
+    #[serde(default = "default_buckets")]
+    pub buckets: Vec<f64>,
+}
+
+inventory::submit! {
+    TransformDescription::new::<SpanMetricsConfig>("span_metrics")
+}
+
+impl GenerateConfig for SpanMetricsConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            namespace: default_namespace(),
+            flush_period_ms: default_flush_period_ms(),
+            dimensions: Vec::new(),
+            buckets: default_buckets(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "span_metrics")]
+impl TransformConfig for SpanMetricsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::multi_output_task(SpanMetrics::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![
+            Output::default(DataType::Trace),
+            Output::default(DataType::Metric).with_port(METRICS_OUTPUT),
+        ]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "span_metrics"
+    }
+}
+
+/// The statistics aggregated for the spans sharing the same tags.
+#[derive(Debug)]
+struct SpanStats {
+    requests: u32,
+    buckets: Vec<u32>,
+    sum: f64,
+}
+
+impl SpanStats {
+    fn new(buckets: usize) -> Self {
+        Self {
+            requests: 0,
+            buckets: vec![0; buckets],
+            sum: 0.0,
+        }
+    }
+
+    fn add(&mut self, duration_secs: f64, upper_limits: &[f64]) {
+        self.requests += 1;
+        self.sum += duration_secs;
+        if let Some(index) = upper_limits
+            .iter()
+            .position(|upper_limit| duration_secs <= *upper_limit)
+        {
+            self.buckets[index] += 1;
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+pub struct SpanMetrics {
+    namespace: String,
+    flush_period: Duration,
+    dimensions: Vec<String>,
+    buckets: Vec<f64>,
+    outputs: Vec<Output>,
+    stats: BTreeMap<MetricTags, SpanStats>,
+}
+
+impl SpanMetrics {
+    pub fn new(config: &SpanMetricsConfig) -> Self {
+        let mut buckets = config.buckets.clone();
+        buckets.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        Self {
+            namespace: config.namespace.clone(),
+            flush_period: Duration::from_millis(config.flush_period_ms),
+            dimensions: config.dimensions.clone(),
+            buckets,
+            outputs: config.outputs(&schema::Definition::empty()),
+            stats: BTreeMap::new(),
+        }
+    }
+
+    fn new_buf(&self) -> TransformOutputsBuf {
+        TransformOutputsBuf::new_with_capacity(self.outputs.clone(), 1)
+    }
+
+    fn span_tags(&self, span: &BTreeMap<String, Value>, error: bool) -> MetricTags {
+        let mut tags = MetricTags::new();
+        for (tag, field) in [
+            ("service", "service"),
+            ("resource", "resource"),
+            ("operation", "name"),
+        ] {
+            if let Some(value) = span.get(field) {
+                tags.insert(tag.to_owned(), value.to_string_lossy());
+            }
+        }
+        tags.insert(
+            "status".to_owned(),
+            if error { "error" } else { "ok" }.to_owned(),
+        );
+
+        let meta = span.get("meta").and_then(Value::as_object);
+        for dimension in &self.dimensions {
+            if let Some(value) = meta.and_then(|meta| meta.get(dimension)) {
+                tags.insert(dimension.clone(), value.to_string_lossy());
+            }
+        }
+        tags
+    }
+
+    fn record(&mut self, trace: &TraceEvent) {
+        let spans = match trace.get("spans") {
+            Some(Value::Array(spans)) => spans,
+            _ => return,
+        };
+        for span in spans.iter().filter_map(Value::as_object) {
+            let error = matches!(span.get("error"), Some(Value::Integer(error)) if *error != 0);
+            let duration_ns = match span.get("duration") {
+                Some(Value::Integer(duration)) => *duration,
+                _ => 0,
+            };
+
+            let tags = self.span_tags(span, error);
+            let buckets = self.buckets.len();
+            self.stats
+                .entry(tags)
+                .or_insert_with(|| SpanStats::new(buckets))
+                .add(duration_ns as f64 / 1e9, &self.buckets);
+        }
+    }
+
+    fn make_metric(&self, name: &str, tags: &MetricTags, value: MetricValue) -> Metric {
+        Metric::new(name, MetricKind::Incremental, value)
+            .with_namespace(Some(self.namespace.clone()))
+            .with_tags(Some(tags.clone()))
+            .with_timestamp(Some(Utc::now()))
+    }
+
+    fn flush_into(&mut self, output: &mut TransformOutputsBuf) {
+        for (tags, stats) in std::mem::take(&mut self.stats) {
+            let requests = stats.requests as f64;
+            output.push_named(
+                METRICS_OUTPUT,
+                self.make_metric(
+                    "span_requests_total",
+                    &tags,
+                    MetricValue::Counter { value: requests },
+                )
+                .into(),
+            );
+            if tags.get("status").map(String::as_str) == Some("error") {
+                output.push_named(
+                    METRICS_OUTPUT,
+                    self.make_metric(
+                        "span_errors_total",
+                        &tags,
+                        MetricValue::Counter { value: requests },
+                    )
+                    .into(),
+                );
+            }
+
+            let buckets = self
+                .buckets
+                .iter()
+                .zip(stats.buckets)
+                .map(|(upper_limit, count)| Bucket {
+                    upper_limit: *upper_limit,
+                    count,
+                })
+                .collect();
+            output.push_named(
+                METRICS_OUTPUT,
+                self.make_metric(
+                    "span_duration_seconds",
+                    &tags,
+                    MetricValue::AggregatedHistogram {
+                        buckets,
+                        count: stats.requests,
+                        sum: stats.sum,
+                    },
+                )
+                .into(),
+            );
+        }
+    }
+}
+
+impl MultiOutputTaskTransform for SpanMetrics {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = TransformOutputsBuf> + Send>> {
+        let mut me = self;
+
+        let mut flush_stream = tokio::time::interval(me.flush_period);
+
+        Box::pin(stream! {
+            loop {
+                let mut output = me.new_buf();
+                let done = tokio::select! {
+                    _ = flush_stream.tick() => {
+                        me.flush_into(&mut output);
+                        false
+                    }
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                me.flush_into(&mut output);
+                                true
+                            }
+                            Some(event) => {
+                                me.record(event.as_trace());
+                                output.push(event);
+                                false
+                            }
+                        }
+                    }
+                };
+                if !output.is_empty() {
+                    yield output;
+                }
+                if done { break }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SpanMetricsConfig>();
+    }
+
+    fn make_span(resource: &str, duration_ms: i64, error: i64) -> Value {
+        let mut meta = BTreeMap::<String, Value>::new();
+        meta.insert("http.method".into(), Value::from("GET"));
+
+        let mut span = BTreeMap::<String, Value>::new();
+        span.insert("service".into(), Value::from("checkout"));
+        span.insert("name".into(), Value::from("http.request"));
+        span.insert("resource".into(), Value::from(resource));
+        span.insert("duration".into(), Value::from(duration_ms * 1_000_000));
+        span.insert("error".into(), Value::from(error));
+        span.insert("meta".into(), Value::from(meta));
+        Value::from(span)
+    }
+
+    fn make_trace(spans: Vec<Value>) -> Event {
+        let mut trace = TraceEvent::default();
+        trace.insert("spans", spans);
+        Event::Trace(trace)
+    }
+
+    #[tokio::test]
+    async fn derives_red_metrics_from_spans() {
+        let config = toml::from_str::<SpanMetricsConfig>(
+            r#"
+namespace = "service"
+dimensions = ["http.method"]
+buckets = [0.3, 1.0]
+"#,
+        )
+        .unwrap();
+        let transform = Box::new(SpanMetrics::new(&config));
+
+        let inputs = vec![
+            make_trace(vec![make_span("/cart", 250, 0), make_span("/cart", 500, 0)]),
+            make_trace(vec![make_span("/cart", 2000, 1)]),
+        ];
+        let mut outputs = transform
+            .transform(Box::pin(stream::iter(inputs)))
+            .collect::<Vec<_>>()
+            .await;
+
+        let traces = outputs
+            .iter_mut()
+            .flat_map(|output| output.drain().collect::<Vec<_>>())
+            .count();
+        assert_eq!(traces, 2);
+
+        let metrics = outputs
+            .iter_mut()
+            .flat_map(|output| output.drain_named(METRICS_OUTPUT).collect::<Vec<_>>())
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+        assert_eq!(metrics.len(), 5);
+
+        let find = |name: &str, status: &str| {
+            metrics
+                .iter()
+                .find(|metric| metric.name() == name && metric.tag_matches("status", status))
+                .unwrap()
+        };
+
+        let requests = find("span_requests_total", "ok");
+        assert_eq!(requests.namespace(), Some("service"));
+        assert!(requests.tag_matches("service", "checkout"));
+        assert!(requests.tag_matches("resource", "/cart"));
+        assert!(requests.tag_matches("operation", "http.request"));
+        assert!(requests.tag_matches("http.method", "GET"));
+        assert_eq!(requests.value(), &MetricValue::Counter { value: 2.0 });
+
+        assert_eq!(
+            find("span_errors_total", "error").value(),
+            &MetricValue::Counter { value: 1.0 }
+        );
+
+        assert_eq!(
+            find("span_duration_seconds", "ok").value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 0.3,
+                        count: 1
+                    },
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 1
+                    },
+                ],
+                count: 2,
+                sum: 0.75,
+            }
+        );
+    }
+}
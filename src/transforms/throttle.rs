@@ -1,21 +1,55 @@
-use std::{num::NonZeroU32, pin::Pin, time::Duration};
+use std::{collections::HashMap, num::NonZeroU32, pin::Pin, time::Duration};
 
 use async_stream::stream;
-use futures::{stream, Stream, StreamExt};
+use enrichment::{Case, IndexHandle, TableSearch};
+use futures::{Stream, StreamExt};
 use governor::{clock, Quota, RateLimiter};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
+use vector_core::ByteSizeOf;
 
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
-    event::Event,
+    event::{Event, Value},
     internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
     schema,
     template::Template,
-    transforms::{TaskTransform, Transform},
+    transforms::{MultiOutputTaskTransform, Transform, TransformOutputsBuf},
 };
 
+/// The name of the output throttled events are routed to.
+pub const DROPPED_OUTPUT: &str = "dropped";
+
+/// What the `threshold` of a window counts.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThrottleMode {
+    /// The number of events.
+    Events,
+    /// The total in-memory size of events, in bytes.
+    Bytes,
+}
+
+impl Default for ThrottleMode {
+    fn default() -> Self {
+        Self::Events
+    }
+}
+
+/// An enrichment table holding the thresholds of keys.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ThresholdTableConfig {
+    /// The name of the enrichment table.
+    table: String,
+    /// The column matched against the rendered `key_field`.
+    key_column: String,
+    /// The column holding the threshold of the key.
+    threshold_column: String,
+}
+
 #[derive(Deserialize, Default, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields, default)]
 pub struct ThrottleConfig {
@@ -23,6 +57,11 @@ pub struct ThrottleConfig {
     window_secs: f64,
     key_field: Option<Template>,
     exclude: Option<AnyCondition>,
+    mode: ThrottleMode,
+    /// Thresholds overriding `threshold` for the given keys.
+    thresholds: IndexMap<String, u32>,
+    /// Looks up the thresholds of keys not found in `thresholds`.
+    threshold_table: Option<ThresholdTableConfig>,
 }
 
 inventory::submit! {
@@ -35,15 +74,18 @@ impl_generate_config_from_default!(ThrottleConfig);
 #[typetag::serde(name = "throttle")]
 impl TransformConfig for ThrottleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Throttle::new(self, context, clock::MonotonicClock).map(Transform::event_task)
+        Throttle::new(self, context, clock::MonotonicClock).map(Transform::multi_output_task)
     }
 
     fn input(&self) -> Input {
-        Input::log()
+        Input::all()
     }
 
     fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
-        vec![Output::default(DataType::Log)]
+        vec![
+            Output::default(DataType::all()),
+            Output::default(DataType::all()).with_port(DROPPED_OUTPUT),
+        ]
     }
 
     fn transform_type(&self) -> &'static str {
@@ -51,12 +93,97 @@ impl TransformConfig for ThrottleConfig {
     }
 }
 
-#[derive(Clone)]
+/// The quota allowing `threshold` cells per `window`, or `None` if the
+/// threshold is too high to replenish a cell at least every nanosecond.
+fn quota(threshold: NonZeroU32, window: Duration) -> Option<Quota> {
+    Quota::with_period(window / threshold.get()).map(|quota| quota.allow_burst(threshold))
+}
+
+fn validate_threshold(value: u32, window: Duration) -> Result<NonZeroU32, ConfigError> {
+    let threshold = NonZeroU32::new(value).ok_or(ConfigError::NonZero)?;
+    match quota(threshold, window) {
+        Some(_) => Ok(threshold),
+        None => Err(ConfigError::ThresholdTooHigh { threshold: value }),
+    }
+}
+
+struct ThresholdTable {
+    search: TableSearch,
+    index: IndexHandle,
+    table: String,
+    key_column: String,
+    threshold_column: String,
+    /// The thresholds looked up so far, cleared along with the idle keys of
+    /// the rate limiters so that changes of the table are picked up.
+    cache: HashMap<String, Option<NonZeroU32>>,
+}
+
+impl ThresholdTable {
+    fn new(
+        config: &ThresholdTableConfig,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        let mut enrichment_tables = enrichment_tables.clone();
+        let index = enrichment_tables.add_index(
+            &config.table,
+            Case::Sensitive,
+            &[config.key_column.as_str()],
+        )?;
+
+        Ok(Self {
+            search: enrichment_tables.as_readonly(),
+            index,
+            table: config.table.clone(),
+            key_column: config.key_column.clone(),
+            threshold_column: config.threshold_column.clone(),
+            cache: HashMap::new(),
+        })
+    }
+
+    /// Looks up the threshold of `key`, ignoring keys without a row and rows
+    /// without a valid threshold.
+    fn lookup(&mut self, key: &str, window: Duration) -> Option<NonZeroU32> {
+        if let Some(threshold) = self.cache.get(key) {
+            return *threshold;
+        }
+
+        let condition = [enrichment::Condition::Equals {
+            field: &self.key_column,
+            value: Value::from(key),
+        }];
+        let select = [self.threshold_column.clone()];
+        let threshold = self
+            .search
+            .find_table_row(
+                &self.table,
+                Case::Sensitive,
+                &condition,
+                Some(&select),
+                Some(self.index),
+            )
+            .ok()
+            .and_then(|mut row| row.remove(&self.threshold_column))
+            .and_then(|value| match value {
+                Value::Integer(value) => u32::try_from(value).ok(),
+                _ => None,
+            })
+            .and_then(|value| validate_threshold(value, window).ok());
+
+        self.cache.insert(key.to_owned(), threshold);
+        threshold
+    }
+}
+
 pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
-    quota: Quota,
+    window: Duration,
+    threshold: NonZeroU32,
+    thresholds: HashMap<String, NonZeroU32>,
+    threshold_table: Option<ThresholdTable>,
+    mode: ThrottleMode,
     flush_keys_interval: Duration,
     key_field: Option<Template>,
     exclude: Option<Condition>,
+    outputs: Vec<Output>,
     clock: C,
 }
 
@@ -70,19 +197,21 @@ where
         context: &TransformContext,
         clock: C,
     ) -> crate::Result<Self> {
-        let flush_keys_interval = Duration::from_secs_f64(config.window_secs);
-
-        let threshold = match NonZeroU32::new(config.threshold) {
-            Some(threshold) => threshold,
-            None => return Err(Box::new(ConfigError::NonZero)),
-        };
-
-        let quota = match Quota::with_period(Duration::from_secs_f64(
-            config.window_secs / threshold.get() as f64,
-        )) {
-            Some(quota) => quota.allow_burst(threshold),
-            None => return Err(Box::new(ConfigError::NonZero)),
-        };
+        if !config.window_secs.is_finite() || config.window_secs <= 0.0 {
+            return Err(Box::new(ConfigError::NonZero));
+        }
+        let window = Duration::from_secs_f64(config.window_secs);
+
+        let thresholds = config
+            .thresholds
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), validate_threshold(*value, window)?)))
+            .collect::<Result<_, ConfigError>>()?;
+        let threshold_table = config
+            .threshold_table
+            .as_ref()
+            .map(|table| ThresholdTable::new(table, &context.enrichment_tables))
+            .transpose()?;
         let exclude = config
             .exclude
             .as_ref()
@@ -90,16 +219,51 @@ where
             .transpose()?;
 
         Ok(Self {
-            quota,
-            clock,
-            flush_keys_interval,
+            window,
+            threshold: validate_threshold(config.threshold, window)?,
+            thresholds,
+            threshold_table,
+            mode: config.mode,
+            flush_keys_interval: window,
             key_field: config.key_field.clone(),
             exclude,
+            outputs: config.outputs(&schema::Definition::empty()),
+            clock,
         })
     }
+
+    /// The threshold of `key`, from `thresholds`, then from the threshold
+    /// table, defaulting to `threshold`.
+    fn threshold_for(&mut self, key: Option<&str>) -> NonZeroU32 {
+        let key = match key {
+            Some(key) => key,
+            None => return self.threshold,
+        };
+        if let Some(threshold) = self.thresholds.get(key) {
+            return *threshold;
+        }
+        self.threshold_table
+            .as_mut()
+            .and_then(|table| table.lookup(key, self.window))
+            .unwrap_or(self.threshold)
+    }
+
+    /// The number of cells `event` consumes. Events larger than the `threshold` of their bucket
+    /// consume the whole burst, as they could never pass otherwise.
+    fn cost(&self, event: &Event, threshold: NonZeroU32) -> NonZeroU32 {
+        match self.mode {
+            ThrottleMode::Events => NonZeroU32::new(1).unwrap(),
+            ThrottleMode::Bytes => {
+                let size = u32::try_from(event.size_of()).unwrap_or(u32::MAX);
+                NonZeroU32::new(size)
+                    .unwrap_or_else(|| NonZeroU32::new(1).unwrap())
+                    .min(threshold)
+            }
+        }
+    }
 }
 
-impl<C, I> TaskTransform<Event> for Throttle<C, I>
+impl<C, I> MultiOutputTaskTransform for Throttle<C, I>
 where
     C: clock::Clock<Instant = I> + Send + 'static,
     I: clock::Reference + Send + 'static,
@@ -107,87 +271,104 @@ where
     fn transform(
         self: Box<Self>,
         mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
-    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
-    where
-        Self: 'static,
-    {
-        let mut flush_keys = tokio::time::interval(self.flush_keys_interval * 2);
+    ) -> Pin<Box<dyn Stream<Item = TransformOutputsBuf> + Send>> {
+        let mut me = self;
+
+        let mut flush_keys = tokio::time::interval(me.flush_keys_interval * 2);
 
         let mut flush_stream = tokio::time::interval(Duration::from_millis(1000));
 
-        let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
-
-        Box::pin(
-            stream! {
-              loop {
-                let mut output = Vec::new();
-                let done = tokio::select! {
-                    biased;
-
-                    maybe_event = input_rx.next() => {
-                        match maybe_event {
-                            None => true,
-                            Some(event) => {
-                                let (throttle, event) = match self.exclude.as_ref() {
-                                        Some(condition) => {
-                                            let (result, event) = condition.check(event);
-                                            (!result, event)
-                                        },
-                                        _ => (true, event)
-                                    };
-                                    if throttle {
-                                        let key = self.key_field.as_ref().and_then(|t| {
-                                            t.render_string(&event)
-                                                .map_err(|error| {
-                                                    emit!(TemplateRenderingError {
-                                                        error,
-                                                        field: Some("key_field"),
-                                                        drop_event: false,
-                                                    })
+        // Keys sharing a threshold share a keyed rate limiter.
+        let mut limiters = HashMap::new();
+
+        Box::pin(stream! {
+          loop {
+            let mut output = TransformOutputsBuf::new_with_capacity(me.outputs.clone(), 1);
+            let done = tokio::select! {
+                biased;
+
+                maybe_event = input_rx.next() => {
+                    match maybe_event {
+                        None => true,
+                        Some(event) => {
+                            let (throttle, event) = match me.exclude.as_ref() {
+                                    Some(condition) => {
+                                        let (result, event) = condition.check(event);
+                                        (!result, event)
+                                    },
+                                    _ => (true, event)
+                                };
+                                if throttle {
+                                    let key = me.key_field.as_ref().and_then(|t| {
+                                        t.render_string(&event)
+                                            .map_err(|error| {
+                                                emit!(TemplateRenderingError {
+                                                    error,
+                                                    field: Some("key_field"),
+                                                    drop_event: false,
                                                 })
-                                                .ok()
-                                        });
-
-                                        match limiter.check_key(&key) {
-                                            Ok(()) => {
-                                                output.push(event);
-                                            }
-                                            _ => {
-                                                if let Some(key) = key {
-                                                  emit!(ThrottleEventDiscarded{key})
-                                                } else {
-                                                  emit!(ThrottleEventDiscarded{key: "None".to_string()})
-                                                }
+                                            })
+                                            .ok()
+                                    });
+
+                                    let threshold = me.threshold_for(key.as_deref());
+                                    let limiter = limiters.entry(threshold).or_insert_with(|| {
+                                        let quota = quota(threshold, me.window)
+                                            .expect("thresholds are validated");
+                                        RateLimiter::dashmap_with_clock(quota, &me.clock)
+                                    });
+
+                                    match limiter.check_key_n(&key, me.cost(&event, threshold)) {
+                                        Ok(()) => {
+                                            output.push(event);
+                                        }
+                                        _ => {
+                                            if let Some(key) = key {
+                                              emit!(ThrottleEventDiscarded{key})
+                                            } else {
+                                              emit!(ThrottleEventDiscarded{key: "None".to_string()})
                                             }
+                                            output.push_named(DROPPED_OUTPUT, event);
                                         }
-                                    } else {
-                                        output.push(event)
                                     }
-                                false
-                            }
+                                } else {
+                                    output.push(event)
+                                }
+                            false
                         }
                     }
-                    _ = flush_keys.tick() => {
+                }
+                _ = flush_keys.tick() => {
+                    for limiter in limiters.values() {
                         limiter.retain_recent();
-                        false
                     }
-                    _ = flush_stream.tick() => {
-                        false
+                    if let Some(table) = me.threshold_table.as_mut() {
+                        table.cache.clear();
                     }
-                };
-                yield stream::iter(output.into_iter());
-                if done { break }
-              }
+                    false
+                }
+                _ = flush_stream.tick() => {
+                    false
+                }
+            };
+            if !output.is_empty() {
+                yield output;
             }
-            .flatten(),
-        )
+            if done { break }
+          }
+        })
     }
 }
 
 #[derive(Debug, Snafu)]
 pub enum ConfigError {
-    #[snafu(display("`threshold`, and `window_secs` must be non-zero"))]
+    #[snafu(display("`threshold`, `thresholds` and `window_secs` must be non-zero"))]
     NonZero,
+    #[snafu(display(
+        "Threshold {} is too high to be replenished within `window_secs`",
+        threshold
+    ))]
+    ThresholdTooHigh { threshold: u32 },
 }
 
 #[cfg(test)]
@@ -197,80 +378,89 @@ mod tests {
     use futures::SinkExt;
 
     use super::*;
-    use crate::event::Event;
+    use crate::event::{LogEvent, Metric, MetricKind, MetricValue};
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<ThrottleConfig>();
     }
 
+    type OutputStream = Pin<Box<dyn Stream<Item = TransformOutputsBuf> + Send>>;
+
+    fn throttle(
+        config: &str,
+        clock: clock::FakeRelativeClock,
+    ) -> (futures::channel::mpsc::Sender<Event>, OutputStream) {
+        let config = toml::from_str::<ThrottleConfig>(config).unwrap();
+        let throttle = Throttle::new(&config, &TransformContext::default(), clock).unwrap();
+
+        let (tx, rx) = futures::channel::mpsc::channel(10);
+        (tx, Box::new(throttle).transform(Box::pin(rx)))
+    }
+
+    /// Returns the number of events passed and dropped for the next event.
+    async fn next_output(out_stream: &mut OutputStream) -> (usize, usize) {
+        let mut output = out_stream
+            .next()
+            .await
+            .expect("Unexpectedly received None in output stream");
+        (
+            output.drain().count(),
+            output.drain_named(DROPPED_OUTPUT).count(),
+        )
+    }
+
     #[tokio::test]
     async fn throttle_events() {
         let clock = clock::FakeRelativeClock::default();
-        let config = toml::from_str::<ThrottleConfig>(
+        let (mut tx, mut out_stream) = throttle(
             r#"
 threshold = 2
 window_secs = 5
 "#,
-        )
-        .unwrap();
-
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
-
-        let throttle = throttle.into_task();
-
-        let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
+            clock.clone(),
+        );
 
         // tokio interval is always immediately ready, so we poll once to make sure
         // we trip it/set the interval in the future
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        assert!(matches!(futures::poll!(out_stream.next()), Poll::Pending));
 
         tx.send(Event::new_empty_log()).await.unwrap();
         tx.send(Event::new_empty_log()).await.unwrap();
 
-        let mut count = 0_u8;
-        while count < 2 {
-            if let Some(_event) = out_stream.next().await {
-                count += 1;
-            } else {
-                panic!("Unexpectedly received None in output stream");
-            }
-        }
-        assert_eq!(2, count);
+        assert_eq!((1, 0), next_output(&mut out_stream).await);
+        assert_eq!((1, 0), next_output(&mut out_stream).await);
 
         clock.advance(Duration::from_secs(2));
 
         tx.send(Event::new_empty_log()).await.unwrap();
 
-        // We should be back to pending, having the second event dropped
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        // The third event is routed to the dropped output
+        assert_eq!((0, 1), next_output(&mut out_stream).await);
 
         clock.advance(Duration::from_secs(3));
 
         tx.send(Event::new_empty_log()).await.unwrap();
 
         // The rate limiter should now be refreshed and allow an additional event through
-        if let Some(_event) = out_stream.next().await {
-        } else {
-            panic!("Unexpectedly received None in output stream");
-        }
+        assert_eq!((1, 0), next_output(&mut out_stream).await);
 
         // We should be back to pending, having nothing waiting for us
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        assert!(matches!(futures::poll!(out_stream.next()), Poll::Pending));
 
         tx.disconnect();
 
         // And still nothing there
-        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+        assert!(matches!(
+            futures::poll!(out_stream.next()),
+            Poll::Ready(None)
+        ));
     }
 
     #[tokio::test]
     async fn throttle_exclude() {
         let clock = clock::FakeRelativeClock::default();
-        let config = toml::from_str::<ThrottleConfig>(
+        let (mut tx, mut out_stream) = throttle(
             r#"
 threshold = 2
 window_secs = 5
@@ -278,94 +468,65 @@ exclude = """
 exists(.special)
 """
 "#,
-        )
-        .unwrap();
-
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
-
-        let throttle = throttle.into_task();
-
-        let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
+            clock.clone(),
+        );
 
         // tokio interval is always immediately ready, so we poll once to make sure
         // we trip it/set the interval in the future
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        assert!(matches!(futures::poll!(out_stream.next()), Poll::Pending));
 
         tx.send(Event::new_empty_log()).await.unwrap();
         tx.send(Event::new_empty_log()).await.unwrap();
 
-        let mut count = 0_u8;
-        while count < 2 {
-            if let Some(_event) = out_stream.next().await {
-                count += 1;
-            } else {
-                panic!("Unexpectedly received None in output stream");
-            }
-        }
-        assert_eq!(2, count);
+        assert_eq!((1, 0), next_output(&mut out_stream).await);
+        assert_eq!((1, 0), next_output(&mut out_stream).await);
 
         clock.advance(Duration::from_secs(2));
 
         tx.send(Event::new_empty_log()).await.unwrap();
 
-        // We should be back to pending, having the second event dropped
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        assert_eq!((0, 1), next_output(&mut out_stream).await);
 
         let mut special_log = Event::new_empty_log();
         special_log.as_mut_log().insert("special", "true");
         tx.send(special_log).await.unwrap();
         // The rate limiter should allow this log through regardless of current limit
-        if let Some(_event) = out_stream.next().await {
-        } else {
-            panic!("Unexpectedly received None in output stream");
-        }
+        assert_eq!((1, 0), next_output(&mut out_stream).await);
 
         clock.advance(Duration::from_secs(3));
 
         tx.send(Event::new_empty_log()).await.unwrap();
 
         // The rate limiter should now be refreshed and allow an additional event through
-        if let Some(_event) = out_stream.next().await {
-        } else {
-            panic!("Unexpectedly received None in output stream");
-        }
+        assert_eq!((1, 0), next_output(&mut out_stream).await);
 
         // We should be back to pending, having nothing waiting for us
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        assert!(matches!(futures::poll!(out_stream.next()), Poll::Pending));
 
         tx.disconnect();
 
         // And still nothing there
-        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+        assert!(matches!(
+            futures::poll!(out_stream.next()),
+            Poll::Ready(None)
+        ));
     }
 
     #[tokio::test]
     async fn throttle_buckets() {
         let clock = clock::FakeRelativeClock::default();
-        let config = toml::from_str::<ThrottleConfig>(
+        let (mut tx, mut out_stream) = throttle(
             r#"
 threshold = 1
 window_secs = 5
 key_field = "{{ bucket }}"
 "#,
-        )
-        .unwrap();
-
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
-
-        let throttle = throttle.into_task();
-
-        let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
+            clock,
+        );
 
         // tokio interval is always immediately ready, so we poll once to make sure
         // we trip it/set the interval in the future
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        assert!(matches!(futures::poll!(out_stream.next()), Poll::Pending));
 
         let mut log_a = Event::new_empty_log();
         log_a.as_mut_log().insert("bucket", "a");
@@ -374,22 +535,142 @@ key_field = "{{ bucket }}"
         tx.send(log_a).await.unwrap();
         tx.send(log_b).await.unwrap();
 
-        let mut count = 0_u8;
-        while count < 2 {
-            if let Some(_event) = out_stream.next().await {
-                count += 1;
-            } else {
-                panic!("Unexpectedly received None in output stream");
-            }
-        }
-        assert_eq!(2, count);
+        assert_eq!((1, 0), next_output(&mut out_stream).await);
+        assert_eq!((1, 0), next_output(&mut out_stream).await);
 
         // We should be back to pending, having nothing waiting for us
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        assert!(matches!(futures::poll!(out_stream.next()), Poll::Pending));
 
         tx.disconnect();
 
         // And still nothing there
-        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+        assert!(matches!(
+            futures::poll!(out_stream.next()),
+            Poll::Ready(None)
+        ));
+    }
+
+    #[tokio::test]
+    async fn throttle_key_thresholds() {
+        let clock = clock::FakeRelativeClock::default();
+        let (mut tx, mut out_stream) = throttle(
+            r#"
+threshold = 1
+window_secs = 5
+key_field = "{{ tags.tenant }}"
+thresholds.premium = 3
+"#,
+            clock,
+        );
+
+        assert!(matches!(futures::poll!(out_stream.next()), Poll::Pending));
+
+        let metric = |tenant: &str| {
+            let metric = Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(
+                vec![("tenant".to_owned(), tenant.to_owned())]
+                    .into_iter()
+                    .collect(),
+            ));
+            Event::from(metric)
+        };
+
+        for _ in 0..4 {
+            tx.send(metric("premium")).await.unwrap();
+        }
+        for _ in 0..2 {
+            tx.send(metric("free")).await.unwrap();
+        }
+
+        let mut outputs = Vec::new();
+        for _ in 0..6 {
+            outputs.push(next_output(&mut out_stream).await);
+        }
+        assert_eq!(
+            outputs,
+            vec![(1, 0), (1, 0), (1, 0), (0, 1), (1, 0), (0, 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn throttle_bytes() {
+        let clock = clock::FakeRelativeClock::default();
+        let event = Event::from(LogEvent::from("a message of some size"));
+        let (mut tx, mut out_stream) = throttle(
+            &format!(
+                r#"
+threshold = {}
+window_secs = 5
+mode = "bytes"
+"#,
+                event.size_of() * 2
+            ),
+            clock,
+        );
+
+        assert!(matches!(futures::poll!(out_stream.next()), Poll::Pending));
+
+        for _ in 0..3 {
+            tx.send(event.clone()).await.unwrap();
+        }
+
+        assert_eq!((1, 0), next_output(&mut out_stream).await);
+        assert_eq!((1, 0), next_output(&mut out_stream).await);
+        assert_eq!((0, 1), next_output(&mut out_stream).await);
+    }
+
+    #[tokio::test]
+    async fn throttle_bytes_oversized_event() {
+        let clock = clock::FakeRelativeClock::default();
+        let event = Event::from(LogEvent::from("a message of some size"));
+        let (mut tx, mut out_stream) = throttle(
+            &format!(
+                r#"
+threshold = {}
+window_secs = 5
+mode = "bytes"
+"#,
+                event.size_of() / 2
+            ),
+            clock.clone(),
+        );
+
+        assert!(matches!(futures::poll!(out_stream.next()), Poll::Pending));
+
+        tx.send(event.clone()).await.unwrap();
+        tx.send(event.clone()).await.unwrap();
+
+        // The event consumes the whole burst instead of being dropped forever.
+        assert_eq!((1, 0), next_output(&mut out_stream).await);
+        assert_eq!((0, 1), next_output(&mut out_stream).await);
+
+        clock.advance(Duration::from_secs(5));
+
+        tx.send(event).await.unwrap();
+
+        assert_eq!((1, 0), next_output(&mut out_stream).await);
+    }
+
+    #[test]
+    fn rejects_zero_thresholds() {
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 1
+window_secs = 5
+thresholds.none = 0
+"#,
+        )
+        .unwrap();
+
+        assert!(Throttle::new(
+            &config,
+            &TransformContext::default(),
+            clock::FakeRelativeClock::default()
+        )
+        .is_err());
     }
 }
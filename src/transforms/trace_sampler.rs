@@ -0,0 +1,489 @@
+use std::{collections::HashMap, num::NonZeroU32, pin::Pin, time::Duration};
+
+use async_stream::stream;
+use futures::{stream, Stream, StreamExt};
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{Event, TraceEvent, Value},
+    expiring_hash_map::ExpiringHashMap,
+    internal_events::TraceSamplerTraceSampled,
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+const fn default_decision_wait_ms() -> u64 {
+    10000
+}
+
+const fn default_rate() -> u64 {
+    10
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TraceSamplerConfig {
+    /// How long to buffer the spans of a trace waiting for its root span
+    /// before making a sampling decision.
+    #[serde(default = "default_decision_wait_ms")]
+    pub decision_wait_ms: u64,
+
+    /// Keep every trace containing a span with an error.
+    #[serde(default = "crate::serde::default_true")]
+    pub keep_errors: bool,
+
+    /// Keep every trace with a span lasting at least this long.
+    pub latency_threshold_ms: Option<u64>,
+
+    /// Keep one out of `rate` of the remaining traces.
+    #[serde(default = "default_rate")]
+    pub rate: u64,
+
+    /// The maximum number of traces kept per second for a service, keyed by
+    /// the service of the root span. Traces with errors are not limited.
+    #[serde(default)]
+    pub service_rate_limits: IndexMap<String, u32>,
+}
+
+inventory::submit! {
+    TransformDescription::new::<TraceSamplerConfig>("trace_sampler")
+}
+
+impl GenerateConfig for TraceSamplerConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            decision_wait_ms: default_decision_wait_ms(),
+            keep_errors: true,
+            latency_threshold_ms: None,
+            rate: default_rate(),
+            service_rate_limits: IndexMap::new(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "trace_sampler")]
+impl TransformConfig for TraceSamplerConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        TraceSampler::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Trace)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "trace_sampler"
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ConfigError {
+    #[snafu(display("`rate` must be non-zero"))]
+    ZeroRate,
+    #[snafu(display("The rate limit of service {:?} must be non-zero", service))]
+    ZeroServiceRateLimit { service: String },
+}
+
+/// The policy a sampling decision was made by.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SamplingPolicy {
+    Error,
+    Latency,
+    Probabilistic,
+    RateLimit,
+}
+
+impl SamplingPolicy {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Latency => "latency",
+            Self::Probabilistic => "probabilistic",
+            Self::RateLimit => "rate_limit",
+        }
+    }
+}
+
+/// The spans of a single trace that have been received so far.
+#[derive(Debug, Default)]
+struct PendingTrace {
+    events: Vec<TraceEvent>,
+    has_root: bool,
+    has_error: bool,
+    root_service: Option<String>,
+    max_duration_ns: i64,
+}
+
+impl PendingTrace {
+    fn add(&mut self, event: TraceEvent) {
+        if let Some(Value::Array(spans)) = event.get("spans") {
+            for span in spans.iter().filter_map(Value::as_object) {
+                if span.get("error").map_or(false, is_truthy) {
+                    self.has_error = true;
+                }
+                if let Some(Value::Integer(duration)) = span.get("duration") {
+                    self.max_duration_ns = self.max_duration_ns.max(*duration);
+                }
+                let is_root = match span.get("parent_id") {
+                    None | Some(Value::Null) => true,
+                    Some(Value::Integer(parent_id)) => *parent_id == 0,
+                    Some(_) => false,
+                };
+                if is_root {
+                    self.has_root = true;
+                    self.root_service = span.get("service").map(|s| s.to_string_lossy());
+                } else if self.root_service.is_none() {
+                    self.root_service = span.get("service").map(|s| s.to_string_lossy());
+                }
+            }
+        }
+        self.events.push(event);
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Integer(i) => *i != 0,
+        Value::Boolean(b) => *b,
+        _ => false,
+    }
+}
+
+/// The trace ID of an event, read from the event itself or its first span.
+fn trace_id(event: &TraceEvent) -> Option<i64> {
+    match event.get("trace_id") {
+        Some(Value::Integer(trace_id)) => Some(*trace_id),
+        _ => match event.get("spans") {
+            Some(Value::Array(spans)) => {
+                spans.iter().filter_map(Value::as_object).find_map(|span| {
+                    match span.get("trace_id") {
+                        Some(Value::Integer(trace_id)) => Some(*trace_id),
+                        _ => None,
+                    }
+                })
+            }
+            _ => None,
+        },
+    }
+}
+
+pub struct TraceSampler {
+    decision_wait: Duration,
+    keep_errors: bool,
+    latency_threshold_ns: Option<i64>,
+    rate: u64,
+    service_rate_limiters: HashMap<String, DefaultDirectRateLimiter>,
+    pending: ExpiringHashMap<i64, PendingTrace>,
+    decisions: ExpiringHashMap<i64, bool>,
+}
+
+impl TraceSampler {
+    pub fn new(config: &TraceSamplerConfig) -> crate::Result<Self> {
+        if config.rate == 0 {
+            return Err(Box::new(ConfigError::ZeroRate));
+        }
+
+        let mut service_rate_limiters = HashMap::with_capacity(config.service_rate_limits.len());
+        for (service, limit) in &config.service_rate_limits {
+            let limit =
+                NonZeroU32::new(*limit).ok_or_else(|| ConfigError::ZeroServiceRateLimit {
+                    service: service.clone(),
+                })?;
+            service_rate_limiters.insert(
+                service.clone(),
+                RateLimiter::direct(Quota::per_second(limit)),
+            );
+        }
+
+        Ok(Self {
+            decision_wait: Duration::from_millis(config.decision_wait_ms),
+            keep_errors: config.keep_errors,
+            latency_threshold_ns: config
+                .latency_threshold_ms
+                .map(|ms| Duration::from_millis(ms).as_nanos() as i64),
+            rate: config.rate,
+            service_rate_limiters,
+            pending: ExpiringHashMap::default(),
+            decisions: ExpiringHashMap::default(),
+        })
+    }
+
+    fn decide(&self, trace_id: i64, trace: &PendingTrace) -> (bool, SamplingPolicy) {
+        if self.keep_errors && trace.has_error {
+            return (true, SamplingPolicy::Error);
+        }
+
+        let (keep, policy) = match self.latency_threshold_ns {
+            Some(threshold) if trace.max_duration_ns >= threshold => {
+                (true, SamplingPolicy::Latency)
+            }
+            _ => (
+                seahash::hash(&trace_id.to_le_bytes()) % self.rate == 0,
+                SamplingPolicy::Probabilistic,
+            ),
+        };
+
+        let limiter = trace
+            .root_service
+            .as_ref()
+            .and_then(|service| self.service_rate_limiters.get(service));
+        match limiter {
+            Some(limiter) if keep && limiter.check().is_err() => (false, SamplingPolicy::RateLimit),
+            _ => (keep, policy),
+        }
+    }
+
+    fn flush_into(&mut self, trace_id: i64, trace: PendingTrace, output: &mut Vec<Event>) {
+        let (keep, policy) = self.decide(trace_id, &trace);
+        emit!(TraceSamplerTraceSampled {
+            kept: keep,
+            policy: policy.as_str(),
+            span_events: trace.events.len(),
+        });
+        if keep {
+            output.extend(trace.events.into_iter().map(Event::Trace));
+        }
+        self.decisions.insert(trace_id, keep, self.decision_wait);
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        let pending = self.pending.drain().collect::<Vec<_>>();
+        for (trace_id, trace) in pending {
+            self.flush_into(trace_id, trace, output);
+        }
+    }
+
+    fn transform_one(&mut self, output: &mut Vec<Event>, event: Event) {
+        let event = event.into_trace();
+        let trace_id = match trace_id(&event) {
+            Some(trace_id) => trace_id,
+            None => {
+                // Without a trace ID there is nothing to correlate the spans
+                // by, so pass them through unsampled.
+                output.push(Event::Trace(event));
+                return;
+            }
+        };
+
+        // Spans arriving shortly after the decision was made follow it.
+        if let Some(keep) = self.decisions.get(&trace_id) {
+            if *keep {
+                output.push(Event::Trace(event));
+            }
+            return;
+        }
+
+        if self.pending.get(&trace_id).is_none() {
+            self.pending
+                .insert(trace_id, PendingTrace::default(), self.decision_wait);
+        }
+        let trace = self
+            .pending
+            .get_mut(&trace_id)
+            .expect("trace was just inserted");
+        trace.add(event);
+
+        if trace.has_root {
+            if let Some((trace, _)) = self.pending.remove(&trace_id) {
+                self.flush_into(trace_id, trace, output);
+            }
+        }
+    }
+}
+
+impl TaskTransform<Event> for TraceSampler {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+
+        Box::pin(
+            stream! {
+              loop {
+                let mut output = Vec::new();
+                let done = tokio::select! {
+                    expired = me.pending.next_expired(), if !me.pending.is_empty() => {
+                      if let Some((trace, trace_id)) = expired {
+                          me.flush_into(trace_id.into_inner(), trace, &mut output);
+                      }
+                      false
+                    }
+                    _ = me.decisions.next_expired(), if !me.decisions.is_empty() => false,
+                    maybe_event = input_rx.next() => {
+                      match maybe_event {
+                        None => {
+                          me.flush_all_into(&mut output);
+                          true
+                        }
+                        Some(event) => {
+                          me.transform_one(&mut output, event);
+                          false
+                        }
+                      }
+                    }
+                };
+                yield stream::iter(output.into_iter());
+                if done { break }
+              }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, task::Poll};
+
+    use futures::SinkExt;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TraceSamplerConfig>();
+    }
+
+    fn make_span(trace_id: i64, span_id: i64, parent_id: i64, error: i64) -> Value {
+        let mut span = BTreeMap::<String, Value>::new();
+        span.insert("service".into(), Value::from("checkout"));
+        span.insert("trace_id".into(), Value::from(trace_id));
+        span.insert("span_id".into(), Value::from(span_id));
+        span.insert("parent_id".into(), Value::from(parent_id));
+        span.insert("duration".into(), Value::from(1_000_000));
+        span.insert("error".into(), Value::from(error));
+        Value::from(span)
+    }
+
+    fn make_trace(spans: Vec<Value>) -> Event {
+        let mut trace = TraceEvent::default();
+        trace.insert("spans", spans);
+        Event::Trace(trace)
+    }
+
+    async fn build(config: &str) -> Box<dyn TaskTransform<Event>> {
+        let config = toml::from_str::<TraceSamplerConfig>(config).unwrap();
+        Box::new(TraceSampler::new(&config).unwrap())
+    }
+
+    #[tokio::test]
+    async fn keeps_traces_with_errors_whole() {
+        // A huge rate drops every trace that isn't kept by another policy.
+        let sampler = build("rate = 9223372036854775807").await;
+
+        let inputs = vec![
+            make_trace(vec![make_span(1, 2, 1, 1)]),
+            make_trace(vec![make_span(2, 4, 3, 0)]),
+            make_trace(vec![make_span(1, 1, 0, 0)]),
+            make_trace(vec![make_span(2, 3, 0, 0)]),
+        ];
+        let output = sampler
+            .transform(Box::pin(stream::iter(inputs)))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(output.len(), 2);
+        for event in output {
+            assert_eq!(trace_id(event.as_trace()), Some(1));
+        }
+    }
+
+    #[tokio::test]
+    async fn keeps_slow_traces() {
+        let sampler = build(
+            r#"
+rate = 9223372036854775807
+latency_threshold_ms = 1
+"#,
+        )
+        .await;
+
+        let output = sampler
+            .transform(Box::pin(stream::iter(vec![make_trace(vec![make_span(
+                1, 1, 0, 0,
+            )])])))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(output.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rate_limits_services() {
+        let sampler = build(
+            r#"
+rate = 1
+
+[service_rate_limits]
+checkout = 2
+"#,
+        )
+        .await;
+
+        let inputs = (1..=5)
+            .map(|trace_id| make_trace(vec![make_span(trace_id, 1, 0, 0)]))
+            .chain(std::iter::once(make_trace(vec![make_span(6, 1, 0, 1)])))
+            .collect::<Vec<_>>();
+        let output = sampler
+            .transform(Box::pin(stream::iter(inputs)))
+            .collect::<Vec<_>>()
+            .await;
+
+        let kept = output
+            .iter()
+            .map(|event| trace_id(event.as_trace()).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(kept, vec![1, 2, 6]);
+    }
+
+    #[tokio::test]
+    async fn decides_after_wait_without_root_span() {
+        tokio::time::pause();
+
+        let sampler = build(
+            r#"
+rate = 1
+decision_wait_ms = 5000
+"#,
+        )
+        .await;
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        let mut out_stream = sampler.transform(Box::pin(rx));
+
+        tx.send(make_trace(vec![make_span(1, 2, 1, 0)]))
+            .await
+            .unwrap();
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+        tokio::time::advance(Duration::from_secs(6)).await;
+        let event = out_stream.next().await.unwrap();
+        assert_eq!(trace_id(event.as_trace()), Some(1));
+
+        // Late spans follow the decision made for their trace.
+        tx.send(make_trace(vec![make_span(1, 1, 0, 0)]))
+            .await
+            .unwrap();
+        let event = out_stream.next().await.unwrap();
+        assert_eq!(trace_id(event.as_trace()), Some(1));
+
+        tx.disconnect();
+        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+    }
+}
@@ -467,6 +467,85 @@ cli: {
 				}
 			}
 		}
+
+		"vrl test": {
+			description: """
+				Run tests of VRL programs. Tests are written in TOML files ending in `.vrl.toml`,
+				each containing test cases for a single program: the input event, and the expected
+				output event, result of the final expression, or error. Enrichment tables the program
+				searches can be provided as fixtures.
+				"""
+
+			flags: _default_flags & {
+				"fail-early": {
+					_short:      "f"
+					description: "Stop at the first failing test"
+				}
+				"no-diff": {
+					_short:      "n"
+					description: "Don't print the difference between the expected and actual outcome of failing tests"
+				}
+			}
+
+			options: {
+				"pattern": {
+					_short:      "p"
+					description: "Only run the tests whose name contains the pattern"
+					type:        "string"
+				}
+				"junit": {
+					description: "Write a JUnit XML report of the test run to the given file"
+					type:        "string"
+				}
+				"timezone": {
+					_short:      "z"
+					description: "The timezone used to parse dates"
+					type:        "string"
+					default:     "UTC"
+				}
+			}
+
+			args: {
+				paths: {
+					type: "list"
+					description: """
+						Any number of test files to run. Directories are searched recursively for
+						files ending in `.vrl.toml`.
+						"""
+					required: true
+				}
+			}
+		}
+
+		"vrl fmt": {
+			description: """
+				Format VRL programs. Comments are preserved. Without any file, the program is read
+				from stdin and the formatted program is written to stdout.
+				"""
+
+			flags: _default_flags & {
+				"check": {
+					_short:      "c"
+					description: "Exit with an error if any of the programs isn't formatted, instead of formatting them"
+				}
+			}
+
+			args: {
+				paths: {
+					type:        "list"
+					description: "Any number of VRL files to format in place"
+					required:    false
+				}
+			}
+		}
+
+		"vrl lsp": {
+			description: """
+				Run a language server for VRL programs, communicating over stdin and stdout. It
+				reports the diagnostics of the compiler, shows the signature of functions and the
+				type of event paths on hover, and completes functions and event paths.
+				"""
+		}
 	}
 
 	env_vars: {
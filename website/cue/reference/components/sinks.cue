@@ -212,6 +212,9 @@ components: sinks: [Name=string]: {
 											if codec == "ndjson" {
 												ndjson: "Newline delimited list of JSON encoded events."
 											}
+											if codec == "syslog" {
+												syslog: "[Syslog](\(urls.syslog_5424)) encoded event, configured with the `syslog` options."
+											}
 										}
 									}
 								}
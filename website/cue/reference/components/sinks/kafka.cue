@@ -127,6 +127,41 @@ components: sinks: kafka: {
 				examples: ["headers"]
 			}
 		}
+		transaction: {
+			common:      false
+			description: "Produce each batch of events within a Kafka transaction. A batch is committed only once all of its events are delivered, and aborted and retried as a whole otherwise. In this mode, the `batch` options also control how many events are grouped into a transaction."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					max_retries: {
+						common:      false
+						description: "The number of times an aborted transaction is retried before its events are rejected."
+						required:    false
+						type: uint: {
+							default: 3
+							unit:    null
+						}
+					}
+					timeout_ms: {
+						common:      false
+						description: "The maximum time a transaction may remain open before the broker aborts it."
+						required:    false
+						type: uint: {
+							default: 60000
+							unit:    "milliseconds"
+						}
+					}
+					transactional_id_prefix: {
+						description: "The prefix of the producer's `transactional.id`, which is completed with the host name. The resulting ID must be unique to this sink and stable across restarts, so that the broker can fence off zombie producers."
+						required:    true
+						type: string: {
+							examples: ["vector-logs"]
+						}
+					}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -160,5 +195,6 @@ components: sinks: kafka: {
 		kafka_produced_messages_bytes_total: components.sources.internal_metrics.output.metrics.kafka_produced_messages_bytes_total
 		kafka_consumed_messages_total:       components.sources.internal_metrics.output.metrics.kafka_consumed_messages_total
 		kafka_consumed_messages_bytes_total: components.sources.internal_metrics.output.metrics.kafka_consumed_messages_bytes_total
+		kafka_transactions_aborted_total:    components.sources.internal_metrics.output.metrics.kafka_transactions_aborted_total
 	}
 }
@@ -79,6 +79,49 @@ components: sinks: loki: {
 			password_example: "${LOKI_PASSWORD}"
 			username_example: "${LOKI_USERNAME}"
 		}}
+		cardinality: {
+			common:      false
+			description: """
+				Limits on the number of distinct streams and label values produced by the `labels` templates. When a
+				limit would be exceeded, the offending labels rendered from templates are removed from the stream and
+				moved into the event instead. Static labels are never moved.
+
+				The limits are counted over a time window as long as `batch.timeout_secs`, after which the counts start
+				over. The window is time-based, and isn't tied to when batches are flushed.
+				"""
+			required: false
+			type: object: options: {
+				fallback_field: {
+					common:      false
+					description: "The field to move offending labels into. If unset, they are inserted at the top level of the event."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["labels"]
+					}
+				}
+				max_label_values: {
+					common:      false
+					description: "The maximum number of distinct values of a single label."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [100]
+						unit: null
+					}
+				}
+				max_streams_per_batch: {
+					common:      false
+					description: "The maximum number of distinct streams, including the stream holding only the static labels that receives the events whose labels are moved."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [1000]
+						unit: null
+					}
+				}
+			}
+		}
 		labels: {
 			description: """
 				A set of labels that are attached to each batch of events. Both keys and values are templatable, which
@@ -195,6 +238,7 @@ components: sinks: loki: {
 		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		demoted_labels_total:             components.sources.internal_metrics.output.metrics.demoted_labels_total
 		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
 		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
 		processed_bytes_total:            components.sources.internal_metrics.output.metrics.processed_bytes_total
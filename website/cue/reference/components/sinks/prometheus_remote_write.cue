@@ -109,6 +109,27 @@ components: sinks: prometheus_remote_write: {
 				items: type: float: examples: [0.005, 0.01]
 			}
 		}
+		histogram_format: {
+			common:      false
+			description: "The format to encode [distribution](\(urls.vector_metric)/#distribution) metrics with a histogram statistic in."
+			required:    false
+			type: string: {
+				default: "classic"
+				enum: {
+					classic: "Classic histograms, using the configured `buckets`."
+					native:  "Native histograms with exponential buckets, using the configured `native_histogram_schema`."
+				}
+			}
+		}
+		native_histogram_schema: {
+			common:      false
+			description: "The schema of native histograms, from -4 to 8. Each power of two is divided into `2^schema` buckets, so higher values give a finer resolution. Only used when `histogram_format` is `native`."
+			required:    false
+			type: int: {
+				default: 3
+				examples: [0, 8]
+			}
+		}
 		quantiles: {
 			common:      false
 			description: "Quantiles to use for aggregating [distribution](\(urls.vector_metric)/#distribution) metrics into a summary."
@@ -21,7 +21,7 @@ components: sinks: socket: {
 				enabled: true
 				codec: {
 					enabled: true
-					enum: ["json", "text"]
+					enum: ["json", "syslog", "text"]
 				}
 			}
 			send_buffer_bytes: {
@@ -328,6 +328,21 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		demoted_labels_total: {
+			description:       "The total number of labels moved into events because a label cardinality limit was reached."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				reason: {
+					description: "The limit that was reached."
+					required:    true
+					enum: {
+						"max_label_values":      "The label had too many distinct values."
+						"max_streams_per_batch": "The stream would have exceeded the number of distinct streams."
+					}
+				}
+			}
+		}
 		decode_errors_total: {
 			description:       "The total number of decode errors seen when decoding data in a source component."
 			type:              "counter"
@@ -644,6 +659,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		join_partial_events_flushed_total: {
+			description:       "The number of partially joined events that Vector has flushed to the `unmatched` output."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		kafka_queue_messages: {
 			description:       "Current number of messages in producer queues."
 			type:              "gauge"
@@ -704,6 +725,39 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		kafka_consumer_assigned_partitions: {
+			description:       "Current number of partitions assigned to the consumer."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_consumer_lag: {
+			description:       "Current number of messages the consumer is behind the end of the partition."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				topic_id: {
+					description: "The Kafka topic."
+					required:    true
+				}
+				partition_id: {
+					description: "The Kafka partition."
+					required:    true
+				}
+			}
+		}
+		kafka_consumer_rebalances_total: {
+			description:       "Total number of consumer group rebalances."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_transactions_aborted_total: {
+			description:       "The total number of Kafka transactions aborted by the `kafka` sink."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		file_delete_errors_total: {
 			description:       "The total number of failures to delete a file. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
@@ -1031,6 +1085,31 @@ components: sources: internal_metrics: {
 				}
 			}
 		}
+		sampled_traces_total: {
+			description:       "The number of traces the trace sampler has made a sampling decision for."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				decision: {
+					description: "Whether the trace was kept or dropped."
+					required:    true
+					enum: {
+						"keep": "The trace was kept."
+						"drop": "The trace was dropped."
+					}
+				}
+				policy: {
+					description: "The policy the decision was made by."
+					required:    true
+					enum: {
+						"error":         "The trace contains a span with an error."
+						"latency":       "The trace contains a span exceeding the latency threshold."
+						"probabilistic": "The trace was sampled by its trace ID."
+						"rate_limit":    "The service of the trace exceeded its rate limit."
+					}
+				}
+			}
+		}
 		stale_events_flushed_total: {
 			description:       "The number of stale events that Vector has flushed."
 			type:              "counter"
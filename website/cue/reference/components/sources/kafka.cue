@@ -121,6 +121,58 @@ components: sources: kafka: {
 			}
 		}
 		librdkafka_options: components._kafka.configuration.librdkafka_options
+		partitions: {
+			common:      false
+			description: "The Kafka partitions to read events from, assigned statically instead of subscribing to `topics`. The consumer group is not rebalanced, but is still used to store the consumer offsets. Exactly one of `topics` and `partitions` must be set."
+			required:    false
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: []
+					options: {
+						partition: {
+							description: "The partition number."
+							required:    true
+							type: int: examples: [0, 1]
+						}
+						start_offset: {
+							common:      false
+							description: "Where to start reading the partition."
+							required:    false
+							type: object: options: {
+								from: {
+									description: "The starting point."
+									required:    true
+									type: string: {
+										enum: {
+											stored:    "The offset stored for the consumer group, or the one given by `auto_offset_reset` if there is none. This is the default."
+											earliest:  "The earliest offset in the partition."
+											latest:    "The end of the partition, so that only new messages are read."
+											timestamp: "The earliest offset whose timestamp is equal to or greater than `timestamp_ms`."
+										}
+									}
+								}
+								timestamp_ms: {
+									common:        false
+									description:   "The timestamp to start reading from, in milliseconds since the Unix epoch."
+									relevant_when: "from = \"timestamp\""
+									required:      false
+									type: int: {
+										default: null
+										examples: [1654041600000]
+									}
+								}
+							}
+						}
+						topic: {
+							description: "The topic of the partition."
+							required:    true
+							type: string: examples: ["topic-1"]
+						}
+					}
+				}
+			}
+		}
 		sasl: {
 			common:      false
 			description: "Options for SASL/SCRAM authentication support."
@@ -176,10 +228,14 @@ components: sources: kafka: {
 		}
 		socket_timeout_ms: components._kafka.configuration.socket_timeout_ms
 		topics: {
-			description: "The Kafka topics names to read events from. Regex is supported if the topic begins with `^`."
-			required:    true
-			type: array: items: type: string: {
-				examples: ["^(prefix1|prefix2)-.+", "topic-1", "topic-2"]
+			common:      true
+			description: "The Kafka topics names to read events from. Regex is supported if the topic begins with `^`. Exactly one of `topics` and `partitions` must be set."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["^(prefix1|prefix2)-.+", "topic-1", "topic-2"]
+				}
 			}
 		}
 	}
@@ -236,6 +292,9 @@ components: sources: kafka: {
 		kafka_produced_messages_bytes_total:  components.sources.internal_metrics.output.metrics.kafka_produced_messages_bytes_total
 		kafka_consumed_messages_total:        components.sources.internal_metrics.output.metrics.kafka_consumed_messages_total
 		kafka_consumed_messages_bytes_total:  components.sources.internal_metrics.output.metrics.kafka_consumed_messages_bytes_total
+		kafka_consumer_assigned_partitions:   components.sources.internal_metrics.output.metrics.kafka_consumer_assigned_partitions
+		kafka_consumer_lag:                   components.sources.internal_metrics.output.metrics.kafka_consumer_lag
+		kafka_consumer_rebalances_total:      components.sources.internal_metrics.output.metrics.kafka_consumer_rebalances_total
 		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:               components.sources.internal_metrics.output.metrics.processed_events_total
 		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
@@ -27,6 +27,69 @@ components: transforms: aggregate: {
 	}
 
 	configuration: {
+		align_to_wall_clock: {
+			common:      false
+			description: "Align the flushes to multiples of `interval_ms` since the Unix epoch, so that windows start and end on wall-clock boundaries."
+			required:    false
+			type: bool: default: false
+		}
+		drop_tags: {
+			common:      false
+			description: "The tags removed from metrics before they are aggregated, rolling up the series only differing by those tags."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["host", "pod"]
+				}
+			}
+		}
+		functions: {
+			common: false
+			description: """
+				A map of metric names to the function aggregating them. Metrics not listed here are
+				aggregated according to their kind, as described in [Aggregation Behavior](#aggregation-behavior).
+				Functions other than `latest_absolute` apply to the values of counters, gauges and
+				distributions.
+				"""
+			required: false
+			type: object: {
+				examples: [
+					{
+						requests_total:   {function: "sum"}
+						request_duration: {function: "percentiles", percentiles: [0.5, 0.99]}
+					},
+				]
+				options: {
+					"*": {
+						description: "The aggregation function of the metric."
+						required:    true
+						type: object: options: {
+							function: {
+								description: "The function aggregating the values of the metric."
+								required:    true
+								type: string: enum: {
+									sum:             "The sum of the values, as a counter."
+									count:           "The number of values, as a counter."
+									min:             "The smallest value, as a gauge."
+									max:             "The largest value, as a gauge."
+									mean:            "The mean of the values, as a gauge."
+									last:            "The last value, as a gauge."
+									latest_absolute: "The latest metric, whatever its type, as an absolute metric."
+									percentiles:     "The given `percentiles` of the values, as a summary."
+								}
+							}
+							percentiles: {
+								description:   "The percentiles to compute, between 0 and 1."
+								relevant_when: "function = \"percentiles\""
+								required:      true
+								type: array: items: type: float: examples: [0.5, 0.99]
+							}
+						}
+					}
+				}
+			}
+		}
 		interval_ms: {
 			common: true
 			description: """
@@ -39,6 +102,33 @@ components: transforms: aggregate: {
 				unit:    "milliseconds"
 			}
 		}
+		window: {
+			common:      false
+			description: "The window metrics are aggregated over."
+			required:    false
+			type: object: options: {
+				type: {
+					description: "The type of window."
+					required:    false
+					type: string: {
+						default: "tumbling"
+						enum: {
+							tumbling: "Each flush emits the metrics received since the previous flush."
+							sliding:  "Each flush emits the metrics received over the last `length_ms`."
+						}
+					}
+				}
+				length_ms: {
+					description:   "The length of the sliding window. Must be a multiple of `interval_ms`."
+					relevant_when: "type = \"sliding\""
+					required:      true
+					type: uint: {
+						examples: [60000]
+						unit: "milliseconds"
+					}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -186,6 +276,18 @@ components: transforms: aggregate: {
 				"""
 		}
 
+		windows: {
+			title: "Windows"
+			body: """
+				By default, windows are tumbling: each flush emits the metrics received since the previous
+				flush. With a sliding window, each flush emits the metrics received over the last
+				`window.length_ms`, so a metric is part of several consecutive flushes. The metrics emitted
+				over sliding windows are therefore always `absolute`. Setting `align_to_wall_clock` makes
+				windows start and end on multiples of `interval_ms` since the Unix epoch, so that, for
+				example, one minute windows match the minutes of the clock.
+				"""
+		}
+
 		advantages: {
 			title: "Advantages of Use"
 			body: """
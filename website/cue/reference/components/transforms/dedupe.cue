@@ -40,9 +40,28 @@ components: transforms: dedupe: {
 							unit:    null
 						}
 					}
+					ttl_secs: {
+						common:      false
+						description: "How long an Event is remembered after it was first seen. Events are remembered until they are evicted from the cache if not set."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [300, 3600]
+							unit: "seconds"
+						}
+					}
 				}
 			}
 		}
+		data_dir: {
+			common:      false
+			description: "The directory the cache is persisted in when `persist` is enabled. Defaults to the [global `data_dir` option](\(urls.vector_configuration_global)/#data_dir)."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+			}
+		}
 		fields: {
 			description: "Options controlling what fields to match against."
 			required:    true
@@ -73,6 +92,21 @@ components: transforms: dedupe: {
 				}
 			}
 		}
+		persist: {
+			common:      false
+			description: "Persist the cache in the data directory, so that duplicates of Events seen before a restart are still detected."
+			required:    false
+			type: bool: default: false
+		}
+		persist_interval_secs: {
+			common:      false
+			description: "How often the cache is persisted. It is also persisted when Vector stops."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
 	}
 
 	input: {
@@ -81,6 +115,13 @@ components: transforms: dedupe: {
 		traces:  false
 	}
 
+	outputs: [
+		{
+			name:        "duplicates"
+			description: "The Events found to be duplicates of a cached Event."
+		},
+	]
+
 	how_it_works: {
 		cache_behavior: {
 			title: "Cache Behavior"
@@ -93,6 +134,33 @@ components: transforms: dedupe: {
 				already in the cache that will put that event back to the head of
 				the cache and reset its place in line, making it once again last
 				entry in line to be evicted.
+
+				Duplicates are routed to the `duplicates` output rather than the
+				default output, and are dropped if that output isn't consumed.
+				"""
+		}
+
+		expiry: {
+			title: "Expiry"
+			body: """
+				Without `cache.ttl_secs`, whether an Event is considered a duplicate
+				depends on how many distinct Events were received since the Event it
+				duplicates, so the outcome varies with the volume of traffic. Setting
+				`cache.ttl_secs` makes Events duplicates of any Event matching them
+				and first seen less than `cache.ttl_secs` ago, as long as
+				`cache.num_events` is large enough to hold all the Events of that
+				period. Seeing a duplicate doesn't extend the period.
+				"""
+		}
+
+		persistence: {
+			title: "Persistence"
+			body: """
+				When `persist` is enabled, the cache is written to the data
+				directory every `persist_interval_secs` and when Vector stops, and
+				loaded when the transform starts. Entries that expired while Vector
+				was stopped are not restored. Duplicates received after the cache was
+				last written and before a crash are not detected.
 				"""
 		}
 
@@ -0,0 +1,137 @@
+package metadata
+
+components: transforms: join: {
+	title: "Join"
+
+	description: """
+		Joins log events from two or more sides into a single log event, correlating
+		them by a templated key such as a request ID.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		reduce: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		key: {
+			description: "The key events are correlated by. Events whose key fails to render are sent to the `<transform_name>.unmatched` output."
+			required:    true
+			type: string: {
+				examples: ["{{ request_id }}", "{{ trace_id }}-{{ span_id }}"]
+				syntax: "template"
+			}
+		}
+		sides: {
+			description: """
+				A table of side names to the conditions identifying the events of each side. An event is assigned
+				to the first side whose condition it matches, regardless of the input it was received from, and
+				events matching no side are sent to the `<transform_name>.unmatched` output. At least two sides
+				must be configured.
+				"""
+			required: true
+			type: object: {
+				options: {
+					"*": {
+						description: "The condition identifying the events of this side."
+						required:    true
+						type: condition: {}
+					}
+				}
+			}
+		}
+		timeout_ms: {
+			common:      false
+			description: "How long to wait for the missing sides of a key, after its first event is received, before the partially joined event is sent to the `<transform_name>.unmatched` output."
+			required:    false
+			type: uint: {
+				default: 30000
+				unit:    "milliseconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Join requests with their responses"
+
+			configuration: {
+				key: "{{ request_id }}"
+				sides: {
+					request:  #".kind == "request""#
+					response: #".kind == "response""#
+				}
+			}
+
+			input: [
+				{log: {kind: "request", request_id:  "abcd1234", path:   "/path"}},
+				{log: {kind: "response", request_id: "abcd1234", status: 200}},
+			]
+			output: log: {
+				request: {kind: "request", request_id:   "abcd1234", path:   "/path"}
+				response: {kind: "response", request_id: "abcd1234", status: 200}
+			}
+		},
+	]
+
+	how_it_works: {
+		sides: {
+			title: "Sides"
+			body: """
+				Sides are selected by their conditions, not by the `inputs` of the transform: events from all
+				inputs are matched against the conditions of the sides, in the order the sides are configured.
+				The conditions must therefore tell the events of each side apart by their contents. If the
+				events of different sources look alike, add a field identifying the source upstream, for
+				example with a `remap` transform, and match on it.
+				"""
+		}
+		merging: {
+			title: "Merging"
+			body: """
+				Once an event has been received for every side of a key, the events are merged into a single
+				event, with each event nested under the name of its side. The metadata of the events, such as
+				their acknowledgement status, is merged as well.
+
+				If a side is received twice for the same key before the join completes, the duplicate is sent to
+				the `<transform_name>.unmatched` output.
+				"""
+		}
+		timeouts: {
+			title: "Timeouts"
+			body: """
+				If the missing sides of a key are not received within `timeout_ms`, the events received so far
+				are merged as above and sent to the `<transform_name>.unmatched` output. Pending keys are
+				flushed the same way when Vector shuts down.
+				"""
+		}
+	}
+
+	outputs: [
+		{
+			name:        "unmatched"
+			description: "Events that could not be joined, referenced as an input by other components with the name `<transform_name>.unmatched`."
+		},
+	]
+
+	telemetry: metrics: {
+		join_partial_events_flushed_total: components.sources.internal_metrics.output.metrics.join_partial_events_flushed_total
+	}
+}
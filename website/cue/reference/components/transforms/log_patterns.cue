@@ -0,0 +1,148 @@
+package metadata
+
+components: transforms: log_patterns: {
+	title: "Log Patterns"
+
+	description: """
+		Clusters log messages into patterns, assigning each log event the ID and
+		template of its pattern.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		data_dir: {
+			common:      false
+			description: "The directory the patterns are persisted in, when `persist` is enabled. By default, the [global `data_dir` option](\(urls.vector_configuration)/global-options#data_dir) is used."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+			}
+		}
+		depth: {
+			common:      false
+			description: "The depth of the prefix tree, including its root and token count layers. Messages only share a pattern if their first `depth - 2` tokens are equal, or all contain digits."
+			required:    false
+			type: uint: {
+				default: 4
+				unit:    null
+			}
+		}
+		field: {
+			common:      true
+			description: "The field holding the message to cluster. Defaults to the `message_key` of the [global log schema](\(urls.vector_configuration)/global-options#log_schema)."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["message", "parent.child"]
+			}
+		}
+		max_children: {
+			common:      false
+			description: "The maximum number of children of a node of the prefix tree. Further tokens are routed to a wildcard node."
+			required:    false
+			type: uint: {
+				default: 100
+				unit:    null
+			}
+		}
+		max_clusters: {
+			common:      false
+			description: "The maximum number of patterns to keep. The least recently used pattern is forgotten when the limit is reached."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    null
+			}
+		}
+		pattern_field: {
+			common:      false
+			description: "The field the template of the pattern is written to."
+			required:    false
+			type: string: default: "pattern"
+		}
+		pattern_id_field: {
+			common:      false
+			description: "The field the ID of the pattern is written to."
+			required:    false
+			type: string: default: "pattern_id"
+		}
+		persist: {
+			common:      true
+			description: "Whether to persist the patterns in the data directory, so that their IDs are kept across restarts."
+			required:    false
+			type: bool: default: false
+		}
+		persist_interval_secs: {
+			common:      false
+			description: "How often the patterns are persisted, when `persist` is enabled. They are also persisted when Vector shuts down."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		similarity_threshold: {
+			common:      true
+			description: "The share, from 0 to 1, of the tokens of a message that must be equal to those of a pattern for the message to join it."
+			required:    false
+			type: float: {
+				default: 0.4
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Cluster connection logs"
+			configuration: {}
+			input: [
+				{log: {message: "connected to 10.0.0.1 in 5 ms"}},
+				{log: {message: "connected to 10.0.0.2 in 12 ms"}},
+			]
+			output: [
+				{log: {message: "connected to 10.0.0.1 in 5 ms", pattern_id:  1, pattern: "connected to 10.0.0.1 in 5 ms"}},
+				{log: {message: "connected to 10.0.0.2 in 12 ms", pattern_id: 1, pattern: "connected to <*> in <*> ms"}},
+			]
+		},
+	]
+
+	how_it_works: {
+		drain: {
+			title: "Clustering"
+			body: """
+				Patterns are mined with the [Drain](https://jiemingzhu.github.io/pub/pjhe_icws2017.pdf) online
+				clustering algorithm. Messages are split into tokens on whitespace, and compared to the patterns
+				with the same number of tokens and the same leading tokens. A message joins the most similar pattern
+				if the share of equal tokens reaches `similarity_threshold`, and the tokens of the pattern that
+				differ from the message are replaced by the `<*>` wildcard. Otherwise, the message starts a new
+				pattern with the next ID.
+
+				As patterns are refined by the messages joining them, the template of a pattern may change over
+				time, while its ID stays the same.
+				"""
+		}
+	}
+}
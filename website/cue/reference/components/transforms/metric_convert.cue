@@ -0,0 +1,162 @@
+package metadata
+
+components: transforms: metric_convert: {
+	title: "Metric Convert"
+
+	description: """
+		Converts metric events between incremental and absolute kinds, gauges
+		to rates, and distributions to aggregated histograms or summaries.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		distributions: {
+			common:      false
+			description: "Converts distributions to aggregated histograms or summaries."
+			required:    false
+			type: object: options: {
+				type: {
+					description: "The type distributions are converted to."
+					required:    true
+					type: string: enum: {
+						aggregated_histogram: "Counts the samples into `buckets`."
+						aggregated_summary:   "Computes the `quantiles` of the samples."
+					}
+				}
+				buckets: {
+					description:   "The upper limits of the buckets."
+					relevant_when: "type = \"aggregated_histogram\""
+					required:      true
+					type: array: items: type: float: examples: [0.1, 1.0, 10.0]
+				}
+				quantiles: {
+					description:   "The quantiles to compute, between 0 and 1."
+					relevant_when: "type = \"aggregated_summary\""
+					required:      true
+					type: array: items: type: float: examples: [0.5, 0.99]
+				}
+			}
+		}
+		expire_after_secs: {
+			common:      false
+			description: "How long the state of a series is kept after the series was last seen."
+			required:    false
+			type: uint: {
+				default: 300
+				unit:    "seconds"
+			}
+		}
+		gauges_to_rates: {
+			common:      false
+			description: "Converts gauges to the per-second rate of change between consecutive values of their series."
+			required:    false
+			type: bool: default: false
+		}
+		kind: {
+			common:      true
+			description: "The kind metrics are converted to. Metrics are left unchanged if not set."
+			required:    false
+			type: string: {
+				default: null
+				enum: {
+					absolute:    "Incremental metrics are accumulated into the total of their series."
+					incremental: "Absolute metrics are converted to the increment since the previous value of their series."
+				}
+			}
+		}
+	}
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	examples: [
+		{
+			title: "Convert absolute counters to increments"
+			configuration: {
+				kind: "incremental"
+			}
+			input: [
+				{metric: {
+					kind: "absolute"
+					name: "requests_total"
+					counter: {
+						value: 10.0
+					}
+				}},
+				{metric: {
+					kind: "absolute"
+					name: "requests_total"
+					counter: {
+						value: 15.0
+					}
+				}},
+			]
+			output: [
+				{metric: {
+					kind: "incremental"
+					name: "requests_total"
+					counter: {
+						value: 5.0
+					}
+				}},
+			]
+		},
+	]
+
+	how_it_works: {
+		state: {
+			title: "State"
+			body: """
+				Conversions are computed from the previous value of each series, identified by the
+				name, namespace and tags of the metric, and kept in memory. Converting to incremental
+				metrics or to rates requires two values of a series, so nothing is emitted for the first
+				value of a series. The state of a series is forgotten once it wasn't seen for
+				`expire_after_secs`, and it is lost when Vector restarts.
+				"""
+		}
+
+		resets: {
+			title: "Counter Resets"
+			body: """
+				When converting to incremental metrics, a counter or histogram whose value decreases is
+				considered reset, for example by a restart of its source, and its new value is emitted as
+				the increment since the reset.
+				"""
+		}
+
+		conversion_order: {
+			title: "Conversion Order"
+			body: """
+				Distributions are converted first, so setting both `distributions` and `kind = "absolute"`
+				turns delta distributions into cumulative histograms, as expected by Prometheus. Gauges
+				converted to rates are always emitted as `absolute` gauges, regardless of `kind`.
+				"""
+		}
+	}
+}
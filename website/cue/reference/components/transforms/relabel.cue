@@ -0,0 +1,185 @@
+package metadata
+
+components: transforms: relabel: {
+	title: "Relabel"
+
+	description: """
+		Rewrites the names and tags of metric events, or drops metric events,
+		following the semantics of Prometheus' `relabel_configs`.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		rules: {
+			description: "The relabeling rules, applied in order. `relabel_configs` is accepted as an alias."
+			required:    true
+			type: array: items: type: object: options: {
+				action: {
+					common:      true
+					description: "The action of the rule."
+					required:    false
+					type: string: {
+						default: "replace"
+						enum: {
+							replace:   "Sets `target_label` to `replacement` if `regex` matches the concatenated source label values."
+							keep:      "Drops metrics whose concatenated source label values don't match `regex`."
+							drop:      "Drops metrics whose concatenated source label values match `regex`."
+							hashmod:   "Sets `target_label` to the MD5 hash of the concatenated source label values modulo `modulus`."
+							labelmap:  "Copies the tags whose names match `regex` to the tags named by `replacement`."
+							labeldrop: "Removes the tags whose names match `regex`."
+							labelkeep: "Removes the tags whose names don't match `regex`."
+						}
+					}
+				}
+				modulus: {
+					description:   "The modulus of the hash of the source label values."
+					relevant_when: "action = \"hashmod\""
+					required:      true
+					type: uint: {
+						examples: [8]
+						unit: null
+					}
+				}
+				regex: {
+					common:      true
+					description: "The regular expression matched against the concatenated source label values, or against tag names for the `labelmap`, `labeldrop` and `labelkeep` actions. It is anchored at both ends."
+					required:    false
+					type: string: {
+						default: "(.*)"
+						examples: ["node_.*", "__meta_kubernetes_pod_label_(.+)"]
+					}
+				}
+				replacement: {
+					common:      false
+					description: "The value written by the `replace` action, or the tag name written by the `labelmap` action. Capture groups of `regex` are referenced as `$1` or `${name}`. An empty value removes the target tag."
+					required:    false
+					type: string: default: "$1"
+				}
+				separator: {
+					common:      false
+					description: "The separator placed between the concatenated source label values."
+					required:    false
+					type: string: default: ";"
+				}
+				source_labels: {
+					common:      true
+					description: "The tags whose values are concatenated and matched against `regex`. The name of the metric is available as `__name__`."
+					required:    false
+					type: array: {
+						default: []
+						items: type: string: examples: ["__name__", "job"]
+					}
+				}
+				target_label: {
+					common:        true
+					description:   "The tag written by the rule. `__name__` writes the name of the metric. Capture groups of `regex` can be referenced as in `replacement`."
+					relevant_when: "action = \"replace\" or action = \"hashmod\""
+					required:      true
+					type: string: examples: ["instance", "__name__"]
+				}
+			}
+		}
+	}
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	examples: [
+		{
+			title: "Rename a metric and keep only some jobs"
+			configuration: {
+				rules: [
+					{
+						source_labels: ["job"]
+						regex:  "node|api"
+						action: "keep"
+					},
+					{
+						source_labels: ["__name__"]
+						regex:        "http_(.*)"
+						target_label: "__name__"
+						replacement:  "web_$1"
+					},
+				]
+			}
+			input: [
+				{metric: {
+					kind: "incremental"
+					name: "http_requests_total"
+					counter: {
+						value: 2.0
+					}
+					tags: {
+						job: "api"
+					}
+				}},
+				{metric: {
+					kind: "incremental"
+					name: "http_requests_total"
+					counter: {
+						value: 1.0
+					}
+					tags: {
+						job: "batch"
+					}
+				}},
+			]
+			output: [
+				{metric: {
+					kind: "incremental"
+					name: "web_requests_total"
+					counter: {
+						value: 2.0
+					}
+					tags: {
+						job: "api"
+					}
+				}},
+			]
+		},
+	]
+
+	how_it_works: {
+		prometheus_compatibility: {
+			title: "Prometheus Compatibility"
+			body: """
+				Rules follow the semantics of Prometheus' `relabel_config`, with the tags of
+				metrics as labels and `__name__` as the name of the metric, so existing rules
+				can be reused as is. Missing source labels are treated as empty values, and
+				setting a tag to an empty value removes it. The name of the metric can't be
+				removed. The `hashmod` action hashes values as Prometheus does, so metrics are
+				sharded the same way.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+}
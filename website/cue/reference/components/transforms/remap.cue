@@ -104,6 +104,20 @@ components: transforms: "remap": {
 				"""
 			type: bool: default: false
 		}
+		runtime: {
+			common:   false
+			required: false
+			description: """
+				The runtime used to execute the VRL program. Both runtimes produce the same results.
+				"""
+			type: string: {
+				default: "ast"
+				enum: {
+					ast: "Resolve the program by walking its compiled expression tree."
+					vm:  "Compile the program to bytecode once, and run it on a stack machine for each event."
+				}
+			}
+		}
 	}
 
 	input: {
@@ -4,7 +4,8 @@ components: transforms: sample: {
 	title: "Sample"
 
 	description: """
-		Samples events at a configurable rate.
+		Samples events at a configurable rate, or at rates adjusted per key to
+		target a throughput.
 		"""
 
 	classes: {
@@ -25,6 +26,35 @@ components: transforms: sample: {
 	}
 
 	configuration: {
+		dynamic: {
+			common:      false
+			description: "Samples events by key at rates adjusted so that the total output stays near a target throughput, instead of at a fixed `rate`. Mutually exclusive with `rate`."
+			required:    false
+			type: object: options: {
+				key: {
+					description: "The template rendering the key events are sampled by."
+					required:    true
+					type: string: {
+						examples: ["{{ service }}"]
+						syntax: "template"
+					}
+				}
+				target_events_per_sec: {
+					description: "The number of events per second to forward, across all keys."
+					required:    true
+					type: float: examples: [100.0]
+				}
+				window_secs: {
+					common:      false
+					description: "The window over which the volume of each key is observed before its sample rate is adjusted."
+					required:    false
+					type: uint: {
+						default: 30
+						unit:    "seconds"
+					}
+				}
+			}
+		}
 		key_field: {
 			common: false
 			description: """
@@ -52,8 +82,9 @@ components: transforms: sample: {
 			description: """
 				The rate at which events will be forwarded, expressed as 1/N. For example,
 				`rate = 10` means 1 out of every 10 events will be forwarded and the rest will be dropped.
+				Required unless `dynamic` is set.
 				"""
-			required: true
+			required: false
 			type: uint: {
 				examples: [10]
 				unit: null
@@ -67,6 +98,21 @@ components: transforms: sample: {
 		traces:  false
 	}
 
+	how_it_works: {
+		dynamic_sampling: {
+			title: "Dynamic Sampling"
+			body: """
+				With `dynamic`, the number of events of each key is counted over each window. At the end
+				of a window, the budget of the next one, `target_events_per_sec` times `window_secs`, is
+				shared between the keys seen, starting from the rarest: keys below their fair share of the
+				remaining budget are kept at 100%, and the budget they leave is shared by the noisier
+				keys, which are sampled 1 out of N events. Every key keeps at least one event per window,
+				and keys not seen during the previous window aren't sampled. Forwarded events are
+				annotated with their effective rate in the `sample_rate` field.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
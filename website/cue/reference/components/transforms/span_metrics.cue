@@ -0,0 +1,136 @@
+package metadata
+
+components: transforms: span_metrics: {
+	title: "Span Metrics"
+
+	description: """
+		Derives request rate, error and duration (RED) metrics from the spans of
+		trace events, while passing the trace events through.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		buckets: {
+			common:      false
+			description: "The upper limits, in seconds, of the buckets of the `span_duration_seconds` histogram."
+			required:    false
+			type: array: {
+				default: [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+				items: type: float: examples: [0.005, 0.01]
+			}
+		}
+		dimensions: {
+			common:      true
+			description: "The span attributes, read from the `meta` of each span, added as tags to the metrics in addition to `service`, `resource`, `operation` and `status`."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["http.method", "http.status_code"]
+				}
+			}
+		}
+		flush_period_ms: {
+			common:      false
+			description: "How often the metrics aggregated from the spans are emitted."
+			required:    false
+			type: uint: {
+				default: 10000
+				unit:    "milliseconds"
+			}
+		}
+		namespace: {
+			common:      true
+			description: "The namespace of the emitted metrics."
+			required:    false
+			type: string: {
+				default: "traces"
+				examples: ["service"]
+			}
+		}
+	}
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	output: metrics: {
+		span_requests_total: {
+			description:       "The number of spans received."
+			type:              "counter"
+			default_namespace: "traces"
+			tags:              _span_tags
+		}
+		span_errors_total: {
+			description:       "The number of spans received with an error."
+			type:              "counter"
+			default_namespace: "traces"
+			tags:              _span_tags
+		}
+		span_duration_seconds: {
+			description:       "The duration of the spans received."
+			type:              "histogram"
+			default_namespace: "traces"
+			tags:              _span_tags
+		}
+
+		_span_tags: {
+			service: {
+				description: "The service of the span."
+				required:    false
+			}
+			resource: {
+				description: "The resource of the span."
+				required:    false
+			}
+			operation: {
+				description: "The name of the operation of the span."
+				required:    false
+			}
+			status: {
+				description: "Whether the span has an error."
+				required:    true
+				enum: {
+					"ok":    "The span has no error."
+					"error": "The span has an error."
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		aggregation: {
+			title: "Aggregation"
+			body: """
+				The spans of the incoming trace events are aggregated by their tags, and incremental metrics are
+				emitted to the `<transform_name>.metrics` output every `flush_period_ms`, and when Vector shuts down.
+				The trace events themselves are passed through unchanged to the default output.
+				"""
+		}
+	}
+
+	outputs: [
+		{
+			name:        "metrics"
+			description: "The derived metrics, referenced as an input by other components with the name `<transform_name>.metrics`."
+		},
+	]
+}
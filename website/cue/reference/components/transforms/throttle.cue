@@ -4,7 +4,7 @@ components: transforms: throttle: {
 	title: "Throttle"
 
 	description: """
-		Rate limits one or more log streams to limit load on downstream services, or to enforce usage quotas on users.
+		Rate limits one or more event streams to limit load on downstream services, or to enforce usage quotas on users.
 		"""
 
 	classes: {
@@ -48,11 +48,24 @@ components: transforms: throttle: {
 				syntax: "template"
 			}
 		}
+		mode: {
+			common:      false
+			description: "What the `threshold` of a bucket counts."
+			required:    false
+			type: string: {
+				default: "events"
+				enum: {
+					events: "The number of events."
+					bytes:  "The total size of events in bytes, as estimated from their size in memory."
+				}
+			}
+		}
 		threshold: {
 			description: """
-				The number of events allowed for a given bucket per configured `window_secs`.
+				The number of events, or bytes if `mode` is `bytes`, allowed for a given bucket per configured
+				`window_secs`.
 
-				Each unique key will have its own `threshold`.
+				Each unique key will have its own `threshold`, unless overridden by `thresholds` or `threshold_table`.
 				"""
 			required: true
 			type: uint: {
@@ -60,6 +73,46 @@ components: transforms: throttle: {
 				unit: null
 			}
 		}
+		threshold_table: {
+			common:      false
+			description: "An enrichment table holding the thresholds of keys not found in `thresholds`."
+			required:    false
+			type: object: options: {
+				table: {
+					description: "The name of the enrichment table."
+					required:    true
+					type: string: examples: ["tenant_quotas"]
+				}
+				key_column: {
+					description: "The column matched against the rendered `key_field`."
+					required:    true
+					type: string: examples: ["tenant"]
+				}
+				threshold_column: {
+					description: "The column holding the threshold of the key, as an integer."
+					required:    true
+					type: string: examples: ["quota"]
+				}
+			}
+		}
+		thresholds: {
+			common:      false
+			description: "Thresholds overriding `threshold` for the buckets of the given keys."
+			required:    false
+			type: object: {
+				examples: [{premium: 10000}]
+				options: {
+					"*": {
+						description: "The threshold of the key."
+						required:    true
+						type: uint: {
+							examples: [10000]
+							unit: null
+						}
+					}
+				}
+			}
+		}
 		window_secs: {
 			description: """
 				The time frame in which the configured `threshold` is applied.
@@ -73,11 +126,25 @@ components: transforms: throttle: {
 	}
 
 	input: {
-		logs:    true
-		metrics: null
-		traces:  false
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
 	}
 
+	outputs: [
+		{
+			name:        "dropped"
+			description: "The events exceeding the threshold of their bucket."
+		},
+	]
+
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
@@ -145,6 +212,18 @@ components: transforms: throttle: {
 						A rate limiter is created with a maximum number of cells equal to the `threshold`, and cells replenish
 						at a rate of `window_secs` divided by `threshold`. For example, a `window_secs` of 60 with a `threshold` of 10
 						replenishes a cell every 6 seconds and allows a burst of up to 10 events.
+
+						When `mode` is `bytes`, each event consumes as many cells as its size in bytes. An event larger
+						than the `threshold` of its bucket consumes all of its cells, so it passes once the bucket is full.
+						"""
+				},
+				{
+					title: "Per-Key Thresholds"
+					body: """
+						The threshold of a bucket is taken from `thresholds` if its key is listed there, then from the
+						`threshold_table` enrichment table, and defaults to `threshold`. Thresholds looked up in the
+						enrichment table are cached for twice the `window_secs`, so updates of the table are picked up
+						after that delay.
 						"""
 				},
 				{
@@ -152,8 +231,8 @@ components: transforms: throttle: {
 					body: """
 						The rate limiter will allow up to `threshold` number of events through and drop any further events
 						for that particular bucket when the rate limiter is at capacity. Any event passed when the rate
-						limiter is at capacity will be discarded and tracked by an `events_discarded_total` metric tagged
-						by the bucket's `key`.
+						limiter is at capacity is routed to the `dropped` output and tracked by an `events_discarded_total`
+						metric tagged by the bucket's `key`.
 						"""
 				},
 			]
@@ -0,0 +1,112 @@
+package metadata
+
+components: transforms: trace_sampler: {
+	title: "Trace Sampler"
+
+	description: """
+		Samples traces as a whole, buffering their spans until the root span is
+		received or a timeout elapses and then applying a set of sampling policies.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		decision_wait_ms: {
+			common:      false
+			description: "How long to buffer the spans of a trace, after its first span is received, waiting for its root span before making a sampling decision."
+			required:    false
+			type: uint: {
+				default: 10000
+				unit:    "milliseconds"
+			}
+		}
+		keep_errors: {
+			common:      true
+			description: "Whether to keep every trace containing a span with an error."
+			required:    false
+			type: bool: default: true
+		}
+		latency_threshold_ms: {
+			common:      true
+			description: "If set, every trace containing a span lasting at least this long is kept."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [500]
+				unit: "milliseconds"
+			}
+		}
+		rate: {
+			common: true
+			description: """
+				The rate at which the traces not kept by another policy are forwarded, expressed as 1/N. For
+				example, `rate = 10` means 1 out of every 10 traces will be forwarded and the rest will be dropped.
+				The decision is based on the trace ID, so that it is consistent across Vector instances.
+				"""
+			required: false
+			type: uint: {
+				default: 10
+				unit:    null
+			}
+		}
+		service_rate_limits: {
+			common:      false
+			description: "A table of service names to the maximum number of traces kept per second for that service. The service of a trace is the service of its root span. Traces with errors are not rate limited."
+			required:    false
+			type: object: {
+				examples: [{checkout: 100}]
+				options: {
+					"*": {
+						description: "The maximum number of traces kept per second for the service."
+						required:    true
+						type: uint: unit: null
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	how_it_works: {
+		policies: {
+			title: "Sampling policies"
+			body: """
+				Once the root span of a trace is received, or `decision_wait_ms` elapses, the buffered spans
+				are kept or dropped together by applying the following policies in order:
+
+				1. If `keep_errors` is enabled, traces containing a span with an error are kept.
+				2. If `latency_threshold_ms` is set, traces containing a span at least that long are kept.
+				3. Otherwise, one out of `rate` traces is kept.
+				4. Traces kept by the previous two policies are dropped if their service exceeds its
+				   limit in `service_rate_limits`.
+
+				Spans received for a trace within `decision_wait_ms` of its decision follow that decision.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+		sampled_traces_total:   components.sources.internal_metrics.output.metrics.sampled_traces_total
+	}
+}
@@ -0,0 +1,72 @@
+package metadata
+
+remap: expressions: function_definition: {
+	title: "Function definition"
+	description: """
+		A _function definition_ defines a function that can be called by the expressions following it, like
+		built-in [VRL functions](\(urls.vrl_functions)). Functions defined in other files are made available with
+		an _import_:
+
+		```coffee
+		import "normalize.vrl"
+		```
+
+		Imported files can only contain function definitions and imports. Their paths are relative to the
+		importing file. For programs set inline, such as with the `source` option of the `remap` transform or
+		in conditions, they are relative to the directory of the Vector configuration file defining the program.
+		"""
+	return: """
+		Definitions don't return a value. Calling the function returns the result of the last expression of its
+		block, whose type is inferred from the block.
+		"""
+
+	grammar: {
+		source: """
+			"fn" ~ function ~ "(" ~ parameters? ~ ")" ~ block
+			"""
+		definitions: {
+			function: {
+				description: """
+					`function` represents the name of the function. It must not be the name of a built-in
+					function, or of a function defined or imported before.
+					"""
+			}
+			parameters: {
+				description: """
+					The `parameters` are comma-delimited variable names, optionally followed by the types of
+					the values they accept:
+
+					```coffee
+					fn greet(name: string, title: string | null) { ... }
+					```
+
+					The types are `string`, `integer`, `float`, `boolean`, `timestamp`, `regex`, `null`,
+					`array`, `object` and `any`, which is the default. All arguments are required, and are
+					checked like those of built-in functions. If the type of an argument is only known at
+					runtime, the call is fallible.
+					"""
+			}
+			block: {
+				description: """
+					The `block` is evaluated when the function is called. It can only access the parameters
+					of the function and the event, and its errors must be handled.
+					"""
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "Shared normalization"
+			input: log: level: " WARN "
+			source: #"""
+				fn normalize_level(level: string) {
+					downcase(strip_whitespace(level))
+				}
+
+				.level = normalize_level!(.level)
+				"""#
+			output: log: level: "warn"
+		},
+	]
+}
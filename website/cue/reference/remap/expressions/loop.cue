@@ -0,0 +1,112 @@
+package metadata
+
+remap: expressions: loop: {
+	title: "Loop"
+	description: """
+		A _loop_ expression executes a block repeatedly. A `while` loop executes its block as long as its predicate
+		evaluates to `true`, and a `loop` executes its block until it is stopped by `break`. Within the block,
+		`break` stops the loop, and `continue` skips to its next iteration.
+
+		A `walk` executes its block for every value nested in an object or array, with the path to the value and the
+		value itself. The values are visited depth-first, parents before their children. `continue` skips the
+		children of the current value, and `break` stops the walk. The walk visits the value as it was when the walk
+		started, even if the block changes it.
+
+		A `traverse` executes its block for every value nested in an object or array like a `walk`, but replaces every
+		value with the result of the block, and returns the value with its nested values replaced. The values are
+		visited depth-first, children before their parents, so the block gets values whose children are already
+		replaced. `continue` keeps the current value as it is, and `break` keeps every value not visited yet as it is.
+
+		To guarantee that programs terminate, a `while` or `loop` fails at runtime after 10,000 iterations. Loops are
+		therefore always fallible, even if their block can't fail, and their errors must be
+		[handled](\(urls.vrl_error_handling)), such as with `_, err = while ... { ... }`.
+		"""
+	return: """
+		A `traverse` returns the target with its nested values replaced. Other loops return `null`.
+		"""
+
+	grammar: {
+		source: """
+			"while" ~ predicate ~ block
+			"loop" ~ block
+			"walk" ~ target ~ "->" ~ "|" ~ path ~ "," ~ value ~ "|" ~ block
+			"traverse" ~ target ~ "->" ~ "|" ~ path ~ "," ~ value ~ "|" ~ block
+			"""
+		definitions: {
+			predicate: {
+				description: """
+					The `predicate` _must_ be an expression that resolves to a Boolean, like the predicate of an
+					[if expression](\(urls.vrl_expressions)#if). It's evaluated before every iteration.
+					"""
+			}
+			target: {
+				description: """
+					The `target` is a path or a variable. Values that aren't objects or arrays have no nested values.
+					"""
+			}
+			path: {
+				description: """
+					The `path` variable is the array of the object keys and array indices leading to the value.
+					"""
+			}
+			value: {
+				description: """
+					The `value` variable is the visited value. Either variable can be `_` when it isn't used.
+					"""
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "While loop"
+			source: #"""
+				i = 0
+				total = 0
+				_, err = while i < 5 {
+					total = total + i
+					i = i + 1
+				}
+				total
+				"""#
+			return: 10
+		},
+		{
+			title: "Loop with break and continue"
+			source: #"""
+				i = 0
+				odd = []
+				_, err = loop {
+					i = i + 1
+					if i > 5 { break }
+					if i == 2 || i == 4 { continue }
+					odd = push(odd, i)
+				}
+				odd
+				"""#
+			return: [1, 3, 5]
+		},
+		{
+			title: "Walk"
+			input: log: user: {name: "ana", roles: ["admin"]}
+			source: #"""
+				paths = []
+				walk . -> |path, _| {
+					paths = push(paths, path)
+				}
+				paths
+				"""#
+			return: [["user"], ["user", "name"], ["user", "roles"], ["user", "roles", 0]]
+		},
+		{
+			title: "Traverse"
+			input: log: user: {name: "ana", roles: ["admin"], age: 32}
+			source: #"""
+				traverse . -> |_, value| {
+					if is_string(value) { "***" } else { value }
+				}
+				"""#
+			return: user: {name: "***", roles: ["***"], age: 32}
+		},
+	]
+}
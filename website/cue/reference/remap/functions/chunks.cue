@@ -0,0 +1,49 @@
+package metadata
+
+remap: functions: chunks: {
+	category:    "Array"
+	description: """
+		Splits the `value` array or string into chunks of `chunk_size` items or bytes.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array or string to split."
+			required:    true
+			type: ["array", "string"]
+		},
+		{
+			name:        "chunk_size"
+			description: "The number of items or bytes in each chunk."
+			required:    true
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`chunk_size` is less than 1",
+	]
+	return: {
+		types: ["array"]
+		rules: [
+			"The last chunk holds the remaining items, and can be smaller than `chunk_size`.",
+			"Strings are split by bytes, so a chunk can hold part of a multi-byte character.",
+		]
+	}
+	examples: [
+		{
+			title: "Split an array into chunks"
+			source: #"""
+				chunks([1, 2, 3, 4, 5], 2)
+				"""#
+			return: [[1, 2], [3, 4], [5]]
+		},
+		{
+			title: "Split a string into chunks"
+			source: #"""
+				chunks("abcdefgh", 3)
+				"""#
+			return: ["abc", "def", "gh"]
+		},
+	]
+}
@@ -0,0 +1,69 @@
+package metadata
+
+remap: functions: community_id: {
+	category:    "String"
+	description: """
+		Generates an ID based on the [Community ID Spec](\(urls.community_id)), so that the same network flow
+		can be correlated across tools such as Zeek and Suricata.
+
+		Both directions of a flow produce the same ID. For ICMP and ICMPv6, pass the message type as
+		`source_port` and the message code as `destination_port`.
+		"""
+
+	arguments: [
+		{
+			name:        "source_ip"
+			description: "The source IP address."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "destination_ip"
+			description: "The destination IP address."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "protocol"
+			description: "The IANA protocol number."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "source_port"
+			description: "The source port, or the ICMP message type."
+			required:    false
+			type: ["integer"]
+		},
+		{
+			name:        "destination_port"
+			description: "The destination port, or the ICMP message code."
+			required:    false
+			type: ["integer"]
+		},
+		{
+			name:        "seed"
+			description: "The seed to hash the flow with."
+			required:    false
+			default:     0
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`source_ip` or `destination_ip` is not a valid IP address.",
+		"`source_ip` and `destination_ip` are not of the same IP version.",
+		"`protocol` is not between 0 and 255.",
+		"`source_port`, `destination_port` or `seed` is not between 0 and 65535.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "TCP flow"
+			source: #"""
+				community_id!(source_ip: "128.232.110.120", destination_ip: "66.35.250.204", protocol: 6, source_port: 34855, destination_port: 80)
+				"""#
+			return: "1:LQU9qZlK+B5F3KDmev6m5PMibrg="
+		},
+	]
+}
@@ -0,0 +1,44 @@
+package metadata
+
+remap: functions: decode_gzip: {
+	category:    "Codec"
+	description: """
+		Decodes the `value` (a [Gzip](\(urls.gzip)) compressed string) into its original string.
+
+		Data made of several concatenated Gzip members is decoded into the concatenation of their
+		contents.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Gzip](\(urls.gzip)) data to decode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid encoded Gzip string.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Gzip data"
+			source: """
+				decode_gzip!(decode_base64!("H4sIAAAAAAACA6vML1XISCxLVSguTU5OLS5OK83JqVRISU3OT0lNUchNBQD7BGDaIAAAAA=="))
+				"""
+			return: "you have successfully decoded me"
+		},
+		{
+			title: "Unpack a CloudWatch Logs subscription payload"
+			input: log: {
+				data: "H4sIAAAAAAACA6tWSi1LzStRslLKSM3JyVeqBQDR9CU7EQAAAA=="
+			}
+			source: """
+				parse_json!(decode_gzip!(decode_base64!(.data)))
+				"""
+			return: {"event": "hello"}
+		},
+	]
+}
@@ -0,0 +1,43 @@
+package metadata
+
+remap: functions: decode_mime_q: {
+	category:    "Codec"
+	description: """
+		Decodes the [MIME encoded-words](\(urls.rfc_2047)) in the `value`, such as those found in
+		email headers, keeping the rest of the `value` as is.
+
+		Both the "Q" and the "B" encodings are supported. Whitespace between two encoded-words is
+		removed, so that text split across several encoded-words is joined back together.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string with encoded-words to decode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"An encoded-word has invalid encoded text.",
+		"An encoded-word uses an unknown character set.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode a single encoded-word"
+			source: """
+				decode_mime_q!("=?utf-8?Q?hello_world=21?=")
+				"""
+			return: "hello world!"
+		},
+		{
+			title: "Decode an email subject"
+			source: """
+				decode_mime_q!("Subject: =?iso-8859-1?Q?Caf=E9?= =?utf-8?B?IOKYlQ==?=")
+				"""
+			return: "Subject: Café ☕"
+		},
+	]
+}
@@ -0,0 +1,38 @@
+package metadata
+
+remap: functions: decode_punycode: {
+	category:    "Codec"
+	description: """
+		Decodes the [Punycode](\(urls.punycode)) labels of the `value` domain name to their Unicode form.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The domain name to decode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid internationalized domain name.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Punycode domain name"
+			source: #"""
+				decode_punycode!("www.xn--caf-dma.com")
+				"""#
+			return: "www.café.com"
+		},
+		{
+			title: "Decode ASCII domain name"
+			source: #"""
+				decode_punycode!("www.cafe.com")
+				"""#
+			return: "www.cafe.com"
+		},
+	]
+}
@@ -0,0 +1,34 @@
+package metadata
+
+remap: functions: decode_snappy: {
+	category:    "Codec"
+	description: """
+		Decodes the `value` (a [Snappy](\(urls.snappy)) compressed string) into its original string.
+
+		The data must use the raw Snappy block format, such as in Prometheus remote write requests,
+		rather than the framed format.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Snappy](\(urls.snappy)) data to decode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid encoded Snappy string.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Snappy data"
+			source: """
+				decode_snappy!(decode_base64!("IHx5b3UgaGF2ZSBzdWNjZXNzZnVsbHkgZGVjb2RlZCBtZQ=="))
+				"""
+			return: "you have successfully decoded me"
+		},
+	]
+}
@@ -0,0 +1,31 @@
+package metadata
+
+remap: functions: decode_zstd: {
+	category:    "Codec"
+	description: """
+		Decodes the `value` (a [Zstandard](\(urls.zstd)) compressed string) into its original string.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Zstandard](\(urls.zstd)) data to decode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid encoded Zstandard string.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Zstandard data"
+			source: """
+				decode_zstd!(decode_base64!("KLUv/QBYAQEAeW91IGhhdmUgc3VjY2Vzc2Z1bGx5IGRlY29kZWQgbWU="))
+				"""
+			return: "you have successfully decoded me"
+		},
+	]
+}
@@ -0,0 +1,38 @@
+package metadata
+
+remap: functions: encode_gzip: {
+	category:    "Codec"
+	description: """
+		Encodes the `value` to [Gzip](\(urls.gzip)).
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to encode."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "compression_level"
+			description: "The compression level, from `0` (no compression) to `9` (best compression)."
+			required:    false
+			type: ["integer"]
+			default: 6
+		},
+	]
+	internal_failure_reasons: [
+		"`compression_level` is not between `0` and `9`.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode to Gzip and back"
+			source: """
+				decode_gzip!(encode_gzip("please encode me", compression_level: 9))
+				"""
+			return: "please encode me"
+		},
+	]
+}
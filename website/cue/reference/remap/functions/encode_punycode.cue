@@ -0,0 +1,39 @@
+package metadata
+
+remap: functions: encode_punycode: {
+	category:    "Codec"
+	description: """
+		Encodes the `value` internationalized domain name to its ASCII form, encoding each label that contains
+		non-ASCII characters as [Punycode](\(urls.punycode)).
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The domain name to encode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid internationalized domain name.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode internationalized domain name"
+			source: #"""
+				encode_punycode!("www.café.com")
+				"""#
+			return: "www.xn--caf-dma.com"
+		},
+		{
+			title: "Encode ASCII domain name"
+			source: #"""
+				encode_punycode!("www.cafe.com")
+				"""#
+			return: "www.cafe.com"
+		},
+	]
+}
@@ -0,0 +1,49 @@
+package metadata
+
+remap: functions: filter: {
+	category:    "Enumerate"
+	description: """
+		Keeps the items of an array, or the fields of an object, for which the closure returns `true`.
+
+		The function uses the "function closure syntax" to allow reading the key/value or index/value
+		combination for each item in the collection. The closure must return a boolean.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array or object to filter."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["array", "object"]
+		rules: [
+			"Returns a new collection of the same type as `value`. The `value` is _not_ modified in place.",
+			"The order of the remaining items is preserved.",
+		]
+	}
+	examples: [
+		{
+			title: "Remove empty strings"
+			input: log: {
+				tags: ["foo", "", "bar", ""]
+			}
+			source: #"""
+				filter(array!(.tags)) -> |_index, value| {
+				    value != ""
+				}
+				"""#
+			return: ["foo", "bar"]
+		},
+		{
+			title: "Remove null fields"
+			source: #"""
+				filter({ "a": 1, "b": null }) -> |_key, value| { value != null }
+				"""#
+			return: {"a": 1}
+		},
+	]
+}
@@ -0,0 +1,36 @@
+package metadata
+
+remap: functions: find_index: {
+	category:    "Enumerate"
+	description: """
+		Returns the index of the first item of the `value` array for which the closure returns
+		`true`, or `null` if there is none.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array to search."
+			required:    true
+			type: ["array"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["integer", "null"]
+	examples: [
+		{
+			title: "Find the first match"
+			source: #"""
+				find_index(["a", "b", "c", "b"]) -> |_index, value| { value == "b" }
+				"""#
+			return: 1
+		},
+		{
+			title: "No match"
+			source: #"""
+				find_index([1, 2, 3]) -> |_index, value| { value == 4 }
+				"""#
+			return: null
+		},
+	]
+}
@@ -0,0 +1,41 @@
+package metadata
+
+remap: functions: group_by: {
+	category:    "Enumerate"
+	description: """
+		Groups the items of the `value` array under the key returned by the closure for each item.
+
+		The closure is called with the index and the item, and must return a string.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array to group."
+			required:    true
+			type: ["array"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["object"]
+		rules: [
+			"Each field of the returned object is an array of the items with that key, in their original order.",
+		]
+	}
+	examples: [
+		{
+			title: "Group log lines by level"
+			input: log: {
+				lines: [{"level": "info", "id": 1}, {"level": "error", "id": 2}, {"level": "info", "id": 3}]
+			}
+			source: #"""
+				group_by(array!(.lines)) -> |_index, line| { string(line.level) ?? "unknown" }
+				"""#
+			return: {
+				error: [{"level": "error", "id": 2}]
+				info: [{"level": "info", "id": 1}, {"level": "info", "id": 3}]
+			}
+		},
+	]
+}
@@ -0,0 +1,54 @@
+package metadata
+
+remap: functions: hmac: {
+	category:    "Cryptography"
+	description: """
+		Calculates an [HMAC](\(urls.hmac)) of the `value` using the given `key`, returned as a hex-encoded string.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the HMAC for."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "key"
+			description: "The secret key. Prefer reading it with `get_secret` over embedding it in the program."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The hashing algorithm to use."
+			enum: {
+				"SHA1":    "SHA-1 algorithm"
+				"SHA-256": "SHA-256 algorithm"
+				"SHA-512": "SHA-512 algorithm"
+			}
+			required: false
+			default:  "SHA-256"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Calculate HMAC-SHA256"
+			source: #"""
+				hmac("Hello there", "super-secret-key")
+				"""#
+			return: "78b184f1832f8aff3934f5e0212454671b2d04d494e3b25075c5e45167029662"
+		},
+		{
+			title: "Calculate HMAC-SHA1 with a secret key"
+			source: #"""
+				hmac("user-1234", string!(get_secret("my_secret")), algorithm: "SHA1")
+				"""#
+			return: "ee3de7aec362ff014025fed998e0bf82a8080954"
+		},
+	]
+}
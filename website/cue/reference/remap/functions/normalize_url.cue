@@ -0,0 +1,63 @@
+package metadata
+
+remap: functions: normalize_url: {
+	category:    "String"
+	description: """
+		[Normalizes](\(urls.url_normalization)) the `value` URL, so that equivalent URLs compare equal.
+
+		The scheme and host are lowercased, internationalized hosts are encoded as Punycode, default ports are
+		removed, `.` and `..` path segments are resolved, and empty queries are removed. By default, query
+		parameters are sorted and the fragment is removed too.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The URL to normalize."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "sort_query"
+			description: "Sort the query parameters by name, then by value."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+		{
+			name:        "remove_fragment"
+			description: "Remove the fragment."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid absolute URL.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Normalize URL"
+			source: #"""
+				normalize_url!("HTTP://www.Example.com:80/a/./b/../c?b=2&a=1#top")
+				"""#
+			return: "http://www.example.com/a/c?a=1&b=2"
+		},
+		{
+			title: "Normalize internationalized host"
+			source: #"""
+				normalize_url!("https://café.example/menu")
+				"""#
+			return: "https://xn--caf-dma.example/menu"
+		},
+		{
+			title: "Keep query order and fragment"
+			source: #"""
+				normalize_url!("https://example.com/?b=2&a=1#top", sort_query: false, remove_fragment: false)
+				"""#
+			return: "https://example.com/?b=2&a=1#top"
+		},
+	]
+}
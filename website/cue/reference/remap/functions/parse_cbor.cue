@@ -0,0 +1,42 @@
+package metadata
+
+remap: functions: parse_cbor: {
+	category:    "Parse"
+	description: """
+		Parses the `value` as [CBOR](\(urls.cbor)).
+		"""
+	notices: [
+		"""
+			Byte strings are returned as strings, tagged values are returned without their tag, and map
+			keys that are integers or byte strings are converted to strings.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The CBOR data to parse."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is not valid CBOR.",
+		"`value` contains an integer that doesn't fit in a 64-bit signed integer.",
+		"`value` contains a map key that is not a string, integer or byte string.",
+	]
+	return: types: ["boolean", "integer", "float", "string", "object", "array", "null"]
+
+	examples: [
+		{
+			title: "Parse CBOR"
+			source: #"""
+				parse_cbor!(decode_base16!("a2616101616282f5f6"))
+				"""#
+			return: {
+				a: 1
+				b: [true, null]
+			}
+		},
+	]
+}
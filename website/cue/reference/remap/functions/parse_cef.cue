@@ -0,0 +1,78 @@
+package metadata
+
+remap: functions: parse_cef: {
+	category:    "Parse"
+	description: """
+		Parses the `value` in [CEF](\(urls.cef)) (Common Event Format) format. Anything before the `CEF:` prefix,
+		such as a Syslog header, is ignored.
+
+		The seven header fields are returned as `cefVersion`, `deviceVendor`, `deviceProduct`, `deviceVersion`,
+		`deviceEventClassId`, `name`, and `severity`. The extension fields are returned alongside them, keyed
+		by their name. All values are strings.
+		"""
+	notices: [
+		"""
+			Escaped pipes (`\\|`) and backslashes (`\\\\`) are unescaped in the header. In the extension, escaped
+			equal signs (`\\=`), backslashes (`\\\\`), and the `\\n` and `\\r` line breaks are unescaped.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "translate_custom_fields"
+			description: "Replace custom fields, such as `cs1`, with the value of their label field, such as `cs1Label`."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` doesn't contain a `CEF:` prefix.",
+		"`value` has fewer than seven header fields.",
+		"The extension of `value` isn't a list of `key=value` pairs.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse CEF message"
+			source: #"""
+				parse_cef!(s'CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 spt=1232')
+				"""#
+			return: {
+				cefVersion:         "0"
+				deviceVendor:       "Security"
+				deviceProduct:      "threatmanager"
+				deviceVersion:      "1.0"
+				deviceEventClassId: "100"
+				name:               "worm successfully stopped"
+				severity:           "10"
+				src:                "10.0.0.1"
+				dst:                "2.1.2.2"
+				spt:                "1232"
+			}
+		},
+		{
+			title: "Translate custom fields"
+			source: #"""
+				parse_cef!(s'CEF:0|Dev|firewall|2.2|1|Connection denied|5|cs1Label=rule cs1=block all', translate_custom_fields: true)
+				"""#
+			return: {
+				cefVersion:         "0"
+				deviceVendor:       "Dev"
+				deviceProduct:      "firewall"
+				deviceVersion:      "2.2"
+				deviceEventClassId: "1"
+				name:               "Connection denied"
+				severity:           "5"
+				rule:               "block all"
+			}
+		},
+	]
+}
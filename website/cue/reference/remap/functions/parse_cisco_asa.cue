@@ -0,0 +1,78 @@
+package metadata
+
+remap: functions: parse_cisco_asa: {
+	category:    "Parse"
+	description: """
+		Parses a [Cisco ASA](\(urls.cisco_asa_messages)) syslog message, such as
+		`%ASA-6-302013: Built inbound TCP connection ...`. Messages from Firepower Threat Defense (`%FTD-`), PIX
+		(`%PIX-`), and FWSM (`%FWSM-`) devices are parsed the same way. Anything before the message prefix, such as
+		a Syslog header, is ignored.
+
+		The `severity`, `message_id`, and `message` fields are always returned. For the following common
+		messages, the fields of the message text, such as addresses, ports, interfaces, and users, are extracted
+		too: 106001, 106006, 106007, 106014, 106015, 106023, 106100, 111008, 113004, 113005, 302013, 302014,
+		302015, 302016, 305011, 305012, 605004, 605005, and 710003.
+		"""
+	notices: [
+		"""
+			The `source` and `destination` fields follow the order in which the addresses appear in the message.
+			For connections built `outbound`, the `source` is the foreign host that is connected to.
+			""",
+		"""
+			Messages outside of the catalog, or whose text doesn't match the expected format, are returned without
+			extracted fields.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` doesn't contain a `%ASA-<severity>-<id>:` message prefix.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse connection message"
+			source: #"""
+				parse_cisco_asa!("%ASA-6-302013: Built inbound TCP connection 447236 for outside:192.0.2.10/52210 (192.0.2.10/52210) to inside:10.0.0.5/443 (203.0.113.5/443)")
+				"""#
+			return: {
+				action:                  "built"
+				connection_id:           447236
+				destination_interface:   "inside"
+				destination_ip:          "10.0.0.5"
+				destination_mapped_ip:   "203.0.113.5"
+				destination_mapped_port: 443
+				destination_port:        443
+				direction:               "inbound"
+				message:                 "Built inbound TCP connection 447236 for outside:192.0.2.10/52210 (192.0.2.10/52210) to inside:10.0.0.5/443 (203.0.113.5/443)"
+				message_id:              302013
+				protocol:                "TCP"
+				severity:                6
+				source_interface:        "outside"
+				source_ip:               "192.0.2.10"
+				source_mapped_ip:        "192.0.2.10"
+				source_mapped_port:      52210
+				source_port:             52210
+			}
+		},
+		{
+			title: "Parse message outside of the catalog"
+			source: #"""
+				parse_cisco_asa!("<166>Jan 05 2022 10:00:00 asa01 : %ASA-5-111010: User 'admin', running 'CLI' from IP 10.0.0.1, executed 'write memory'")
+				"""#
+			return: {
+				message:    "User 'admin', running 'CLI' from IP 10.0.0.1, executed 'write memory'"
+				message_id: 111010
+				severity:   5
+			}
+		},
+	]
+}
@@ -0,0 +1,91 @@
+package metadata
+
+remap: functions: parse_etld: {
+	category:    "Parse"
+	description: """
+		Parses the [eTLD](\(urls.public_suffix_list)) (effective top-level domain) of the `value` domain name, using
+		the Public Suffix List.
+
+		Returns the `etld`, the `registered_domain` (the eTLD and one more label), and the `subdomain` to its left.
+		`etld_plus` is the eTLD along with `plus_parts` more labels. `known_suffix` is `false` if the eTLD isn't in
+		the list, in which case the last label of the domain name is used.
+		"""
+	notices: [
+		"""
+			A copy of the Public Suffix List is embedded in Vector, so it only changes when Vector is upgraded. Use
+			the `psl` argument to read a more recent list from a file instead.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The domain name to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "plus_parts"
+			description: "The number of labels to the left of the eTLD to include in `etld_plus`."
+			required:    false
+			default:     0
+			type: ["integer"]
+		},
+		{
+			name: "psl"
+			description: """
+				The path to a file in the Public Suffix List format to use instead of the embedded list. The file
+				is read once, when Vector starts.
+				"""
+			required: false
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`plus_parts` is negative.",
+		"The eTLD of `value` can't be determined.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse eTLD"
+			source: #"""
+				parse_etld!("www.example.co.uk")
+				"""#
+			return: {
+				etld:              "co.uk"
+				etld_plus:         "co.uk"
+				known_suffix:      true
+				registered_domain: "example.co.uk"
+				subdomain:         "www"
+			}
+		},
+		{
+			title: "Parse eTLD with parts"
+			source: #"""
+				parse_etld!("a.b.example.com", plus_parts: 2)
+				"""#
+			return: {
+				etld:              "com"
+				etld_plus:         "b.example.com"
+				known_suffix:      true
+				registered_domain: "example.com"
+				subdomain:         "a.b"
+			}
+		},
+		{
+			title: "Parse unknown eTLD"
+			source: #"""
+				parse_etld!("vector.local")
+				"""#
+			return: {
+				etld:              "local"
+				etld_plus:         "local"
+				known_suffix:      false
+				registered_domain: "vector.local"
+				subdomain:         null
+			}
+		},
+	]
+}
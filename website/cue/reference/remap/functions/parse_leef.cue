@@ -0,0 +1,70 @@
+package metadata
+
+remap: functions: parse_leef: {
+	category:    "Parse"
+	description: """
+		Parses the `value` in [LEEF](\(urls.leef)) (Log Event Extended Format) format, version 1.0 or 2.0.
+		Anything before the `LEEF:` prefix, such as a Syslog header, is ignored.
+
+		The header fields are returned as `leefVersion`, `deviceVendor`, `deviceProduct`, `deviceVersion`, and
+		`eventId`. The event attributes are returned alongside them, keyed by their name. All values are strings.
+		"""
+	notices: [
+		"""
+			LEEF 1.0 attributes are tab separated. LEEF 2.0 attributes are separated by the delimiter given in the
+			header, either as a character or as its hex code, such as `x5E`, and default to being tab separated.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` doesn't contain a `LEEF:` prefix.",
+		"`value` has fewer than five header fields.",
+		"The LEEF version isn't 1.0 or 2.0.",
+		"The delimiter in the LEEF 2.0 header is invalid.",
+		"An attribute isn't a `key=value` pair.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse LEEF 1.0 message"
+			source: #"""
+				parse_leef!("LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=192.0.2.0\tdst=172.50.123.1\tsev=5")
+				"""#
+			return: {
+				leefVersion:   "1.0"
+				deviceVendor:  "Microsoft"
+				deviceProduct: "MSExchange"
+				deviceVersion: "4.0 SP1"
+				eventId:       "15345"
+				src:           "192.0.2.0"
+				dst:           "172.50.123.1"
+				sev:           "5"
+			}
+		},
+		{
+			title: "Parse LEEF 2.0 message with a custom delimiter"
+			source: #"""
+				parse_leef!("LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^sev=5")
+				"""#
+			return: {
+				leefVersion:   "2.0"
+				deviceVendor:  "Lancope"
+				deviceProduct: "StealthWatch"
+				deviceVersion: "1.0"
+				eventId:       "41"
+				src:           "10.0.1.8"
+				dst:           "10.0.0.5"
+				sev:           "5"
+			}
+		},
+	]
+}
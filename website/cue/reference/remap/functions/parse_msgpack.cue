@@ -0,0 +1,42 @@
+package metadata
+
+remap: functions: parse_msgpack: {
+	category:    "Parse"
+	description: """
+		Parses the `value` as [MessagePack](\(urls.messagepack)).
+		"""
+	notices: [
+		"""
+			Binary values are returned as strings, extension values are returned as their data, and map
+			keys that are integers or binary values are converted to strings.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The MessagePack data to parse."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is not valid MessagePack.",
+		"`value` contains an integer that doesn't fit in a 64-bit signed integer.",
+		"`value` contains a map key that is not a string, integer or binary value.",
+	]
+	return: types: ["boolean", "integer", "float", "string", "object", "array", "null"]
+
+	examples: [
+		{
+			title: "Parse MessagePack"
+			source: #"""
+				parse_msgpack!(decode_base16!("82a16101a16292c3c0"))
+				"""#
+			return: {
+				a: 1
+				b: [true, null]
+			}
+		},
+	]
+}
@@ -0,0 +1,59 @@
+package metadata
+
+remap: functions: parse_windows_event_xml: {
+	category:    "Parse"
+	description: """
+		Parses a Windows event in its [XML representation](\(urls.windows_event_schema)), as sent by Windows Event
+		Forwarding or exported from the event log.
+
+		The `System` element is returned as snake case fields, such as `event_id`, `provider_name`, and
+		`time_created`. The `Data` elements of `EventData` are returned in `event_data`, keyed by their `Name`
+		attribute, or as `param1`, `param2`, and so on if they are unnamed. `UserData` is returned in `user_data`,
+		and the rendered message, if any, in `message`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The XML string to parse."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't valid XML.",
+		"`value` doesn't have an `Event` root element with a `System` element.",
+		"A numeric field, such as `EventID`, isn't an integer.",
+		"`TimeCreated` isn't a valid RFC 3339 timestamp.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse forwarded event"
+			source: #"""
+				parse_windows_event_xml!(s'<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event"><System><Provider Name="Microsoft-Windows-Security-Auditing" Guid="{54849625-5478-4994-a5ba-3e3b0328c30d}"/><EventID>4625</EventID><Version>0</Version><Level>0</Level><Task>12544</Task><Opcode>0</Opcode><Keywords>0x8010000000000000</Keywords><TimeCreated SystemTime="2022-03-14T09:21:07.5461529Z"/><EventRecordID>188921</EventRecordID><Execution ProcessID="652" ThreadID="4308"/><Channel>Security</Channel><Computer>dc01.example.com</Computer><Security/></System><EventData><Data Name="TargetUserName">alice</Data><Data Name="IpAddress">192.0.2.15</Data></EventData></Event>')
+				"""#
+			return: {
+				channel:  "Security"
+				computer: "dc01.example.com"
+				event_data: {
+					IpAddress:      "192.0.2.15"
+					TargetUserName: "alice"
+				}
+				event_id:      4625
+				keywords:      "0x8010000000000000"
+				level:         0
+				opcode:        0
+				process_id:    652
+				provider_guid: "{54849625-5478-4994-a5ba-3e3b0328c30d}"
+				provider_name: "Microsoft-Windows-Security-Auditing"
+				record_id:     188921
+				task:          12544
+				thread_id:     4308
+				time_created:  "2022-03-14T09:21:07.546152900Z"
+				version:       0
+			}
+		},
+	]
+}
@@ -0,0 +1,56 @@
+package metadata
+
+remap: functions: reduce: {
+	category:    "Enumerate"
+	description: """
+		Combines the items of an array, or the values of an object, into a single value.
+
+		The closure is called with the accumulator and an item, and returns the new accumulator. The
+		accumulator starts as `initial`, and its type inside the closure is the type of `initial`, so
+		the closure should return a value of that same type.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array or object to reduce."
+			required:    true
+			type: ["array", "object"]
+		},
+		{
+			name:        "initial"
+			description: "The initial value of the accumulator."
+			required:    true
+			type: ["any"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["any"]
+		rules: [
+			"Returns `initial` if `value` is empty.",
+			"Object values are visited in the order of their keys.",
+		]
+	}
+	examples: [
+		{
+			title: "Sum an array"
+			source: #"""
+				reduce([1, 2, 3], 0) -> |sum, value| { sum + value }
+				"""#
+			return: 6
+		},
+		{
+			title: "Sum the sizes of requests"
+			input: log: {
+				requests: [{"bytes": 120}, {"bytes": 80}]
+			}
+			source: #"""
+				reduce(array!(.requests), 0) -> |total, request| {
+				    total + (int(request.bytes) ?? 0)
+				}
+				"""#
+			return: 200
+		},
+	]
+}
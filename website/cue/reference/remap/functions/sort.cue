@@ -0,0 +1,52 @@
+package metadata
+
+remap: functions: sort: {
+	category:    "Array"
+	description: """
+		Sorts the items of the `value` array.
+
+		Integers and floats are compared by their numeric value. Items of different types are
+		grouped by type, in the order: strings, regexes, numbers, booleans, timestamps, objects,
+		arrays and `null`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array to sort."
+			required:    true
+			type: ["array"]
+		},
+		{
+			name:        "reverse"
+			description: "Whether to sort in descending order."
+			required:    false
+			type: ["boolean"]
+			default: false
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["array"]
+		rules: [
+			"Returns a new array. The `value` is _not_ modified in place.",
+			"The sort is stable: equal items keep their order.",
+		]
+	}
+	examples: [
+		{
+			title: "Sort numbers"
+			source: #"""
+				sort([3, 1.5, 2])
+				"""#
+			return: [1.5, 2, 3]
+		},
+		{
+			title: "Sort strings in descending order"
+			source: #"""
+				sort(["a", "c", "b"], reverse: true)
+				"""#
+			return: ["c", "b", "a"]
+		},
+	]
+}
@@ -0,0 +1,54 @@
+package metadata
+
+remap: functions: sort_by: {
+	category:    "Enumerate"
+	description: """
+		Sorts the items of the `value` array by the key returned by the closure for each item.
+
+		Keys are compared the same way as the items sorted by [`sort`](#sort). The closure is
+		called once for each item.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array to sort."
+			required:    true
+			type: ["array"]
+		},
+		{
+			name:        "reverse"
+			description: "Whether to sort in descending order."
+			required:    false
+			type: ["boolean"]
+			default: false
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["array"]
+		rules: [
+			"Returns a new array. The `value` is _not_ modified in place.",
+			"The sort is stable: items with equal keys keep their order.",
+		]
+	}
+	examples: [
+		{
+			title: "Sort objects by a field"
+			input: log: {
+				events: [{"ts": 3, "msg": "c"}, {"ts": 1, "msg": "a"}, {"ts": 2, "msg": "b"}]
+			}
+			source: #"""
+				sort_by(array!(.events)) -> |event| { event.ts }
+				"""#
+			return: [{"ts": 1, "msg": "a"}, {"ts": 2, "msg": "b"}, {"ts": 3, "msg": "c"}]
+		},
+		{
+			title: "Sort strings by length in descending order"
+			source: #"""
+				sort_by(["a", "ccc", "bb"], reverse: true) -> |value| { strlen(string!(value)) }
+				"""#
+			return: ["ccc", "bb", "a"]
+		},
+	]
+}
@@ -0,0 +1,55 @@
+package metadata
+
+remap: functions: xxhash: {
+	category:    "Cryptography"
+	description: """
+		Calculates an [xxHash](\(urls.xxhash)) hash of the `value`.
+		"""
+	notices: [
+		"""
+			Due to limitations in the underlying VRL data types, the 64-bit variants convert the unsigned hash
+			result to a signed 64-bit integer, and the `XXH3-128` variant returns the hash as a decimal string.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the hash for."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "variant"
+			description: "The variant of the algorithm to use."
+			enum: {
+				"XXH32":    "32-bit XXH32 algorithm"
+				"XXH64":    "64-bit XXH64 algorithm"
+				"XXH3-64":  "64-bit XXH3 algorithm"
+				"XXH3-128": "128-bit XXH3 algorithm"
+			}
+			required: false
+			default:  "XXH32"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["integer", "string"]
+
+	examples: [
+		{
+			title: "Calculate xxhash"
+			source: #"""
+				xxhash("foobar")
+				"""#
+			return: 3986901679
+		},
+		{
+			title: "Calculate 64-bit xxhash"
+			source: #"""
+				xxhash("foobar", "XXH64")
+				"""#
+			return: -6725556575634347271
+		},
+	]
+}
@@ -0,0 +1,49 @@
+package metadata
+
+remap: functions: zip: {
+	category:    "Array"
+	description: """
+		Pairs the items of two arrays, or, given a single array of arrays, groups the items of the
+		nested arrays by their index.
+		"""
+
+	arguments: [
+		{
+			name:        "array_0"
+			description: "The first array, or an array of arrays if `array_1` is missing."
+			required:    true
+			type: ["array"]
+		},
+		{
+			name:        "array_1"
+			description: "The second array."
+			required:    false
+			type: ["array"]
+		},
+	]
+	internal_failure_reasons: [
+		"`array_1` is missing and an item of `array_0` is not an array",
+	]
+	return: {
+		types: ["array"]
+		rules: [
+			"The returned array is as long as the shortest of the arrays.",
+		]
+	}
+	examples: [
+		{
+			title: "Zip two arrays"
+			source: #"""
+				zip([1, 2, 3], ["a", "b", "c"])
+				"""#
+			return: [[1, "a"], [2, "b"], [3, "c"]]
+		},
+		{
+			title: "Zip an array of arrays"
+			source: #"""
+				zip([[1, 2], ["a", "b"], [true, false, null]])
+				"""#
+			return: [[1, "a", true], [2, "b", false]]
+		},
+	]
+}
@@ -24,10 +24,12 @@ remap: syntax: keywords: {
 		* `std`
 		* `then`
 		* `this`
+		* `traverse`
 		* `true`
 		* `type`
 		* `until`
 		* `use`
+		* `walk`
 		* `while`
 		"""
 }
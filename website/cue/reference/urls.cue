@@ -85,6 +85,7 @@ urls: {
 	azure_blob_endpoints:                                     "https://docs.microsoft.com/en-us/rest/api/storageservices/blob-service-rest-api"
 	azure_monitor:                                            "https://azure.microsoft.com/en-us/services/monitor/"
 	azure_monitor_logs_endpoints:                             "https://docs.microsoft.com/en-us/rest/api/monitor/"
+	base16:                                                   "https://tools.ietf.org/html/rfc4648#section-8"
 	base64:                                                   "\(wikipedia)/wiki/Base64"
 	base64_padding:                                           "\(wikipedia)/wiki/Base64#Output_padding"
 	base64_standard:                                          "https://tools.ietf.org/html/rfc4648#section-4"
@@ -94,7 +95,9 @@ urls: {
 	bind_dnstap:                                              "https://kb.isc.org/docs/aa-01342"
 	b_tree_map:                                               "https://doc.rust-lang.org/std/collections/struct.BTreeMap.html"
 	cargo_audit:                                              "\(github)/RustSec/cargo-audit"
+	cbor:                                                     "https://cbor.io"
 	centos:                                                   "https://www.centos.org/"
+	cef:                                                      "https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors/pdfdoc/common-event-format-v25/common-event-format-v25.pdf"
 	chrono_time_formats:                                      "https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers"
 	cgroups_limit_resources:                                  "https://the.binbashtheory.com/control-resources-cgroups/"
 	clickhouse:                                               "https://clickhouse.yandex/"
@@ -102,6 +105,7 @@ urls: {
 	cloudsmith:                                               "https://cloudsmith.io/~timber/repos/vector/packages/"
 	cloudsmith_apt:                                           "https://cloudsmith.io/~timber/repos/vector/setup/#formats-deb"
 	cloudsmith_yum:                                           "https://cloudsmith.io/~timber/repos/vector/setup/#formats-rpm"
+	community_id:                                             "\(github)/corelight/community-id-spec"
 	console:                                                  "\(wikipedia)/wiki/System_console"
 	conventional_commits:                                     "https://www.conventionalcommits.org"
 	contributing:                                             "\(vector_repo)/blob/master/CONTRIBUTING.md#setup"
@@ -161,6 +165,7 @@ urls: {
 	dpkg:                                                     "https://wiki.debian.org/dpkg"
 	dry_code:                                                 "\(wikipedia)/wiki/Don%27t_repeat_yourself"
 	cidr:                                                     "\(wikipedia)/wiki/Classless_Inter-Domain_Routing"
+	cisco_asa_messages:                                       "https://www.cisco.com/c/en/us/td/docs/security/asa/syslog/b_syslog.html"
 	elastic_beats:                                            "https://www.elastic.co/beats/"
 	elasticsearch:                                            "https://www.elastic.co/products/elasticsearch"
 	elasticsearch_bulk:                                       "https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html"
@@ -228,6 +233,7 @@ urls: {
 	heroku:                                                   "https://www.heroku.com"
 	heroku_http_log_drain:                                    "https://devcenter.heroku.com/articles/log-drains#https-drains"
 	heroku_start:                                             "https://devcenter.heroku.com/start"
+	hmac:                                                     "\(wikipedia)/wiki/HMAC"
 	homebrew:                                                 "https://brew.sh/"
 	homebrew_services:                                        "\(github)/Homebrew/homebrew-services"
 	honeycomb:                                                "https://honeycomb.io"
@@ -282,6 +288,7 @@ urls: {
 	kubernetes_rbac:                                          "\(kubernetes)/docs/reference/access-authn-authz/rbac/"
 	kubernetes_request_verbs:                                 "\(kubernetes)/docs/reference/access-authn-authz/authorization/#determine-the-request-verb"
 	kubernetes_watch_api:                                     "\(kubernetes)/docs/reference/generated/kubernetes-api/v1.10/#watch-30"
+	leef:                                                     "https://www.ibm.com/docs/en/dsm?topic=overview-leef-event-components"
 	leveldb:                                                  "\(github)/google/leveldb"
 	leveldb_sys_2:                                            "https://crates.io/crates/leveldb-sys"
 	leveldb_sys_3:                                            "\(github)/vectordotdev/leveldb-sys/tree/v3.0.0"
@@ -330,6 +337,7 @@ urls: {
 	maxmind_geolite2_city:                                    "https://dev.maxmind.com/geoip/geoip2/geolite2/#Download_Access"
 	memory_safety:                                            "\(wikipedia)/wiki/Memory_safety"
 	memory_safety_bugs:                                       "https://thenewstack.io/microsoft-rust-is-the-industrys-best-chance-at-safe-systems-programming/"
+	messagepack:                                              "https://msgpack.org"
 	metric_event_source:                                      "\(vector_repo)/blob/master/src/event/metric.rs"
 	mlua:                                                     "\(github)/khvzak/mlua"
 	mongodb:                                                  "https://www.mongodb.com"
@@ -391,8 +399,10 @@ urls: {
 	prometheus_remote_write:                                  "https://prometheus.io/docs/prometheus/latest/configuration/configuration/#remote_write"
 	prometheus_remote_write_protocol:                         "https://docs.google.com/document/d/1LPhVRSFkGNSuU1fBd81ulhsCPR4hkSZyyBj1SZ8fWOM/edit#heading=h.n0d0vphea3fe"
 	protobuf:                                                 "https://developers.google.com/protocol-buffers"
+	public_suffix_list:                                       "https://publicsuffix.org/"
 	pulsar:                                                   "https://pulsar.apache.org/"
 	pulsar_protocol:                                          "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	punycode:                                                 "\(wikipedia)/wiki/Punycode"
 	raspbian:                                                 "https://www.raspbian.org/"
 	rdkafka:                                                  "\(github)/edenhill/librdkafka"
 	regex:                                                    "\(wikipedia)/wiki/Regular_expression"
@@ -403,6 +413,7 @@ urls: {
 	rfc_793:                                                  "https://tools.ietf.org/html/rfc793"
 	rfc_822:                                                  "https://tools.ietf.org/html/rfc822#section-5"
 	rfc_1035:                                                 "https://tools.ietf.org/html/rfc1035"
+	rfc_2047:                                                 "https://tools.ietf.org/html/rfc2047"
 	rfc_2064:                                                 "\(vector_repo)/blob/master/rfcs/2020-03-17-2064-event-driven-observability.md"
 	rfc_2136:                                                 "https://tools.ietf.org/html/rfc2136"
 	rfc_2460:                                                 "https://tools.ietf.org/html/rfc2460"
@@ -427,6 +438,7 @@ urls: {
 	rustup:                                                   "https://rustup.rs"
 	redis:                                                    "https://redis.io"
 	redis_rs:                                                 "https://github.com/mitsuhiko/redis-rs"
+	seahash:                                                  "https://docs.rs/seahash"
 	sematext:                                                 "https://sematext.com"
 	sematext_create_logs_app:                                 "https://apps.sematext.com/ui/integrations"
 	sematext_es:                                              "https://sematext.com/docs/logs/index-events-via-elasticsearch-api/"
@@ -493,6 +505,7 @@ urls: {
 	utf8:                                                     "\(wikipedia)/wiki/UTF-8"
 	uuidv4:                                                   "\(wikipedia)/wiki/Universally_unique_identifier#Version_4_(random)"
 	url:                                                      "\(wikipedia)/wiki/URL"
+	url_normalization:                                        "\(wikipedia)/wiki/URI_normalization"
 	us_social_security_number:                                "https://www.ssa.gov/history/ssn/geocard.html"
 	user_agent:                                               "https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/User-Agent"
 	vector_agent_role:                                        "/docs/setup/deployment/roles/#agent"
@@ -623,9 +636,11 @@ urls: {
 	websocket:                                                "\(wikipedia)/wiki/WebSocket"
 	wikipedia:                                                "https://en.wikipedia.org"
 	windows:                                                  "https://www.microsoft.com/en-us/windows"
+	windows_event_schema:                                     "https://docs.microsoft.com/en-us/windows/win32/wes/eventschema-schema"
 	windows_installer:                                        "\(wikipedia)/wiki/Windows_Installer"
 	windows_service:                                          "https://docs.microsoft.com/en-us/powershell/module/microsoft.powershell.management/new-service"
 	woothee:                                                  "https://github.com/woothee/woothee"
+	xxhash:                                                   "https://cyan4973.github.io/xxHash/"
 	yaml:                                                     "https://yaml.org/"
 	ytt:                                                      "https://carvel.dev/ytt/"
 	yum:                                                      "\(wikipedia)/wiki/Yum_(software)"